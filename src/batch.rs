@@ -0,0 +1,116 @@
+use crate::gen_witness::{WitnessGenerator, WitnessOptions};
+use crate::limits::{check_input_sizes, ProvingPermit};
+use crate::logging::{debug, warn};
+use crate::prove::Prover;
+use crate::serialization::{deserialize_json, serialize_proof};
+use crate::ExternalEZKLError;
+use ezkl::circuit::CheckMode;
+use ezkl::graph::GraphWitness;
+use ezkl::pfsys::ProofType;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The outcome of a single item of a batch call.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug)]
+pub enum BatchItemResult {
+    /// The item succeeded, producing the given JSON `String`.
+    Success { json: String },
+    /// The item failed with the given error; the other items are unaffected.
+    Failure { error: ExternalEZKLError },
+}
+
+impl From<Result<String, ExternalEZKLError>> for BatchItemResult {
+    fn from(result: Result<String, ExternalEZKLError>) -> Self {
+        match result {
+            Ok(json) => BatchItemResult::Success { json },
+            Err(error) => BatchItemResult::Failure { error },
+        }
+    }
+}
+
+/// Generates a witness for each of the given inputs, deserializing the circuit, vk and SRS only once.
+///
+/// A failing input does not abort the batch; its error is reported at its index instead.
+///
+/// # Arguments
+///
+/// * `inputs_json` - A `Vec<String>` containing the JSON representation of each input.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(Vec<BatchItemResult>)` - The witness JSON or the error of each input, in input order.
+/// * `Err(ExternalEZKLError)` - If the circuit, vk or SRS could not be deserialized.
+#[cfg_attr(feature = "ffi", export)]
+pub async fn gen_witness_batch(
+    inputs_json: Vec<String>,
+    compiled_circuit: Vec<u8>,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+) -> Result<Vec<BatchItemResult>, ExternalEZKLError> {
+    check_input_sizes!(inputs_json, compiled_circuit, vk, srs);
+    let generator = WitnessGenerator::new(&compiled_circuit, Some(&vk), Some(&srs))?;
+    let options = WitnessOptions::default();
+
+    let mut results = Vec::with_capacity(inputs_json.len());
+    for (index, input_json) in inputs_json.iter().enumerate() {
+        let result = generator
+            .generate(input_json, &options)
+            .await
+            .and_then(|witness| witness.as_json().map_err(ExternalEZKLError::from));
+        log_item("witness generation", index, &result);
+        results.push(result.into());
+    }
+
+    Ok(results)
+}
+
+/// Generates a proof for each of the given witnesses, deserializing the circuit, pk and SRS only once.
+///
+/// Uses the default proving configuration, like `prove`. The batch holds a single proving slot
+/// for its whole duration. A failing witness does not abort the batch; its error is reported at
+/// its index instead.
+///
+/// # Arguments
+///
+/// * `witnesses_json` - A `Vec<String>` containing the JSON representation of each witness.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(Vec<BatchItemResult>)` - The proof JSON or the error of each witness, in input order.
+/// * `Err(ExternalEZKLError)` - If the circuit, pk or SRS could not be deserialized.
+#[cfg_attr(feature = "ffi", export)]
+pub fn prove_batch(
+    witnesses_json: Vec<String>,
+    compiled_circuit: Vec<u8>,
+    pk: Vec<u8>,
+    srs: Vec<u8>,
+) -> Result<Vec<BatchItemResult>, ExternalEZKLError> {
+    check_input_sizes!(witnesses_json, compiled_circuit, pk, srs);
+    let _permit = ProvingPermit::acquire()?;
+    let prover = Prover::new(&compiled_circuit, &pk, Some(&srs), None)?;
+
+    let mut results = Vec::with_capacity(witnesses_json.len());
+    for (index, witness_json) in witnesses_json.iter().enumerate() {
+        let result = deserialize_json::<GraphWitness>("witness_json", witness_json)
+            .and_then(|witness| prover.prove(witness, ProofType::Single, CheckMode::SAFE))
+            .and_then(serialize_proof);
+        log_item("proving", index, &result);
+        results.push(result.into());
+    }
+
+    Ok(results)
+}
+
+fn log_item(operation: &str, index: usize, result: &Result<String, ExternalEZKLError>) {
+    match result {
+        Ok(_) => debug!("batch {} of item {} succeeded", operation, index),
+        Err(e) => warn!("batch {} of item {} failed: {}", operation, index, e),
+    }
+}
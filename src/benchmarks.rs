@@ -0,0 +1,137 @@
+use crate::clock::Instant;
+use crate::inspect::CommitmentsWrapper;
+use crate::limits::check_input_sizes;
+use crate::prove::Prover;
+use crate::serialization::{deserialize_circuit, deserialize_json};
+use crate::verify::verify_snark;
+use crate::ExternalEZKLError;
+use ezkl::circuit::CheckMode;
+use ezkl::graph::GraphWitness;
+use ezkl::pfsys::ProofType;
+use ezkl::Commitments;
+use halo2_proofs::SerdeFormat::RawBytes;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The cost of proving and verifying a witness with one commitment scheme.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct CommitmentMeasurement {
+    /// The commitment scheme the proof was created with.
+    pub commitment: CommitmentsWrapper,
+    /// The size of the proof in bytes, without its instances.
+    pub proof_bytes: u64,
+    /// The size of the verification key in bytes.
+    pub vk_bytes: u64,
+    /// The time spent loading the proving key and SRS, in milliseconds.
+    pub load_ms: u64,
+    /// The time spent proving, in milliseconds.
+    pub prove_ms: u64,
+    /// The time spent verifying, without loading the SRS and vk, in milliseconds.
+    pub verify_ms: u64,
+    /// Whether the proof verified.
+    pub verified: bool,
+}
+
+/// The costs of proving and verifying the same witness with KZG and with IPA.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct CommitmentComparison {
+    /// The log₂ of the number of rows of the circuit, which sizes the SRS of both schemes.
+    pub logrows: u32,
+    /// The number of rows used by the circuit.
+    pub num_rows: u64,
+    /// The total number of cells assigned by the circuit.
+    pub total_assignments: u64,
+    /// The measurement with the KZG commitment scheme.
+    pub kzg: CommitmentMeasurement,
+    /// The measurement with the IPA commitment scheme.
+    pub ipa: CommitmentMeasurement,
+}
+
+/// Proves and verifies a witness with both commitment schemes, to choose between them on a device.
+///
+/// KZG proofs are smaller and verify in constant time, but need a trusted setup, while IPA
+/// needs none but verifies in time linear in the size of the circuit. Both proofs are created
+/// in `UNSAFE` mode, so the prove times don't include the verification `SAFE` mode performs,
+/// and the schemes are measured one after the other on the calling thread.
+///
+/// # Arguments
+///
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form. Its
+///   commitment scheme is replaced by each of the schemes in turn.
+/// * `witness_json` - A `String` containing the JSON representation of the witness, proven with both schemes.
+/// * `srs_kzg` - A `Vec<u8>` containing the KZG Structured Reference String (SRS) in binary form.
+/// * `srs_ipa` - A `Vec<u8>` containing the IPA Structured Reference String (SRS) in binary form.
+/// * `pk_kzg` - A `Vec<u8>` containing the Proving Key (PK) generated for the circuit with KZG.
+/// * `pk_ipa` - A `Vec<u8>` containing the Proving Key (PK) generated for the circuit with IPA.
+///
+/// # Returns
+///
+/// * `Ok(CommitmentComparison)` - The proof sizes and the prove and verify times of both schemes.
+/// * `Err(ExternalEZKLError)` - An error that occurred while loading the inputs or proving.
+#[cfg_attr(feature = "ffi", export)]
+pub fn compare_commitments(
+    compiled_circuit: Vec<u8>,
+    witness_json: String,
+    srs_kzg: Vec<u8>,
+    srs_ipa: Vec<u8>,
+    pk_kzg: Vec<u8>,
+    pk_ipa: Vec<u8>,
+) -> Result<CommitmentComparison, ExternalEZKLError> {
+    check_input_sizes!(
+        compiled_circuit,
+        witness_json,
+        srs_kzg,
+        srs_ipa,
+        pk_kzg,
+        pk_ipa
+    );
+    let circuit = deserialize_circuit(&compiled_circuit)?;
+    drop(compiled_circuit);
+    let witness: GraphWitness = deserialize_json("witness_json", &witness_json)?;
+    drop(witness_json);
+
+    let measure = |commitment: Commitments, pk: &[u8], srs: &[u8]| {
+        let mut circuit = circuit.clone();
+        circuit.core.settings.run_args.commitment = Some(commitment);
+        let settings_json = circuit.settings().as_json()?;
+
+        let started = Instant::now();
+        let prover = Prover::from_circuit(circuit, pk, Some(srs), None)?;
+        let load_ms = started.elapsed().as_millis() as u64;
+
+        let started = Instant::now();
+        let snark = prover.prove(witness.clone(), ProofType::Single, CheckMode::UNSAFE)?;
+        let prove_ms = started.elapsed().as_millis() as u64;
+
+        let mut vk = vec![];
+        prover
+            .vk()
+            .write(&mut vk, RawBytes)
+            .map_err(|e| ExternalEZKLError::InternalError(e.to_string()))?;
+        drop(prover);
+
+        let proof_bytes = snark.proof.len() as u64;
+        let verification = verify_snark(snark, settings_json, &vk, Some(srs), false, true)?;
+        Ok::<_, ExternalEZKLError>(CommitmentMeasurement {
+            commitment: commitment.into(),
+            proof_bytes,
+            vk_bytes: vk.len() as u64,
+            load_ms,
+            prove_ms,
+            verify_ms: verification.elapsed.unwrap_or_default().as_millis() as u64,
+            verified: verification.result.is_ok()
+                && verification.expected_instances == verification.provided_instances,
+        })
+    };
+
+    let settings = circuit.settings();
+    Ok(CommitmentComparison {
+        logrows: settings.run_args.logrows,
+        num_rows: settings.num_rows as u64,
+        total_assignments: settings.total_assignments as u64,
+        kzg: measure(Commitments::KZG, &pk_kzg, &srs_kzg)?,
+        ipa: measure(Commitments::IPA, &pk_ipa, &srs_ipa)?,
+    })
+}
@@ -0,0 +1,346 @@
+use ios_ezkl::{error_to_json, EZKLError, ErrorReport};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: ezkl-porter-cli <command> [options]
+
+commands:
+  gen-witness   --input <input.json> --circuit <network.ezkl> --vk <vk.key> --srs <kzg.srs>
+  prove         --witness <witness.json> --circuit <network.ezkl> --pk <pk.key> --srs <kzg.srs>
+  verify        --proof <proof.json> --settings <settings.json> --vk <vk.key> --srs <kzg.srs>
+  inspect-proof --proof <proof.json>
+  circuit-info  --circuit <network.ezkl>
+
+Results are printed to stdout as JSON, errors to stderr as the JSON of `error_to_json`.
+
+exit codes:
+  0   success
+  1   internal error
+  2   invalid input: InvalidInput, JsonError, MissingInput, ShapeMismatch
+  3   invalid artifact: LoadVk, LoadPk, SrsRead, FingerprintMismatch, Unsupported,
+      ExternalVerificationFailed
+  4   retry later: Busy, TimedOut, AlreadyInitialized, ConcurrentModification, Network
+  5   the proof is invalid: OutputMismatch
+  64  invalid command line
+  66  an artifact file could not be read";
+
+/// Exit code of an invalid proof.
+const EXIT_INVALID_PROOF: u8 = 5;
+/// Exit code of an invalid command line, `EX_USAGE` of `sysexits.h`.
+const EXIT_USAGE: u8 = 64;
+/// Exit code of an artifact file that could not be read, `EX_NOINPUT` of `sysexits.h`.
+const EXIT_NO_INPUT: u8 = 66;
+
+fn main() -> ExitCode {
+    let command = match parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+
+    match run(command) {
+        Ok(Outcome::Output(output)) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Ok(Outcome::InvalidProof(output)) => {
+            println!("{}", output);
+            ExitCode::from(EXIT_INVALID_PROOF)
+        }
+        Err(Failure::Read(path, e)) => {
+            eprintln!("failed to read {}: {}", path.display(), e);
+            ExitCode::from(EXIT_NO_INPUT)
+        }
+        Err(Failure::Call(e)) => {
+            let code = exit_code(&e);
+            let report = ErrorReport {
+                error: e,
+                stage: None,
+            };
+            match error_to_json(report) {
+                Ok(json) => eprintln!("{}", json),
+                Err(e) => eprintln!("{}", e),
+            }
+            ExitCode::from(code)
+        }
+    }
+}
+
+/// A subcommand with the paths of the artifacts it reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    GenWitness {
+        input: PathBuf,
+        circuit: PathBuf,
+        vk: PathBuf,
+        srs: PathBuf,
+    },
+    Prove {
+        witness: PathBuf,
+        circuit: PathBuf,
+        pk: PathBuf,
+        srs: PathBuf,
+    },
+    Verify {
+        proof: PathBuf,
+        settings: PathBuf,
+        vk: PathBuf,
+        srs: PathBuf,
+    },
+    InspectProof {
+        proof: PathBuf,
+    },
+    CircuitInfo {
+        circuit: PathBuf,
+    },
+}
+
+/// The options of a subcommand, by name without the leading `--`.
+struct Options(Vec<(String, PathBuf)>);
+
+impl Options {
+    fn parse(
+        mut args: impl Iterator<Item = String>,
+        names: &[&'static str],
+    ) -> Result<Self, String> {
+        let mut options = vec![];
+        while let Some(arg) = args.next() {
+            let name = arg
+                .strip_prefix("--")
+                .filter(|name| names.iter().any(|known| known == name))
+                .ok_or_else(|| format!("unexpected argument {}", arg))?;
+            if options.iter().any(|(seen, _)| seen == name) {
+                return Err(format!("{} is given more than once", arg));
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| format!("{} requires a value", arg))?;
+            options.push((name.to_string(), PathBuf::from(value)));
+        }
+        Ok(Options(options))
+    }
+
+    fn take(&mut self, name: &str) -> Result<PathBuf, String> {
+        let index = self
+            .0
+            .iter()
+            .position(|(option, _)| option == name)
+            .ok_or_else(|| format!("--{} is required", name))?;
+        Ok(self.0.remove(index).1)
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let command = args.next().ok_or("a command is required")?;
+    let command = match command.as_str() {
+        "gen-witness" => {
+            let mut options = Options::parse(args, &["input", "circuit", "vk", "srs"])?;
+            Command::GenWitness {
+                input: options.take("input")?,
+                circuit: options.take("circuit")?,
+                vk: options.take("vk")?,
+                srs: options.take("srs")?,
+            }
+        }
+        "prove" => {
+            let mut options = Options::parse(args, &["witness", "circuit", "pk", "srs"])?;
+            Command::Prove {
+                witness: options.take("witness")?,
+                circuit: options.take("circuit")?,
+                pk: options.take("pk")?,
+                srs: options.take("srs")?,
+            }
+        }
+        "verify" => {
+            let mut options = Options::parse(args, &["proof", "settings", "vk", "srs"])?;
+            Command::Verify {
+                proof: options.take("proof")?,
+                settings: options.take("settings")?,
+                vk: options.take("vk")?,
+                srs: options.take("srs")?,
+            }
+        }
+        "inspect-proof" => {
+            let mut options = Options::parse(args, &["proof"])?;
+            Command::InspectProof {
+                proof: options.take("proof")?,
+            }
+        }
+        "circuit-info" => {
+            let mut options = Options::parse(args, &["circuit"])?;
+            Command::CircuitInfo {
+                circuit: options.take("circuit")?,
+            }
+        }
+        other => return Err(format!("unknown command {}", other)),
+    };
+    Ok(command)
+}
+
+/// The JSON printed by a successful run.
+enum Outcome {
+    Output(String),
+    /// The result of verifying an invalid proof, which exits with a distinct code.
+    InvalidProof(String),
+}
+
+enum Failure {
+    Read(PathBuf, std::io::Error),
+    Call(EZKLError),
+}
+
+impl From<EZKLError> for Failure {
+    fn from(e: EZKLError) -> Self {
+        Failure::Call(e)
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, Failure> {
+    std::fs::read(path).map_err(|e| Failure::Read(path.to_path_buf(), e))
+}
+
+fn read_string(path: &Path) -> Result<String, Failure> {
+    std::fs::read_to_string(path).map_err(|e| Failure::Read(path.to_path_buf(), e))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, Failure> {
+    serde_json::to_string(value).map_err(|e| Failure::Call(e.into()))
+}
+
+/// Runs a command with the same exported functions the bindings call.
+fn run(command: Command) -> Result<Outcome, Failure> {
+    let output = match command {
+        Command::GenWitness {
+            input,
+            circuit,
+            vk,
+            srs,
+        } => {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .build()
+                .map_err(|e| Failure::Call(EZKLError::InternalError(e.to_string())))?;
+            runtime.block_on(ios_ezkl::gen_witness(
+                read_string(&input)?,
+                read(&circuit)?,
+                read(&vk)?,
+                read(&srs)?,
+            ))?
+        }
+        Command::Prove {
+            witness,
+            circuit,
+            pk,
+            srs,
+        } => ios_ezkl::prove_advanced(
+            read_string(&witness)?,
+            read(&circuit)?,
+            read(&pk)?,
+            read(&srs)?,
+            ios_ezkl::default_prove_options(),
+        )?,
+        Command::Verify {
+            proof,
+            settings,
+            vk,
+            srs,
+        } => {
+            let valid = ios_ezkl::verify(
+                read_string(&proof)?,
+                read_string(&settings)?,
+                read(&vk)?,
+                read(&srs)?,
+            )?;
+            let output = serde_json::json!({ "valid": valid }).to_string();
+            if !valid {
+                return Ok(Outcome::InvalidProof(output));
+            }
+            output
+        }
+        Command::InspectProof { proof } => {
+            to_json(&ios_ezkl::inspect_proof(read_string(&proof)?)?)?
+        }
+        Command::CircuitInfo { circuit } => to_json(&ios_ezkl::circuit_info(read(&circuit)?)?)?,
+    };
+    Ok(Outcome::Output(output))
+}
+
+/// Maps an error to the exit code of its category, see `USAGE`.
+fn exit_code(e: &EZKLError) -> u8 {
+    match e {
+        EZKLError::InternalError(_) => 1,
+        EZKLError::InvalidInput(_)
+        | EZKLError::JsonError { .. }
+        | EZKLError::MissingInput { .. }
+        | EZKLError::ShapeMismatch { .. } => 2,
+        EZKLError::LoadVk { .. }
+        | EZKLError::LoadPk { .. }
+        | EZKLError::SrsRead { .. }
+        | EZKLError::FingerprintMismatch { .. }
+        | EZKLError::Unsupported { .. }
+        | EZKLError::ExternalVerificationFailed { .. } => 3,
+        EZKLError::Busy { .. }
+        | EZKLError::TimedOut { .. }
+        | EZKLError::AlreadyInitialized
+        | EZKLError::ConcurrentModification { .. }
+        | EZKLError::Network { .. } => 4,
+        EZKLError::OutputMismatch { .. } => EXIT_INVALID_PROOF,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse_args(args(&[
+                "verify",
+                "--vk",
+                "vk.key",
+                "--proof",
+                "proof.json",
+                "--srs",
+                "kzg.srs",
+                "--settings",
+                "settings.json",
+            ])),
+            Ok(Command::Verify {
+                proof: PathBuf::from("proof.json"),
+                settings: PathBuf::from("settings.json"),
+                vk: PathBuf::from("vk.key"),
+                srs: PathBuf::from("kzg.srs"),
+            })
+        );
+        assert_eq!(
+            parse_args(args(&["circuit-info", "--circuit", "network.ezkl"])),
+            Ok(Command::CircuitInfo {
+                circuit: PathBuf::from("network.ezkl"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_args_rejects_invalid_command_lines() {
+        for invalid in [
+            &[][..],
+            &["aggregate"],
+            &["inspect-proof"],
+            &["inspect-proof", "--proof"],
+            &["inspect-proof", "--circuit", "network.ezkl"],
+            &["inspect-proof", "--proof", "a.json", "--proof", "b.json"],
+        ] {
+            assert!(parse_args(args(invalid)).is_err(), "{:?}", invalid);
+        }
+    }
+}
@@ -1,8 +1,12 @@
 use camino::Utf8Path;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::fs::remove_dir_all;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::SystemTime;
 use uniffi_bindgen::bindings::SwiftBindingGenerator;
 use uniffi_bindgen::library_mode::generate_bindings;
 use uuid::Uuid;
@@ -10,10 +14,364 @@ use uuid::Uuid;
 // Name of the Rust library to generate bindings for
 const LIBRARY_NAME: &str = "ios_ezkl";
 
+// Snapshot of the exported C symbols, used to detect unexpected changes to the FFI surface
+const SYMBOLS_SNAPSHOT: &str = "ffi-symbols.txt";
+
+// Environment variable that accepts changes to the exported C symbols and updates the snapshot
+const ACCEPT_SYMBOLS_ENV: &str = "ACCEPT_FFI_SYMBOL_CHANGES";
+
+// Snapshot of the exported C symbols of the verify-only build
+const VERIFIER_SYMBOLS_SNAPSHOT: &str = "ffi-symbols-verifier.txt";
+
+// Snapshot of the Swift names integrators spell out, used to detect unexpected renames
+const SWIFT_API_SNAPSHOT: &str = "swift-api-snapshot.txt";
+
+// Environment variable that accepts changes to the Swift names and updates the snapshot
+const ACCEPT_SWIFT_API_ENV: &str = "ACCEPT_SWIFT_API_CHANGES";
+
+// Enums and records whose Swift names are recorded in the Swift API snapshot
+const SWIFT_API_ENUMS: &[&str] = &["CheckModeWrapper", "ProofTypeWrapper"];
+const SWIFT_API_RECORDS: &[&str] = &["ProveOptions"];
+
+// Defaults of enum fields of records as `(record, field, default)`, which uniffi 0.28 can't
+// declare from Rust, so that Swift callers can write `ProveOptions()`
+const SWIFT_ENUM_DEFAULTS: &[(&str, &str, &str)] = &[
+    ("ProveOptions", "proofType", ".single"),
+    ("ProveOptions", "checkMode", ".safe"),
+];
+
+// Default name of the Swift module, the bindings directory and the XCFramework
+const DEFAULT_MODULE_NAME: &str = "EzklCore";
+
+// Default module name of the verify-only build, so that it is never mistaken for the full one
+const VERIFIER_MODULE_NAME: &str = "EzklVerifier";
+
+/// The build manifest written into the bindings directory.
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// The file the `--size-report` flag writes the size of each slice to, next to the manifest.
+const SIZE_REPORT_FILE: &str = "size-report.json";
+
+/// The human readable version of the size report.
+const SIZE_REPORT_MARKDOWN_FILE: &str = "size-report.md";
+
+/// The number of crates listed per slice in the markdown size report.
+const SIZE_REPORT_TOP_CRATES: usize = 15;
+
 fn main() {
+    match parse_verify_manifest(std::env::args().skip(1)) {
+        Ok(Some(manifest_path)) => {
+            match verify_manifest(&manifest_path) {
+                Ok(count) => println!("All {} files match {}", count, manifest_path.display()),
+                Err(problems) => {
+                    for problem in problems {
+                        eprintln!("{}", problem);
+                    }
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Ok(None) => {}
+        Err(e) => panic!("{}", e),
+    }
+
     let mode = determine_build_mode();
+    let profile = match parse_profile(std::env::args().skip(1)) {
+        Ok(profile) => profile,
+        Err(e) => panic!("{}", e),
+    };
+    let names = match parse_module_name(std::env::args().skip(1)) {
+        Ok(Some(module_name)) => BindingNames::custom(&module_name),
+        Ok(None) => profile.default_names(LIBRARY_NAME),
+        Err(e) => panic!("{}", e),
+    };
+    let size_report = parse_size_report(std::env::args().skip(1));
+
+    build_bindings(LIBRARY_NAME, &names, mode, profile, size_report);
+}
+
+/// The feature set the library is built with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Profile {
+    /// The default features, including the prover.
+    Full,
+    /// Verification and inspection only, leaving out the prover to shrink the framework.
+    Verifier,
+}
+
+impl Profile {
+    /// The arguments selecting the features of the profile in `cargo build`.
+    fn cargo_args(self) -> &'static [&'static str] {
+        match self {
+            Profile::Full => &[],
+            Profile::Verifier => &["--no-default-features", "--features", "verifier"],
+        }
+    }
+
+    /// The name of the profile, as passed to `--profile`.
+    fn name(self) -> &'static str {
+        match self {
+            Profile::Full => "full",
+            Profile::Verifier => "verifier",
+        }
+    }
+
+    /// The cargo features the library is built with.
+    fn features(self) -> &'static [&'static str] {
+        match self {
+            Profile::Full => &["prover"],
+            Profile::Verifier => &["verifier"],
+        }
+    }
+
+    /// The snapshot the exported symbols of the profile are checked against.
+    fn symbols_snapshot(self) -> &'static str {
+        match self {
+            Profile::Full => SYMBOLS_SNAPSHOT,
+            Profile::Verifier => VERIFIER_SYMBOLS_SNAPSHOT,
+        }
+    }
+
+    /// The snapshot the Swift names of the profile are checked against, if it exports them.
+    fn swift_api_snapshot(self) -> Option<&'static str> {
+        match self {
+            Profile::Full => Some(SWIFT_API_SNAPSHOT),
+            Profile::Verifier => None,
+        }
+    }
+
+    /// The names used when no module name is given.
+    fn default_names(self, library_name: &str) -> BindingNames {
+        match self {
+            Profile::Full => BindingNames::default_for(library_name),
+            Profile::Verifier => BindingNames::custom(VERIFIER_MODULE_NAME),
+        }
+    }
+}
+
+/// Reads the `--profile <full|verifier>` (or `--profile=<name>`) flag from the arguments.
+fn parse_profile(mut args: impl Iterator<Item = String>) -> Result<Profile, String> {
+    let mut profile = Profile::Full;
+    while let Some(arg) = args.next() {
+        let value = if arg == "--profile" {
+            args.next()
+                .ok_or_else(|| "--profile requires a value".to_string())?
+        } else if let Some(value) = arg.strip_prefix("--profile=") {
+            value.to_string()
+        } else {
+            continue;
+        };
+        profile = match value.as_str() {
+            "full" => Profile::Full,
+            "verifier" => Profile::Verifier,
+            _ => {
+                return Err(format!(
+                    "unknown profile `{}`: expected `full` or `verifier`",
+                    value
+                ))
+            }
+        };
+    }
+    Ok(profile)
+}
+
+/// The names of the generated Swift module and the artifacts it ships in.
+///
+/// Only these names can be changed here. The C symbols and the `<library>FFI.h` header keep the
+/// uniffi namespace, which can only be changed by rebuilding with a different
+/// `uniffi::setup_scaffolding!` namespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BindingNames {
+    /// The name of the Swift module, e.g. `EzklCore`.
+    module: String,
+    /// The name of the clang module wrapping the C header, imported by the Swift file.
+    ffi_module: String,
+}
+
+impl BindingNames {
+    /// The names used when no module name is given, which keep the FFI module uniffi generates.
+    fn default_for(library_name: &str) -> Self {
+        BindingNames {
+            module: DEFAULT_MODULE_NAME.to_string(),
+            ffi_module: format!("{}FFI", library_name),
+        }
+    }
+
+    /// The names for a white-label module, which also renames the FFI module so that
+    /// differently named builds can be linked into the same app.
+    fn custom(module_name: &str) -> Self {
+        BindingNames {
+            module: module_name.to_string(),
+            ffi_module: format!("{}FFI", module_name),
+        }
+    }
+
+    fn swift_file(&self) -> String {
+        format!("{}.swift", self.module)
+    }
 
-    build_bindings(LIBRARY_NAME, mode);
+    fn framework(&self) -> String {
+        format!("{}.xcframework", self.module)
+    }
+
+    fn framework_zip(&self) -> String {
+        format!("{}.xcframework.zip", self.module)
+    }
+
+    fn bindings_dir(&self) -> String {
+        format!("{}Bindings", self.module)
+    }
+}
+
+/// Reads the `--module-name <name>` (or `--module-name=<name>`) flag from the arguments.
+fn parse_module_name(mut args: impl Iterator<Item = String>) -> Result<Option<String>, String> {
+    let mut module_name = None;
+    while let Some(arg) = args.next() {
+        let value = if arg == "--module-name" {
+            args.next()
+                .ok_or_else(|| "--module-name requires a value".to_string())?
+        } else if let Some(value) = arg.strip_prefix("--module-name=") {
+            value.to_string()
+        } else {
+            continue;
+        };
+        let mut chars = value.chars();
+        let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(format!(
+                "invalid module name `{}`: it must be a Swift identifier",
+                value
+            ));
+        }
+        module_name = Some(value);
+    }
+    Ok(module_name)
+}
+
+/// Renames the FFI module in a generated Swift file or modulemap, keeping the header file name.
+fn rename_ffi_module(contents: &str, from: &str, to: &str) -> String {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut renamed = String::with_capacity(contents.len());
+    let mut rest = contents;
+    while let Some(index) = rest.find(from) {
+        let (before, after) = (&rest[..index], &rest[index + from.len()..]);
+        let preceding = before.chars().last().or_else(|| renamed.chars().last());
+        let standalone = !preceding.is_some_and(is_ident)
+            && !after.starts_with(|c: char| is_ident(c) || c == '.');
+        renamed.push_str(before);
+        renamed.push_str(if standalone { to } else { from });
+        rest = after;
+    }
+    renamed.push_str(rest);
+    renamed
+}
+
+/// Returns the range of the parameters of the initializer of a generated Swift record.
+fn record_init_params(swift: &str, record: &str) -> Option<std::ops::Range<usize>> {
+    let start = swift.find(&format!("public struct {} {{", record))?;
+    let params = start + swift[start..].find("public init(")? + "public init(".len();
+    let end = params + swift[params..].find(") {\n")?;
+    Some(params..end)
+}
+
+/// Gives enum fields of the generated Swift records a default value in their initializer.
+fn add_enum_defaults(swift: &str, defaults: &[(&str, &str, &str)]) -> String {
+    let mut swift = swift.to_string();
+    for (record, field, default) in defaults {
+        let Some(params) = record_init_params(&swift, record) else {
+            continue;
+        };
+        let declaration = format!("{}: ", field);
+        let Some(position) = swift[params.clone()]
+            .match_indices(&declaration)
+            .map(|(index, _)| params.start + index)
+            .find(|&index| !swift[..index].ends_with(|c: char| c.is_ascii_alphanumeric()))
+        else {
+            continue;
+        };
+        let type_end = swift[position..params.end]
+            .find(',')
+            .map_or(params.end, |index| position + index);
+        if !swift[position..type_end].contains('=') {
+            let type_end = position + swift[position..type_end].trim_end().len();
+            swift.insert_str(type_end, &format!(" = {}", default));
+        }
+    }
+    swift
+}
+
+/// Renders the Swift names of the enum cases and record initializers recorded in the snapshot.
+fn swift_api(swift: &str) -> String {
+    let mut api = String::new();
+    for name in SWIFT_API_ENUMS {
+        let Some(start) = swift.find(&format!("public enum {} {{", name)) else {
+            continue;
+        };
+        let body = &swift[start..];
+        let body = &body[..body.find("\n}").unwrap_or(body.len())];
+        for case in body
+            .lines()
+            .filter_map(|line| line.trim().strip_prefix("case "))
+        {
+            api.push_str(&format!("{}.{}\n", name, case));
+        }
+    }
+    for name in SWIFT_API_RECORDS {
+        let Some(params) = record_init_params(swift, name) else {
+            continue;
+        };
+        let params = strip_doc_comments(&swift[params]);
+        let params: Vec<&str> = params
+            .split(',')
+            .map(str::trim)
+            .filter(|param| !param.is_empty())
+            .collect();
+        api.push_str(&format!("{}({})\n", name, params.join(", ")));
+    }
+    api
+}
+
+/// Removes the `/** ... */` doc comments uniffi emits for documented items.
+fn strip_doc_comments(swift: &str) -> String {
+    let mut stripped = String::with_capacity(swift.len());
+    let mut rest = swift;
+    while let Some(start) = rest.find("/**") {
+        stripped.push_str(&rest[..start]);
+        rest = rest[start..]
+            .find("*/")
+            .map_or("", |end| &rest[start + end + 2..]);
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// Compares the Swift names against the committed snapshot and fails on unexpected changes.
+///
+/// A missing snapshot is an error as well. The snapshot is written, or updated, only if the changes
+/// are accepted by setting the `ACCEPT_SWIFT_API_CHANGES` environment variable.
+fn check_swift_api(swift: &str, snapshot_path: &Path) {
+    let api = swift_api(swift);
+    if std::env::var_os(ACCEPT_SWIFT_API_ENV).is_some() {
+        fs::write(snapshot_path, api).expect("Failed to write Swift API snapshot");
+        return;
+    }
+    let snapshot = match fs::read_to_string(snapshot_path) {
+        Ok(snapshot) => snapshot,
+        Err(e) => panic!(
+            "Failed to read Swift API snapshot {}: {}\nSet {} to create it.",
+            snapshot_path.display(),
+            e,
+            ACCEPT_SWIFT_API_ENV
+        ),
+    };
+    if snapshot != api {
+        panic!(
+            "Swift names changed unexpectedly.\nExpected:\n{}\nGenerated:\n{}\nSet {} to accept the changes.",
+            snapshot, api, ACCEPT_SWIFT_API_ENV
+        );
+    }
 }
 
 /// Determines the build mode based on the CONFIGURATION environment variable.
@@ -27,7 +385,13 @@ fn determine_build_mode() -> &'static str {
 }
 
 /// Builds the Swift bindings and XCFramework for the specified library and build mode.
-fn build_bindings(library_name: &str, mode: &str) {
+fn build_bindings(
+    library_name: &str,
+    names: &BindingNames,
+    mode: &str,
+    profile: Profile,
+    size_report: bool,
+) {
     // Get the root directory of this Cargo project
     let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")
         .map(PathBuf::from)
@@ -41,8 +405,8 @@ fn build_bindings(library_name: &str, mode: &str) {
 
     // Define directories for Swift bindings and output bindings
     let swift_bindings_dir = tmp_dir.join("SwiftBindings");
-    let bindings_out = create_bindings_out_dir(&tmp_dir);
-    let framework_out = bindings_out.join("EzklCore.xcframework");
+    let bindings_out = create_bindings_out_dir(&tmp_dir, names);
+    let framework_out = bindings_out.join(names.framework());
 
     // Define target architectures for building
     #[allow(clippy::useless_vec)]
@@ -54,38 +418,109 @@ fn build_bindings(library_name: &str, mode: &str) {
     // Build the library for each architecture and combine them
     let out_lib_paths: Vec<PathBuf> = target_archs
         .iter()
-        .map(|archs| build_combined_archs(library_name, archs, &build_dir, mode))
+        .map(|archs| build_combined_archs(library_name, archs, &build_dir, mode, profile))
         .collect();
 
-    // Generate the path to the built dynamic library (.dylib)
-    let out_dylib_path = build_dir.join(format!(
-        "{}/{}/lib{}.dylib",
-        target_archs[0][0], mode, library_name
-    ));
+    // Build the dynamic library for the host in this run, as the bindings are generated from it
+    let out_dylib_path = build_host_dylib(library_name, &build_dir, mode, profile);
+    // The binaries in `src/bin` are not part of the library, so changing them does not make it stale
+    let sources = [manifest_dir.join("src"), manifest_dir.join("Cargo.toml")];
+    let excluded = [manifest_dir.join("src").join("bin")];
+    if let Err(e) = check_fresh(&out_dylib_path, &sources, &excluded) {
+        panic!("Refusing to generate bindings from a stale library: {}", e);
+    }
 
     // Generate Swift bindings using uniffi_bindgen
     generate_ios_bindings(&out_dylib_path, &swift_bindings_dir)
         .expect("Failed to generate iOS bindings");
 
-    // Move the generated Swift file to the bindings output directory
-    fs::rename(
-        swift_bindings_dir.join(format!("{}.swift", library_name)),
-        bindings_out.join("EzklCore.swift"),
+    // Move the generated Swift file to the bindings output directory, importing the FFI module by its name
+    let generated_ffi_module = format!("{}FFI", library_name);
+    let swift_file = swift_bindings_dir.join(format!("{}.swift", library_name));
+    let swift_source = fs::read_to_string(&swift_file).expect("Failed to read swift bindings file");
+    let swift_source = add_enum_defaults(&swift_source, SWIFT_ENUM_DEFAULTS);
+    if let Some(snapshot) = profile.swift_api_snapshot() {
+        check_swift_api(&swift_source, &manifest_dir.join(snapshot));
+    }
+    fs::write(
+        bindings_out.join(names.swift_file()),
+        rename_ffi_module(&swift_source, &generated_ffi_module, &names.ffi_module),
     )
     .expect("Failed to copy swift bindings file");
+    fs::remove_file(swift_file).expect("Failed to remove swift bindings file");
 
-    // Rename the `ios_ezklFFI.modulemap` file to `module.modulemap`
-    fs::rename(
-        swift_bindings_dir.join(format!("{}FFI.modulemap", library_name)),
+    // Rename the `ios_ezklFFI.modulemap` file to `module.modulemap`, declaring the FFI module by its name
+    let modulemap_file = swift_bindings_dir.join(format!("{}.modulemap", generated_ffi_module));
+    let modulemap = fs::read_to_string(&modulemap_file).expect("Failed to read modulemap file");
+    fs::write(
         swift_bindings_dir.join("module.modulemap"),
+        rename_ffi_module(&modulemap, &generated_ffi_module, &names.ffi_module),
     )
     .expect("Failed to rename modulemap file");
+    fs::remove_file(modulemap_file).expect("Failed to rename modulemap file");
 
     // Create the XCFramework from the combined libraries and Swift bindings
     create_xcframework(&out_lib_paths, &swift_bindings_dir, &framework_out);
 
+    // Emit the stable C interface for non-Swift consumers and check it did not change unexpectedly
+    let include_dir = bindings_out.join("include");
+    emit_c_interface(library_name, &swift_bindings_dir, &include_dir);
+    check_exported_symbols(
+        &include_dir.join(format!("{}FFI.h", library_name)),
+        &manifest_dir.join(profile.symbols_snapshot()),
+    );
+
+    // Report what each slice contributes to the app size, compared with the previous build
+    if size_report {
+        let previous = read_size_report(
+            &manifest_dir
+                .join(names.bindings_dir())
+                .join(SIZE_REPORT_FILE),
+        );
+        let slices = target_archs
+            .concat()
+            .iter()
+            .map(|arch| {
+                let lib = lib_path(&build_dir, arch, mode, library_name, "a");
+                measure_slice(arch, &lib)
+                    .unwrap_or_else(|e| panic!("Failed to measure {}: {}", arch, e))
+            })
+            .collect();
+        let report = with_deltas(
+            SizeReport {
+                cargo_profile: mode.to_string(),
+                slices,
+            },
+            previous.as_ref(),
+        );
+        write_size_report(&bindings_out, &report).expect("Failed to write the size report");
+        print!("{}", size_report_markdown(&report));
+    }
+
+    // Zip the XCFramework for distribution and record what went into the build
+    let zip_path = bindings_out.join(names.framework_zip());
+    zip_xcframework(&framework_out, &zip_path);
+    let lock = fs::read_to_string(manifest_dir.join("Cargo.lock")).unwrap_or_default();
+    let manifest = BuildManifest {
+        module_name: names.module.clone(),
+        profile: profile.name().to_string(),
+        cargo_profile: mode.to_string(),
+        features: profile.features().iter().map(|f| f.to_string()).collect(),
+        targets: target_archs
+            .concat()
+            .iter()
+            .map(|t| t.to_string())
+            .collect(),
+        rustc_version: rustc_version(),
+        ezkl_revision: ezkl_revision(&lock).unwrap_or_else(|| "unknown".to_string()),
+        xcframework_zip_sha256: sha256_file(&zip_path).expect("Failed to hash the XCFramework zip"),
+        files: hash_tree(&bindings_out).expect("Failed to hash the bindings"),
+    };
+    write_manifest(&bindings_out.join(MANIFEST_FILE), &manifest)
+        .expect("Failed to write the build manifest");
+
     // Define the destination directory for the bindings
-    let bindings_dest = manifest_dir.join("EzklCoreBindings");
+    let bindings_dest = manifest_dir.join(names.bindings_dir());
     if bindings_dest.exists() {
         fs::remove_dir_all(&bindings_dest).expect("Failed to remove existing bindings directory");
     }
@@ -99,8 +534,8 @@ fn build_bindings(library_name: &str, mode: &str) {
 
 /// Creates the output directory for the bindings.
 /// Returns the path to the bindings output directory.
-fn create_bindings_out_dir(base_dir: &Path) -> PathBuf {
-    let bindings_out = base_dir.join("EzklCoreBindings");
+fn create_bindings_out_dir(base_dir: &Path, names: &BindingNames) -> PathBuf {
+    let bindings_out = base_dir.join(names.bindings_dir());
     fs::create_dir_all(&bindings_out).expect("Failed to create bindings output directory");
     bindings_out
 }
@@ -112,19 +547,26 @@ fn build_combined_archs(
     archs: &[&str],
     build_dir: &Path,
     mode: &str,
+    profile: Profile,
 ) -> PathBuf {
     // Build the library for each architecture
     let out_lib_paths: Vec<PathBuf> = archs
         .iter()
         .map(|&arch| {
-            build_for_arch(arch, build_dir, mode);
-            build_dir
-                .join(arch)
-                .join(mode)
-                .join(format!("lib{}.a", library_name))
+            build_for_arch(arch, build_dir, mode, profile);
+            lib_path(build_dir, arch, mode, library_name, "a")
         })
         .collect();
 
+    // Make sure every slice was built, so that a failure names the missing slices
+    let missing = missing_paths(&out_lib_paths);
+    if !missing.is_empty() {
+        panic!(
+            "Cannot combine libraries for {:?}, missing slices: {:?}",
+            archs, missing
+        );
+    }
+
     // Create a unique temporary directory for the combined library
     let lib_out = mktemp_local(build_dir).join(format!("lib{}.a", library_name));
 
@@ -146,8 +588,31 @@ fn build_combined_archs(
     lib_out
 }
 
+/// Returns the path of a library built by cargo for an architecture: `build_dir/arch/mode/lib<name>.<extension>`.
+fn lib_path(
+    build_dir: &Path,
+    arch: &str,
+    mode: &str,
+    library_name: &str,
+    extension: &str,
+) -> PathBuf {
+    build_dir
+        .join(arch)
+        .join(mode)
+        .join(format!("lib{}.{}", library_name, extension))
+}
+
+/// Returns the paths that do not exist.
+fn missing_paths(paths: &[PathBuf]) -> Vec<PathBuf> {
+    paths
+        .iter()
+        .filter(|path| !path.exists())
+        .cloned()
+        .collect()
+}
+
 /// Builds the library for a specific architecture.
-fn build_for_arch(arch: &str, build_dir: &Path, mode: &str) {
+fn build_for_arch(arch: &str, build_dir: &Path, mode: &str, profile: Profile) {
     // Ensure the target architecture is installed
     install_arch(arch);
 
@@ -159,6 +624,7 @@ fn build_for_arch(arch: &str, build_dir: &Path, mode: &str) {
     }
     build_cmd
         .arg("--lib")
+        .args(profile.cargo_args())
         .env("CARGO_BUILD_TARGET_DIR", build_dir)
         .env("CARGO_BUILD_TARGET", arch);
 
@@ -168,6 +634,78 @@ fn build_for_arch(arch: &str, build_dir: &Path, mode: &str) {
     }
 }
 
+/// Builds the dynamic library for the host and returns its path.
+fn build_host_dylib(library_name: &str, build_dir: &Path, mode: &str, profile: Profile) -> PathBuf {
+    let mut build_cmd = Command::new("cargo");
+    build_cmd.arg("build");
+    if mode == "release" {
+        build_cmd.arg("--release");
+    }
+    build_cmd
+        .arg("--lib")
+        .args(profile.cargo_args())
+        .env("CARGO_BUILD_TARGET_DIR", build_dir)
+        .env_remove("CARGO_BUILD_TARGET");
+
+    let status = build_cmd.status().expect("Failed to run cargo build");
+    if !status.success() {
+        panic!("cargo build failed for the host dynamic library");
+    }
+
+    build_dir.join(mode).join(format!(
+        "{}{}{}",
+        std::env::consts::DLL_PREFIX,
+        library_name,
+        std::env::consts::DLL_SUFFIX
+    ))
+}
+
+/// Checks that the artifact exists and is newer than every file in the given source paths,
+/// except those at or below the excluded paths.
+fn check_fresh(artifact: &Path, sources: &[PathBuf], excluded: &[PathBuf]) -> Result<(), String> {
+    let built = fs::metadata(artifact)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| format!("cannot read {}: {}", artifact.display(), e))?;
+
+    let mut newest: Option<(SystemTime, PathBuf)> = None;
+    for source in sources {
+        newest_file(source, excluded, &mut newest)
+            .map_err(|e| format!("cannot read {}: {}", source.display(), e))?;
+    }
+
+    match newest {
+        Some((modified, path)) if modified > built => Err(format!(
+            "{} is older than {}; rebuild the library",
+            artifact.display(),
+            path.display()
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Finds the most recently modified file at or below `path`, skipping the excluded paths.
+fn newest_file(
+    path: &Path,
+    excluded: &[PathBuf],
+    newest: &mut Option<(SystemTime, PathBuf)>,
+) -> std::io::Result<()> {
+    if excluded.iter().any(|excluded| path.starts_with(excluded)) {
+        return Ok(());
+    }
+    let metadata = fs::metadata(path)?;
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path)? {
+            newest_file(&entry?.path(), excluded, newest)?;
+        }
+    } else {
+        let modified = metadata.modified()?;
+        if newest.as_ref().is_none_or(|(time, _)| modified > *time) {
+            *newest = Some((modified, path.to_path_buf()));
+        }
+    }
+    Ok(())
+}
+
 /// Installs the specified target architecture using rustup.
 fn install_arch(arch: &str) {
     let status = Command::new("rustup")
@@ -233,6 +771,486 @@ fn create_xcframework(lib_paths: &[PathBuf], swift_bindings_dir: &Path, framewor
     }
 }
 
+/// Emits the C header, the modulemap and a description of the uniffi contract into `include_dir`.
+///
+/// These give Dart FFI, JSI and other non-Swift consumers stable names to build against.
+fn emit_c_interface(library_name: &str, swift_bindings_dir: &Path, include_dir: &Path) {
+    fs::create_dir_all(include_dir).expect("Failed to create include directory");
+
+    let header = format!("{}FFI.h", library_name);
+    fs::copy(swift_bindings_dir.join(&header), include_dir.join(&header))
+        .expect("Failed to copy C header");
+    fs::copy(
+        swift_bindings_dir.join("module.modulemap"),
+        include_dir.join("module.modulemap"),
+    )
+    .expect("Failed to copy modulemap");
+
+    let header_contents =
+        fs::read_to_string(include_dir.join(&header)).expect("Failed to read C header");
+    let symbols = exported_symbols(&header_contents, library_name);
+    fs::write(
+        include_dir.join("ezkl_c_api.h"),
+        c_api_description(library_name, &symbols),
+    )
+    .expect("Failed to write C API description");
+}
+
+/// Extracts the uniffi symbols exported by the library from its C header.
+fn exported_symbols(header: &str, library_name: &str) -> BTreeSet<String> {
+    let prefixes = [
+        format!("uniffi_{}_", library_name),
+        format!("ffi_{}_", library_name),
+    ];
+    header
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .filter(|token| {
+            prefixes
+                .iter()
+                .any(|prefix| token.starts_with(prefix.as_str()))
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Renders a header documenting the uniffi calling convention of the exported functions.
+fn c_api_description(library_name: &str, symbols: &BTreeSet<String>) -> String {
+    let function_prefix = format!("uniffi_{}_fn_func_", library_name);
+    let functions: Vec<&str> = symbols
+        .iter()
+        .filter_map(|symbol| symbol.strip_prefix(function_prefix.as_str()))
+        .collect();
+
+    let mut description = format!(
+        "// Generated by gen-bindings. Do not edit.
+//
+// C interface of `{lib}`, following the uniffi calling convention:
+//
+// * Exported functions are named `{fn_prefix}<name>` and their checksums
+//   `uniffi_{lib}_checksum_func_<name>`; the namespace `{lib}` is pinned and
+//   does not change between releases.
+// * Strings, byte arrays, records and enums are passed as `RustBuffer`s, allocated
+//   and freed with `ffi_{lib}_rustbuffer_alloc` / `ffi_{lib}_rustbuffer_free`.
+// * Every function takes a trailing `RustCallStatus *`. A `code` of 0 means success,
+//   1 means the function returned an `EZKLError`, serialized into `error_buf` as a
+//   big-endian i32 variant index (starting at 1) followed by the variant's fields,
+//   and 2 means a panic, with the message in `error_buf`.
+// * Async functions return a future handle driven with the
+//   `ffi_{lib}_rust_future_*` functions.
+//
+// Exported functions:
+",
+        lib = library_name,
+        fn_prefix = function_prefix,
+    );
+    for function in functions {
+        description.push_str(&format!("//   {}\n", function));
+    }
+    description.push_str(&format!("\n#include \"{}FFI.h\"\n", library_name));
+    description
+}
+
+/// Compares the exported symbols against the committed snapshot and fails on unexpected changes.
+///
+/// A missing snapshot is an error as well. The snapshot is written, or updated, only if the changes
+/// are accepted by setting the `ACCEPT_FFI_SYMBOL_CHANGES` environment variable.
+fn check_exported_symbols(header_path: &Path, snapshot_path: &Path) {
+    let header = fs::read_to_string(header_path).expect("Failed to read C header");
+    let symbols = exported_symbols(&header, LIBRARY_NAME);
+    let rendered: String = symbols
+        .iter()
+        .map(|symbol| format!("{}\n", symbol))
+        .collect();
+
+    if std::env::var_os(ACCEPT_SYMBOLS_ENV).is_some() {
+        fs::write(snapshot_path, rendered).expect("Failed to write symbols snapshot");
+        return;
+    }
+    let snapshot = match fs::read_to_string(snapshot_path) {
+        Ok(snapshot) => snapshot,
+        Err(e) => panic!(
+            "Failed to read symbols snapshot {}: {}\nSet {} to create it.",
+            snapshot_path.display(),
+            e,
+            ACCEPT_SYMBOLS_ENV
+        ),
+    };
+    let expected: BTreeSet<String> = snapshot.lines().map(str::to_string).collect();
+    let (added, removed) = diff_symbols(&expected, &symbols);
+    if !added.is_empty() || !removed.is_empty() {
+        panic!(
+            "Exported C symbols changed unexpectedly.\nAdded: {:?}\nRemoved: {:?}\nSet {} to accept the changes.",
+            added, removed, ACCEPT_SYMBOLS_ENV
+        );
+    }
+}
+
+/// Returns the symbols added and removed relative to the expected set.
+fn diff_symbols(
+    expected: &BTreeSet<String>,
+    actual: &BTreeSet<String>,
+) -> (Vec<String>, Vec<String>) {
+    let added = actual.difference(expected).cloned().collect();
+    let removed = expected.difference(actual).cloned().collect();
+    (added, removed)
+}
+
+/// Reads the `--verify-manifest <path>` (or `--verify-manifest=<path>`) flag from the arguments.
+fn parse_verify_manifest(
+    mut args: impl Iterator<Item = String>,
+) -> Result<Option<PathBuf>, String> {
+    while let Some(arg) = args.next() {
+        if arg == "--verify-manifest" {
+            return args
+                .next()
+                .map(|path| Some(PathBuf::from(path)))
+                .ok_or_else(|| "--verify-manifest requires a path".to_string());
+        } else if let Some(path) = arg.strip_prefix("--verify-manifest=") {
+            return Ok(Some(PathBuf::from(path)));
+        }
+    }
+    Ok(None)
+}
+
+/// What went into a build of the bindings, written next to the XCFramework.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct BuildManifest {
+    /// The name of the Swift module.
+    module_name: String,
+    /// The profile passed to `--profile`.
+    profile: String,
+    /// The cargo profile, `release` or `debug`.
+    cargo_profile: String,
+    /// The cargo features the library was built with.
+    features: Vec<String>,
+    /// The target triples of the slices in the XCFramework.
+    targets: Vec<String>,
+    /// The output of `rustc --version`.
+    rustc_version: String,
+    /// The git revision of ezkl, from `Cargo.lock`.
+    ezkl_revision: String,
+    /// The sha256 of the zipped XCFramework.
+    xcframework_zip_sha256: String,
+    /// The sha256 of every file in the bindings directory, by path relative to it.
+    files: BTreeMap<String, String>,
+}
+
+/// Zips the XCFramework with `ditto`, which keeps the framework's symlinks and metadata.
+fn zip_xcframework(framework: &Path, zip_path: &Path) {
+    let status = Command::new("ditto")
+        .args(["-c", "-k", "--sequesterRsrc", "--keepParent"])
+        .arg(framework)
+        .arg(zip_path)
+        .status()
+        .expect("Failed to run ditto");
+    if !status.success() {
+        panic!(
+            "ditto failed to zip the XCFramework with status: {}",
+            status
+        );
+    }
+}
+
+/// Returns the version of the rustc cargo builds with.
+fn rustc_version() -> String {
+    Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string()))
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Reads the git revision ezkl is locked to from the contents of `Cargo.lock`.
+fn ezkl_revision(lock: &str) -> Option<String> {
+    let package = lock
+        .split("[[package]]")
+        .find(|package| package.lines().any(|line| line.trim() == "name = \"ezkl\""))?;
+    let source = package
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("source = "))?
+        .trim_matches('"');
+    Some(match source.rsplit_once('#') {
+        Some((_, revision)) => revision.to_string(),
+        None => source.to_string(),
+    })
+}
+
+fn sha256_file(path: &Path) -> std::io::Result<String> {
+    Ok(hex::encode(Sha256::digest(fs::read(path)?)))
+}
+
+/// Hashes every file under `dir` except the manifest, by path relative to `dir`.
+fn hash_tree(dir: &Path) -> std::io::Result<BTreeMap<String, String>> {
+    fn visit(root: &Path, dir: &Path, files: &mut BTreeMap<String, String>) -> std::io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            // Frameworks contain symlinks to their current version, which are hashed through
+            if path.is_dir() {
+                visit(root, &path, files)?;
+                continue;
+            }
+            let relative = path
+                .strip_prefix(root)
+                .expect("walked outside the root")
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            if relative != MANIFEST_FILE {
+                files.insert(relative, sha256_file(&path)?);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = BTreeMap::new();
+    visit(dir, dir, &mut files)?;
+    Ok(files)
+}
+
+fn write_manifest(path: &Path, manifest: &BuildManifest) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(manifest)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    fs::write(path, json)
+}
+
+/// Re-hashes the bindings next to a manifest and compares them with it.
+///
+/// Returns the number of files checked, or every file that is missing, changed or not listed.
+fn verify_manifest(manifest_path: &Path) -> Result<usize, Vec<String>> {
+    let manifest: BuildManifest = fs::read_to_string(manifest_path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        .map_err(|e| vec![format!("failed to read {}: {}", manifest_path.display(), e)])?;
+    let dir = manifest_path.parent().unwrap_or(Path::new("."));
+    let actual =
+        hash_tree(dir).map_err(|e| vec![format!("failed to hash {}: {}", dir.display(), e)])?;
+
+    let mut problems = vec![];
+    for (name, expected) in &manifest.files {
+        match actual.get(name) {
+            None => problems.push(format!("missing: {}", name)),
+            Some(digest) if digest != expected => problems.push(format!("changed: {}", name)),
+            Some(_) => {}
+        }
+    }
+    for name in actual
+        .keys()
+        .filter(|name| !manifest.files.contains_key(*name))
+    {
+        problems.push(format!("not in the manifest: {}", name));
+    }
+    let zip = format!("{}.xcframework.zip", manifest.module_name);
+    if actual.get(&zip) != Some(&manifest.xcframework_zip_sha256) {
+        problems.push(format!("the XCFramework zip {} does not match", zip));
+    }
+
+    if problems.is_empty() {
+        Ok(manifest.files.len())
+    } else {
+        Err(problems)
+    }
+}
+
+/// Returns whether the `--size-report` flag is among the arguments.
+fn parse_size_report(mut args: impl Iterator<Item = String>) -> bool {
+    args.any(|arg| arg == "--size-report")
+}
+
+/// The size each target slice of the static library contributes, written by `--size-report`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SizeReport {
+    /// The cargo profile, `release` or `debug`.
+    cargo_profile: String,
+    /// The size of each slice.
+    slices: Vec<SliceSize>,
+}
+
+/// The size of the static library built for one target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SliceSize {
+    /// The target triple of the slice.
+    target: String,
+    /// The size of the archive on disk, including symbol tables and debug info.
+    file_bytes: u64,
+    /// The code and data of every object in the archive, as reported by `size`, which is what
+    /// the linker can pull into the app.
+    linked_bytes: u64,
+    /// The change of `linked_bytes` since the previous report, if it had the target.
+    delta_bytes: Option<i64>,
+    /// The code and data per crate, largest first.
+    crates: Vec<CrateSize>,
+}
+
+/// The code and data of the objects of one crate in a slice.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CrateSize {
+    /// The crate name, or `[other objects]` for objects not compiled by rustc, such as C code
+    /// built by build scripts.
+    name: String,
+    bytes: u64,
+    /// The change since the previous report, if it listed the crate for the same target.
+    delta_bytes: Option<i64>,
+}
+
+/// Runs `size` on the static library of a target and sums up the sizes of its objects by crate.
+fn measure_slice(target: &str, lib: &Path) -> Result<SliceSize, String> {
+    let file_bytes = fs::metadata(lib).map_err(|e| e.to_string())?.len();
+    let output = Command::new("size")
+        .arg(lib)
+        .output()
+        .map_err(|e| format!("failed to run size: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "size failed with status {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let objects = parse_size_output(&String::from_utf8_lossy(&output.stdout))?;
+    Ok(slice_size(target, file_bytes, &objects))
+}
+
+/// Parses the output of `size` on an archive into the total size of each object.
+///
+/// Both the Berkeley format of GNU and LLVM `size`, whose `filename` column reads
+/// `object.o (ex libfoo.a)`, and the format of the Xcode `size`, with segment columns and
+/// `libfoo.a(object.o)`, are read by their `dec` column.
+fn parse_size_output(output: &str) -> Result<Vec<(String, u64)>, String> {
+    let mut lines = output.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next().ok_or("size printed nothing")?;
+    let dec_column = header
+        .split_whitespace()
+        .position(|column| column == "dec")
+        .ok_or_else(|| format!("no `dec` column in the size header `{}`", header.trim()))?;
+
+    // Some versions repeat the header for every member of an archive
+    lines
+        .filter(|line| line.trim() != header.trim())
+        .map(|line| {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            let dec = columns
+                .get(dec_column)
+                .and_then(|dec| dec.parse::<u64>().ok())
+                .ok_or_else(|| format!("unexpected size output `{}`", line.trim()))?;
+            // The file name follows the `hex` column after `dec`, and may contain spaces
+            let name = columns.get(dec_column + 2..).unwrap_or_default().join(" ");
+            let object = match name.split_once(" (ex ") {
+                Some((object, _)) => object.to_string(),
+                None => match name.split_once('(') {
+                    Some((_, object)) => object.trim_end_matches(')').to_string(),
+                    None => name,
+                },
+            };
+            Ok((object, dec))
+        })
+        .collect()
+}
+
+/// Returns the crate an object of a static library was compiled from.
+///
+/// rustc names the objects of a crate `<crate>-<hash>.<codegen unit>.rcgu.o`, while build scripts
+/// compiling C code name them freely.
+fn crate_of_object(object: &str) -> String {
+    match object.split_once('-') {
+        Some((name, _)) if object.ends_with(".rcgu.o") => name.to_string(),
+        _ => "[other objects]".to_string(),
+    }
+}
+
+/// Sums up the sizes of the objects of a slice by crate, largest first.
+fn slice_size(target: &str, file_bytes: u64, objects: &[(String, u64)]) -> SliceSize {
+    let mut by_crate: BTreeMap<String, u64> = BTreeMap::new();
+    for (object, bytes) in objects {
+        *by_crate.entry(crate_of_object(object)).or_default() += bytes;
+    }
+    let mut crates: Vec<CrateSize> = by_crate
+        .into_iter()
+        .map(|(name, bytes)| CrateSize {
+            name,
+            bytes,
+            delta_bytes: None,
+        })
+        .collect();
+    crates.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+
+    SliceSize {
+        target: target.to_string(),
+        file_bytes,
+        linked_bytes: crates.iter().map(|c| c.bytes).sum(),
+        delta_bytes: None,
+        crates,
+    }
+}
+
+/// Fills in the changes since a previous report.
+fn with_deltas(mut report: SizeReport, previous: Option<&SizeReport>) -> SizeReport {
+    let delta = |now: u64, before: u64| now as i64 - before as i64;
+    for slice in &mut report.slices {
+        let Some(before) =
+            previous.and_then(|previous| previous.slices.iter().find(|s| s.target == slice.target))
+        else {
+            continue;
+        };
+        slice.delta_bytes = Some(delta(slice.linked_bytes, before.linked_bytes));
+        for krate in &mut slice.crates {
+            krate.delta_bytes = before
+                .crates
+                .iter()
+                .find(|c| c.name == krate.name)
+                .map(|c| delta(krate.bytes, c.bytes));
+        }
+    }
+    report
+}
+
+/// Renders the largest crates of each slice as markdown tables.
+fn size_report_markdown(report: &SizeReport) -> String {
+    fn delta(delta_bytes: Option<i64>) -> String {
+        match delta_bytes {
+            Some(delta) => format!("{:+}", delta),
+            None => "new".to_string(),
+        }
+    }
+
+    let mut markdown = format!("# Binary size ({})\n", report.cargo_profile);
+    for slice in &report.slices {
+        markdown.push_str(&format!(
+            "\n## {}\n\nLinked: {} bytes ({}), archive: {} bytes\n\n| Crate | Bytes | Change |\n| --- | ---: | ---: |\n",
+            slice.target,
+            slice.linked_bytes,
+            delta(slice.delta_bytes),
+            slice.file_bytes
+        ));
+        for krate in slice.crates.iter().take(SIZE_REPORT_TOP_CRATES) {
+            markdown.push_str(&format!(
+                "| {} | {} | {} |\n",
+                krate.name,
+                krate.bytes,
+                delta(krate.delta_bytes)
+            ));
+        }
+    }
+    markdown
+}
+
+/// Reads the report of a previous build, if there is one.
+fn read_size_report(path: &Path) -> Option<SizeReport> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn write_size_report(dir: &Path, report: &SizeReport) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(report)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    fs::write(dir.join(SIZE_REPORT_FILE), json)?;
+    fs::write(
+        dir.join(SIZE_REPORT_MARKDOWN_FILE),
+        size_report_markdown(report),
+    )
+}
+
 /// Creates a temporary directory inside the build path with a unique UUID.
 /// This ensures unique build artifacts for concurrent builds.
 fn mktemp_local(build_path: &Path) -> PathBuf {
@@ -261,3 +1279,611 @@ fn cleanup_temp_dirs(build_dir: &Path) {
         fs::remove_dir_all(tmp_dir).expect("Failed to remove temporary directories");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const HEADER: &str = "
+void uniffi_ios_ezkl_fn_func_prove(RustBuffer witness_json, RustCallStatus *_Nonnull out_status);
+uint16_t uniffi_ios_ezkl_checksum_func_prove(void);
+RustBuffer ffi_ios_ezkl_rustbuffer_alloc(uint64_t size, RustCallStatus *_Nonnull out_status);
+void other_library_fn(void);
+";
+
+    const SWIFT_SOURCE: &str = "
+#if canImport(ios_ezklFFI)
+    import ios_ezklFFI
+#endif
+private let ios_ezklFFIVersion = 1
+";
+
+    const SWIFT_RECORDS: &str = "
+public enum CheckModeWrapper {
+    
+    case safe
+    case unchecked
+}
+
+public struct ProveOptions {
+    /**
+     * The proof type.
+     */
+    public var proofType: ProofTypeWrapper
+    public var checkMode: CheckModeWrapper
+    public var expectedVariables: [VariableBinding]
+
+    // Default memberwise initializers are never public by default, so we
+    // declare one manually.
+    public init(
+        /**
+         * The proof type.
+         */proofType: ProofTypeWrapper, checkMode: CheckModeWrapper, expectedVariables: [VariableBinding] = []) {
+        self.proofType = proofType
+        self.checkMode = checkMode
+        self.expectedVariables = expectedVariables
+    }
+}
+";
+
+    const MODULEMAP: &str = "module ios_ezklFFI {
+    header \"ios_ezklFFI.h\"
+    export *
+}
+";
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_parse_module_name() {
+        assert_eq!(parse_module_name(args(&["--debug"])), Ok(None));
+        assert_eq!(
+            parse_module_name(args(&["--module-name", "AcmeProver"])),
+            Ok(Some("AcmeProver".to_string()))
+        );
+        assert_eq!(
+            parse_module_name(args(&["--debug", "--module-name=Acme_2"])),
+            Ok(Some("Acme_2".to_string()))
+        );
+        assert!(parse_module_name(args(&["--module-name"])).is_err());
+        assert!(parse_module_name(args(&["--module-name", "2Acme"])).is_err());
+        assert!(parse_module_name(args(&["--module-name", "Acme-Prover"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        assert_eq!(parse_profile(args(&["--debug"])), Ok(Profile::Full));
+        assert_eq!(
+            parse_profile(args(&["--profile", "verifier"])),
+            Ok(Profile::Verifier)
+        );
+        assert_eq!(
+            parse_profile(args(&["--module-name", "Acme", "--profile=full"])),
+            Ok(Profile::Full)
+        );
+        assert!(parse_profile(args(&["--profile"])).is_err());
+        assert!(parse_profile(args(&["--profile", "prover"])).is_err());
+    }
+
+    #[test]
+    fn test_profiles() {
+        assert!(Profile::Full.cargo_args().is_empty());
+        assert_eq!(
+            Profile::Verifier.cargo_args(),
+            ["--no-default-features", "--features", "verifier"]
+        );
+        assert_eq!(Profile::Full.features(), ["prover"]);
+        assert_eq!(Profile::Verifier.features(), ["verifier"]);
+        assert_eq!(Profile::Verifier.name(), "verifier");
+        assert_ne!(
+            Profile::Full.symbols_snapshot(),
+            Profile::Verifier.symbols_snapshot()
+        );
+        assert_eq!(Profile::Full.swift_api_snapshot(), Some(SWIFT_API_SNAPSHOT));
+        assert_eq!(Profile::Verifier.swift_api_snapshot(), None);
+
+        let verifier = Profile::Verifier.default_names("ios_ezkl");
+        assert_eq!(verifier.framework(), "EzklVerifier.xcframework");
+        assert_eq!(verifier.ffi_module, "EzklVerifierFFI");
+        assert_eq!(
+            Profile::Full.default_names("ios_ezkl"),
+            BindingNames::default_for("ios_ezkl")
+        );
+    }
+
+    #[test]
+    fn test_binding_names() {
+        let default = BindingNames::default_for("ios_ezkl");
+        assert_eq!(default.swift_file(), "EzklCore.swift");
+        assert_eq!(default.framework(), "EzklCore.xcframework");
+        assert_eq!(default.bindings_dir(), "EzklCoreBindings");
+        assert_eq!(default.framework_zip(), "EzklCore.xcframework.zip");
+        assert_eq!(default.ffi_module, "ios_ezklFFI");
+
+        let custom = BindingNames::custom("AcmeProver");
+        assert_eq!(custom.swift_file(), "AcmeProver.swift");
+        assert_eq!(custom.framework(), "AcmeProver.xcframework");
+        assert_eq!(custom.bindings_dir(), "AcmeProverBindings");
+        assert_eq!(custom.ffi_module, "AcmeProverFFI");
+    }
+
+    #[test]
+    fn test_add_enum_defaults() {
+        let swift = add_enum_defaults(SWIFT_RECORDS, SWIFT_ENUM_DEFAULTS);
+        assert!(swift.contains(
+            "*/proofType: ProofTypeWrapper = .single, checkMode: CheckModeWrapper = .safe, expectedVariables: [VariableBinding] = []) {"
+        ));
+        // The stored properties are left alone
+        assert!(swift.contains("public var proofType: ProofTypeWrapper\n"));
+        // Fields that already have a default keep it
+        assert_eq!(add_enum_defaults(&swift, SWIFT_ENUM_DEFAULTS), swift);
+        // Bindings without the record are left untouched
+        assert_eq!(
+            add_enum_defaults(SWIFT_SOURCE, SWIFT_ENUM_DEFAULTS),
+            SWIFT_SOURCE
+        );
+    }
+
+    #[test]
+    fn test_swift_api() {
+        let swift = add_enum_defaults(SWIFT_RECORDS, SWIFT_ENUM_DEFAULTS);
+        assert_eq!(
+            swift_api(&swift),
+            "CheckModeWrapper.safe
+CheckModeWrapper.unchecked
+ProveOptions(proofType: ProofTypeWrapper = .single, checkMode: CheckModeWrapper = .safe, expectedVariables: [VariableBinding] = [])
+"
+        );
+    }
+
+    #[test]
+    fn test_committed_swift_api_snapshot() {
+        let snapshot =
+            fs::read_to_string(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(SWIFT_API_SNAPSHOT))
+                .expect("The Swift API snapshot is committed");
+        // No case is a Swift keyword that callers would have to escape
+        for keyword in ["unsafe", "default", "protocol", "self"] {
+            assert!(
+                !snapshot
+                    .lines()
+                    .any(|line| line.ends_with(&format!(".{}", keyword))),
+                "{}",
+                snapshot
+            );
+        }
+        assert!(snapshot.contains("CheckModeWrapper.safe\nCheckModeWrapper.unchecked\n"));
+        // Every field of `ProveOptions` has a default, so Swift callers can write `ProveOptions()`
+        let init = snapshot
+            .lines()
+            .find_map(|line| line.strip_prefix("ProveOptions("))
+            .expect("The snapshot records the ProveOptions initializer");
+        for param in init.trim_end_matches(')').split(", ") {
+            assert!(param.contains(" = "), "{} has no default", param);
+        }
+    }
+
+    #[test]
+    fn test_rename_ffi_module() {
+        let names = BindingNames::custom("AcmeProver");
+
+        let swift = rename_ffi_module(SWIFT_SOURCE, "ios_ezklFFI", &names.ffi_module);
+        assert!(swift.contains("#if canImport(AcmeProverFFI)"));
+        assert!(swift.contains("import AcmeProverFFI\n"));
+        // Identifiers merely containing the module name are left alone
+        assert!(swift.contains("ios_ezklFFIVersion"));
+
+        let modulemap = rename_ffi_module(MODULEMAP, "ios_ezklFFI", &names.ffi_module);
+        assert!(modulemap.starts_with("module AcmeProverFFI {"));
+        // The header keeps the name derived from the uniffi namespace
+        assert!(modulemap.contains("header \"ios_ezklFFI.h\""));
+
+        // Every reference to the module is renamed, and nothing else
+        for renamed in [&swift, &modulemap] {
+            let remaining = renamed.matches("ios_ezklFFI").count();
+            let kept = renamed.matches("ios_ezklFFI.h").count()
+                + renamed.matches("ios_ezklFFIVersion").count();
+            assert_eq!(remaining, kept);
+        }
+
+        // The default names leave the generated files untouched
+        let default = BindingNames::default_for("ios_ezkl");
+        assert_eq!(
+            rename_ffi_module(MODULEMAP, "ios_ezklFFI", &default.ffi_module),
+            MODULEMAP
+        );
+    }
+
+    #[test]
+    fn test_lib_path_absolute_base() {
+        assert_eq!(
+            lib_path(
+                Path::new("/tmp/ezkl/build"),
+                "aarch64-apple-ios",
+                "release",
+                "ios_ezkl",
+                "a"
+            ),
+            PathBuf::from("/tmp/ezkl/build/aarch64-apple-ios/release/libios_ezkl.a")
+        );
+    }
+
+    #[test]
+    fn test_lib_path_relative_base() {
+        assert_eq!(
+            lib_path(
+                Path::new("build"),
+                "x86_64-apple-ios",
+                "debug",
+                "ios_ezkl",
+                "dylib"
+            ),
+            PathBuf::from("build/x86_64-apple-ios/debug/libios_ezkl.dylib")
+        );
+    }
+
+    #[test]
+    fn test_lib_path_base_with_spaces() {
+        let path = lib_path(
+            Path::new("/Users/me/My Projects/ezkl ios/build"),
+            "aarch64-apple-ios-sim",
+            "release",
+            "ios_ezkl",
+            "a",
+        );
+        assert_eq!(
+            path,
+            PathBuf::from(
+                "/Users/me/My Projects/ezkl ios/build/aarch64-apple-ios-sim/release/libios_ezkl.a"
+            )
+        );
+        assert_eq!(path.components().count(), 9);
+    }
+
+    #[test]
+    fn test_missing_paths() {
+        let existing = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        let missing = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("does-not-exist.a");
+        assert_eq!(missing_paths(&[existing, missing.clone()]), vec![missing]);
+    }
+
+    #[test]
+    fn test_check_fresh() {
+        let dir = std::env::temp_dir().join(format!("gen-bindings-fresh-{}", Uuid::new_v4()));
+        let src_dir = dir.join("src");
+        let bin_dir = src_dir.join("bin");
+        fs::create_dir_all(&bin_dir).unwrap();
+        let source = src_dir.join("lib.rs");
+        let bin_source = bin_dir.join("gen-bindings.rs");
+        let artifact = dir.join("libios_ezkl.dylib");
+        fs::write(&source, "").unwrap();
+        fs::write(&bin_source, "").unwrap();
+        fs::write(&artifact, "").unwrap();
+
+        let now = SystemTime::now();
+        let set_modified = |path: &Path, time: SystemTime| {
+            fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(time)
+                .unwrap()
+        };
+
+        // The library was built after the last change to the sources
+        set_modified(&source, now - Duration::from_secs(60));
+        set_modified(&bin_source, now - Duration::from_secs(60));
+        set_modified(&artifact, now);
+        let sources = [src_dir.clone()];
+        let excluded = [bin_dir.clone()];
+        assert!(check_fresh(&artifact, &sources, &excluded).is_ok());
+
+        // Only a binary changed after the library was built
+        set_modified(&bin_source, now + Duration::from_secs(60));
+        assert!(check_fresh(&artifact, &sources, &excluded).is_ok());
+        let error = check_fresh(&artifact, &sources, &[]).unwrap_err();
+        assert!(error.contains("gen-bindings.rs"), "{}", error);
+
+        // A library source changed after the library was built
+        set_modified(&source, now + Duration::from_secs(120));
+        let error = check_fresh(&artifact, &sources, &excluded).unwrap_err();
+        assert!(error.contains("lib.rs"), "{}", error);
+
+        // The library was not built at all
+        assert!(check_fresh(&dir.join("missing.dylib"), &sources, &excluded).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_exported_symbols() {
+        let symbols = exported_symbols(HEADER, "ios_ezkl");
+        let expected: BTreeSet<String> = [
+            "ffi_ios_ezkl_rustbuffer_alloc",
+            "uniffi_ios_ezkl_checksum_func_prove",
+            "uniffi_ios_ezkl_fn_func_prove",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(symbols, expected);
+    }
+
+    #[test]
+    fn test_diff_symbols_detects_changes() {
+        let expected = exported_symbols(HEADER, "ios_ezkl");
+        let changed = exported_symbols(&HEADER.replace("func_prove", "func_prove_v2"), "ios_ezkl");
+
+        let (added, removed) = diff_symbols(&expected, &expected);
+        assert!(added.is_empty() && removed.is_empty());
+
+        let (added, removed) = diff_symbols(&expected, &changed);
+        assert_eq!(
+            added,
+            vec![
+                "uniffi_ios_ezkl_checksum_func_prove_v2".to_string(),
+                "uniffi_ios_ezkl_fn_func_prove_v2".to_string()
+            ]
+        );
+        assert_eq!(
+            removed,
+            vec![
+                "uniffi_ios_ezkl_checksum_func_prove".to_string(),
+                "uniffi_ios_ezkl_fn_func_prove".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_c_api_description_lists_functions() {
+        let symbols = exported_symbols(HEADER, "ios_ezkl");
+        let description = c_api_description("ios_ezkl", &symbols);
+        assert!(description.contains("//   prove\n"));
+        assert!(description.contains("#include \"ios_ezklFFI.h\""));
+    }
+
+    #[test]
+    fn test_parse_verify_manifest() {
+        assert_eq!(parse_verify_manifest(args(&["--debug"])), Ok(None));
+        assert_eq!(
+            parse_verify_manifest(args(&["--verify-manifest", "out/manifest.json"])),
+            Ok(Some(PathBuf::from("out/manifest.json")))
+        );
+        assert_eq!(
+            parse_verify_manifest(args(&["--verify-manifest=manifest.json"])),
+            Ok(Some(PathBuf::from("manifest.json")))
+        );
+        assert!(parse_verify_manifest(args(&["--verify-manifest"])).is_err());
+    }
+
+    #[test]
+    fn test_ezkl_revision() {
+        let lock = r#"
+[[package]]
+name = "ezkl-derive"
+version = "0.1.0"
+source = "git+https://example.com/other.git#0000000"
+
+[[package]]
+name = "ezkl"
+version = "13.0.2"
+source = "git+https://github.com/zkonduit/ezkl.git?tag=v13.0.2#778652852263979570f200554e103a8387fa4571"
+"#;
+        assert_eq!(
+            ezkl_revision(lock).as_deref(),
+            Some("778652852263979570f200554e103a8387fa4571")
+        );
+        assert_eq!(ezkl_revision("[[package]]\nname = \"serde\"\n"), None);
+    }
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let dir = std::env::temp_dir().join(format!("gen-bindings-manifest-{}", Uuid::new_v4()));
+        let slice = dir.join("Acme.xcframework/ios-arm64/libios_ezkl.a");
+        fs::create_dir_all(slice.parent().unwrap()).unwrap();
+        fs::write(&slice, "device slice").unwrap();
+        fs::write(dir.join("Acme.swift"), "import AcmeFFI").unwrap();
+        let zip = dir.join("Acme.xcframework.zip");
+        fs::write(&zip, "zipped").unwrap();
+
+        let manifest = BuildManifest {
+            module_name: "Acme".to_string(),
+            profile: Profile::Full.name().to_string(),
+            cargo_profile: "release".to_string(),
+            features: vec!["prover".to_string()],
+            targets: vec!["aarch64-apple-ios".to_string()],
+            rustc_version: "rustc 1.80.0".to_string(),
+            ezkl_revision: "778652852263979570f200554e103a8387fa4571".to_string(),
+            xcframework_zip_sha256: sha256_file(&zip).unwrap(),
+            files: hash_tree(&dir).unwrap(),
+        };
+        assert_eq!(
+            manifest.files.keys().collect::<Vec<_>>(),
+            [
+                "Acme.swift",
+                "Acme.xcframework.zip",
+                "Acme.xcframework/ios-arm64/libios_ezkl.a"
+            ]
+        );
+        assert_eq!(
+            manifest.files["Acme.swift"],
+            hex::encode(Sha256::digest(b"import AcmeFFI"))
+        );
+
+        let manifest_path = dir.join(MANIFEST_FILE);
+        write_manifest(&manifest_path, &manifest).unwrap();
+        let written: BuildManifest =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        assert_eq!(written, manifest);
+        // The manifest does not list itself
+        assert_eq!(verify_manifest(&manifest_path), Ok(3));
+
+        // A changed slice, a removed file and an unlisted file are all reported
+        fs::write(&slice, "tampered slice").unwrap();
+        fs::remove_file(dir.join("Acme.swift")).unwrap();
+        fs::write(dir.join("extra.txt"), "").unwrap();
+        let problems = verify_manifest(&manifest_path).unwrap_err();
+        assert_eq!(
+            problems,
+            [
+                "missing: Acme.swift",
+                "changed: Acme.xcframework/ios-arm64/libios_ezkl.a",
+                "not in the manifest: extra.txt"
+            ]
+        );
+
+        // A changed zip is reported on its own as well
+        fs::write(&zip, "rezipped").unwrap();
+        let problems = verify_manifest(&manifest_path).unwrap_err();
+        assert!(problems
+            .iter()
+            .any(|p| p == "the XCFramework zip Acme.xcframework.zip does not match"));
+
+        // A manifest that cannot be read is an error, not a pass
+        assert!(verify_manifest(&dir.join("missing.json")).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+    const BERKELEY_SIZE_OUTPUT: &str = "   text\t   data\t    bss\t    dec\t    hex\tfilename
+ 812344\t  10240\t      0\t 822584\t  c8d38\tezkl-3f2a9c1d0b7e6a54.ezkl.8c1f2e3a-cgu.0.rcgu.o (ex libios_ezkl.a)
+ 400000\t   2000\t      8\t 402008\t  62258\tezkl-3f2a9c1d0b7e6a54.ezkl.8c1f2e3a-cgu.1.rcgu.o (ex libios_ezkl.a)
+ 301200\t    512\t      0\t 301712\t  49a90\thalo2_proofs-0e1d2c3b4a596877.halo2_proofs.77aa01b2-cgu.0.rcgu.o (ex libios_ezkl.a)
+  12000\t    100\t      0\t  12100\t   2f44\tios_ezkl-9a8b7c6d5e4f3a2b.1x2y3z-cgu.0.rcgu.o (ex libios_ezkl.a)
+   4096\t      0\t      0\t   4096\t   1000\t1a2b3c4d5e6f7a8b-blst.o (ex libios_ezkl.a)
+";
+
+    const XCODE_SIZE_OUTPUT: &str = "__TEXT\t__DATA\t__OBJC\tothers\tdec\thex
+812344\t10240\t0\t0\t822584\tc8d38\tlibios_ezkl.a(ezkl-3f2a9c1d0b7e6a54.ezkl.8c1f2e3a-cgu.0.rcgu.o)
+__TEXT\t__DATA\t__OBJC\tothers\tdec\thex
+4096\t0\t0\t0\t4096\t1000\tlibios_ezkl.a(1a2b3c4d5e6f7a8b-blst.o)
+";
+
+    #[test]
+    fn test_parse_size_report() {
+        assert!(!parse_size_report(args(&["--debug"])));
+        assert!(parse_size_report(args(&[
+            "--profile",
+            "verifier",
+            "--size-report"
+        ])));
+    }
+
+    #[test]
+    fn test_parse_size_output() {
+        let objects = parse_size_output(BERKELEY_SIZE_OUTPUT).unwrap();
+        assert_eq!(objects.len(), 5);
+        assert_eq!(
+            objects[0],
+            (
+                "ezkl-3f2a9c1d0b7e6a54.ezkl.8c1f2e3a-cgu.0.rcgu.o".to_string(),
+                822584
+            )
+        );
+        assert_eq!(objects[4], ("1a2b3c4d5e6f7a8b-blst.o".to_string(), 4096));
+
+        let objects = parse_size_output(XCODE_SIZE_OUTPUT).unwrap();
+        assert_eq!(
+            objects,
+            [
+                (
+                    "ezkl-3f2a9c1d0b7e6a54.ezkl.8c1f2e3a-cgu.0.rcgu.o".to_string(),
+                    822584
+                ),
+                ("1a2b3c4d5e6f7a8b-blst.o".to_string(), 4096)
+            ]
+        );
+
+        assert!(parse_size_output("").is_err());
+        assert!(parse_size_output("text data bss\n1 2 3 x.o\n").is_err());
+        assert!(parse_size_output("text data bss dec hex filename\n1 2 3 x 6 x.o\n").is_err());
+    }
+
+    #[test]
+    fn test_slice_size_groups_objects_by_crate() {
+        let objects = parse_size_output(BERKELEY_SIZE_OUTPUT).unwrap();
+        let slice = slice_size("aarch64-apple-ios", 5_000_000, &objects);
+        assert_eq!(slice.linked_bytes, 822584 + 402008 + 301712 + 12100 + 4096);
+        assert_eq!(slice.file_bytes, 5_000_000);
+        let crates: Vec<(&str, u64)> = slice
+            .crates
+            .iter()
+            .map(|c| (c.name.as_str(), c.bytes))
+            .collect();
+        assert_eq!(
+            crates,
+            [
+                ("ezkl", 822584 + 402008),
+                ("halo2_proofs", 301712),
+                ("ios_ezkl", 12100),
+                ("[other objects]", 4096)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_size_report_deltas() {
+        let objects = parse_size_output(BERKELEY_SIZE_OUTPUT).unwrap();
+        let report = |objects: &[(String, u64)]| SizeReport {
+            cargo_profile: "release".to_string(),
+            slices: vec![slice_size("aarch64-apple-ios", 0, objects)],
+        };
+
+        // Without a previous report every size is new
+        let first = with_deltas(report(&objects), None);
+        assert_eq!(first.slices[0].delta_bytes, None);
+        let markdown = size_report_markdown(&first);
+        assert!(markdown.contains("## aarch64-apple-ios"), "{}", markdown);
+        assert!(
+            markdown.contains("| ezkl | 1224592 | new |"),
+            "{}",
+            markdown
+        );
+
+        // The ezkl objects grew, the C objects are gone and a crate was added
+        let mut grown = objects.clone();
+        grown[0].1 += 1000;
+        grown.pop();
+        grown.push((
+            "serde_json-0011223344556677.a1-cgu.0.rcgu.o".to_string(),
+            500,
+        ));
+        let second = with_deltas(report(&grown), Some(&first));
+        assert_eq!(second.slices[0].delta_bytes, Some(1000 - 4096 + 500));
+        let delta = |name: &str| {
+            second.slices[0]
+                .crates
+                .iter()
+                .find(|c| c.name == name)
+                .unwrap()
+                .delta_bytes
+        };
+        assert_eq!(delta("ezkl"), Some(1000));
+        assert_eq!(delta("halo2_proofs"), Some(0));
+        assert_eq!(delta("serde_json"), None);
+        let markdown = size_report_markdown(&second);
+        assert!(
+            markdown.contains("| ezkl | 1225592 | +1000 |"),
+            "{}",
+            markdown
+        );
+        assert!(
+            markdown.contains("| halo2_proofs | 301712 | +0 |"),
+            "{}",
+            markdown
+        );
+
+        // The report is read back for the next build
+        let dir = std::env::temp_dir().join(format!("gen-bindings-size-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        write_size_report(&dir, &second).unwrap();
+        assert_eq!(read_size_report(&dir.join(SIZE_REPORT_FILE)), Some(second));
+        assert!(dir.join(SIZE_REPORT_MARKDOWN_FILE).exists());
+        assert_eq!(read_size_report(&dir.join("missing.json")), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
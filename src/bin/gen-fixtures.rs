@@ -0,0 +1,405 @@
+use ezkl::commands::DEFAULT_DISABLE_SELECTOR_COMPRESSION;
+use ezkl::graph::GraphCircuit;
+use ezkl::pfsys::srs::gen_srs;
+use ezkl::pfsys::{create_keys, save_pk, save_vk};
+use ezkl::{Commitments, RunArgs};
+use halo2_proofs::halo2curves::bn256::{Bn256, G1Affine};
+use halo2_proofs::poly::commitment::Params;
+use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+// Names of the generated fixture files, matching the layout of `tests/ezkl-sample`
+const SETTINGS_FILE: &str = "settings.json";
+const CIRCUIT_FILE: &str = "network.ezkl";
+const SRS_FILE: &str = "kzg.srs";
+const INPUT_FILE: &str = "input.json";
+const WITNESS_FILE: &str = "witness.json";
+const VK_FILE: &str = "vk.key";
+const PK_FILE: &str = "pk.key";
+const MANIFEST_FILE: &str = "manifest.json";
+
+const USAGE: &str = "usage: gen-fixtures (--model <model.onnx> | --circuit <network.ezkl>) \
+    [--run-args <run_args.json>] [--input <input.json>] --out <dir> [--overwrite-srs]";
+
+fn main() {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => panic!("{}\n{}", e, USAGE),
+    };
+
+    match generate_fixtures(&args) {
+        Ok(manifest) => println!(
+            "Generated fixtures with logrows {} in {}",
+            manifest.logrows,
+            args.out_dir.display()
+        ),
+        Err(e) => panic!("Failed to generate fixtures: {}", e),
+    }
+}
+
+/// Where the circuit of the fixtures comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ModelSource {
+    /// An ONNX model, compiled with the given run args.
+    Onnx(PathBuf),
+    /// An already compiled circuit, to regenerate the other fixtures only.
+    Compiled(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FixtureArgs {
+    source: ModelSource,
+    /// A JSON file with the `RunArgs` to compile the ONNX model with.
+    run_args: Option<PathBuf>,
+    /// The input to generate the witness for; one is derived from the input shapes if missing.
+    input: Option<PathBuf>,
+    out_dir: PathBuf,
+    /// Whether to replace an SRS already in the output directory instead of reusing it.
+    overwrite_srs: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<FixtureArgs, String> {
+    let mut source = None;
+    let mut run_args = None;
+    let mut input = None;
+    let mut out_dir = None;
+    let mut overwrite_srs = false;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .map(PathBuf::from)
+                .ok_or_else(|| format!("{} requires a value", arg))
+        };
+        match arg.as_str() {
+            "--model" => source = Some(ModelSource::Onnx(value()?)),
+            "--circuit" => source = Some(ModelSource::Compiled(value()?)),
+            "--run-args" => run_args = Some(value()?),
+            "--input" => input = Some(value()?),
+            "--out" => out_dir = Some(value()?),
+            "--overwrite-srs" => overwrite_srs = true,
+            _ => return Err(format!("unknown argument `{}`", arg)),
+        }
+    }
+
+    let source = source.ok_or_else(|| "either --model or --circuit is required".to_string())?;
+    if run_args.is_some() && matches!(source, ModelSource::Compiled(_)) {
+        return Err("--run-args only applies to --model".to_string());
+    }
+    // Required, so the fixtures used by the tests are only replaced when asked for
+    let out_dir = out_dir.ok_or_else(|| "--out is required".to_string())?;
+    Ok(FixtureArgs {
+        source,
+        run_args,
+        input,
+        out_dir,
+        overwrite_srs,
+    })
+}
+
+/// Records how a fixture set was generated, to tell whether it needs regenerating.
+#[derive(Debug, Serialize)]
+struct Manifest {
+    /// The version of this crate.
+    crate_version: String,
+    /// The ezkl version recorded in the circuit settings.
+    ezkl_version: String,
+    logrows: u32,
+    /// The hex encoded SHA-256 digest of every generated file.
+    files: BTreeMap<String, String>,
+}
+
+fn generate_fixtures(args: &FixtureArgs) -> Result<Manifest, String> {
+    fs::create_dir_all(&args.out_dir)
+        .map_err(|e| format!("failed to create {}: {}", args.out_dir.display(), e))?;
+    let out = |name: &str| args.out_dir.join(name);
+
+    let circuit = load_circuit(args)?;
+    let settings = circuit.settings().clone();
+    if Commitments::from(settings.run_args.commitment) != Commitments::KZG {
+        return Err("only KZG fixtures are supported".to_string());
+    }
+    let logrows = settings.run_args.logrows;
+
+    write(&out(SETTINGS_FILE), settings.as_json().map_err(to_string)?)?;
+    write(
+        &out(CIRCUIT_FILE),
+        bincode::serialize(&circuit).map_err(to_string)?,
+    )?;
+
+    let params = load_or_generate_srs(&out(SRS_FILE), logrows, args.overwrite_srs)?;
+
+    println!("Generating the keys...");
+    let pk = create_keys::<KZGCommitmentScheme<Bn256>, GraphCircuit>(
+        &circuit,
+        &params,
+        DEFAULT_DISABLE_SELECTOR_COMPRESSION
+            .parse()
+            .map_err(to_string)?,
+    )
+    .map_err(to_string)?;
+    save_vk::<G1Affine>(&out(VK_FILE), pk.get_vk()).map_err(to_string)?;
+    save_pk::<G1Affine>(&out(PK_FILE), &pk).map_err(to_string)?;
+
+    let input_json = match &args.input {
+        Some(path) => read_to_string(path)?,
+        None => sample_input(&circuit)?,
+    };
+    write(&out(INPUT_FILE), &input_json)?;
+
+    println!("Generating the witness...");
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .map_err(to_string)?;
+    let witness_json = runtime
+        .block_on(ios_ezkl::gen_witness(
+            input_json,
+            read(&out(CIRCUIT_FILE))?,
+            read(&out(VK_FILE))?,
+            read(&out(SRS_FILE))?,
+        ))
+        .map_err(to_string)?;
+    write(&out(WITNESS_FILE), witness_json)?;
+
+    let mut files = BTreeMap::new();
+    for name in [
+        SETTINGS_FILE,
+        CIRCUIT_FILE,
+        SRS_FILE,
+        INPUT_FILE,
+        WITNESS_FILE,
+        VK_FILE,
+        PK_FILE,
+    ] {
+        files.insert(
+            name.to_string(),
+            hex::encode(Sha256::digest(read(&out(name))?)),
+        );
+    }
+    let manifest = Manifest {
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        ezkl_version: settings.version.clone(),
+        logrows,
+        files,
+    };
+    write(
+        &out(MANIFEST_FILE),
+        serde_json::to_string_pretty(&manifest).map_err(to_string)?,
+    )?;
+
+    Ok(manifest)
+}
+
+/// Reuses the SRS at `path`, or generates one if there is none or `overwrite` is set.
+///
+/// An existing SRS is never replaced implicitly, as generating one is slow and the committed
+/// keys are only valid for the SRS they were created with.
+fn load_or_generate_srs(
+    path: &Path,
+    logrows: u32,
+    overwrite: bool,
+) -> Result<ParamsKZG<Bn256>, String> {
+    if path.exists() && !overwrite {
+        println!("Reusing the SRS in {}...", path.display());
+        let file = fs::File::open(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        let params = ParamsKZG::<Bn256>::read(&mut BufReader::new(file)).map_err(to_string)?;
+        if params.k() != logrows {
+            return Err(format!(
+                "{} has k = {}, but the circuit needs k = {}; pass --overwrite-srs to replace it",
+                path.display(),
+                params.k(),
+                logrows
+            ));
+        }
+        return Ok(params);
+    }
+
+    println!("Generating an SRS with k = {}...", logrows);
+    let params = gen_srs::<KZGCommitmentScheme<Bn256>>(logrows);
+    let mut srs_writer = BufWriter::new(fs::File::create(path).map_err(to_string)?);
+    params.write(&mut srs_writer).map_err(to_string)?;
+    srs_writer.flush().map_err(to_string)?;
+    Ok(params)
+}
+
+/// Compiles the ONNX model, or loads the compiled circuit.
+fn load_circuit(args: &FixtureArgs) -> Result<GraphCircuit, String> {
+    match &args.source {
+        ModelSource::Onnx(model_path) => {
+            let run_args: RunArgs = match &args.run_args {
+                Some(path) => serde_json::from_str(&read_to_string(path)?).map_err(to_string)?,
+                None => RunArgs::default(),
+            };
+            println!("Compiling {}...", model_path.display());
+            GraphCircuit::from_run_args(&run_args, model_path).map_err(to_string)
+        }
+        ModelSource::Compiled(circuit_path) => {
+            GraphCircuit::load(circuit_path.clone()).map_err(to_string)
+        }
+    }
+}
+
+/// Derives a deterministic input with values in `[0, 0.1)` from the input shapes of the circuit.
+fn sample_input(circuit: &GraphCircuit) -> Result<String, String> {
+    let shapes = circuit.model().graph.input_shapes().map_err(to_string)?;
+    let input_data: Vec<Vec<f64>> = shapes
+        .iter()
+        .map(|shape| {
+            (0..shape.iter().product::<usize>())
+                .map(|i| (i * 7919 % 1000) as f64 / 10_000.0)
+                .collect()
+        })
+        .collect();
+    Ok(serde_json::json!({ "input_data": input_data }).to_string())
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))
+}
+
+fn read_to_string(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+fn to_string(e: impl std::fmt::Display) -> String {
+    e.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ezkl::graph::GraphSettings;
+
+    // The fixtures used by the tests
+    const SAMPLE_DIR: &str = "tests/ezkl-sample";
+
+    fn args(args: &[&str]) -> Result<FixtureArgs, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = args(&[
+            "--model",
+            "model.onnx",
+            "--run-args",
+            "args.json",
+            "--out",
+            "out",
+        ])
+        .unwrap();
+        assert_eq!(
+            parsed.source,
+            ModelSource::Onnx(PathBuf::from("model.onnx"))
+        );
+        assert_eq!(parsed.run_args, Some(PathBuf::from("args.json")));
+        assert_eq!(parsed.input, None);
+        assert_eq!(parsed.out_dir, PathBuf::from("out"));
+        assert!(!parsed.overwrite_srs);
+
+        let parsed = args(&[
+            "--circuit",
+            "network.ezkl",
+            "--out",
+            "out",
+            "--overwrite-srs",
+        ])
+        .unwrap();
+        assert_eq!(
+            parsed.source,
+            ModelSource::Compiled(PathBuf::from("network.ezkl"))
+        );
+        assert!(parsed.overwrite_srs);
+
+        assert!(args(&[]).is_err());
+        assert!(args(&["--model"]).is_err());
+        // There is no default output directory
+        assert!(args(&["--circuit", "network.ezkl"]).is_err());
+        assert!(args(&[
+            "--circuit",
+            "network.ezkl",
+            "--out",
+            "out",
+            "--run-args",
+            "args.json"
+        ])
+        .is_err());
+        assert!(args(&["--circuit", "network.ezkl", "--out", "out", "--bogus"]).is_err());
+    }
+
+    #[test]
+    fn test_existing_srs_is_reused() {
+        let out_dir = std::env::temp_dir().join(format!("gen-fixtures-srs-{}", std::process::id()));
+        fs::create_dir_all(&out_dir).unwrap();
+        let path = out_dir.join(SRS_FILE);
+
+        load_or_generate_srs(&path, 4, false).unwrap();
+        let generated = read(&path).unwrap();
+
+        // Not replaced without --overwrite-srs, even for a circuit of another size
+        let reused = load_or_generate_srs(&path, 4, false).unwrap();
+        assert_eq!(reused.k(), 4);
+        assert!(load_or_generate_srs(&path, 5, false).is_err());
+        assert_eq!(read(&path).unwrap(), generated);
+
+        let replaced = load_or_generate_srs(&path, 5, true).unwrap();
+        assert_eq!(replaced.k(), 5);
+        assert_ne!(read(&path).unwrap(), generated);
+
+        fs::remove_dir_all(out_dir).unwrap();
+    }
+
+    #[test]
+    fn test_regenerated_fixtures_prove_and_verify() {
+        let sample = Path::new(SAMPLE_DIR);
+        let out_dir = std::env::temp_dir().join(format!("gen-fixtures-{}", std::process::id()));
+        let manifest = generate_fixtures(&FixtureArgs {
+            source: ModelSource::Compiled(sample.join(CIRCUIT_FILE)),
+            run_args: None,
+            input: Some(sample.join(INPUT_FILE)),
+            out_dir: out_dir.clone(),
+            overwrite_srs: false,
+        })
+        .expect("Failed to generate fixtures");
+
+        // The manifest matches the generated files
+        for (name, digest) in &manifest.files {
+            let contents = read(&out_dir.join(name)).unwrap();
+            assert_eq!(*digest, hex::encode(Sha256::digest(contents)), "{}", name);
+        }
+        let settings = read_to_string(&out_dir.join(SETTINGS_FILE)).unwrap();
+        let parsed: GraphSettings = serde_json::from_str(&settings).unwrap();
+        assert_eq!(parsed.run_args.logrows, manifest.logrows);
+
+        // The same end-to-end flow as the integration tests passes on the regenerated fixtures
+        let read_fixture = |name: &str| read(&out_dir.join(name)).unwrap();
+        let proof = ios_ezkl::prove(
+            read_to_string(&out_dir.join(WITNESS_FILE)).unwrap(),
+            read_fixture(CIRCUIT_FILE),
+            read_fixture(PK_FILE),
+            read_fixture(SRS_FILE),
+        )
+        .expect("Proving failed on the regenerated fixtures");
+        let valid = ios_ezkl::verify(
+            proof,
+            settings,
+            read_fixture(VK_FILE),
+            read_fixture(SRS_FILE),
+        )
+        .expect("Verification failed on the regenerated fixtures");
+        assert!(valid);
+
+        fs::remove_dir_all(out_dir).unwrap();
+    }
+}
@@ -0,0 +1,115 @@
+use crate::fingerprint::{check, of_settings, vk_fingerprint};
+use crate::limits::check_input_sizes;
+use crate::serialization::{deserialize_json, deserialize_proof};
+use crate::verify::verify;
+use crate::ExternalEZKLError;
+use ezkl::graph::GraphSettings;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The version of the bundle format written by [`create_bundle`].
+const BUNDLE_VERSION: u32 = 1;
+
+/// A proof together with the identifiers of the circuit and verification key it belongs to.
+#[derive(Serialize, Deserialize)]
+struct AttestationBundle {
+    version: u32,
+    /// The proof, embedded verbatim.
+    proof: Box<RawValue>,
+    /// The circuit settings, embedded verbatim.
+    settings: Box<RawValue>,
+    /// The fingerprint of the settings.
+    settings_hash: String,
+    /// The fingerprint of the verification key, as returned by `vk_fingerprint`.
+    vk_fingerprint: String,
+}
+
+/// Packs a proof with the settings and the fingerprint of the verification key it is verified
+/// with into a single JSON document, so relying parties can't verify it against the wrong key.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The JSON representation of the bundle.
+/// * `Err(ExternalEZKLError)` - If the proof, the settings or the verification key could not be parsed.
+#[cfg_attr(feature = "ffi", export)]
+pub fn create_bundle(
+    proof_json: String,
+    settings_json: String,
+    vk: Vec<u8>,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(proof_json, settings_json, vk);
+    let settings: GraphSettings = deserialize_json("settings_json", &settings_json)?;
+    // Only checked to be a proof, it is embedded as is
+    deserialize_proof("proof_json", &proof_json)?;
+
+    let bundle = AttestationBundle {
+        version: BUNDLE_VERSION,
+        settings_hash: of_settings(&settings)?,
+        vk_fingerprint: vk_fingerprint(vk, settings_json.clone())?,
+        proof: deserialize_json("proof_json", &proof_json)?,
+        settings: deserialize_json("settings_json", &settings_json)?,
+    };
+    Ok(serde_json::to_string(&bundle)?)
+}
+
+/// Verifies the proof in a bundle created by [`create_bundle`], after checking that the bundle
+/// belongs to the supplied verification key and, if given, the expected settings.
+///
+/// # Arguments
+///
+/// * `bundle_json` - A `String` containing the JSON representation of the bundle.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `expected_settings_json` - The settings the bundle is expected to have been created for, if known.
+///
+/// # Returns
+///
+/// * `Ok(bool)` - `true` if the proof is valid.
+/// * `Err(ExternalEZKLError)` - `FingerprintMismatch` if the bundle does not belong to the supplied
+///   verification key or expected settings, or an error if the proof is invalid.
+#[cfg_attr(feature = "ffi", export)]
+pub fn verify_bundle(
+    bundle_json: String,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+    expected_settings_json: Option<String>,
+) -> Result<bool, ExternalEZKLError> {
+    check_input_sizes!(bundle_json, vk, srs, expected_settings_json);
+    let bundle: AttestationBundle = deserialize_json("bundle_json", &bundle_json)?;
+    if bundle.version != BUNDLE_VERSION {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "unsupported bundle version {}, expected {}",
+            bundle.version, BUNDLE_VERSION
+        )));
+    }
+
+    let settings_json = bundle.settings.get().to_string();
+    let settings: GraphSettings = deserialize_json("bundle_json", &settings_json)?;
+    check(
+        "bundled settings",
+        Some(&bundle.settings_hash),
+        &of_settings(&settings)?,
+    )?;
+    if let Some(expected_settings_json) = expected_settings_json {
+        let expected: GraphSettings =
+            deserialize_json("expected_settings_json", &expected_settings_json)?;
+        check(
+            "settings",
+            Some(&of_settings(&expected)?),
+            &bundle.settings_hash,
+        )?;
+    }
+
+    let actual = vk_fingerprint(vk.clone(), settings_json.clone())?;
+    check("verification key", Some(&bundle.vk_fingerprint), &actual)?;
+
+    verify(bundle.proof.get().to_string(), settings_json, vk, srs)
+}
@@ -0,0 +1,105 @@
+use crate::limits::check_input_sizes;
+use crate::logging::info;
+use crate::serialization::{deserialize_circuit, deserialize_json};
+use crate::ExternalEZKLError;
+use ezkl::fieldutils::felt_to_integer_rep;
+use ezkl::graph::{scale_to_multiplier, GraphWitness};
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// Converts the outputs of a witness into the input JSON of another circuit, to chain models
+/// where the outputs of the first are the inputs of the second.
+///
+/// The outputs are dequantized with the output scales of the source circuit and snapped to the
+/// input scales of the target circuit, which quantizes them again during witness generation.
+///
+/// # Arguments
+///
+/// * `witness_json` - A `String` containing the JSON representation of a witness of the source circuit.
+/// * `source_circuit` - A `Vec<u8>` containing the compiled circuit that generated the witness.
+/// * `target_circuit` - A `Vec<u8>` containing the compiled circuit the input is for.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The input JSON for the target circuit, in the form accepted by `gen_witness`.
+/// * `Err(ExternalEZKLError)` - If the number or shapes of the outputs don't match the inputs of
+///   the target circuit, or if an output can't be represented at the target input scale.
+#[cfg_attr(feature = "ffi", export)]
+pub fn witness_to_input(
+    witness_json: String,
+    source_circuit: Vec<u8>,
+    target_circuit: Vec<u8>,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(witness_json, source_circuit, target_circuit);
+    let witness: GraphWitness = deserialize_json("witness_json", &witness_json)?;
+    let source = deserialize_circuit(&source_circuit)?;
+    let target = deserialize_circuit(&target_circuit)?;
+
+    let output_scales = &source.settings().model_output_scales;
+    let input_scales = &target.settings().model_input_scales;
+    let input_shapes = target.model().graph.input_shapes()?;
+
+    if witness.outputs.len() != output_scales.len() {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the witness has {} outputs, but the source circuit has {}",
+            witness.outputs.len(),
+            output_scales.len()
+        )));
+    }
+    if witness.outputs.len() != input_shapes.len() {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the source circuit has {} outputs, but the target circuit has {} inputs",
+            witness.outputs.len(),
+            input_shapes.len()
+        )));
+    }
+    if input_scales.len() != input_shapes.len() {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the target circuit has {} inputs, but its settings list {} input scales",
+            input_shapes.len(),
+            input_scales.len()
+        )));
+    }
+
+    let mut input_data = Vec::with_capacity(witness.outputs.len());
+    for (i, output) in witness.outputs.iter().enumerate() {
+        let source_scale = output_scales[i];
+        let target_scale = input_scales[i];
+        let shape = &input_shapes[i];
+
+        let expected_len: usize = shape.iter().product();
+        if output.len() != expected_len {
+            return Err(ExternalEZKLError::InvalidInput(format!(
+                "output {} has {} elements, but input {} of the target circuit has shape {:?} ({} elements)",
+                i,
+                output.len(),
+                i,
+                shape,
+                expected_len
+            )));
+        }
+
+        let source_multiplier = scale_to_multiplier(source_scale);
+        let target_multiplier = scale_to_multiplier(target_scale);
+        let mut values = Vec::with_capacity(output.len());
+        for (j, felt) in output.iter().enumerate() {
+            let quantized = felt_to_integer_rep(*felt);
+            let value = quantized as f64 / source_multiplier;
+            let requantized = (value * target_multiplier).round();
+            if requantized / target_multiplier != value {
+                return Err(ExternalEZKLError::InvalidInput(format!(
+                    "element {} of output {} ({}) at scale {} is not representable at scale {} of input {} of the target circuit",
+                    j, i, value, source_scale, target_scale, i
+                )));
+            }
+            values.push(requantized / target_multiplier);
+        }
+        input_data.push(values);
+    }
+
+    info!(
+        "converted {} witness outputs into inputs of the target circuit",
+        input_data.len()
+    );
+    Ok(serde_json::json!({ "input_data": input_data }).to_string())
+}
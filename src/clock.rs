@@ -0,0 +1,6 @@
+// wasm32-unknown-unknown has no clock of its own, so `std::time::Instant::now` panics there;
+// `web_time` reads the clock of the JavaScript host instead.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::Instant;
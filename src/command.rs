@@ -0,0 +1,214 @@
+use crate::gen_witness::{gen_witness_internal, WitnessOptions};
+use crate::inspect::{circuit_info, inspect_proof};
+use crate::limits::check_input_sizes;
+use crate::verify::verify;
+use crate::ExternalEZKLError;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::future::Future;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The commands accepted by [`execute`].
+///
+/// Proving is deliberately not available through the dispatcher yet.
+pub const SUPPORTED_COMMANDS: [&str; 4] =
+    ["verify", "gen_witness", "inspect_proof", "circuit_info"];
+
+#[derive(Deserialize)]
+struct VerifyArgs {
+    proof_json: String,
+    settings_json: String,
+    vk: Base64Bytes,
+    srs: Base64Bytes,
+}
+
+#[derive(Deserialize)]
+struct GenWitnessArgs {
+    input_json: String,
+    compiled_circuit: Base64Bytes,
+    vk: Base64Bytes,
+    srs: Base64Bytes,
+}
+
+#[derive(Deserialize)]
+struct InspectProofArgs {
+    proof_json: String,
+}
+
+#[derive(Deserialize)]
+struct CircuitInfoArgs {
+    compiled_circuit: Base64Bytes,
+}
+
+/// Binary arguments are passed as base64 strings, as JSON has no byte type.
+struct Base64Bytes(Vec<u8>);
+
+impl<'de> Deserialize<'de> for Base64Bytes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        BASE64
+            .decode(encoded)
+            .map(Base64Bytes)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Returns the names of the commands accepted by [`execute`].
+#[cfg_attr(feature = "ffi", export)]
+pub fn supported_commands() -> Vec<String> {
+    SUPPORTED_COMMANDS
+        .iter()
+        .map(|cmd| cmd.to_string())
+        .collect()
+}
+
+/// Executes a command described in JSON, for bridging layers that can only pass strings.
+///
+/// The command has the form `{ "cmd": "verify", "args": { ... } }`, where binary arguments
+/// (circuit, keys, SRS) are base64 encoded. The response is either `{ "result": ... }` or
+/// `{ "error": { "kind": ..., "message": ... } }`, where `kind` is one of `invalid_command`,
+/// `unknown_command`, `invalid_args` or `failed`.
+///
+/// # Arguments
+///
+/// * `command_json` - A `String` containing the JSON representation of the command.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The JSON response, containing either the result or the error of the command.
+/// * `Err(ExternalEZKLError)` - If the response could not be serialized.
+#[cfg_attr(feature = "ffi", export)]
+pub fn execute(command_json: String) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(command_json);
+    let response = match dispatch(&command_json) {
+        Ok(result) => json!({ "result": result }),
+        Err(CommandError { kind, message }) => json!({
+            "error": { "kind": kind, "message": message }
+        }),
+    };
+    Ok(serde_json::to_string(&response)?)
+}
+
+struct CommandError {
+    kind: &'static str,
+    message: String,
+}
+
+impl CommandError {
+    fn new(kind: &'static str, message: impl ToString) -> Self {
+        CommandError {
+            kind,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<ExternalEZKLError> for CommandError {
+    fn from(e: ExternalEZKLError) -> Self {
+        CommandError::new("failed", e)
+    }
+}
+
+impl From<serde_json::Error> for CommandError {
+    fn from(e: serde_json::Error) -> Self {
+        CommandError::new("failed", e)
+    }
+}
+
+fn dispatch(command_json: &str) -> Result<Value, CommandError> {
+    let command: Value =
+        serde_json::from_str(command_json).map_err(|e| CommandError::new("invalid_command", e))?;
+    let cmd = command["cmd"]
+        .as_str()
+        .ok_or_else(|| CommandError::new("invalid_command", "missing string field `cmd`"))?;
+    let args = command.get("args").cloned().unwrap_or(Value::Null);
+
+    match cmd {
+        "verify" => {
+            let args: VerifyArgs = parse_args(cmd, args)?;
+            let valid = verify(args.proof_json, args.settings_json, args.vk.0, args.srs.0)?;
+            Ok(json!(valid))
+        }
+        "gen_witness" => {
+            let args: GenWitnessArgs = parse_args(cmd, args)?;
+            let witness = block_on(|| async move {
+                gen_witness_internal(
+                    &args.compiled_circuit.0,
+                    args.input_json,
+                    Some(&args.vk.0),
+                    Some(&args.srs.0),
+                    &WitnessOptions::default(),
+                )
+                .await
+            })??;
+            Ok(serde_json::to_value(&witness)?)
+        }
+        "inspect_proof" => {
+            let args: InspectProofArgs = parse_args(cmd, args)?;
+            Ok(serde_json::to_value(inspect_proof(args.proof_json)?)?)
+        }
+        "circuit_info" => {
+            let args: CircuitInfoArgs = parse_args(cmd, args)?;
+            Ok(serde_json::to_value(circuit_info(
+                args.compiled_circuit.0,
+            )?)?)
+        }
+        _ => Err(CommandError::new(
+            "unknown_command",
+            format!(
+                "unknown command `{}`, expected one of {:?}",
+                cmd, SUPPORTED_COMMANDS
+            ),
+        )),
+    }
+}
+
+fn parse_args<T: DeserializeOwned>(cmd: &str, args: Value) -> Result<T, CommandError> {
+    serde_json::from_value(args).map_err(|e| {
+        CommandError::new(
+            "invalid_args",
+            format!("invalid arguments for `{}`: {}", cmd, e),
+        )
+    })
+}
+
+/// Runs a future to completion on the calling thread, for the synchronous entry point, as wasm32
+/// has no threads to spawn.
+#[cfg(target_arch = "wasm32")]
+fn block_on<Fut: Future>(
+    make_future: impl FnOnce() -> Fut + Send,
+) -> Result<Fut::Output, CommandError>
+where
+    Fut::Output: Send,
+{
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .map_err(|e| CommandError::new("failed", e))?;
+    Ok(runtime.block_on(make_future()))
+}
+
+/// Runs a future to completion on a dedicated thread, for the synchronous entry point.
+#[cfg(not(target_arch = "wasm32"))]
+fn block_on<Fut: Future>(
+    make_future: impl FnOnce() -> Fut + Send,
+) -> Result<Fut::Output, CommandError>
+where
+    Fut::Output: Send,
+{
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|e| CommandError::new("failed", e))?;
+                Ok(runtime.block_on(make_future()))
+            })
+            .join()
+            .map_err(|_| CommandError::new("failed", "the command panicked"))?
+    })
+}
@@ -0,0 +1,99 @@
+use crate::logging::debug;
+use crate::migration::migrate_artifact;
+use crate::serialization::deserialize_json;
+use crate::store::ArtifactKind;
+use crate::ExternalEZKLError;
+use serde_json::{Map, Value};
+
+/// How strictly a verification reads settings written by other ezkl tooling.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerifyStrictness {
+    /// Settings must be in the layout the pinned ezkl writes, as this library emits them.
+    #[default]
+    Strict,
+    /// Settings written by the ezkl CLI and Python bindings of other versions are accepted too:
+    /// the settings layouts `migrate_artifact` migrates, and the spellings of run args listed in
+    /// [`RUN_ARGS_ALIASES`]. Proofs are read as with `Strict`; convert proofs in older layouts
+    /// with `normalize_proof` first.
+    CliCompatible,
+}
+
+/// The options of a verification call.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VerifyOptions {
+    /// How strictly the settings are read. `Strict` by default.
+    pub verify_strictness: VerifyStrictness,
+}
+
+/// The spellings of visibilities the pinned ezkl doesn't read, with the one it reads.
+///
+/// The Python bindings take visibilities in lowercase, and later ezkl versions renamed the
+/// polycommit visibility.
+const VISIBILITY_ALIASES: &[(&str, &str)] = &[
+    ("private", "Private"),
+    ("public", "Public"),
+    ("fixed", "Fixed"),
+    ("polycommit", "KZGCommit"),
+    ("PolyCommit", "KZGCommit"),
+    ("kzgcommit", "KZGCommit"),
+];
+
+/// The run args holding a visibility.
+const VISIBILITY_KEYS: &[&str] = &["input_visibility", "output_visibility", "param_visibility"];
+
+/// The spellings of other run args the pinned ezkl doesn't read, by run arg, with the one it
+/// reads. The CLI and Python bindings take these in lowercase.
+const RUN_ARGS_ALIASES: &[(&str, &str, &str)] = &[
+    ("check_mode", "safe", "SAFE"),
+    ("check_mode", "unsafe", "UNSAFE"),
+    ("commitment", "kzg", "KZG"),
+    ("commitment", "ipa", "IPA"),
+];
+
+/// Rewrites settings written by the ezkl CLI or Python bindings to the layout and spellings the
+/// pinned ezkl reads.
+///
+/// Settings in a layout that can't be migrated are returned with only the spellings rewritten,
+/// so that reading them fails with the usual error.
+pub(crate) fn cli_compatible_settings(settings_json: &str) -> Result<String, ExternalEZKLError> {
+    let mut value: Value = deserialize_json("settings_json", settings_json)?;
+    if let Some(settings) = value.as_object_mut() {
+        rewrite_aliases(settings);
+    }
+    let rewritten = value.to_string();
+    match migrate_artifact(ArtifactKind::Settings, rewritten.clone().into_bytes()) {
+        Ok(migrated) => {
+            String::from_utf8(migrated).map_err(|e| ExternalEZKLError::InternalError(e.to_string()))
+        }
+        Err(ExternalEZKLError::Unsupported { .. }) => Ok(rewritten),
+        Err(e) => Err(e),
+    }
+}
+
+/// Rewrites the aliased spellings of the run args, and of the check mode the settings repeat at
+/// the top level.
+fn rewrite_aliases(settings: &mut Map<String, Value>) {
+    let visibility_aliases = VISIBILITY_KEYS.iter().flat_map(|&key| {
+        VISIBILITY_ALIASES
+            .iter()
+            .map(move |&(alias, canonical)| (key, alias, canonical))
+    });
+    let aliases: Vec<(&str, &str, &str)> = visibility_aliases
+        .chain(RUN_ARGS_ALIASES.iter().copied())
+        .collect();
+
+    let mut rewrite = |fields: &mut Map<String, Value>| {
+        for &(key, alias, canonical) in &aliases {
+            if let Some(value) = fields.get_mut(key).filter(|value| **value == alias) {
+                debug!("reading `{}` of `{}` as `{}`", alias, key, canonical);
+                *value = Value::String(canonical.to_string());
+            }
+        }
+    };
+    if let Some(Value::Object(run_args)) = settings.get_mut("run_args") {
+        rewrite(run_args);
+    }
+    rewrite(settings);
+}
@@ -0,0 +1,84 @@
+use crate::ExternalEZKLError;
+use flate2::bufread::GzDecoder;
+#[cfg(feature = "prover")]
+use flate2::write::GzEncoder;
+#[cfg(feature = "prover")]
+use flate2::Compression;
+#[cfg(feature = "prover")]
+use std::io::Write;
+use std::io::{BufRead, Read};
+
+/// The first two bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Returns whether the bytes start like a gzip stream. JSON text never does.
+pub(crate) fn is_gzip(bytes: &[u8]) -> bool {
+    bytes.starts_with(&GZIP_MAGIC)
+}
+
+/// Compresses a JSON string with gzip.
+#[cfg(feature = "prover")]
+pub(crate) fn gzip(json: &str) -> Result<Vec<u8>, ExternalEZKLError> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(json.as_bytes())
+        .and_then(|_| encoder.finish())
+        .map_err(|e| ExternalEZKLError::InternalError(format!("failed to compress JSON: {}", e)))
+}
+
+/// Returns the JSON output of a call, or its gzip compression if `compress` is set.
+///
+/// The JSON string is empty when it is compressed, so the output crosses the FFI only once.
+#[cfg(feature = "prover")]
+pub(crate) fn json_output(
+    json: String,
+    compress: bool,
+) -> Result<(String, Option<Vec<u8>>), ExternalEZKLError> {
+    if !compress {
+        return Ok((json, None));
+    }
+    let compressed = gzip(&json)?;
+    Ok((String::new(), Some(compressed)))
+}
+
+/// Reads JSON text from bytes that are either plain UTF-8 or gzip-compressed.
+///
+/// # Arguments
+///
+/// * `field` - The name of the argument, used in the error.
+/// * `bytes` - The JSON text, or its gzip compression.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The JSON text.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` if the bytes can't be decompressed or aren't UTF-8.
+pub(crate) fn json_text(field: &str, bytes: Vec<u8>) -> Result<String, ExternalEZKLError> {
+    let bytes = if is_gzip(&bytes) {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut decompressed)
+            .map_err(|e| {
+                ExternalEZKLError::InvalidInput(format!(
+                    "`{}` looks gzip-compressed but could not be decompressed: {}",
+                    field, e
+                ))
+            })?;
+        decompressed
+    } else {
+        bytes
+    };
+    String::from_utf8(bytes).map_err(|e| {
+        ExternalEZKLError::InvalidInput(format!("`{}` is not UTF-8 JSON text: {}", field, e))
+    })
+}
+
+/// Wraps a reader so that gzip-compressed content is decompressed while it is read.
+pub(crate) fn decoding_reader<'a, R: BufRead + 'a>(
+    mut reader: R,
+) -> std::io::Result<Box<dyn Read + 'a>> {
+    if is_gzip(reader.fill_buf()?) {
+        Ok(Box::new(GzDecoder::new(reader)))
+    } else {
+        Ok(Box::new(reader))
+    }
+}
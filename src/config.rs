@@ -0,0 +1,158 @@
+use crate::executor::ThreadPriority;
+use crate::limits::ResourceLimits;
+use crate::logging::{self, EzklLogger, LogLevel};
+use crate::ExternalEZKLError;
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The global configuration of the library.
+///
+/// Every exported function works with the default configuration if [`initialize`] is never called.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Clone)]
+pub struct EzklConfig {
+    /// The logger receiving the log messages of the library, if any.
+    pub logger: Option<Arc<dyn EzklLogger>>,
+    /// The minimum level of the messages forwarded to the logger.
+    pub log_level: LogLevel,
+    /// The number of threads used for proving. `None` uses one thread per core.
+    pub max_threads: Option<u32>,
+    /// A directory to cache generated witnesses in, keyed by the circuit, the vk and the input.
+    /// Repeated witness generation for the same input, e.g. when retrying a failed proof, then
+    /// reads the witness from disk. The cached witnesses are stored unencrypted and contain the
+    /// private inputs in plain text, so the directory needs the same protection as the inputs.
+    pub witness_cache_dir: Option<String>,
+    /// The age in seconds after which a temporary file left behind by an interrupted `*_to_file`
+    /// call is considered stale and removed by the next call writing to the same directory.
+    pub stale_temp_file_age_secs: u64,
+    /// The limits on the resources the library may use at once.
+    pub limits: ResourceLimits,
+    /// If `true`, the input JSON, the witness JSON, the parsed input data, the quantized input
+    /// tensors and the inputs and outputs of the witness are overwritten once they are no longer
+    /// needed. Copies made inside ezkl and halo2, such as the cells assigned while generating the
+    /// witness and proving, and the strings returned to the caller, can't be wiped.
+    pub zeroize_inputs: bool,
+    /// The capacity in bytes of the buffers used to read and write artifacts, such as keys, SRS
+    /// and witnesses. `None` uses ezkl's `EZKL_BUF_CAPACITY`, which ezkl itself reads its files with.
+    #[cfg_attr(feature = "ffi", uniffi(default = None))]
+    pub io_buffer_capacity: Option<u64>,
+    /// The priority of the worker threads `prove_async` runs on. `None` uses `Utility`.
+    #[cfg_attr(feature = "ffi", uniffi(default = None))]
+    pub prove_thread_priority: Option<ThreadPriority>,
+    /// The priority of the worker threads `verify_async` runs on. `None` uses `UserInitiated`.
+    #[cfg_attr(feature = "ffi", uniffi(default = None))]
+    pub verify_thread_priority: Option<ThreadPriority>,
+    /// If `true`, the path-based functions check that each file they read, e.g. in an app group
+    /// container shared with another process, did not change while it was read. A file that
+    /// changed is read once more, and `ConcurrentModification` is returned if it changed again.
+    /// The check hashes each file before and after reading it.
+    #[cfg_attr(feature = "ffi", uniffi(default = false))]
+    pub coordinated_reads: bool,
+}
+
+impl Default for EzklConfig {
+    fn default() -> Self {
+        EzklConfig {
+            logger: None,
+            log_level: LogLevel::Info,
+            max_threads: None,
+            witness_cache_dir: None,
+            stale_temp_file_age_secs: 60 * 60,
+            limits: ResourceLimits::default(),
+            zeroize_inputs: true,
+            io_buffer_capacity: None,
+            prove_thread_priority: None,
+            verify_thread_priority: None,
+            coordinated_reads: false,
+        }
+    }
+}
+
+impl PartialEq for EzklConfig {
+    fn eq(&self, other: &Self) -> bool {
+        let same_logger = match (&self.logger, &other.logger) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        same_logger
+            && self.log_level == other.log_level
+            && self.max_threads == other.max_threads
+            && self.witness_cache_dir == other.witness_cache_dir
+            && self.stale_temp_file_age_secs == other.stale_temp_file_age_secs
+            && self.limits == other.limits
+            && self.zeroize_inputs == other.zeroize_inputs
+            && self.io_buffer_capacity == other.io_buffer_capacity
+            && self.prove_thread_priority == other.prove_thread_priority
+            && self.verify_thread_priority == other.verify_thread_priority
+            && self.coordinated_reads == other.coordinated_reads
+    }
+}
+
+static CONFIG: Mutex<Option<EzklConfig>> = Mutex::new(None);
+
+/// Initializes the library with the given configuration.
+///
+/// The logger, thread count and resource limits are applied together, atomically with respect to
+/// concurrent calls. Calling this again with an identical configuration is a no-op.
+///
+/// # Arguments
+///
+/// * `config` - An `EzklConfig` record with the configuration to apply.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the configuration was applied, or the same configuration was already applied.
+/// * `Err(ExternalEZKLError)` - `AlreadyInitialized` if a different configuration was already applied,
+///   or an error that occurred while applying the configuration.
+#[cfg_attr(feature = "ffi", export)]
+pub fn initialize(config: EzklConfig) -> Result<(), ExternalEZKLError> {
+    let mut current = CONFIG
+        .lock()
+        .map_err(|_| ExternalEZKLError::InternalError("configuration lock poisoned".to_string()))?;
+
+    match current.as_ref() {
+        Some(existing) if *existing == config => return Ok(()),
+        Some(_) => return Err(ExternalEZKLError::AlreadyInitialized),
+        None => {}
+    }
+
+    if config.io_buffer_capacity == Some(0) {
+        return Err(ExternalEZKLError::InvalidInput(
+            "`io_buffer_capacity` must be at least one byte".to_string(),
+        ));
+    }
+    // wasm32 has no threads to spawn, so proving runs on the calling one
+    #[cfg(target_arch = "wasm32")]
+    if config.max_threads.is_some() {
+        return Err(ExternalEZKLError::InvalidInput(
+            "`max_threads` is not supported by WebAssembly builds".to_string(),
+        ));
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(max_threads) = config.max_threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(max_threads as usize)
+            .build_global()
+            .map_err(|e| {
+                ExternalEZKLError::InternalError(format!(
+                    "the thread count must be configured before any proof is generated: {}",
+                    e
+                ))
+            })?;
+    }
+    logging::install(config.logger.clone(), config.log_level);
+
+    *current = Some(config);
+    Ok(())
+}
+
+/// Returns the configuration currently in effect, which is the default if [`initialize`] was never called.
+pub(crate) fn current() -> EzklConfig {
+    CONFIG
+        .lock()
+        .ok()
+        .and_then(|config| config.clone())
+        .unwrap_or_default()
+}
@@ -0,0 +1,117 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Enables or disables the memory diagnostics mode.
+///
+/// When enabled, the detailed exported functions report the peak number of bytes allocated
+/// during the call. This requires the library to be built with the `diagnostics` feature, which
+/// replaces the global allocator with a counting one; without it this call has no effect.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether the peak memory usage should be tracked.
+#[cfg_attr(feature = "ffi", export)]
+pub fn set_diagnostics(enabled: bool) {
+    ENABLED.store(enabled && cfg!(feature = "diagnostics"), Ordering::SeqCst);
+}
+
+/// Tracks the peak memory usage over the lifetime of a single exported call.
+///
+/// The peak is shared by the whole process, so calls running concurrently report the combined
+/// peak of all of them.
+pub(crate) struct CallScope {
+    baseline: Option<usize>,
+}
+
+impl CallScope {
+    /// Starts tracking the peak memory usage, resetting the peak left over by previous calls.
+    pub(crate) fn begin() -> Self {
+        if !ENABLED.load(Ordering::SeqCst) {
+            return CallScope { baseline: None };
+        }
+        let baseline = CURRENT_BYTES.load(Ordering::SeqCst);
+        PEAK_BYTES.store(baseline, Ordering::SeqCst);
+        CallScope {
+            baseline: Some(baseline),
+        }
+    }
+
+    /// Returns the peak number of bytes allocated since the scope began, if diagnostics are enabled.
+    pub(crate) fn peak_bytes(&self) -> Option<u64> {
+        self.baseline
+            .map(|baseline| PEAK_BYTES.load(Ordering::SeqCst).saturating_sub(baseline) as u64)
+    }
+}
+
+/// Runs `f` and returns its result with the peak number of bytes it allocated, if diagnostics are enabled.
+pub fn measure_peak_bytes<T>(f: impl FnOnce() -> T) -> (T, Option<u64>) {
+    let scope = CallScope::begin();
+    let result = f();
+    (result, scope.peak_bytes())
+}
+
+/// Returns the number of bytes currently allocated by the process, if diagnostics are enabled.
+pub fn allocated_bytes() -> Option<u64> {
+    ENABLED
+        .load(Ordering::SeqCst)
+        .then(|| CURRENT_BYTES.load(Ordering::SeqCst) as u64)
+}
+
+#[cfg(feature = "diagnostics")]
+mod allocator {
+    use super::{CURRENT_BYTES, PEAK_BYTES};
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::Ordering;
+
+    /// A global allocator counting the bytes currently allocated and the peak reached.
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    fn record_alloc(size: usize) {
+        let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        CURRENT_BYTES.fetch_sub(size, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record_dealloc(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record_dealloc(layout.size());
+                record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+}
@@ -1,10 +1,68 @@
 use crate::InnerEZKLError;
+use ezkl::graph::errors::GraphError;
+use ezkl::pfsys::srs::SrsError;
+use ezkl::pfsys::PfsysError;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
 use std::fmt::Display;
+#[cfg(feature = "ffi")]
+use uniffi::export;
 
-#[derive(uniffi::Error, Debug)]
+#[cfg_attr(feature = "ffi", derive(uniffi::Error))]
+#[derive(Debug)]
 pub enum EZKLError {
     InternalError(String),
     InvalidInput(String),
+    AlreadyInitialized,
+    Busy {
+        running_proofs: u32,
+    },
+    JsonError {
+        field: String,
+        line: u64,
+        column: u64,
+        message: String,
+    },
+    FingerprintMismatch {
+        artifact: String,
+        expected: String,
+        actual: String,
+    },
+    TimedOut {
+        elapsed_ms: u64,
+    },
+    MissingInput {
+        node: u64,
+    },
+    ShapeMismatch {
+        node: u64,
+        detail: String,
+    },
+    LoadVk {
+        detail: String,
+    },
+    LoadPk {
+        detail: String,
+    },
+    SrsRead {
+        detail: String,
+    },
+    ConcurrentModification {
+        path: String,
+    },
+    Unsupported {
+        detail: String,
+    },
+    ExternalVerificationFailed {
+        detail: String,
+    },
+    Network {
+        detail: String,
+    },
+    OutputMismatch {
+        expected: String,
+        actual: String,
+    },
 }
 
 impl Display for EZKLError {
@@ -12,18 +70,243 @@ impl Display for EZKLError {
         match self {
             EZKLError::InternalError(e) => write!(f, "Internal error: {}", e),
             EZKLError::InvalidInput(e) => write!(f, "Invalid input: {}", e),
+            EZKLError::AlreadyInitialized => {
+                write!(f, "Already initialized with a different configuration")
+            }
+            EZKLError::Busy { running_proofs } => {
+                write!(f, "Busy: {} proofs are already running", running_proofs)
+            }
+            EZKLError::JsonError {
+                field,
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Invalid JSON in `{}` at line {}, column {}: {}",
+                field, line, column, message
+            ),
+            EZKLError::FingerprintMismatch {
+                artifact,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Fingerprint mismatch: the {} has fingerprint {}, but {} was expected",
+                artifact, actual, expected
+            ),
+            EZKLError::TimedOut { elapsed_ms } => {
+                write!(
+                    f,
+                    "Timed out: the call exceeded its time budget after {} ms",
+                    elapsed_ms
+                )
+            }
+            EZKLError::MissingInput { node } => {
+                write!(f, "Missing input: node {} of the model has no input", node)
+            }
+            EZKLError::ShapeMismatch { node, detail } => {
+                write!(f, "Shape mismatch at node {}: {}", node, detail)
+            }
+            EZKLError::LoadVk { detail } => {
+                write!(f, "Failed to load the verification key: {}", detail)
+            }
+            EZKLError::LoadPk { detail } => write!(f, "Failed to load the proving key: {}", detail),
+            EZKLError::SrsRead { detail } => write!(f, "Failed to read the SRS: {}", detail),
+            EZKLError::ConcurrentModification { path } => write!(
+                f,
+                "Concurrent modification: `{}` changed while it was read, twice",
+                path
+            ),
+            EZKLError::Unsupported { detail } => write!(f, "Unsupported: {}", detail),
+            EZKLError::ExternalVerificationFailed { detail } => write!(
+                f,
+                "The proof does not verify against the given verification key: {}",
+                detail
+            ),
+            EZKLError::Network { detail } => write!(f, "Network error: {}", detail),
+            EZKLError::OutputMismatch { expected, actual } => write!(
+                f,
+                "Output mismatch: the claimed outputs hash to {}, but the proof attests to {}",
+                actual, expected
+            ),
         }
     }
 }
 
+impl EZKLError {
+    /// The name of the variant, as it appears in the bindings.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            EZKLError::InternalError(_) => "InternalError",
+            EZKLError::InvalidInput(_) => "InvalidInput",
+            EZKLError::AlreadyInitialized => "AlreadyInitialized",
+            EZKLError::Busy { .. } => "Busy",
+            EZKLError::JsonError { .. } => "JsonError",
+            EZKLError::FingerprintMismatch { .. } => "FingerprintMismatch",
+            EZKLError::TimedOut { .. } => "TimedOut",
+            EZKLError::MissingInput { .. } => "MissingInput",
+            EZKLError::ShapeMismatch { .. } => "ShapeMismatch",
+            EZKLError::LoadVk { .. } => "LoadVk",
+            EZKLError::LoadPk { .. } => "LoadPk",
+            EZKLError::SrsRead { .. } => "SrsRead",
+            EZKLError::ConcurrentModification { .. } => "ConcurrentModification",
+            EZKLError::Unsupported { .. } => "Unsupported",
+            EZKLError::ExternalVerificationFailed { .. } => "ExternalVerificationFailed",
+            EZKLError::Network { .. } => "Network",
+            EZKLError::OutputMismatch { .. } => "OutputMismatch",
+        }
+    }
+}
+
+/// Serializes the error as a flat map with the variant name, the display message and the
+/// structured fields of the variant.
+impl Serialize for EZKLError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("variant", self.variant_name())?;
+        map.serialize_entry("message", &self.to_string())?;
+        match self {
+            EZKLError::InternalError(detail)
+            | EZKLError::InvalidInput(detail)
+            | EZKLError::LoadVk { detail }
+            | EZKLError::LoadPk { detail }
+            | EZKLError::SrsRead { detail }
+            | EZKLError::Unsupported { detail }
+            | EZKLError::ExternalVerificationFailed { detail }
+            | EZKLError::Network { detail } => {
+                map.serialize_entry("detail", detail)?;
+            }
+            EZKLError::AlreadyInitialized => {}
+            EZKLError::Busy { running_proofs } => {
+                map.serialize_entry("running_proofs", running_proofs)?;
+            }
+            EZKLError::JsonError {
+                field,
+                line,
+                column,
+                message: _,
+            } => {
+                map.serialize_entry("field", field)?;
+                map.serialize_entry("line", line)?;
+                map.serialize_entry("column", column)?;
+            }
+            EZKLError::FingerprintMismatch {
+                artifact,
+                expected,
+                actual,
+            } => {
+                map.serialize_entry("artifact", artifact)?;
+                map.serialize_entry("expected", expected)?;
+                map.serialize_entry("actual", actual)?;
+            }
+            EZKLError::TimedOut { elapsed_ms } => {
+                map.serialize_entry("elapsed_ms", elapsed_ms)?;
+            }
+            EZKLError::MissingInput { node } => {
+                map.serialize_entry("node", node)?;
+            }
+            EZKLError::ShapeMismatch { node, detail } => {
+                map.serialize_entry("node", node)?;
+                map.serialize_entry("detail", detail)?;
+            }
+            EZKLError::ConcurrentModification { path } => {
+                map.serialize_entry("path", path)?;
+            }
+            EZKLError::OutputMismatch { expected, actual } => {
+                map.serialize_entry("expected", expected)?;
+                map.serialize_entry("actual", actual)?;
+            }
+        }
+        map.end()
+    }
+}
+
+/// An error together with the stage of the caller's pipeline it occurred in, for reporting.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug)]
+pub struct ErrorReport {
+    /// The error returned by one of the exported functions.
+    #[serde(flatten)]
+    pub error: EZKLError,
+    /// The stage the error occurred in, e.g. `"gen_witness"` or `"prove"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stage: Option<String>,
+}
+
+/// Serializes an error to canonical JSON, e.g. to upload diagnostics to a backend.
+///
+/// The JSON object contains the `variant` name, the `message` shown to users, the `stage` if
+/// one was given, and the structured fields of the variant, such as the `artifact` and the
+/// `expected` and `actual` fingerprints of a `FingerprintMismatch`.
+///
+/// # Arguments
+///
+/// * `report` - An `ErrorReport` with the error and the stage it occurred in.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The JSON representation of the error.
+/// * `Err(ExternalEZKLError)` - If serialization fails.
+#[cfg_attr(feature = "ffi", export)]
+pub fn error_to_json(report: ErrorReport) -> Result<String, EZKLError> {
+    Ok(serde_json::to_string(&report)?)
+}
+
+// The errors callers can act on keep their own variants, everything else is an internal error
 impl From<InnerEZKLError> for EZKLError {
     fn from(e: InnerEZKLError) -> Self {
-        EZKLError::InternalError(e.to_string())
+        match e {
+            InnerEZKLError::GraphError(e) => e.into(),
+            InnerEZKLError::PfsysError(e) => e.into(),
+            InnerEZKLError::SrsError(e) => e.into(),
+            e => EZKLError::InternalError(e.to_string()),
+        }
+    }
+}
+
+impl From<GraphError> for EZKLError {
+    fn from(e: GraphError) -> Self {
+        match e {
+            GraphError::MissingInput(node) => EZKLError::MissingInput { node: node as u64 },
+            GraphError::InvalidDims(node, detail) => EZKLError::ShapeMismatch {
+                node: node as u64,
+                detail,
+            },
+            e => EZKLError::InternalError(InnerEZKLError::from(e).to_string()),
+        }
+    }
+}
+
+impl From<PfsysError> for EZKLError {
+    fn from(e: PfsysError) -> Self {
+        match e {
+            PfsysError::LoadVk(detail) => EZKLError::LoadVk { detail },
+            PfsysError::LoadPk(detail) => EZKLError::LoadPk { detail },
+            e => EZKLError::InternalError(InnerEZKLError::from(e).to_string()),
+        }
+    }
+}
+
+impl From<SrsError> for EZKLError {
+    fn from(e: SrsError) -> Self {
+        match e {
+            SrsError::ReadError(detail) => EZKLError::SrsRead { detail },
+            e => EZKLError::InternalError(InnerEZKLError::from(e).to_string()),
+        }
+    }
+}
+
+// Lets app-implemented callback interfaces, such as `ArtifactProvider`, return this error
+#[cfg(feature = "ffi")]
+impl From<uniffi::UnexpectedUniFFICallbackError> for EZKLError {
+    fn from(e: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        EZKLError::InternalError(e.reason)
     }
 }
 
-impl From<ezkl::graph::errors::GraphError> for EZKLError {
-    fn from(e: ezkl::graph::errors::GraphError) -> Self {
-        EZKLError::InternalError(e.to_string())
+impl From<serde_json::Error> for EZKLError {
+    fn from(e: serde_json::Error) -> Self {
+        InnerEZKLError::from(e).into()
     }
 }
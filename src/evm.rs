@@ -0,0 +1,228 @@
+use crate::limits::check_input_sizes;
+use crate::normalize::{encode_felt, FeltEncoding};
+use crate::serialization::{deserialize_json, deserialize_proof};
+use crate::ExternalEZKLError;
+use ezkl::graph::GraphSettings;
+use ezkl::pfsys::{Snark, TranscriptType};
+use ezkl::Commitments;
+use halo2_proofs::halo2curves::bn256::{Fr, G1Affine};
+use halo2_proofs::halo2curves::ff::PrimeField;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The selector of `verifyProof(bytes,uint256[])`, the entry point of the ezkl Solidity verifier.
+const VERIFY_PROOF_SELECTOR: [u8; 4] = [0x1e, 0x8e, 0x1e, 0x13];
+/// The size of an ABI word.
+const WORD_LEN: usize = 32;
+/// The gas charged per zero byte of calldata.
+const ZERO_BYTE_GAS: u64 = 4;
+/// The gas charged per non-zero byte of calldata.
+const NON_ZERO_BYTE_GAS: u64 = 16;
+/// The base cost of every transaction.
+const TRANSACTION_GAS: u64 = 21_000;
+/// A rough cost of a KZG verifier independent of the instances, dominated by the pairing check
+/// and the multi-scalar multiplication over the proof commitments.
+const VERIFIER_BASE_GAS: u64 = 300_000;
+/// The cost of an elliptic curve multiplication and addition per public instance.
+const VERIFIER_INSTANCE_GAS: u64 = 6_150;
+
+/// An estimate of the gas needed to verify a proof on-chain.
+///
+/// Only the calldata figures are exact; the verification gas depends on the generated verifier
+/// contract and is a rough estimate.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasEstimate {
+    /// The size of the calldata of the `verifyProof` call in bytes.
+    pub calldata_bytes: u64,
+    /// The gas charged for the calldata, at 16 gas per non-zero and 4 gas per zero byte.
+    pub calldata_gas: u64,
+    /// A rough estimate of the gas used by the verifier contract.
+    pub estimated_verification_gas: u64,
+    /// The estimated total gas of the transaction, including the base transaction cost.
+    pub estimated_total_gas: u64,
+}
+
+/// Encodes a proof as the calldata of a `verifyProof(bytes,uint256[])` call to the ezkl
+/// Solidity verifier, e.g. to hand it to a relayer.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof.
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)` - The ABI encoded calldata.
+/// * `Err(ExternalEZKLError)` - If the proof could not be parsed or was not created with the EVM transcript.
+#[cfg_attr(feature = "ffi", export)]
+pub fn encode_evm_calldata(proof_json: String) -> Result<Vec<u8>, ExternalEZKLError> {
+    check_input_sizes!(proof_json);
+    let proof = evm_proof(&proof_json)?;
+    Ok(encode_calldata(&proof))
+}
+
+/// Returns the public instances of a proof as 32-byte big-endian words, in the order and encoding
+/// they have in the `uint256[]` of [`encode_evm_calldata`], e.g. for web3 libraries taking `[Data]`.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof.
+///
+/// # Returns
+///
+/// * `Ok(Vec<Vec<u8>>)` - The instances of every column, flattened, as 32 bytes each.
+/// * `Err(ExternalEZKLError)` - If the proof could not be parsed.
+#[cfg_attr(feature = "ffi", export)]
+pub fn proof_instances_bytes(proof_json: String) -> Result<Vec<Vec<u8>>, ExternalEZKLError> {
+    check_input_sizes!(proof_json);
+    let proof = deserialize_proof("proof_json", &proof_json)?;
+    Ok(proof
+        .instances
+        .iter()
+        .flatten()
+        .map(|instance| instance_word(instance).to_vec())
+        .collect())
+}
+
+/// Converts 32-byte big-endian words, as returned by [`proof_instances_bytes`], back into field
+/// elements, e.g. to compare expected outputs with the instances reported by `inspect_proof`.
+///
+/// # Arguments
+///
+/// * `words` - The instances as 32 bytes each.
+///
+/// # Returns
+///
+/// * `Ok(Vec<String>)` - The instances as `0x`-prefixed big-endian hex, as in `inspect_proof`.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` naming the first word that is not 32 bytes long or
+///   not smaller than the modulus of the field.
+#[cfg_attr(feature = "ffi", export)]
+pub fn instances_from_bytes(words: Vec<Vec<u8>>) -> Result<Vec<String>, ExternalEZKLError> {
+    check_input_sizes!(words);
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            if word.len() != WORD_LEN {
+                return Err(ExternalEZKLError::InvalidInput(format!(
+                    "instance {} has {} bytes, instances are {} bytes",
+                    i,
+                    word.len(),
+                    WORD_LEN
+                )));
+            }
+            let mut repr = <Fr as PrimeField>::Repr::default();
+            repr.as_mut().copy_from_slice(word);
+            repr.as_mut().reverse();
+            let felt: Option<Fr> = Fr::from_repr(repr).into();
+            let felt = felt.ok_or_else(|| {
+                ExternalEZKLError::InvalidInput(format!(
+                    "instance {} is not smaller than the modulus of the field",
+                    i
+                ))
+            })?;
+            Ok(encode_felt(&felt, FeltEncoding::Hex))
+        })
+        .collect()
+}
+
+/// Estimates the gas needed to verify a proof on-chain.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+///
+/// # Returns
+///
+/// * `Ok(GasEstimate)` - The exact calldata size and gas, and a rough estimate of the verification gas.
+/// * `Err(ExternalEZKLError)` - If the proof can't be verified on-chain, e.g. because it was
+///   created with the Poseidon transcript or the IPA commitment scheme.
+#[cfg_attr(feature = "ffi", export)]
+pub fn estimate_evm_gas(
+    proof_json: String,
+    settings_json: String,
+) -> Result<GasEstimate, ExternalEZKLError> {
+    check_input_sizes!(proof_json, settings_json);
+    let settings: GraphSettings = deserialize_json("settings_json", &settings_json)?;
+    if Commitments::from(settings.run_args.commitment) != Commitments::KZG {
+        return Err(ExternalEZKLError::InvalidInput(
+            "only KZG proofs can be verified on-chain".to_string(),
+        ));
+    }
+    let proof = evm_proof(&proof_json)?;
+
+    let calldata = encode_calldata(&proof);
+    let calldata_gas = calldata
+        .iter()
+        .map(|&byte| {
+            if byte == 0 {
+                ZERO_BYTE_GAS
+            } else {
+                NON_ZERO_BYTE_GAS
+            }
+        })
+        .sum();
+    let num_instances = proof.instances.iter().map(Vec::len).sum::<usize>() as u64;
+    let estimated_verification_gas = VERIFIER_BASE_GAS + num_instances * VERIFIER_INSTANCE_GAS;
+
+    Ok(GasEstimate {
+        calldata_bytes: calldata.len() as u64,
+        calldata_gas,
+        estimated_verification_gas,
+        estimated_total_gas: TRANSACTION_GAS + calldata_gas + estimated_verification_gas,
+    })
+}
+
+/// Parses a proof, rejecting proofs that can't be verified by the Solidity verifier.
+fn evm_proof(proof_json: &str) -> Result<Snark<Fr, G1Affine>, ExternalEZKLError> {
+    let proof = deserialize_proof("proof_json", proof_json)?;
+    if matches!(proof.transcript_type, TranscriptType::Poseidon) {
+        return Err(ExternalEZKLError::InvalidInput(
+            "the proof was created with the Poseidon transcript, which the EVM verifier can't verify; prove with the Single proof type".to_string(),
+        ));
+    }
+    Ok(proof)
+}
+
+/// ABI encodes `verifyProof(proof.proof, instances)`.
+fn encode_calldata(proof: &Snark<Fr, G1Affine>) -> Vec<u8> {
+    let instances: Vec<&Fr> = proof.instances.iter().flatten().collect();
+    let proof_words = proof.proof.len().div_ceil(WORD_LEN);
+    let instances_offset = 2 * WORD_LEN + WORD_LEN + proof_words * WORD_LEN;
+
+    let mut calldata = Vec::with_capacity(instances_offset + (1 + instances.len()) * WORD_LEN + 4);
+    calldata.extend_from_slice(&VERIFY_PROOF_SELECTOR);
+    // The offsets of the dynamic arguments, relative to the start of the arguments
+    calldata.extend_from_slice(&word(2 * WORD_LEN as u64));
+    calldata.extend_from_slice(&word(instances_offset as u64));
+
+    calldata.extend_from_slice(&word(proof.proof.len() as u64));
+    calldata.extend_from_slice(&proof.proof);
+    calldata.resize(
+        calldata.len() + proof_words * WORD_LEN - proof.proof.len(),
+        0,
+    );
+
+    calldata.extend_from_slice(&word(instances.len() as u64));
+    for instance in instances {
+        calldata.extend_from_slice(&instance_word(instance));
+    }
+    calldata
+}
+
+/// Encodes a field element as a big-endian `uint256` ABI word.
+fn instance_word(instance: &Fr) -> [u8; WORD_LEN] {
+    // Field elements are little-endian, uint256 words big-endian
+    let mut word = [0; WORD_LEN];
+    word.copy_from_slice(instance.to_repr().as_ref());
+    word.reverse();
+    word
+}
+
+/// Encodes an integer as a big-endian ABI word.
+fn word(value: u64) -> [u8; WORD_LEN] {
+    let mut word = [0; WORD_LEN];
+    word[WORD_LEN - 8..].copy_from_slice(&value.to_be_bytes());
+    word
+}
@@ -0,0 +1,198 @@
+use crate::config;
+use crate::logging::warn;
+use crate::ExternalEZKLError;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// The scheduling priority of the worker threads the async functions run their work on.
+///
+/// On iOS and macOS the priority is applied as the quality of service class of the thread, so
+/// that the scheduler does not throttle long running work. It has no effect on other platforms.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadPriority {
+    /// Work the user is interacting with, such as animations.
+    UserInteractive,
+    /// Work the user started and is waiting for, such as verifying a proof shown on screen.
+    UserInitiated,
+    /// The priority of threads that were not given one.
+    Default,
+    /// Long running work the user is aware of, such as proving with a progress indicator.
+    Utility,
+    /// Work the user is not aware of, such as prefetching.
+    Background,
+}
+
+impl ThreadPriority {
+    /// The `qos_class_t` value of the priority, from `<sys/qos.h>`.
+    #[cfg_attr(not(target_vendor = "apple"), allow(dead_code))]
+    fn qos_class(self) -> u32 {
+        match self {
+            ThreadPriority::UserInteractive => 0x21,
+            ThreadPriority::UserInitiated => 0x19,
+            ThreadPriority::Default => 0x15,
+            ThreadPriority::Utility => 0x11,
+            ThreadPriority::Background => 0x09,
+        }
+    }
+}
+
+/// The kind of work an async function runs, which selects the worker thread it runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WorkKind {
+    Prove,
+    Verify,
+}
+
+impl WorkKind {
+    /// The name of the worker threads running this kind of work.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn thread_name(self) -> &'static str {
+        match self {
+            WorkKind::Prove => "ezkl-prove",
+            WorkKind::Verify => "ezkl-verify",
+        }
+    }
+
+    /// The configured priority of this kind of work, or its default.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn priority(self) -> ThreadPriority {
+        let config = config::current();
+        match self {
+            WorkKind::Prove => config
+                .prove_thread_priority
+                .unwrap_or(ThreadPriority::Utility),
+            WorkKind::Verify => config
+                .verify_thread_priority
+                .unwrap_or(ThreadPriority::UserInitiated),
+        }
+    }
+}
+
+#[cfg(target_vendor = "apple")]
+extern "C" {
+    fn pthread_set_qos_class_self_np(qos_class: u32, relative_priority: i32) -> i32;
+}
+
+/// Applies the priority to the calling thread.
+#[cfg(not(target_arch = "wasm32"))]
+fn apply_priority(priority: ThreadPriority) {
+    #[cfg(target_vendor = "apple")]
+    {
+        // SAFETY: the call only changes the scheduling of the calling thread
+        let result = unsafe { pthread_set_qos_class_self_np(priority.qos_class(), 0) };
+        if result != 0 {
+            warn!("failed to set the thread priority to {:?}", priority);
+        }
+    }
+    #[cfg(not(target_vendor = "apple"))]
+    let _ = priority;
+}
+
+/// The result of the work, shared between the worker thread and the awaiting future.
+struct Slot<T> {
+    result: Option<Result<T, ExternalEZKLError>>,
+    waker: Option<Waker>,
+}
+
+/// Completes the future of the work, with an error if the worker exits without a result.
+struct Completion<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> Completion<T> {
+    fn complete(&self, result: Result<T, ExternalEZKLError>) {
+        let waker = match self.slot.lock() {
+            Ok(mut slot) => {
+                if slot.result.is_none() {
+                    slot.result = Some(result);
+                }
+                slot.waker.take()
+            }
+            Err(_) => None,
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for Completion<T> {
+    fn drop(&mut self) {
+        // Without a result, the worker panicked or could not be spawned
+        self.complete(Err(ExternalEZKLError::InternalError(
+            "the worker thread exited without a result".to_string(),
+        )));
+    }
+}
+
+/// The future of work running on a worker thread.
+pub(crate) struct WorkerFuture<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> Future for WorkerFuture<T> {
+    type Output = Result<T, ExternalEZKLError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = match self.slot.lock() {
+            Ok(slot) => slot,
+            Err(_) => {
+                return Poll::Ready(Err(ExternalEZKLError::InternalError(
+                    "worker result lock poisoned".to_string(),
+                )))
+            }
+        };
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Runs blocking work on a new worker thread named and prioritized for its kind.
+///
+/// The futures of exported async functions are polled by the foreign executor, so running the
+/// work on a thread of our own keeps it off the caller's thread and lets it be prioritized
+/// independently of the caller.
+pub(crate) fn run_on_worker<T, F>(kind: WorkKind, work: F) -> WorkerFuture<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, ExternalEZKLError> + Send + 'static,
+{
+    let slot = Arc::new(Mutex::new(Slot {
+        result: None,
+        waker: None,
+    }));
+    let completion = Completion { slot: slot.clone() };
+
+    // wasm32 has no threads to spawn, so the work runs on the calling one
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = kind;
+        completion.complete(work());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let priority = kind.priority();
+        let spawned = std::thread::Builder::new()
+            .name(kind.thread_name().to_string())
+            .spawn(move || {
+                apply_priority(priority);
+                let result = work();
+                completion.complete(result);
+            });
+        if let Err(e) = spawned {
+            // The completion was dropped with the closure, failing the future
+            warn!("failed to spawn a worker thread: {}", e);
+        }
+    }
+
+    WorkerFuture { slot }
+}
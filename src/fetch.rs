@@ -0,0 +1,273 @@
+use crate::files::io_error;
+use crate::logging::{debug, info, warn};
+use crate::progress::{KeyGenStage, ProgressListener};
+use crate::ExternalEZKLError;
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::sync::Arc;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The suffix of the file a download is written to until it is complete and verified.
+const PARTIAL_SUFFIX: &str = ".part";
+/// The number of requests a call makes before giving up, resuming after each interruption.
+const MAX_ATTEMPTS: u32 = 3;
+/// The number of bytes downloaded between two progress reports.
+const PROGRESS_INTERVAL_BYTES: u64 = 1024 * 1024;
+
+/// Downloads an artifact, such as an SRS or a circuit bundle, and moves it to `dest_path` once
+/// its SHA-256 digest matches the pinned one.
+///
+/// The download is written to `<dest_path>.part` and hashed while it streams. An interrupted
+/// download is resumed with a `Range` request, both within the call and by the next call with
+/// the same `dest_path`, so large files don't restart from zero on flaky connections. A file
+/// whose digest mismatches is deleted and never moved to `dest_path`; if the download resumed a
+/// partial file, e.g. of an earlier version of the artifact, it is downloaded once more from the
+/// start first. If `dest_path` already holds the artifact, nothing is downloaded.
+///
+/// # Arguments
+///
+/// * `url` - The HTTP or HTTPS URL of the artifact.
+/// * `expected_sha256` - The hex-encoded SHA-256 digest the artifact must have.
+/// * `dest_path` - The path the verified artifact is moved to.
+/// * `listener` - The listener receiving the `Download` progress in bytes, if any. The total is
+///   0 while the server didn't report it.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the artifact at `dest_path` has the expected digest.
+/// * `Err(ExternalEZKLError)` - `FingerprintMismatch` if the downloaded artifact has a different
+///   digest, `Network` if the download failed after resuming it, or `InvalidInput` for an
+///   invalid digest or a path that cannot be written.
+#[cfg_attr(feature = "ffi", export)]
+pub fn fetch_artifact(
+    url: String,
+    expected_sha256: String,
+    dest_path: String,
+    listener: Option<Arc<dyn ProgressListener>>,
+) -> Result<(), ExternalEZKLError> {
+    let expected = expected_sha256.to_ascii_lowercase();
+    if expected.len() != 64 || !expected.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "`{}` is not a hex-encoded SHA-256 digest",
+            expected_sha256
+        )));
+    }
+    if digest_of_file(&dest_path).is_some_and(|actual| actual == expected) {
+        debug!("{} is already downloaded", dest_path);
+        return Ok(());
+    }
+
+    let partial_path = format!("{}{}", dest_path, PARTIAL_SUFFIX);
+    let mut partial = PartialFile::open(&partial_path)?;
+    let resumed_from = partial.len;
+    let mut actual = download(&url, &mut partial, listener.as_deref())?;
+    if actual != expected && resumed_from > 0 {
+        warn!(
+            "the download of {} resumed from {} bytes has digest {}, downloading it from the start",
+            url, resumed_from, actual
+        );
+        partial.restart()?;
+        actual = download(&url, &mut partial, listener.as_deref())?;
+    }
+    if actual != expected {
+        drop(partial);
+        let _ = std::fs::remove_file(&partial_path);
+        return Err(ExternalEZKLError::FingerprintMismatch {
+            artifact: format!("download of {}", url),
+            expected,
+            actual,
+        });
+    }
+
+    partial
+        .file
+        .sync_all()
+        .map_err(|e| io_error("sync", &partial_path, e))?;
+    drop(partial);
+    std::fs::rename(&partial_path, &dest_path).map_err(|e| io_error("move", &dest_path, e))?;
+    info!("downloaded {} to {}", url, dest_path);
+    Ok(())
+}
+
+/// A partially downloaded file, with the digest of the bytes it holds.
+struct PartialFile {
+    path: String,
+    file: File,
+    hasher: Sha256,
+    len: u64,
+}
+
+impl PartialFile {
+    /// Opens the partial file for appending, hashing the bytes an earlier call downloaded.
+    fn open(path: &str) -> Result<Self, ExternalEZKLError> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)
+            .map_err(|e| io_error("create", path, e))?;
+        let mut hasher = Sha256::new();
+        let len = std::io::copy(&mut file, &mut hasher).map_err(|e| io_error("read", path, e))?;
+        Ok(PartialFile {
+            path: path.to_string(),
+            file,
+            hasher,
+            len,
+        })
+    }
+
+    /// Discards the downloaded bytes.
+    fn restart(&mut self) -> Result<(), ExternalEZKLError> {
+        self.file
+            .set_len(0)
+            .map_err(|e| io_error("truncate", &self.path, e))?;
+        self.hasher = Sha256::new();
+        self.len = 0;
+        Ok(())
+    }
+
+    fn append(&mut self, bytes: &[u8]) -> Result<(), ExternalEZKLError> {
+        self.file
+            .write_all(bytes)
+            .map_err(|e| io_error("write", &self.path, e))?;
+        self.hasher.update(bytes);
+        self.len += bytes.len() as u64;
+        Ok(())
+    }
+}
+
+/// Downloads the rest of the artifact into the partial file, resuming after interruptions, and
+/// returns the hex-encoded digest of the complete file.
+fn download(
+    url: &str,
+    partial: &mut PartialFile,
+    listener: Option<&dyn ProgressListener>,
+) -> Result<String, ExternalEZKLError> {
+    let mut attempt = 1;
+    loop {
+        match download_attempt(url, partial, listener) {
+            Ok(()) => return Ok(hex::encode(partial.hasher.clone().finalize())),
+            Err(e) if attempt < MAX_ATTEMPTS => {
+                warn!(
+                    "download of {} interrupted after {} bytes, resuming: {}",
+                    url, partial.len, e
+                );
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Requests the bytes after those in the partial file and appends them, until the response ends.
+fn download_attempt(
+    url: &str,
+    partial: &mut PartialFile,
+    listener: Option<&dyn ProgressListener>,
+) -> Result<(), ExternalEZKLError> {
+    let mut request = ureq::get(url);
+    if partial.len > 0 {
+        request = request.set("Range", &format!("bytes={}-", partial.len));
+    }
+    let response = match request.call() {
+        Ok(response) => response,
+        // The partial file holds the whole artifact, or more if it changed on the server
+        Err(ureq::Error::Status(416, _)) if partial.len > 0 => {
+            partial.restart()?;
+            return Err(network_error(url, "the requested range is not satisfiable"));
+        }
+        Err(ureq::Error::Status(status, _)) => {
+            return Err(network_error(url, format!("HTTP status {}", status)));
+        }
+        Err(e) => return Err(network_error(url, e)),
+    };
+
+    let total = match response.status() {
+        206 => {
+            let content_range = response.header("Content-Range").unwrap_or_default();
+            let Some(total) = resumed_total(content_range, partial.len) else {
+                partial.restart()?;
+                return Err(network_error(
+                    url,
+                    format!("unexpected Content-Range `{}`", content_range),
+                ));
+            };
+            Some(total)
+        }
+        _ => {
+            // The server ignored the range and sends the whole artifact
+            if partial.len > 0 {
+                debug!(
+                    "{} doesn't support resumption, downloading it from the start",
+                    url
+                );
+                partial.restart()?;
+            }
+            response
+                .header("Content-Length")
+                .and_then(|length| length.parse().ok())
+        }
+    };
+
+    let mut reader = response.into_reader();
+    let mut buffer = vec![0; 64 * 1024];
+    let mut reported = partial.len;
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .map_err(|e| network_error(url, e))?;
+        if read == 0 {
+            break;
+        }
+        partial.append(&buffer[..read])?;
+        if partial.len - reported >= PROGRESS_INTERVAL_BYTES {
+            reported = partial.len;
+            report(listener, partial.len, total.unwrap_or(0));
+        }
+    }
+
+    if let Some(total) = total.filter(|total| partial.len < *total) {
+        return Err(network_error(
+            url,
+            format!(
+                "the connection closed after {} of {} bytes",
+                partial.len, total
+            ),
+        ));
+    }
+    report(listener, partial.len, total.unwrap_or(partial.len));
+    Ok(())
+}
+
+/// Returns the total length of the artifact from the `Content-Range` of a response resuming at
+/// `offset`, or `None` if the response doesn't resume there.
+fn resumed_total(content_range: &str, offset: u64) -> Option<u64> {
+    let (range, total) = content_range.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, _) = range.split_once('-')?;
+    if start.parse::<u64>().ok()? != offset {
+        return None;
+    }
+    total.parse().ok()
+}
+
+fn report(listener: Option<&dyn ProgressListener>, completed: u64, total: u64) {
+    if let Some(listener) = listener {
+        listener.on_progress(KeyGenStage::Download, completed, total);
+    }
+}
+
+/// Returns the hex-encoded SHA-256 digest of a file, or `None` if it can't be read.
+fn digest_of_file(path: &str) -> Option<String> {
+    let mut file = File::open(path).ok()?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).ok()?;
+    Some(hex::encode(hasher.finalize()))
+}
+
+fn network_error(url: &str, detail: impl std::fmt::Display) -> ExternalEZKLError {
+    ExternalEZKLError::Network {
+        detail: format!("failed to download {}: {}", url, detail),
+    }
+}
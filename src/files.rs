@@ -0,0 +1,623 @@
+use crate::config;
+#[cfg(feature = "prover")]
+use crate::gen_witness::{gen_witness_internal, WitnessOptions};
+use crate::io::ezkl_writer;
+#[cfg(feature = "prover")]
+use crate::limits::{check_input_sizes, ProvingPermit, TimeBudget};
+use crate::logging::warn;
+#[cfg(feature = "prover")]
+use crate::prove::{prove_witness, ProveOptions};
+#[cfg(feature = "prover")]
+use crate::sensitive::Sensitive;
+#[cfg(feature = "prover")]
+use crate::serialization::deserialize_json_file;
+use crate::serialization::deserialize_proof_file;
+#[cfg(feature = "prover")]
+use crate::store::{ArtifactKind, ArtifactStore};
+use crate::verify::verify_snark;
+use crate::ExternalEZKLError;
+#[cfg(feature = "prover")]
+use ezkl::graph::GraphWitness;
+#[cfg(feature = "prover")]
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+#[cfg(any(test, feature = "test-hooks"))]
+use std::sync::atomic::{AtomicU32, Ordering};
+#[cfg(feature = "prover")]
+use std::sync::Arc;
+#[cfg(any(test, feature = "test-hooks"))]
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+#[cfg(feature = "ffi")]
+use uniffi::export;
+use uuid::Uuid;
+
+/// The separator between the output file name and the unique id of its temporary file.
+const TEMP_MARKER: &str = ".tmp-";
+
+/// Set to make a write fail before the temporary file is moved, to test interrupted writes: the
+/// number of writes until the failing one, counting it, or 0 to let every write succeed.
+#[cfg(any(test, feature = "test-hooks"))]
+static FAILING_WRITE: AtomicU32 = AtomicU32::new(0);
+
+/// Metadata about an artifact written to a file.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactMetadata {
+    /// The size of the written file in bytes.
+    pub byte_length: u64,
+    /// The hex encoded SHA-256 digest of the written file.
+    pub sha256: String,
+}
+
+/// Generates a witness and writes it directly to a file instead of returning it.
+///
+/// Use this for circuits whose witness is too large to pass back efficiently as a `String`.
+///
+/// # Arguments
+///
+/// * `input_json` - A `String` containing the JSON representation of the input data for the circuit.
+/// * `circuit_path` - The path of the compiled circuit.
+/// * `vk_path` - The path of the Verification Key (VK).
+/// * `srs_path` - The path of the Structured Reference String (SRS).
+/// * `out_path` - The path the witness JSON is written to.
+///
+/// # Returns
+///
+/// * `Ok(ArtifactMetadata)` - The size and digest of the written witness.
+/// * `Err(ExternalEZKLError)` - An error that occurred during witness generation; no file is left behind.
+#[cfg(feature = "prover")]
+#[cfg_attr(feature = "ffi", export)]
+pub async fn gen_witness_to_file(
+    input_json: String,
+    circuit_path: String,
+    vk_path: String,
+    srs_path: String,
+    out_path: String,
+) -> Result<ArtifactMetadata, ExternalEZKLError> {
+    check_input_sizes!(input_json);
+    let output = OutputFile::create(&out_path)?;
+
+    let compiled_circuit = read_artifact(&circuit_path)?;
+    let vk = read_artifact(&vk_path)?;
+    let srs = read_artifact(&srs_path)?;
+    write_witness(input_json, &compiled_circuit, &vk, &srs, output).await
+}
+
+/// Generates a witness like `gen_witness_to_file`, reading the circuit, vk and SRS from an artifact store.
+///
+/// # Arguments
+///
+/// * `input_json` - A `String` containing the JSON representation of the input data for the circuit.
+/// * `store` - The `ArtifactStore` holding the artifacts of the circuit.
+/// * `circuit_fingerprint` - The fingerprint of the circuit the artifacts are stored under.
+/// * `out_path` - The path the witness JSON is written to.
+///
+/// # Returns
+///
+/// * `Ok(ArtifactMetadata)` - The size and digest of the written witness.
+/// * `Err(ExternalEZKLError)` - If an artifact is missing or corrupted, or an error that occurred
+///   during witness generation; no file is left behind.
+#[cfg(feature = "prover")]
+#[cfg_attr(feature = "ffi", export)]
+pub async fn gen_witness_to_file_from_store(
+    input_json: String,
+    store: Arc<ArtifactStore>,
+    circuit_fingerprint: String,
+    out_path: String,
+) -> Result<ArtifactMetadata, ExternalEZKLError> {
+    check_input_sizes!(input_json);
+    let output = OutputFile::create(&out_path)?;
+
+    let compiled_circuit = store.read(ArtifactKind::CompiledCircuit, &circuit_fingerprint)?;
+    let vk = store.read(ArtifactKind::VerificationKey, &circuit_fingerprint)?;
+    let srs = store.read(ArtifactKind::Srs, &circuit_fingerprint)?;
+    write_witness(input_json, &compiled_circuit, &vk, &srs, output).await
+}
+
+#[cfg(feature = "prover")]
+async fn write_witness(
+    input_json: String,
+    compiled_circuit: &[u8],
+    vk: &[u8],
+    srs: &[u8],
+    output: OutputFile,
+) -> Result<ArtifactMetadata, ExternalEZKLError> {
+    let witness = gen_witness_internal(
+        compiled_circuit,
+        input_json,
+        Some(vk),
+        Some(srs),
+        &WitnessOptions::default(),
+    )
+    .await?;
+
+    output.write_json(&witness)
+}
+
+/// Generates a proof from a witness file and writes it directly to a file instead of returning it.
+///
+/// Uses the default proving configuration, like `prove`. The witness is parsed while it is read,
+/// so the peak memory holds the parsed witness but never the whole witness JSON.
+///
+/// # Arguments
+///
+/// * `witness_path` - The path of the witness JSON generated for the circuit input.
+/// * `circuit_path` - The path of the compiled circuit.
+/// * `pk_path` - The path of the Proving Key (PK).
+/// * `srs_path` - The path of the Structured Reference String (SRS).
+/// * `out_path` - The path the proof JSON is written to.
+///
+/// # Returns
+///
+/// * `Ok(ArtifactMetadata)` - The size and digest of the written proof.
+/// * `Err(ExternalEZKLError)` - An error that occurred during proving; no file is left behind.
+#[cfg(feature = "prover")]
+#[cfg_attr(feature = "ffi", export)]
+pub fn prove_to_file(
+    witness_path: String,
+    circuit_path: String,
+    pk_path: String,
+    srs_path: String,
+    out_path: String,
+) -> Result<ArtifactMetadata, ExternalEZKLError> {
+    let output = OutputFile::create(&out_path)?;
+
+    let data: Sensitive<GraphWitness> = Sensitive::new(coordinated_read(&witness_path, || {
+        deserialize_json_file("witness_path", &witness_path)
+    })?);
+    let compiled_circuit = read_artifact(&circuit_path)?;
+    let pk = read_artifact(&pk_path)?;
+    let srs = read_artifact(&srs_path)?;
+    write_proof(data, &compiled_circuit, &pk, &srs, output)
+}
+
+/// Generates a proof like `prove_to_file`, reading the circuit, pk and SRS from an artifact store.
+///
+/// # Arguments
+///
+/// * `witness_path` - The path of the witness JSON generated for the circuit input.
+/// * `store` - The `ArtifactStore` holding the artifacts of the circuit.
+/// * `circuit_fingerprint` - The fingerprint of the circuit the artifacts are stored under.
+/// * `out_path` - The path the proof JSON is written to.
+///
+/// # Returns
+///
+/// * `Ok(ArtifactMetadata)` - The size and digest of the written proof.
+/// * `Err(ExternalEZKLError)` - If an artifact is missing or corrupted, or an error that occurred
+///   during proving; no file is left behind.
+#[cfg(feature = "prover")]
+#[cfg_attr(feature = "ffi", export)]
+pub fn prove_to_file_from_store(
+    witness_path: String,
+    store: Arc<ArtifactStore>,
+    circuit_fingerprint: String,
+    out_path: String,
+) -> Result<ArtifactMetadata, ExternalEZKLError> {
+    let output = OutputFile::create(&out_path)?;
+
+    let data: Sensitive<GraphWitness> = Sensitive::new(coordinated_read(&witness_path, || {
+        deserialize_json_file("witness_path", &witness_path)
+    })?);
+    let compiled_circuit = store.read(ArtifactKind::CompiledCircuit, &circuit_fingerprint)?;
+    let pk = store.read(ArtifactKind::ProvingKey, &circuit_fingerprint)?;
+    let srs = store.read(ArtifactKind::Srs, &circuit_fingerprint)?;
+    write_proof(data, &compiled_circuit, &pk, &srs, output)
+}
+
+#[cfg(feature = "prover")]
+fn write_proof(
+    data: Sensitive<GraphWitness>,
+    compiled_circuit: &[u8],
+    pk: &[u8],
+    srs: &[u8],
+    output: OutputFile,
+) -> Result<ArtifactMetadata, ExternalEZKLError> {
+    let options = ProveOptions::default();
+    let budget = TimeBudget::start(options.time_budget_ms);
+    let _permit = ProvingPermit::acquire()?;
+    let snark = prove_witness(data, compiled_circuit, pk, Some(srs), &options, &budget)?;
+
+    output.write_json(&snark)
+}
+
+/// Verifies a proof file, like `verify`.
+///
+/// The proof is parsed while it is read, so the peak memory holds the parsed proof but never
+/// the whole proof JSON.
+///
+/// # Arguments
+///
+/// * `proof_path` - The path of the proof JSON.
+/// * `settings_path` - The path of the circuit settings JSON.
+/// * `vk_path` - The path of the Verification Key (VK).
+/// * `srs_path` - The path of the Structured Reference String (SRS).
+///
+/// # Returns
+///
+/// * `Ok(bool)` - `true` if the proof is valid.
+/// * `Err(ExternalEZKLError)` - If a file could not be read, or an error that occurred during verification.
+#[cfg_attr(feature = "ffi", export)]
+pub fn verify_from_file(
+    proof_path: String,
+    settings_path: String,
+    vk_path: String,
+    srs_path: String,
+) -> Result<bool, ExternalEZKLError> {
+    let proof = coordinated_read(&proof_path, || {
+        deserialize_proof_file("proof_path", &proof_path)
+    })?;
+    let settings_json = coordinated_read(&settings_path, || {
+        std::fs::read_to_string(&settings_path).map_err(|e| io_error("read", &settings_path, e))
+    })?;
+    let vk = read_artifact(&vk_path)?;
+    let srs = read_artifact(&srs_path)?;
+
+    verify_snark(proof, settings_json, &vk, Some(&srs), false, false)?.into_validity()
+}
+
+pub(crate) fn read_artifact(path: &str) -> Result<Vec<u8>, ExternalEZKLError> {
+    coordinated_read(path, || {
+        std::fs::read(path).map_err(|e| io_error("read", path, e))
+    })
+}
+
+/// The state of a file that changes whenever the file is written or replaced.
+///
+/// The content hash catches rewrites that keep the length and land within the resolution of
+/// the modification time, at the cost of reading the file twice more.
+#[derive(Debug, PartialEq, Eq)]
+struct FileSnapshot {
+    len: u64,
+    modified: Option<SystemTime>,
+    #[cfg(unix)]
+    inode: u64,
+    sha256: [u8; 32],
+}
+
+impl FileSnapshot {
+    fn take(path: &str) -> Result<Self, ExternalEZKLError> {
+        let mut file = File::open(path).map_err(|e| io_error("read", path, e))?;
+        let metadata = file.metadata().map_err(|e| io_error("read", path, e))?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut file, &mut hasher).map_err(|e| io_error("read", path, e))?;
+        Ok(FileSnapshot {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+            #[cfg(unix)]
+            inode: std::os::unix::fs::MetadataExt::ino(&metadata),
+            sha256: hasher.finalize().into(),
+        })
+    }
+}
+
+/// Hooks run between taking the snapshot of a file and reading it, to simulate another process
+/// writing the file in tests.
+#[cfg(any(test, feature = "test-hooks"))]
+static READ_HOOKS: Mutex<Vec<ReadHook>> = Mutex::new(vec![]);
+
+#[cfg(any(test, feature = "test-hooks"))]
+struct ReadHook {
+    path: PathBuf,
+    remaining: u32,
+    hook: Box<dyn Fn() + Send>,
+}
+
+#[cfg(any(test, feature = "test-hooks"))]
+fn run_read_hooks(path: &str) {
+    let Ok(mut hooks) = READ_HOOKS.lock() else {
+        return;
+    };
+    for hook in hooks.iter_mut() {
+        if hook.remaining > 0 && hook.path == Path::new(path) {
+            hook.remaining -= 1;
+            (hook.hook)();
+        }
+    }
+    hooks.retain(|hook| hook.remaining > 0);
+}
+
+/// Reads a file with `read`, checking that it didn't change meanwhile if `coordinated_reads`
+/// is configured.
+///
+/// A file that changed is read once more, as the first read may have seen it partially
+/// rewritten. The result of a read is returned, error or not, only if the file was stable.
+pub(crate) fn coordinated_read<T>(
+    path: &str,
+    read: impl Fn() -> Result<T, ExternalEZKLError>,
+) -> Result<T, ExternalEZKLError> {
+    if !config::current().coordinated_reads {
+        return read();
+    }
+    for attempt in 0..2 {
+        let before = FileSnapshot::take(path)?;
+        #[cfg(any(test, feature = "test-hooks"))]
+        run_read_hooks(path);
+        let result = read();
+        if FileSnapshot::take(path)? == before {
+            return result;
+        }
+        warn!(
+            "`{}` changed while it was read (attempt {} of 2)",
+            path,
+            attempt + 1
+        );
+    }
+    Err(ExternalEZKLError::ConcurrentModification {
+        path: path.to_string(),
+    })
+}
+
+/// Runs `hook` the next `times` times `path` is read with `coordinated_reads` configured, after
+/// the snapshot of the file was taken, to simulate another process rewriting it.
+#[cfg(any(test, feature = "test-hooks"))]
+pub fn on_coordinated_read(path: &str, times: u32, hook: impl Fn() + Send + 'static) {
+    if let Ok(mut hooks) = READ_HOOKS.lock() {
+        hooks.push(ReadHook {
+            path: PathBuf::from(path),
+            remaining: times,
+            hook: Box::new(hook),
+        });
+    }
+}
+
+pub(crate) fn io_error(action: &str, path: &str, e: std::io::Error) -> ExternalEZKLError {
+    ExternalEZKLError::InvalidInput(format!("failed to {} `{}`: {}", action, path, e))
+}
+
+/// An output file that is written to a temporary file in the same directory and only moved to
+/// its final path once it was written completely, so that an interrupted call never leaves a
+/// truncated artifact behind.
+pub(crate) struct OutputFile {
+    path: PathBuf,
+    temp_path: PathBuf,
+    writer: Option<BufWriter<File>>,
+    completed: bool,
+}
+
+impl OutputFile {
+    /// Opens the temporary output file, so that unwritable paths are rejected before any work is done.
+    ///
+    /// Temporary files left behind by interrupted calls writing to the same directory are removed first.
+    pub(crate) fn create(path: &str) -> Result<Self, ExternalEZKLError> {
+        let path = PathBuf::from(path);
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| {
+                ExternalEZKLError::InvalidInput(format!("`{}` is not a file path", path.display()))
+            })?
+            .to_string_lossy()
+            .into_owned();
+        let dir = output_dir(&path);
+        if let Err(e) = remove_stale_temp_files(&dir, stale_temp_file_age()) {
+            warn!(
+                "failed to clean up temporary files in {}: {}",
+                dir.display(),
+                e
+            );
+        }
+
+        let temp_path = dir.join(format!("{}{}{}", file_name, TEMP_MARKER, Uuid::new_v4()));
+        let writer = ezkl_writer(&temp_path)
+            .map_err(|e| io_error("create", &temp_path.display().to_string(), e))?;
+        Ok(OutputFile {
+            path,
+            temp_path,
+            writer: Some(writer),
+            completed: false,
+        })
+    }
+
+    /// Serializes the value as JSON into the temporary file, syncs it to disk and moves it to the
+    /// output path, then returns the metadata of the written file.
+    #[cfg(feature = "prover")]
+    pub(crate) fn write_json<T: Serialize>(
+        self,
+        value: &T,
+    ) -> Result<ArtifactMetadata, ExternalEZKLError> {
+        self.write_with(|writer| Ok(serde_json::to_writer(writer, value)?))
+    }
+
+    /// Writes the bytes into the temporary file, syncs it to disk and moves it to the output
+    /// path, then returns the metadata of the written file.
+    pub(crate) fn write_bytes(self, bytes: &[u8]) -> Result<ArtifactMetadata, ExternalEZKLError> {
+        let temp_path = self.temp_path.display().to_string();
+        self.write_with(|writer| {
+            writer
+                .write_all(bytes)
+                .map_err(|e| io_error("write", &temp_path, e))
+        })
+    }
+
+    /// Streams the output into the temporary file through a buffered writer, syncs it to disk
+    /// and moves it to the output path, then returns the metadata of the written file.
+    pub(crate) fn write_with(
+        self,
+        write: impl FnOnce(&mut dyn Write) -> Result<(), ExternalEZKLError>,
+    ) -> Result<ArtifactMetadata, ExternalEZKLError> {
+        self.stage_with(write)?.commit()
+    }
+
+    /// Streams the output into the temporary file and syncs it to disk like
+    /// [`OutputFile::write_with`], but leaves it at the temporary path until the returned
+    /// [`StagedFile`] is committed, so that outputs belonging together are moved in place only
+    /// once all of them were written.
+    pub(crate) fn stage_with(
+        mut self,
+        write: impl FnOnce(&mut dyn Write) -> Result<(), ExternalEZKLError>,
+    ) -> Result<StagedFile, ExternalEZKLError> {
+        let writer = self.writer.take().ok_or_else(|| {
+            ExternalEZKLError::InternalError("output file already written".to_string())
+        })?;
+        let temp_path = self.temp_path.display().to_string();
+
+        let mut writer = HashingWriter::new(writer);
+        write(&mut writer)?;
+        writer
+            .flush()
+            .map_err(|e| io_error("write", &temp_path, e))?;
+        writer
+            .inner
+            .get_ref()
+            .sync_all()
+            .map_err(|e| io_error("sync", &temp_path, e))?;
+        #[cfg(any(test, feature = "test-hooks"))]
+        if FAILING_WRITE.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            == Ok(1)
+        {
+            return Err(ExternalEZKLError::InternalError(
+                "injected write failure".to_string(),
+            ));
+        }
+
+        Ok(StagedFile {
+            metadata: writer.metadata(),
+            output: self,
+        })
+    }
+}
+
+/// An output file written completely to its temporary file, which is removed unless committed.
+pub(crate) struct StagedFile {
+    output: OutputFile,
+    metadata: ArtifactMetadata,
+}
+
+impl StagedFile {
+    /// The size and digest of the written file.
+    #[cfg(feature = "prover")]
+    pub(crate) fn metadata(&self) -> &ArtifactMetadata {
+        &self.metadata
+    }
+
+    /// Moves the temporary file to the output path and returns the metadata of the written file.
+    pub(crate) fn commit(mut self) -> Result<ArtifactMetadata, ExternalEZKLError> {
+        let output = &mut self.output;
+        std::fs::rename(&output.temp_path, &output.path)
+            .map_err(|e| io_error("move", &output.path.display().to_string(), e))?;
+        output.completed = true;
+        Ok(self.metadata)
+    }
+}
+
+impl Drop for OutputFile {
+    fn drop(&mut self) {
+        if !self.completed {
+            let _ = std::fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+/// Returns the directory an output file is written to.
+fn output_dir(path: &Path) -> PathBuf {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+fn stale_temp_file_age() -> Duration {
+    Duration::from_secs(config::current().stale_temp_file_age_secs)
+}
+
+/// Returns `true` if the file name is the name of a temporary output file.
+fn is_temp_file(file_name: &str) -> bool {
+    file_name
+        .rsplit_once(TEMP_MARKER)
+        .is_some_and(|(_, id)| Uuid::parse_str(id).is_ok())
+}
+
+/// Removes the temporary output files in the directory that were last modified longer than
+/// `max_age` ago, and returns how many were removed.
+fn remove_stale_temp_files(dir: &Path, max_age: Duration) -> std::io::Result<u32> {
+    let mut removed = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if !is_temp_file(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        let age = entry
+            .metadata()?
+            .modified()?
+            .elapsed()
+            .unwrap_or(Duration::ZERO);
+        if age >= max_age {
+            std::fs::remove_file(entry.path())?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Removes the temporary files left behind in a directory by `*_to_file` calls that were
+/// interrupted, e.g. because the app was killed while writing.
+///
+/// Only temporary files older than the configured `stale_temp_file_age_secs` are removed, so
+/// calls still writing to the directory are not affected. The written artifacts are never removed.
+///
+/// # Arguments
+///
+/// * `dir` - The directory the artifacts were written to.
+///
+/// # Returns
+///
+/// * `Ok(u32)` - The number of removed temporary files.
+/// * `Err(ExternalEZKLError)` - If the directory could not be read or a file could not be removed.
+#[cfg_attr(feature = "ffi", export)]
+pub fn cleanup_artifacts(dir: String) -> Result<u32, ExternalEZKLError> {
+    remove_stale_temp_files(Path::new(&dir), stale_temp_file_age())
+        .map_err(|e| io_error("clean up", &dir, e))
+}
+
+/// Makes the next `*_to_file` call fail after writing its temporary file, as if it was interrupted.
+#[cfg(any(test, feature = "test-hooks"))]
+pub fn fail_next_artifact_write() {
+    fail_artifact_write_after(0);
+}
+
+/// Makes the artifact write following the next `writes` ones fail after writing its temporary
+/// file, e.g. to interrupt the second of two files written by one call.
+#[cfg(any(test, feature = "test-hooks"))]
+pub fn fail_artifact_write_after(writes: u32) {
+    FAILING_WRITE.store(writes + 1, Ordering::SeqCst);
+}
+
+/// A writer computing the length and SHA-256 digest of everything written through it.
+struct HashingWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+    byte_length: u64,
+}
+
+impl<W: Write> HashingWriter<W> {
+    fn new(inner: W) -> Self {
+        HashingWriter {
+            inner,
+            hasher: Sha256::new(),
+            byte_length: 0,
+        }
+    }
+
+    fn metadata(self) -> ArtifactMetadata {
+        ArtifactMetadata {
+            byte_length: self.byte_length,
+            sha256: hex::encode(self.hasher.finalize()),
+        }
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.byte_length += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
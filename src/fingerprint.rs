@@ -0,0 +1,264 @@
+use crate::limits::check_input_sizes;
+#[cfg(feature = "prover")]
+use crate::limits::{ProvingPermit, TimeBudget};
+#[cfg(feature = "prover")]
+use crate::prove::{prove_loaded, ProveOptions, Prover};
+#[cfg(feature = "prover")]
+use crate::sensitive::Sensitive;
+#[cfg(feature = "prover")]
+use crate::serialization::serialize_proof;
+use crate::serialization::{
+    deserialize_circuit, deserialize_json, deserialize_pk, deserialize_proof, deserialize_vk,
+};
+use crate::verify::verify;
+use crate::ExternalEZKLError;
+#[cfg(feature = "prover")]
+use ezkl::graph::GraphWitness;
+use ezkl::graph::{GraphCircuit, GraphSettings};
+use halo2_proofs::halo2curves::bn256::{Bn256, Fr, G1Affine};
+use halo2_proofs::halo2curves::ff::PrimeField;
+use halo2_proofs::plonk::VerifyingKey;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use sha2::{Digest, Sha256};
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The fingerprints a caller expects the artifacts passed to a prove call to have.
+#[cfg(feature = "prover")]
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, Default)]
+pub struct ExpectedFingerprints {
+    /// The expected fingerprint of the compiled circuit, as returned by `circuit_fingerprint`.
+    pub circuit: Option<String>,
+    /// The expected fingerprint of the proving key, as returned by `pk_fingerprint`.
+    pub pk: Option<String>,
+}
+
+/// Computes the fingerprint of a circuit from its settings and model.
+///
+/// The structure is re-serialized before hashing, so the fingerprint does not depend on how
+/// the compiled circuit file was produced.
+pub(crate) fn of_circuit(circuit: &GraphCircuit) -> Result<String, ExternalEZKLError> {
+    let encode = |e: bincode::Error| ExternalEZKLError::InternalError(e.to_string());
+    let mut hasher = Sha256::new();
+    hasher.update(bincode::serialize(circuit.settings()).map_err(encode)?);
+    hasher.update(bincode::serialize(circuit.model()).map_err(encode)?);
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Computes the fingerprint of circuit settings.
+///
+/// Like the circuit fingerprint, it is computed over the re-serialized settings, so it does not
+/// depend on the formatting of the settings JSON.
+pub(crate) fn of_settings(settings: &GraphSettings) -> Result<String, ExternalEZKLError> {
+    let encoded = bincode::serialize(settings)
+        .map_err(|e| ExternalEZKLError::InternalError(e.to_string()))?;
+    Ok(hex::encode(Sha256::digest(encoded)))
+}
+
+/// Computes the fingerprint of a verification key from its transcript representation, which
+/// commits to the constraint system and the fixed columns of the circuit.
+pub(crate) fn of_vk(vk: &VerifyingKey<G1Affine>) -> String {
+    hex::encode(Sha256::digest(vk.transcript_repr().to_repr()))
+}
+
+/// Fails with `FingerprintMismatch` if an expected fingerprint is given and differs from the actual one.
+pub(crate) fn check(
+    artifact: &str,
+    expected: Option<&str>,
+    actual: &str,
+) -> Result<(), ExternalEZKLError> {
+    match expected {
+        Some(expected) if !expected.eq_ignore_ascii_case(actual) => {
+            Err(ExternalEZKLError::FingerprintMismatch {
+                artifact: artifact.to_string(),
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Computes the digest of a proof over its instances and proof bytes.
+///
+/// Every length is hashed before the values it counts, so that moving an instance between
+/// columns or into the proof bytes changes the digest.
+pub(crate) fn of_proof(instances: &[Vec<Fr>], proof: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update((instances.len() as u64).to_le_bytes());
+    for column in instances {
+        hasher.update((column.len() as u64).to_le_bytes());
+        for instance in column {
+            hasher.update(instance.to_repr());
+        }
+    }
+    hasher.update((proof.len() as u64).to_le_bytes());
+    hasher.update(proof);
+    hex::encode(hasher.finalize())
+}
+
+/// Computes a canonical digest of a proof, to detect locally whether an identical proof was
+/// already submitted.
+///
+/// The digest covers the public instances and the proof bytes only. It does not depend on the
+/// formatting or key order of the proof JSON, nor on the human readable public inputs and other
+/// fields that do not change what the proof proves.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The hex encoded sha256 digest of the proof.
+/// * `Err(ExternalEZKLError)` - If the proof could not be parsed.
+#[cfg_attr(feature = "ffi", export)]
+pub fn proof_digest(proof_json: String) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(proof_json);
+    let proof = deserialize_proof("proof_json", &proof_json)?;
+    Ok(of_proof(&proof.instances, &proof.proof))
+}
+
+/// Computes a stable fingerprint of a compiled circuit, to match it with its other artifacts.
+///
+/// # Arguments
+///
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The hex encoded fingerprint of the circuit.
+/// * `Err(ExternalEZKLError)` - If the circuit could not be deserialized.
+#[cfg_attr(feature = "ffi", export)]
+pub fn circuit_fingerprint(compiled_circuit: Vec<u8>) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(compiled_circuit);
+    of_circuit(&deserialize_circuit(&compiled_circuit)?)
+}
+
+/// Computes a stable fingerprint of a verification key.
+///
+/// The fingerprint of a verification key equals the fingerprint of the proving key it belongs to.
+///
+/// # Arguments
+///
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The hex encoded fingerprint of the verification key.
+/// * `Err(ExternalEZKLError)` - If the settings or the verification key could not be deserialized.
+#[cfg_attr(feature = "ffi", export)]
+pub fn vk_fingerprint(vk: Vec<u8>, settings_json: String) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(vk, settings_json);
+    let settings: GraphSettings = deserialize_json("settings_json", &settings_json)?;
+    let vk = deserialize_vk::<KZGCommitmentScheme<Bn256>, GraphCircuit>(&vk, settings)?;
+    Ok(of_vk(&vk))
+}
+
+/// Computes a stable fingerprint of a proving key.
+///
+/// The fingerprint of a proving key equals the fingerprint of its verification key.
+///
+/// # Arguments
+///
+/// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The hex encoded fingerprint of the proving key.
+/// * `Err(ExternalEZKLError)` - If the settings or the proving key could not be deserialized.
+#[cfg_attr(feature = "ffi", export)]
+pub fn pk_fingerprint(pk: Vec<u8>, settings_json: String) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(pk, settings_json);
+    let settings: GraphSettings = deserialize_json("settings_json", &settings_json)?;
+    let pk = deserialize_pk::<KZGCommitmentScheme<Bn256>, GraphCircuit>(&pk, settings)?;
+    Ok(of_vk(pk.get_vk()))
+}
+
+/// Proves a circuit like `prove_advanced`, after checking the circuit and proving key against their expected fingerprints.
+///
+/// # Arguments
+///
+/// * `witness_json` - A `String` containing the JSON representation of the witness generated for the circuit input.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `expected` - The fingerprints the circuit and proving key are expected to have; absent values are not checked.
+/// * `options` - A `ProveOptions` record with the proof type, check mode, SRS extension and expected variables to prove with.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated proof as a JSON `String`.
+/// * `Err(ExternalEZKLError)` - `FingerprintMismatch` if an artifact does not match, or an error that occurred during proving.
+#[cfg(feature = "prover")]
+#[cfg_attr(feature = "ffi", export)]
+pub fn prove_with_fingerprints(
+    witness_json: String,
+    compiled_circuit: Vec<u8>,
+    pk: Vec<u8>,
+    srs: Vec<u8>,
+    expected: ExpectedFingerprints,
+    options: ProveOptions,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(witness_json, compiled_circuit, pk, srs);
+    if options.compress_output {
+        return Err(ExternalEZKLError::InvalidInput(
+            "`compress_output` is only supported by `prove_detailed`, which returns the compressed proof".to_string(),
+        ));
+    }
+    let budget = TimeBudget::start(options.time_budget_ms);
+    let witness_json = Sensitive::new(witness_json);
+    let data: Sensitive<GraphWitness> =
+        Sensitive::new(deserialize_json("witness_json", &witness_json)?);
+    drop(witness_json);
+
+    let _permit = ProvingPermit::acquire()?;
+    budget.check("load_artifacts")?;
+    let prover = Prover::new(
+        &compiled_circuit,
+        &pk,
+        Some(&srs),
+        options.srs_extension.as_deref(),
+    )?;
+    check(
+        "circuit",
+        expected.circuit.as_deref(),
+        &of_circuit(prover.circuit())?,
+    )?;
+    check("proving key", expected.pk.as_deref(), &of_vk(prover.vk()))?;
+
+    let snark = prove_loaded(&prover, data.into_inner(), &options, &budget)?;
+    serialize_proof(&snark)
+}
+
+/// Verifies a proof like `verify`, after checking the verification key against its expected fingerprint.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof to be verified.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `expected_vk_fingerprint` - The fingerprint the verification key is expected to have.
+///
+/// # Returns
+///
+/// * `Ok(bool)` - `true` if the proof is valid, `false` if the proof is invalid.
+/// * `Err(ExternalEZKLError)` - `FingerprintMismatch` if the verification key does not match, or an error that occurred during verification.
+#[cfg_attr(feature = "ffi", export)]
+pub fn verify_with_fingerprint(
+    proof_json: String,
+    settings_json: String,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+    expected_vk_fingerprint: String,
+) -> Result<bool, ExternalEZKLError> {
+    check_input_sizes!(proof_json, settings_json, vk, srs);
+    let actual = vk_fingerprint(vk.clone(), settings_json.clone())?;
+    check("verification key", Some(&expected_vk_fingerprint), &actual)?;
+
+    verify(proof_json, settings_json, vk, srs)
+}
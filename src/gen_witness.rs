@@ -1,15 +1,42 @@
-use crate::serialization::{deserialize_circuit, deserialize_params_prover, deserialize_vk};
+use crate::clock::Instant;
+use crate::compression::json_output;
+use crate::diagnostics::CallScope;
+use crate::fingerprint::{of_circuit, of_vk};
+use crate::inspect::PolyCommitments;
+use crate::limits::check_input_sizes;
+use crate::logging::{debug, trace};
+use crate::non_finite::{
+    check_finite_inputs, quote_non_finite_literals, restore_non_finite, take_non_finite,
+    SanitizedInput,
+};
+use crate::prove::Prover;
+use crate::sensitive::Sensitive;
+use crate::serialization::{
+    deserialize_circuit, deserialize_json, deserialize_params_prover, deserialize_vk,
+};
+use crate::settings::{
+    check_lookup_settings, check_variables, check_vk_matches_settings, requires_srs_for_witness,
+    requires_vk_for_witness, LookupSettings, VariableBinding,
+};
+use crate::trace::Interval;
+use crate::witness_cache::WitnessCache;
 use crate::ExternalEZKLError;
 use colored_json::ToColoredJson;
 use ezkl::circuit::region::RegionSettings;
-use ezkl::graph::input::GraphData;
+use ezkl::graph::input::{DataSource, GraphData};
 use ezkl::graph::{GraphCircuit, GraphWitness};
-use ezkl::{Commitments, EZKLError as InnerEZKLError};
-use halo2_proofs::halo2curves::bn256::{Bn256, G1Affine};
+use ezkl::tensor::Tensor;
+use ezkl::Commitments;
+use halo2_proofs::halo2curves::bn256::{Bn256, Fr, G1Affine};
+use halo2_proofs::plonk::VerifyingKey;
+use halo2_proofs::poly::commitment::Params;
 use halo2_proofs::poly::ipa::commitment::{IPACommitmentScheme, ParamsIPA};
 use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
-use std::time::Instant;
-use uniffi::deps::log::{debug, trace, warn};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+#[cfg(feature = "ffi")]
 use uniffi::export;
 
 /// Generates a witness for a given circuit and input data.
@@ -27,112 +54,756 @@ use uniffi::export;
 ///
 /// * `Ok(String)` - The generated witness as a JSON `String`.
 /// * `Err(ExternalEZKLError)` - An error that occurred during witness generation.
-#[export]
+#[cfg_attr(feature = "ffi", export)]
 pub async fn gen_witness(
     input_json: String,
     compiled_circuit: Vec<u8>,
     vk: Vec<u8>,
     srs: Vec<u8>,
 ) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(input_json, compiled_circuit, vk, srs);
     // Generate the witness internally
-    let graph = gen_witness_internal(&compiled_circuit, input_json, Some(&vk), Some(&srs)).await?;
+    let graph = gen_witness_internal(
+        &compiled_circuit,
+        input_json,
+        Some(&vk),
+        Some(&srs),
+        &WitnessOptions::default(),
+    )
+    .await?;
     // Convert the witness graph to JSON
     let witness_json = graph.as_json()?;
 
     Ok(witness_json)
 }
 
+/// The dynamic range observed by the circuit while generating a witness.
+///
+/// These are the same values the EZKL CLI reports after `gen-witness`, and are what you need
+/// to tune the `lookup_range` and the input/param scales of the circuit settings off-device.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicRange {
+    /// The largest value that was looked up in a lookup table.
+    pub max_lookup_inputs: i64,
+    /// The smallest value that was looked up in a lookup table.
+    pub min_lookup_inputs: i64,
+    /// The largest range that had to be range checked.
+    pub max_range_size: i64,
+}
+
+impl From<&GraphWitness> for DynamicRange {
+    fn from(witness: &GraphWitness) -> Self {
+        DynamicRange {
+            max_lookup_inputs: witness.max_lookup_inputs as i64,
+            min_lookup_inputs: witness.min_lookup_inputs as i64,
+            max_range_size: witness.max_range_size as i64,
+        }
+    }
+}
+
+/// The result of a detailed witness generation.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct WitnessResult {
+    /// The generated witness as a JSON `String`, empty if the options asked for a compressed witness.
+    pub witness_json: String,
+    /// The generated witness as gzip-compressed JSON, if the options asked for it.
+    pub witness_gzip: Option<Vec<u8>>,
+    /// The dynamic range observed during witness generation.
+    pub dynamic_range: DynamicRange,
+    /// The commitments to the values the circuit commits to with polycommit visibility.
+    pub commitments: PolyCommitments,
+    /// The peak number of bytes allocated during the call, if diagnostics are enabled.
+    pub peak_memory_bytes: Option<u64>,
+    /// Whether the witness was read from the witness cache instead of being generated.
+    pub from_cache: bool,
+    /// The NaN and infinite input values that were replaced, if the options asked to sanitize
+    /// the input.
+    pub sanitized_inputs: Vec<SanitizedInput>,
+}
+
+/// Generates a witness like [`gen_witness`], without requiring the vk and SRS of circuits that
+/// don't need them.
+///
+/// Only circuits with polycommit visibility need the vk and SRS to generate a witness, as
+/// reported by `requires_vk_for_witness` and `requires_srs_for_witness` in `CircuitInfo`. Apps
+/// can therefore defer downloading the SRS until proving for all other circuits.
+///
+/// # Arguments
+///
+/// * `input_json` - A `String` containing the JSON representation of the input data for the circuit.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `vk` - The Verification Key (VK) in binary form, if available.
+/// * `srs` - The Structured Reference String (SRS) in binary form, if available.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated witness as a JSON `String`.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` naming the artifact if the circuit requires an omitted vk or SRS, or an error that occurred during witness generation.
+#[cfg_attr(feature = "ffi", export)]
+pub async fn gen_witness_simple(
+    input_json: String,
+    compiled_circuit: Vec<u8>,
+    vk: Option<Vec<u8>>,
+    srs: Option<Vec<u8>>,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(input_json, compiled_circuit, vk, srs);
+    let witness = gen_witness_internal(
+        &compiled_circuit,
+        input_json,
+        vk.as_deref(),
+        srs.as_deref(),
+        &WitnessOptions::default(),
+    )
+    .await?;
+
+    Ok(witness.as_json()?)
+}
+
+/// Generates a witness for a given circuit and input data, and reports information about it.
+///
+/// Behaves like [`gen_witness`], but additionally returns the dynamic range observed by the circuit
+/// and whether the witness was read from the witness cache.
+///
+/// # Arguments
+///
+/// * `input_json` - A `String` containing the JSON representation of the input data for the circuit.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(WitnessResult)` - The generated witness and its dynamic range.
+/// * `Err(ExternalEZKLError)` - An error that occurred during witness generation.
+#[cfg_attr(feature = "ffi", export)]
+pub async fn gen_witness_detailed(
+    input_json: String,
+    compiled_circuit: Vec<u8>,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+) -> Result<WitnessResult, ExternalEZKLError> {
+    gen_witness_detailed_with_options(
+        input_json,
+        compiled_circuit,
+        vk,
+        srs,
+        WitnessOptions::default(),
+    )
+    .await
+}
+
+/// Generates a witness like [`gen_witness_detailed`], using advanced options.
+///
+/// # Arguments
+///
+/// * `input_json` - A `String` containing the JSON representation of the input data for the circuit.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `options` - A `WitnessOptions` record controlling remote data sources and output compression.
+///
+/// # Returns
+///
+/// * `Ok(WitnessResult)` - The generated witness, compressed if requested, and its dynamic range.
+/// * `Err(ExternalEZKLError)` - An error that occurred during witness generation.
+#[cfg_attr(feature = "ffi", export)]
+pub async fn gen_witness_detailed_with_options(
+    input_json: String,
+    compiled_circuit: Vec<u8>,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+    options: WitnessOptions,
+) -> Result<WitnessResult, ExternalEZKLError> {
+    check_input_sizes!(input_json, compiled_circuit, vk, srs);
+    let scope = CallScope::begin();
+    let input_json = Sensitive::new(input_json);
+    let generated = WitnessGenerator::new(&compiled_circuit, Some(&vk), Some(&srs))?
+        .generate_cached(&input_json, &options)
+        .await?;
+    let witness = generated.witness;
+    let (witness_json, witness_gzip) = json_output(witness.as_json()?, options.compress_output)?;
+
+    Ok(WitnessResult {
+        witness_json,
+        witness_gzip,
+        dynamic_range: DynamicRange::from(&witness),
+        commitments: PolyCommitments::from_witness(&witness),
+        peak_memory_bytes: scope.peak_bytes(),
+        from_cache: generated.from_cache,
+        sanitized_inputs: generated.sanitized_inputs,
+    })
+}
+
+/// Reads the dynamic range observed by the circuit from a previously generated witness.
+///
+/// # Arguments
+///
+/// * `witness_json` - A `String` containing the JSON representation of the witness.
+///
+/// # Returns
+///
+/// * `Ok(DynamicRange)` - The dynamic range recorded in the witness.
+/// * `Err(ExternalEZKLError)` - If the witness could not be parsed.
+#[cfg_attr(feature = "ffi", export)]
+pub fn witness_dynamic_range(witness_json: String) -> Result<DynamicRange, ExternalEZKLError> {
+    check_input_sizes!(witness_json);
+    let witness: GraphWitness = deserialize_json("witness_json", &witness_json)?;
+
+    Ok(DynamicRange::from(&witness))
+}
+
+/// The default time allowed for fetching remote input data before giving up.
+pub const DEFAULT_NETWORK_TIMEOUT_MS: u64 = 30_000;
+
+/// Options controlling how witness generation treats its input data.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct WitnessOptions {
+    /// If `true`, inputs that reference a remote (e.g. on-chain) data source are rejected
+    /// immediately instead of being fetched over the network.
+    pub offline: bool,
+    /// The maximum time in milliseconds to wait for remote input data. `None` waits indefinitely.
+    pub network_timeout_ms: Option<u64>,
+    /// The values the variables of the circuit, such as `batch_size`, are expected to have been
+    /// compiled with, checked before the input is loaded. The call fails if one differs, as the
+    /// compiled circuit cannot be resized.
+    pub expected_variables: Vec<VariableBinding>,
+    /// If `true`, the detailed witness calls return the witness gzip-compressed in `witness_gzip`
+    /// instead of as a JSON string.
+    #[cfg_attr(feature = "ffi", uniffi(default = false))]
+    pub compress_output: bool,
+    /// If `false`, the forward pass doesn't check that values are within the lookup range and the
+    /// range checks of the circuit, which saves time and memory on circuits with large lookup
+    /// tables. A value out of range then fails proving, or `mock`, instead of witness generation,
+    /// so unchecked witnesses bypass the witness cache. This is the only lookup setting that can
+    /// be tuned at witness time.
+    #[cfg_attr(feature = "ffi", uniffi(default = true))]
+    pub check_range: bool,
+    /// The lookup settings the circuit is expected to be compiled with, checked before the input
+    /// is loaded. They size the lookup tables of the circuit, so different values require
+    /// recompiling the model. `None` checks nothing.
+    #[cfg_attr(feature = "ffi", uniffi(default = None))]
+    pub lookup_settings: Option<LookupSettings>,
+    /// If `true`, NaN input values are replaced with zero and infinite ones are clamped to the
+    /// lookup range at the scale of their input, and the replacements are reported in
+    /// `sanitized_inputs` of `WitnessResult`. Otherwise the first such value is rejected with
+    /// `InvalidInput` naming its input and offset.
+    #[cfg_attr(feature = "ffi", uniffi(default = false))]
+    pub sanitize: bool,
+}
+
+impl Default for WitnessOptions {
+    fn default() -> Self {
+        WitnessOptions {
+            offline: false,
+            network_timeout_ms: Some(DEFAULT_NETWORK_TIMEOUT_MS),
+            expected_variables: vec![],
+            compress_output: false,
+            check_range: true,
+            lookup_settings: None,
+            sanitize: false,
+        }
+    }
+}
+
+/// Generates a witness for a given circuit and input data using advanced options.
+///
+/// # Arguments
+///
+/// * `input_json` - A `String` containing the JSON representation of the input data for the circuit.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `options` - A `WitnessOptions` record controlling how remote data sources are handled.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated witness as a JSON `String`.
+/// * `Err(ExternalEZKLError)` - An error that occurred during witness generation.
+#[cfg_attr(feature = "ffi", export)]
+pub async fn gen_witness_advanced(
+    input_json: String,
+    compiled_circuit: Vec<u8>,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+    options: WitnessOptions,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(input_json, compiled_circuit, vk, srs);
+    if options.compress_output {
+        return Err(ExternalEZKLError::InvalidInput(
+            "`compress_output` is only supported by `gen_witness_detailed_with_options`, which returns the compressed witness".to_string(),
+        ));
+    }
+    let witness = gen_witness_internal(
+        &compiled_circuit,
+        input_json,
+        Some(&vk),
+        Some(&srs),
+        &options,
+    )
+    .await?;
+
+    Ok(witness.as_json()?)
+}
+
 pub async fn gen_witness_internal(
     compiled_circuit: &[u8],
     input_data: String,
     serialised_vk: Option<&[u8]>,
     serialised_srs: Option<&[u8]>,
-) -> Result<GraphWitness, InnerEZKLError> {
-    // these aren't real values so the sanity checks are mostly meaningless
+    options: &WitnessOptions,
+) -> Result<GraphWitness, ExternalEZKLError> {
+    let input_data = Sensitive::new(input_data);
+    WitnessGenerator::new(compiled_circuit, serialised_vk, serialised_srs)?
+        .generate(&input_data, options)
+        .await
+}
 
-    let mut circuit = deserialize_circuit(compiled_circuit)?;
-    let data: GraphData = serde_json::from_str(&input_data)?;
-    let settings = circuit.settings().clone();
+/// The SRS used to commit to polycommit visibility modules.
+#[allow(clippy::upper_case_acronyms)]
+pub(crate) enum PolycommitSrs {
+    KZG(ParamsKZG<Bn256>),
+    IPA(ParamsIPA<G1Affine>),
+}
 
-    let vk = if let Some(vk) = serialised_vk {
-        Some(deserialize_vk::<KZGCommitmentScheme<Bn256>, GraphCircuit>(
-            vk,
-            settings.clone(),
-        )?)
-    } else {
-        None
-    };
+impl PolycommitSrs {
+    /// Computes the digest of the SRS, which the polynomial commitments in a witness depend on.
+    fn digest(&self) -> Result<String, ExternalEZKLError> {
+        let mut hasher = Sha256::new();
+        match self {
+            PolycommitSrs::KZG(srs) => srs.write(&mut hasher),
+            PolycommitSrs::IPA(srs) => srs.write(&mut hasher),
+        }
+        .map_err(|e| ExternalEZKLError::InternalError(e.to_string()))?;
+        Ok(hex::encode(hasher.finalize()))
+    }
+}
 
-    let mut input = circuit.load_graph_input(&data).await?;
-
-    // if any of the settings have kzg visibility then we need to load the srs
-
-    let region_settings = RegionSettings::all_true();
-
-    let start_time = Instant::now();
-    let witness = if settings.module_requires_polycommit() {
-        if serialised_srs.is_some() {
-            match Commitments::from(settings.run_args.commitment) {
-                Commitments::KZG => {
-                    let srs: ParamsKZG<Bn256> = deserialize_params_prover::<
-                        KZGCommitmentScheme<Bn256>,
-                    >(
-                        serialised_srs, settings.run_args.logrows
-                    )?;
-                    circuit.forward::<KZGCommitmentScheme<_>>(
-                        &mut input,
-                        vk.as_ref(),
-                        Some(&srs),
-                        region_settings,
-                    )?
-                }
-                Commitments::IPA => {
-                    let srs: ParamsIPA<G1Affine> = deserialize_params_prover::<
-                        IPACommitmentScheme<G1Affine>,
-                    >(
-                        serialised_srs, settings.run_args.logrows
-                    )?;
-                    circuit.forward::<IPACommitmentScheme<_>>(
-                        &mut input,
-                        vk.as_ref(),
-                        Some(&srs),
-                        region_settings,
-                    )?
-                }
+/// A witness generated by a [`WitnessGenerator`].
+pub(crate) struct Generated {
+    pub(crate) witness: GraphWitness,
+    /// Whether the witness was read from the witness cache instead of being generated.
+    pub(crate) from_cache: bool,
+    /// The non-finite input values that were replaced before generating the witness.
+    pub(crate) sanitized_inputs: Vec<SanitizedInput>,
+}
+
+/// A circuit, vk and SRS deserialized once, to generate any number of witnesses from.
+pub(crate) struct WitnessGenerator {
+    circuit: GraphCircuit,
+    vk: Option<VerifyingKey<G1Affine>>,
+    srs: Option<PolycommitSrs>,
+    /// The fingerprint of the circuit, vk and SRS, computed on first use by the witness cache.
+    fingerprint: OnceLock<String>,
+    /// Copies of the circuit left by previous forward passes, reused instead of cloning the
+    /// model and settings for every witness. Holds at most one copy per concurrent call.
+    warm: Mutex<Vec<GraphCircuit>>,
+}
+
+impl WitnessGenerator {
+    /// Deserializes the artifacts needed for witness generation.
+    ///
+    /// The SRS is only loaded if the circuit has a module that requires a polynomial commitment.
+    pub(crate) fn new(
+        compiled_circuit: &[u8],
+        serialised_vk: Option<&[u8]>,
+        serialised_srs: Option<&[u8]>,
+    ) -> Result<Self, ExternalEZKLError> {
+        // these aren't real values so the sanity checks are mostly meaningless
+
+        let circuit = deserialize_circuit(compiled_circuit)?;
+        let vk = if let Some(vk) = serialised_vk {
+            Some(deserialize_vk::<KZGCommitmentScheme<Bn256>, GraphCircuit>(
+                vk,
+                circuit.settings().clone(),
+            )?)
+        } else {
+            None
+        };
+
+        Self::from_loaded(circuit, vk, serialised_srs)
+    }
+
+    /// Prepares witness generation for the circuit and SRS already loaded by a prover, so proving
+    /// from input data deserializes them only once.
+    pub(crate) fn for_prover(
+        prover: &Prover,
+        serialised_vk: &[u8],
+    ) -> Result<Self, ExternalEZKLError> {
+        let circuit = prover.circuit().clone();
+        let vk = deserialize_vk::<KZGCommitmentScheme<Bn256>, GraphCircuit>(
+            serialised_vk,
+            circuit.settings().clone(),
+        )?;
+        Self::with_srs(circuit, Some(vk), || Ok(prover.polycommit_srs()))
+    }
+
+    /// Prepares witness generation for an already deserialized circuit and vk.
+    pub(crate) fn from_loaded(
+        circuit: GraphCircuit,
+        vk: Option<VerifyingKey<G1Affine>>,
+        serialised_srs: Option<&[u8]>,
+    ) -> Result<Self, ExternalEZKLError> {
+        let logrows = circuit.settings().run_args.logrows;
+        let commitment = Commitments::from(circuit.settings().run_args.commitment);
+        Self::with_srs(circuit, vk, || {
+            if serialised_srs.is_none() {
+                return Err(ExternalEZKLError::InvalidInput(
+                    "the circuit has polycommit visibility, so witness generation requires the SRS"
+                        .to_string(),
+                ));
             }
+            Ok(match commitment {
+                Commitments::KZG => PolycommitSrs::KZG(deserialize_params_prover::<
+                    KZGCommitmentScheme<Bn256>,
+                >(serialised_srs, logrows)?),
+                Commitments::IPA => PolycommitSrs::IPA(deserialize_params_prover::<
+                    IPACommitmentScheme<G1Affine>,
+                >(serialised_srs, logrows)?),
+            })
+        })
+    }
+
+    /// Prepares witness generation like [`Self::from_loaded`], calling `load_srs` only if the
+    /// circuit has a module that requires a polynomial commitment.
+    fn with_srs(
+        circuit: GraphCircuit,
+        vk: Option<VerifyingKey<G1Affine>>,
+        load_srs: impl FnOnce() -> Result<PolycommitSrs, ExternalEZKLError>,
+    ) -> Result<Self, ExternalEZKLError> {
+        let settings = circuit.settings();
+        if let Some(vk) = &vk {
+            check_vk_matches_settings(vk, settings)?;
+        } else if requires_vk_for_witness(settings) {
+            return Err(ExternalEZKLError::InvalidInput(
+                "the circuit has polycommit visibility, so witness generation requires the vk"
+                    .to_string(),
+            ));
+        }
+
+        // if any of the settings have kzg visibility then we need to load the srs
+        let srs = if requires_srs_for_witness(settings) {
+            Some(load_srs()?)
         } else {
-            warn!("SRS for poly commit does not exist (will be ignored)");
-            circuit.forward::<KZGCommitmentScheme<Bn256>>(
+            None
+        };
+
+        Ok(WitnessGenerator {
+            circuit,
+            vk,
+            srs,
+            fingerprint: OnceLock::new(),
+            warm: Mutex::new(vec![]),
+        })
+    }
+
+    /// Generates a witness for the given input data.
+    ///
+    /// Each witness is generated on its own copy of the circuit, so inputs don't affect each other.
+    /// Copies are kept warm between calls, so a generator clones the circuit once per concurrent
+    /// call rather than once per witness.
+    pub(crate) async fn generate(
+        &self,
+        input_data: &str,
+        options: &WitnessOptions,
+    ) -> Result<GraphWitness, ExternalEZKLError> {
+        Ok(self.generate_cached(input_data, options).await?.witness)
+    }
+
+    /// Generates a witness like [`Self::generate`], reading it from the witness cache if one is
+    /// configured, and returns whether it was read from the cache.
+    pub(crate) async fn generate_cached(
+        &self,
+        input_data: &str,
+        options: &WitnessOptions,
+    ) -> Result<Generated, ExternalEZKLError> {
+        let data = Sensitive::new(deserialize_graph_data(input_data)?);
+        self.generate_from_data(data, options).await
+    }
+
+    /// Generates a witness like [`Self::generate_cached`] from input data that is already parsed.
+    pub(crate) async fn generate_from_data(
+        &self,
+        mut data: Sensitive<GraphData>,
+        options: &WitnessOptions,
+    ) -> Result<Generated, ExternalEZKLError> {
+        check_variables(&self.circuit, &options.expected_variables)?;
+        check_lookup_settings(&self.circuit, options.lookup_settings.as_ref())?;
+        let sanitized_inputs =
+            check_finite_inputs(&mut data, self.circuit.settings(), options.sanitize)?;
+        let generated = |witness, from_cache| Generated {
+            witness,
+            from_cache,
+            sanitized_inputs,
+        };
+
+        // Remote data may change between calls, so only inline data is cached, and witnesses
+        // generated without range checks are neither served from the cache nor stored in it
+        let cache = match remote_data_source(&data) {
+            Some(_) => None,
+            None if !options.check_range => None,
+            None => WitnessCache::configured(),
+        };
+        let Some(cache) = cache else {
+            return Ok(generated(self.forward(data, options).await?, false));
+        };
+
+        let key = cache.key(self.fingerprint()?, &data)?;
+        if let Some(witness) = cache.load(&key) {
+            return Ok(generated(witness, true));
+        }
+        let witness = self.forward(data, options).await?;
+        cache.store(&key, &witness);
+        Ok(generated(witness, false))
+    }
+
+    /// Returns the fingerprint of the circuit, the vk and the SRS, which together determine the
+    /// witness. The SRS is only loaded, and part of the fingerprint, for polycommit visibility.
+    fn fingerprint(&self) -> Result<&str, ExternalEZKLError> {
+        if let Some(fingerprint) = self.fingerprint.get() {
+            return Ok(fingerprint);
+        }
+        let fingerprint = format!(
+            "{}:{}:{}",
+            of_circuit(&self.circuit)?,
+            self.vk.as_ref().map(of_vk).unwrap_or_default(),
+            self.srs
+                .as_ref()
+                .map(PolycommitSrs::digest)
+                .transpose()?
+                .unwrap_or_default()
+        );
+        Ok(self.fingerprint.get_or_init(|| fingerprint))
+    }
+
+    /// Runs the forward pass of the circuit on the parsed input data.
+    async fn forward(
+        &self,
+        data: Sensitive<GraphData>,
+        options: &WitnessOptions,
+    ) -> Result<GraphWitness, ExternalEZKLError> {
+        let mut circuit = self.take_circuit();
+        let remote = remote_data_source(&data);
+        let reusable = remote.is_none();
+
+        let mut input = Sensitive::new(match remote {
+            Some(source) if options.offline => {
+                return Err(ExternalEZKLError::InvalidInput(format!(
+                    "input references remote data source {}; provide inline data or enable network fetching",
+                    source
+                )));
+            }
+            Some(source) => load_remote_graph_input(&mut circuit, &data, &source, options)?,
+            None => load_inline_graph_input(&mut circuit, &data).await?,
+        });
+        drop(data);
+
+        let region_settings = RegionSettings {
+            check_range: options.check_range,
+            ..RegionSettings::all_true()
+        };
+        let vk = self.vk.as_ref();
+
+        let start_time = Instant::now();
+        let interval = Interval::begin("forward");
+        let witness = match &self.srs {
+            Some(PolycommitSrs::KZG(srs)) => circuit.forward::<KZGCommitmentScheme<_>>(
+                &mut input,
+                vk,
+                Some(srs),
+                region_settings,
+            )?,
+            Some(PolycommitSrs::IPA(srs)) => circuit.forward::<IPACommitmentScheme<_>>(
+                &mut input,
+                vk,
+                Some(srs),
+                region_settings,
+            )?,
+            None => circuit.forward::<KZGCommitmentScheme<Bn256>>(
                 &mut input,
-                vk.as_ref(),
+                vk,
                 None,
                 region_settings,
-            )?
+            )?,
+        };
+        drop(interval);
+
+        // print each variable tuple (symbol, value) as symbol=value
+        trace!(
+            "witness generation {:?} took {:?}",
+            circuit
+                .settings()
+                .run_args
+                .variables
+                .iter()
+                .map(|v| { format!("{}={}", v.0, v.1) })
+                .collect::<Vec<_>>(),
+            start_time.elapsed()
+        );
+
+        // print the witness in debug
+        debug!("witness: \n {}", witness.as_json()?.to_colored_json_auto()?);
+
+        if reusable {
+            self.return_circuit(circuit);
+        }
+        Ok(witness)
+    }
+
+    /// Takes a warm copy of the circuit, cloning it only if every copy is in use.
+    fn take_circuit(&self) -> GraphCircuit {
+        let warm = self.warm.lock().ok().and_then(|mut warm| warm.pop());
+        warm.unwrap_or_else(|| {
+            trace!("cloning the circuit for witness generation");
+            self.circuit.clone()
+        })
+    }
+
+    /// Returns a copy of the circuit after a forward pass on inline data, which leaves the model
+    /// and settings unchanged. Copies used to fetch remote data are dropped instead.
+    fn return_circuit(&self, circuit: GraphCircuit) {
+        if let Ok(mut warm) = self.warm.lock() {
+            warm.push(circuit);
+        }
+    }
+}
+
+/// Parses the input data, accepting both the `GraphData` object and a bare array of inputs.
+///
+/// A top-level array of arrays is taken as the `input_data` of a `GraphData`, and a flat array
+/// as its single input. NaN and infinite inputs, which JSON can't represent, are read from
+/// `null`, from strings such as `"NaN"` and `"-Infinity"` and from the bare `NaN` and
+/// `Infinity` tokens some encoders write, for [`check_finite_inputs`] to report. Errors are
+/// reported against the `GraphData` form.
+pub(crate) fn deserialize_graph_data(input_data: &str) -> Result<GraphData, ExternalEZKLError> {
+    let error = match deserialize_json::<GraphData>("input_json", input_data) {
+        Ok(data) => return Ok(data),
+        Err(e) => e,
+    };
+
+    let Ok(mut value) = serde_json::from_str::<Value>(&quote_non_finite_literals(input_data))
+    else {
+        return Err(error);
+    };
+    let non_finite = take_non_finite(&mut value);
+    let mut data = match value {
+        Value::Array(values) => {
+            let inputs = if values.iter().all(Value::is_array) {
+                Value::Array(values)
+            } else {
+                Value::Array(vec![Value::Array(values)])
+            };
+            match serde_json::from_value::<DataSource>(inputs) {
+                Ok(source @ DataSource::File(_)) => {
+                    debug!("interpreting the top-level array of the input as `input_data`");
+                    GraphData::new(source)
+                }
+                _ => return Err(error),
+            }
         }
-    } else {
-        circuit.forward::<KZGCommitmentScheme<Bn256>>(
-            &mut input,
-            vk.as_ref(),
-            None,
-            region_settings,
-        )?
+        value if !non_finite.is_empty() => {
+            serde_json::from_value::<GraphData>(value).map_err(|_| error)?
+        }
+        _ => return Err(error),
     };
+    restore_non_finite(&mut data, non_finite);
+    Ok(data)
+}
 
-    // print each variable tuple (symbol, value) as symbol=value
-    trace!(
-        "witness generation {:?} took {:?}",
-        circuit
-            .settings()
-            .run_args
-            .variables
-            .iter()
-            .map(|v| { format!("{}={}", v.0, v.1) })
-            .collect::<Vec<_>>(),
-        start_time.elapsed()
-    );
-
-    // print the witness in debug
-    debug!("witness: \n {}", witness.as_json()?.to_colored_json_auto()?);
-
-    Ok(witness)
+/// Describes the remote data source referenced by the input, if any.
+///
+/// Inline (file) data is the only source that can be loaded without network access.
+fn remote_data_source(data: &GraphData) -> Option<String> {
+    match &data.input_data {
+        DataSource::File(_) => None,
+        #[cfg(not(target_arch = "wasm32"))]
+        DataSource::OnChain(source) => Some(format!("on-chain RPC {}", source.rpc)),
+        #[allow(unreachable_patterns)]
+        _ => Some("of an unsupported kind".to_string()),
+    }
+}
+
+/// Loads inline input data.
+#[cfg(not(target_arch = "wasm32"))]
+async fn load_inline_graph_input(
+    circuit: &mut GraphCircuit,
+    data: &GraphData,
+) -> Result<Vec<Tensor<Fr>>, ExternalEZKLError> {
+    Ok(circuit.load_graph_input(data).await?)
+}
+
+/// Loads inline input data, which ezkl reads synchronously on wasm32.
+#[cfg(target_arch = "wasm32")]
+async fn load_inline_graph_input(
+    circuit: &mut GraphCircuit,
+    data: &GraphData,
+) -> Result<Vec<Tensor<Fr>>, ExternalEZKLError> {
+    Ok(circuit.load_graph_from_file_exclusively(data)?)
+}
+
+/// Rejects remote data sources on wasm32, which has no threads to run the fetch on; the host
+/// fetches the data and passes it inline instead.
+#[cfg(target_arch = "wasm32")]
+fn load_remote_graph_input(
+    _circuit: &mut GraphCircuit,
+    _data: &GraphData,
+    source: &str,
+    _options: &WitnessOptions,
+) -> Result<Vec<Tensor<Fr>>, ExternalEZKLError> {
+    Err(ExternalEZKLError::InvalidInput(format!(
+        "input references remote data source {}, which WebAssembly builds cannot fetch; provide inline data",
+        source
+    )))
+}
+
+/// Loads input data from a remote data source, giving up after the configured timeout.
+///
+/// The fetch runs on a dedicated tokio runtime, as the foreign executor driving the exported
+/// futures does not provide the reactor required for network I/O and timers.
+#[cfg(not(target_arch = "wasm32"))]
+fn load_remote_graph_input(
+    circuit: &mut GraphCircuit,
+    data: &GraphData,
+    source: &str,
+    options: &WitnessOptions,
+) -> Result<Vec<Tensor<Fr>>, ExternalEZKLError> {
+    debug!("fetching input data from remote data source {}", source);
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|e| ExternalEZKLError::InternalError(e.to_string()))?;
+                runtime.block_on(async {
+                    match options.network_timeout_ms {
+                        Some(timeout_ms) => tokio::time::timeout(
+                            Duration::from_millis(timeout_ms),
+                            circuit.load_graph_input(data),
+                        )
+                        .await
+                        .map_err(|_| {
+                            ExternalEZKLError::InternalError(format!(
+                                "timed out after {} ms fetching remote data source {}",
+                                timeout_ms, source
+                            ))
+                        })?
+                        .map_err(ExternalEZKLError::from),
+                        None => circuit
+                            .load_graph_input(data)
+                            .await
+                            .map_err(ExternalEZKLError::from),
+                    }
+                })
+            })
+            .join()
+            .map_err(|_| {
+                ExternalEZKLError::InternalError(format!(
+                    "panicked while fetching remote data source {}",
+                    source
+                ))
+            })?
+    })
 }
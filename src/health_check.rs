@@ -0,0 +1,78 @@
+use crate::clock::Instant;
+use crate::self_test::{self_test, SelfTestReport};
+use crate::ExternalEZKLError;
+use ezkl::graph::{GraphCircuit, Model};
+use ezkl::pfsys::create_keys;
+use ezkl::RunArgs;
+use halo2_proofs::halo2curves::bn256::Bn256;
+use halo2_proofs::poly::commitment::{Params, ParamsProver};
+use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
+use halo2_proofs::SerdeFormat::RawBytes;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// A minuscule ONNX model without lookups, see `assets/health-check/README.md`.
+const HEALTH_CHECK_MODEL: &[u8] = include_bytes!("../assets/health-check/network.onnx");
+/// A sample input of the health check model.
+const HEALTH_CHECK_INPUT: &str = include_str!("../assets/health-check/input.json");
+/// The logrows the health check model is compiled at, ample for its handful of rows.
+const HEALTH_CHECK_LOGROWS: u32 = 8;
+
+/// The report of a health check.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct HealthReport {
+    /// The time spent compiling the built-in circuit and generating its SRS and keys in milliseconds.
+    pub setup_ms: u64,
+    /// The outcome and timing of each stage run on the built-in circuit.
+    pub report: SelfTestReport,
+}
+
+/// Runs a built-in circuit end to end to check that the native stack works, e.g. from a debug menu.
+///
+/// Unlike `self_test`, no artifacts have to be provided: the circuit is compiled from an embedded
+/// model and its SRS and keys are generated on the fly. The SRS is generated from local randomness, so it is only fit
+/// for this check.
+///
+/// # Returns
+///
+/// * `Ok(HealthReport)` - The setup time and the outcome and timing of each stage, up to the first failure.
+/// * `Err(ExternalEZKLError)` - If the built-in circuit could not be compiled or its SRS or keys generated.
+#[cfg_attr(feature = "ffi", export)]
+pub async fn quick_health_check() -> Result<HealthReport, ExternalEZKLError> {
+    let started = Instant::now();
+    let run_args = RunArgs {
+        logrows: HEALTH_CHECK_LOGROWS,
+        ..RunArgs::default()
+    };
+    let model = Model::new(&mut &HEALTH_CHECK_MODEL[..], &run_args).map_err(internal_error)?;
+    let circuit = GraphCircuit::new(model, &run_args).map_err(internal_error)?;
+    let circuit_bytes = bincode::serialize(&circuit).map_err(internal_error)?;
+    let params = ParamsKZG::<Bn256>::new(circuit.settings().run_args.logrows);
+    let pk = create_keys::<KZGCommitmentScheme<Bn256>, GraphCircuit>(&circuit, &params, false)
+        .map_err(internal_error)?;
+
+    let mut srs = vec![];
+    params.write(&mut srs).map_err(internal_error)?;
+    let mut pk_bytes = vec![];
+    pk.write(&mut pk_bytes, RawBytes).map_err(internal_error)?;
+    let mut vk_bytes = vec![];
+    pk.get_vk()
+        .write(&mut vk_bytes, RawBytes)
+        .map_err(internal_error)?;
+    let setup_ms = started.elapsed().as_millis() as u64;
+
+    let report = self_test(
+        circuit_bytes,
+        pk_bytes,
+        vk_bytes,
+        srs,
+        HEALTH_CHECK_INPUT.to_string(),
+    )
+    .await?;
+    Ok(HealthReport { setup_ms, report })
+}
+
+fn internal_error(e: impl std::fmt::Display) -> ExternalEZKLError {
+    ExternalEZKLError::InternalError(e.to_string())
+}
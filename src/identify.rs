@@ -0,0 +1,167 @@
+use crate::inspect::CommitmentsWrapper;
+use crate::limits::check_input_sizes;
+use crate::serialization::{deserialize_json, deserialize_params_verifier, deserialize_proof};
+use crate::settings::check_logrows;
+use crate::srs::SrsHeader;
+use crate::verify::{verify_with_params, VerifierParams};
+use crate::ExternalEZKLError;
+use ezkl::graph::GraphSettings;
+use ezkl::Commitments;
+use halo2_proofs::halo2curves::bn256::{Bn256, G1Affine};
+use halo2_proofs::poly::commitment::Params;
+use halo2_proofs::poly::ipa::commitment::{IPACommitmentScheme, ParamsIPA};
+use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// A verification key a proof may belong to, e.g. of one version of a circuit.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct VkCandidate {
+    /// The label reported if the proof verifies with this candidate, e.g. the circuit version.
+    pub label: String,
+    /// The JSON representation of the circuit settings of the candidate.
+    pub settings_json: String,
+    /// The verification key of the candidate in binary form.
+    pub vk: Vec<u8>,
+}
+
+/// The candidate a proof was identified with.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdentifyResult {
+    /// The proof is valid for the candidate with this label, the first one it verified with.
+    Match { label: String },
+    /// The proof is not valid for any of the candidates.
+    NoMatch,
+}
+
+/// The verifier parameters of an SRS, deserialized at most once per commitment scheme.
+struct SharedParams<'a> {
+    srs: &'a [u8],
+    kzg: Option<ParamsKZG<Bn256>>,
+    ipa: Option<ParamsIPA<G1Affine>>,
+}
+
+impl SharedParams<'_> {
+    /// Returns the verifier parameters for a circuit.
+    fn for_circuit(
+        &mut self,
+        commitment: Commitments,
+        logrows: u32,
+    ) -> Result<VerifierParams, ExternalEZKLError> {
+        match commitment {
+            Commitments::KZG => {
+                let params = match &self.kzg {
+                    Some(params) => params.clone(),
+                    None => {
+                        // only need G_0 for the verification with shplonk, which suffices for
+                        // circuits of any size
+                        let params = deserialize_params_verifier::<KZGCommitmentScheme<Bn256>>(
+                            Some(self.srs),
+                            1,
+                        )?;
+                        self.kzg.insert(params).clone()
+                    }
+                };
+                Ok(VerifierParams::KZG(params))
+            }
+            Commitments::IPA => {
+                let mut params = match &self.ipa {
+                    Some(params) => params.clone(),
+                    None => {
+                        let k = SrsHeader::read(self.srs)?.k;
+                        let params = deserialize_params_verifier::<IPACommitmentScheme<G1Affine>>(
+                            Some(self.srs),
+                            k,
+                        )?;
+                        self.ipa.insert(params).clone()
+                    }
+                };
+                if params.k() < logrows {
+                    return Err(ExternalEZKLError::InvalidInput(format!(
+                        "IPA verification requires the full SRS for logrows {}, but the SRS has k = {}",
+                        logrows,
+                        params.k()
+                    )));
+                }
+                if logrows < params.k() {
+                    params.downsize(logrows);
+                }
+                Ok(VerifierParams::IPA(params))
+            }
+        }
+    }
+}
+
+/// Identifies which of several verification keys a proof belongs to, and verifies it.
+///
+/// Candidates are first matched on the structure of the proof, its number of public instances
+/// and its commitment scheme, which needs neither the SRS nor the vk. Only the candidates that
+/// match are verified, in order, with an SRS that is deserialized once for all of them.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof.
+/// * `candidates` - The labeled settings and verification keys the proof may belong to.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(IdentifyResult)` - The label of the first candidate the proof is valid for, or `NoMatch`.
+/// * `Err(ExternalEZKLError)` - If the proof, the settings of a candidate or the SRS could not be
+///   read, or the vk of a matching candidate could not be loaded.
+#[cfg_attr(feature = "ffi", export)]
+pub fn identify_and_verify(
+    proof_json: String,
+    candidates: Vec<VkCandidate>,
+    srs: Vec<u8>,
+) -> Result<IdentifyResult, ExternalEZKLError> {
+    check_input_sizes!(proof_json, srs);
+    let proof = deserialize_proof("proof_json", &proof_json)?;
+    drop(proof_json);
+    let provided_instances: Vec<usize> = proof.instances.iter().map(Vec::len).collect();
+    let srs_commitment = SrsHeader::read(&srs)?.commitment_guess;
+
+    let mut params = SharedParams {
+        srs: &srs,
+        kzg: None,
+        ipa: None,
+    };
+    for candidate in candidates {
+        let settings: GraphSettings = deserialize_json(
+            &format!("settings_json of {}", candidate.label),
+            &candidate.settings_json,
+        )?;
+        check_logrows(&settings)?;
+        let commitment = Commitments::from(settings.run_args.commitment);
+
+        // The circuit exposes all of its public instances in a single column
+        let expected_instances = vec![settings.total_instances().iter().sum::<usize>()];
+        let plausible = provided_instances == expected_instances
+            && proof
+                .commitment
+                .as_ref()
+                .is_none_or(|proven| *proven == commitment)
+            && srs_commitment.is_none_or(|guess| guess == CommitmentsWrapper::from(commitment));
+        if !plausible {
+            continue;
+        }
+
+        let logrows = settings.run_args.logrows;
+        let candidate_params = params.for_circuit(commitment, logrows)?;
+        let (result, _) = verify_with_params(
+            proof.clone(),
+            settings,
+            &candidate.vk,
+            candidate_params.as_params_ref(),
+            false,
+        )?;
+        if result.is_ok() {
+            return Ok(IdentifyResult::Match {
+                label: candidate.label,
+            });
+        }
+    }
+    Ok(IdentifyResult::NoMatch)
+}
@@ -0,0 +1,589 @@
+use crate::limits::check_input_sizes;
+use crate::serialization::{deserialize_circuit, deserialize_json, deserialize_proof};
+use crate::settings::{requires_srs_for_witness, requires_vk_for_witness, VisibilityWrapper};
+use crate::ExternalEZKLError;
+use ezkl::graph::modules::ModuleForwardResult;
+use ezkl::graph::{GraphSettings, GraphWitness, Visibility};
+use ezkl::pfsys::{ProofSplitCommit, TranscriptType};
+use ezkl::Commitments;
+use halo2_proofs::halo2curves::bn256::G1Affine;
+use halo2_proofs::halo2curves::group::GroupEncoding;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// Information about a compiled circuit, read from its settings.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone)]
+pub struct CircuitInfo {
+    /// The log₂ of the number of rows of the circuit.
+    pub logrows: u32,
+    /// The number of rows used by the circuit.
+    pub num_rows: u64,
+    /// The total number of cells assigned by the circuit.
+    pub total_assignments: u64,
+    /// The fixed-point scale of each model input.
+    pub input_scales: Vec<i32>,
+    /// The fixed-point scale of each model output.
+    pub output_scales: Vec<i32>,
+    /// The total number of public instances of a proof of the circuit.
+    pub total_instances: u64,
+    /// The polynomial commitment scheme of the circuit.
+    pub commitment: CommitmentsWrapper,
+    /// The visibility of the model parameters. `Fixed` params are baked into the vk, so proofs
+    /// of the circuit carry no param instances.
+    pub param_visibility: VisibilityWrapper,
+    /// Whether proofs of the circuit expose the model outputs as public instances. Circuits with
+    /// private, hashed or committed outputs only prove that the model was executed, and the
+    /// outputs of their `GroupedInstances` hold the hashes, if any, or nothing.
+    pub outputs_public: bool,
+    /// The JSON representation of the circuit settings.
+    pub settings_json: String,
+    /// Whether witness generation needs the SRS, so that apps can defer downloading it until proving otherwise.
+    pub requires_srs_for_witness: bool,
+    /// Whether witness generation needs the verification key.
+    pub requires_vk_for_witness: bool,
+}
+
+impl CircuitInfo {
+    pub(crate) fn from_settings(settings: &GraphSettings) -> Result<Self, ExternalEZKLError> {
+        Ok(CircuitInfo {
+            logrows: settings.run_args.logrows,
+            num_rows: settings.num_rows as u64,
+            total_assignments: settings.total_assignments as u64,
+            input_scales: settings.model_input_scales.clone(),
+            output_scales: settings.model_output_scales.clone(),
+            total_instances: settings.total_instances().iter().sum::<usize>() as u64,
+            commitment: Commitments::from(settings.run_args.commitment).into(),
+            param_visibility: (&settings.run_args.param_visibility).into(),
+            outputs_public: matches!(settings.run_args.output_visibility, Visibility::Public),
+            settings_json: settings.as_json()?,
+            encoding,
+            requires_srs_for_witness: requires_srs_for_witness(settings),
+            requires_vk_for_witness: requires_vk_for_witness(settings),
+        })
+    }
+}
+
+/// The polynomial commitment scheme of a circuit or SRS.
+#[allow(clippy::upper_case_acronyms)]
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitmentsWrapper {
+    // KZG is the default scheme, with small proofs verifiable on-chain
+    KZG,
+    // IPA needs no trusted setup
+    IPA,
+}
+
+impl From<Commitments> for CommitmentsWrapper {
+    fn from(commitment: Commitments) -> Self {
+        match commitment {
+            Commitments::KZG => CommitmentsWrapper::KZG,
+            Commitments::IPA => CommitmentsWrapper::IPA,
+        }
+    }
+}
+
+impl From<CommitmentsWrapper> for Commitments {
+    fn from(commitment: CommitmentsWrapper) -> Self {
+        match commitment {
+            CommitmentsWrapper::KZG => Commitments::KZG,
+            CommitmentsWrapper::IPA => Commitments::IPA,
+        }
+    }
+}
+
+/// The transcript a proof was created with.
+#[allow(clippy::upper_case_acronyms)]
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptTypeWrapper {
+    // EVM is used for proofs verified on-chain
+    EVM,
+    // Poseidon is used for proofs that are aggregated
+    Poseidon,
+}
+
+impl From<TranscriptType> for TranscriptTypeWrapper {
+    fn from(transcript: TranscriptType) -> Self {
+        match transcript {
+            TranscriptType::EVM => TranscriptTypeWrapper::EVM,
+            TranscriptType::Poseidon => TranscriptTypeWrapper::Poseidon,
+        }
+    }
+}
+
+/// The location of the output commitments in a proof of a circuit that commits to its outputs
+/// with polycommit visibility, which the proof splits from the other commitments.
+///
+/// Backends verifying the output commitments separately from the proof need it to select their
+/// verifier flow. Proofs of circuits that don't commit to their outputs have no split.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofSplitCommitWrapper {
+    /// The index of the first output commitment among the commitments of the proof.
+    pub start: u64,
+    /// The index after the last output commitment.
+    pub end: u64,
+}
+
+impl ProofSplitCommitWrapper {
+    pub(crate) fn from_split(split: &ProofSplitCommit) -> Result<Self, ExternalEZKLError> {
+        // The fields of the ezkl type are private, but it serializes to the same fields
+        Ok(serde_json::from_value(serde_json::to_value(split)?)?)
+    }
+
+    pub(crate) fn to_split(self) -> Result<ProofSplitCommit, ExternalEZKLError> {
+        if self.start > self.end {
+            return Err(ExternalEZKLError::InvalidInput(format!(
+                "the split commitment starts at {} after its end {}",
+                self.start, self.end
+            )));
+        }
+        Ok(serde_json::from_value(serde_json::to_value(self)?)?)
+    }
+}
+
+/// Information about a proof, read without verifying it.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone)]
+pub struct ProofInfo {
+    /// The public instances of the proof per column, as big-endian hex field elements.
+    pub instances: Vec<Vec<String>>,
+    /// The size of the proof in bytes.
+    pub proof_size_bytes: u64,
+    /// The transcript the proof was created with.
+    pub transcript: TranscriptTypeWrapper,
+    /// The commitment scheme the proof was created with, if recorded in the proof.
+    pub commitment: Option<CommitmentsWrapper>,
+    /// The JSON representation of the human readable public inputs and outputs, if present. Every
+    /// field is an array, which is empty for values that are not public, e.g. the outputs of a
+    /// circuit with private outputs.
+    pub pretty_public_inputs_json: Option<String>,
+    /// The location of the split output commitments, if the proof was made with them.
+    pub split_commit: Option<ProofSplitCommitWrapper>,
+}
+
+/// Reads information about a compiled circuit.
+///
+/// # Arguments
+///
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+///
+/// # Returns
+///
+/// * `Ok(CircuitInfo)` - Information about the circuit.
+/// * `Err(ExternalEZKLError)` - If the circuit could not be deserialized.
+#[cfg_attr(feature = "ffi", export)]
+pub fn circuit_info(compiled_circuit: Vec<u8>) -> Result<CircuitInfo, ExternalEZKLError> {
+    check_input_sizes!(compiled_circuit);
+    let circuit = deserialize_circuit(&compiled_circuit)?;
+    CircuitInfo::from_settings(circuit.settings())
+}
+
+/// Creates a skeleton input JSON for a circuit, to fill in with the actual input values.
+///
+/// The template is a `GraphData` object whose `input_data` holds one flat tensor of zeros per
+/// circuit input, with the length the circuit expects. The extra `_shapes` field lists the
+/// shape and fixed-point scale of each input, and is ignored when the input is loaded, so the
+/// template is accepted by `gen_witness` as is.
+///
+/// # Arguments
+///
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The JSON representation of the input template.
+/// * `Err(ExternalEZKLError)` - If the circuit could not be deserialized.
+#[cfg_attr(feature = "ffi", export)]
+pub fn input_template(compiled_circuit: Vec<u8>) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(compiled_circuit);
+    let circuit = deserialize_circuit(&compiled_circuit)?;
+    let shapes = circuit.model().graph.input_shapes()?;
+    let scales = &circuit.settings().model_input_scales;
+
+    let input_data: Vec<Vec<f64>> = shapes
+        .iter()
+        .map(|shape| vec![0.0; shape.iter().product()])
+        .collect();
+    let described: Vec<Value> = shapes
+        .iter()
+        .zip(scales)
+        .map(|(shape, scale)| json!({ "shape": shape, "scale": scale }))
+        .collect();
+
+    Ok(serde_json::to_string_pretty(&json!({
+        "input_data": input_data,
+        "_shapes": described,
+    }))?)
+}
+
+/// The number of nodes of a graph performing one operation.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct OpCount {
+    /// The name of the operation, as ezkl describes it, e.g. `RELU` or `CONST`.
+    pub op: String,
+    /// The number of nodes performing the operation.
+    pub count: u64,
+}
+
+/// A node of the graph of a circuit.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct LayerSummary {
+    /// The index of the node in the graph.
+    pub index: u64,
+    /// The name of the operation of the node.
+    pub op: String,
+    /// The shape of each output of the node.
+    pub output_shapes: Vec<Vec<u64>>,
+}
+
+/// The structure of the model a circuit was compiled from.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct GraphSummary {
+    /// The number of nodes of the graph, including its inputs and constants.
+    pub num_nodes: u64,
+    /// The number of nodes per operation, sorted by operation name.
+    pub op_counts: Vec<OpCount>,
+    /// The total number of values of the constants of the graph, i.e. its parameters.
+    pub total_parameters: u64,
+    /// Every node of the graph, in the order they are evaluated.
+    pub layers: Vec<LayerSummary>,
+}
+
+/// Summarizes the operations of the model a circuit was compiled from, e.g. to show users what
+/// the model computes before they consent to proving.
+///
+/// Operations are named as ezkl describes them, so operations this library does not know
+/// specifically are reported too. A subgraph, such as the body of a loop, is reported as a
+/// single `SUBGRAPH` node.
+///
+/// # Arguments
+///
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+///
+/// # Returns
+///
+/// * `Ok(GraphSummary)` - The summary of the graph of the model.
+/// * `Err(ExternalEZKLError)` - If the circuit could not be deserialized.
+#[cfg_attr(feature = "ffi", export)]
+pub fn graph_summary(compiled_circuit: Vec<u8>) -> Result<GraphSummary, ExternalEZKLError> {
+    check_input_sizes!(compiled_circuit);
+    let circuit = deserialize_circuit(&compiled_circuit)?;
+    let nodes = &circuit.model().graph.nodes;
+
+    let mut op_counts: BTreeMap<String, u64> = BTreeMap::new();
+    let mut total_parameters = 0u64;
+    let mut layers = Vec::with_capacity(nodes.len());
+    for (index, node) in nodes {
+        let op = node.as_str();
+        let output_shapes = node.out_dims();
+        *op_counts.entry(op.clone()).or_default() += 1;
+        if node.is_constant() {
+            total_parameters += output_shapes
+                .iter()
+                .map(|shape| shape.iter().product::<usize>() as u64)
+                .sum::<u64>();
+        }
+        layers.push(LayerSummary {
+            index: *index as u64,
+            op,
+            output_shapes: output_shapes
+                .iter()
+                .map(|shape| shape.iter().map(|&dim| dim as u64).collect())
+                .collect(),
+        });
+    }
+
+    Ok(GraphSummary {
+        num_nodes: nodes.len() as u64,
+        op_counts: op_counts
+            .into_iter()
+            .map(|(op, count)| OpCount { op, count })
+            .collect(),
+        total_parameters,
+        layers,
+    })
+}
+
+/// Reads information about a proof without verifying it.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof.
+///
+/// # Returns
+///
+/// * `Ok(ProofInfo)` - Information about the proof.
+/// * `Err(ExternalEZKLError)` - If the proof could not be parsed.
+#[cfg_attr(feature = "ffi", export)]
+pub fn inspect_proof(proof_json: String) -> Result<ProofInfo, ExternalEZKLError> {
+    check_input_sizes!(proof_json);
+    let proof = deserialize_proof("proof_json", &proof_json)?;
+
+    let pretty_public_inputs_json = proof
+        .pretty_public_inputs
+        .as_ref()
+        .map(serde_json::to_value)
+        .transpose()?
+        .map(|pretty| empty_arrays_for_nulls(pretty).to_string());
+
+    Ok(ProofInfo {
+        instances: proof
+            .instances
+            .iter()
+            .map(|column| column.iter().map(|value| format!("{:?}", value)).collect())
+            .collect(),
+        proof_size_bytes: proof.proof.len() as u64,
+        transcript: proof.transcript_type.into(),
+        commitment: proof.commitment.map(CommitmentsWrapper::from),
+        pretty_public_inputs_json,
+        split_commit: proof
+            .split
+            .as_ref()
+            .map(ProofSplitCommitWrapper::from_split)
+            .transpose()?,
+    })
+}
+
+/// Replaces the `null` fields of a JSON object with empty arrays, so that values that are not
+/// public read the same whether they were left out or recorded as empty.
+fn empty_arrays_for_nulls(mut value: Value) -> Value {
+    if let Value::Object(fields) = &mut value {
+        for field in fields.values_mut().filter(|field| field.is_null()) {
+            *field = Value::Array(vec![]);
+        }
+    }
+    value
+}
+
+/// The public instances of a proof, grouped by what they are derived from.
+///
+/// Each instance is a big-endian hex field element, as in `ProofInfo`. Depending on the
+/// visibility, the inputs and outputs are either the quantized values themselves or their
+/// Poseidon hashes, one per tensor. The params are the Poseidon hashes of the model parameters.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupedInstances {
+    /// The instances derived from the model inputs.
+    pub inputs: Vec<String>,
+    /// The instances derived from the model parameters.
+    pub params: Vec<String>,
+    /// The instances derived from the model outputs, empty if the outputs are private or
+    /// committed to.
+    pub outputs: Vec<String>,
+}
+
+/// The number of public instances derived from the inputs, params and outputs of a circuit.
+///
+/// The instances are laid out as `GraphCircuit::prepare_public_inputs` lays them out: the
+/// inputs, then the params, then the outputs. Public inputs and outputs contribute every
+/// value of their tensors, and hashed ones a single Poseidon hash per tensor. Params only
+/// contribute instances if they are hashed publicly; fixed params, baked into the vk, and
+/// private or committed ones contribute none.
+pub(crate) fn instance_layout(settings: &GraphSettings) -> (usize, usize, usize) {
+    let num_inputs = settings.model_input_scales.len();
+    let num_outputs = settings.model_output_scales.len();
+    let run_args = &settings.run_args;
+    let is_hashed_public = |visibility: &Visibility| {
+        matches!(
+            visibility,
+            Visibility::Hashed {
+                hash_is_public: true,
+                ..
+            }
+        )
+    };
+
+    // The instance shapes hold the public inputs followed by the public outputs
+    let mut shapes = settings.model_instance_shapes.iter();
+    let mut public_values = |visible: bool, count: usize| -> usize {
+        if !visible {
+            return 0;
+        }
+        shapes
+            .by_ref()
+            .take(count)
+            .map(|shape| shape.iter().product::<usize>())
+            .sum()
+    };
+    let public_inputs = public_values(
+        matches!(run_args.input_visibility, Visibility::Public),
+        num_inputs,
+    );
+    let public_outputs = public_values(
+        matches!(run_args.output_visibility, Visibility::Public),
+        num_outputs,
+    );
+
+    // The hashes of the inputs, params and outputs are all counted as module instances
+    let hashed_inputs = if is_hashed_public(&run_args.input_visibility) {
+        num_inputs
+    } else {
+        0
+    };
+    let hashed_outputs = if is_hashed_public(&run_args.output_visibility) {
+        num_outputs
+    } else {
+        0
+    };
+    let hashed_params = if is_hashed_public(&run_args.param_visibility) {
+        let module_instances: usize = settings.module_sizes.num_instances().iter().sum();
+        module_instances.saturating_sub(hashed_inputs + hashed_outputs)
+    } else {
+        0
+    };
+
+    (
+        public_inputs + hashed_inputs,
+        hashed_params,
+        public_outputs + hashed_outputs,
+    )
+}
+
+/// Splits the public instances of a proof into those derived from the inputs, params and outputs.
+///
+/// The order of the instances depends on the visibility of the inputs, params and outputs of
+/// the circuit, so this reads the layout from the settings instead of relying on fixed offsets.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+///
+/// # Returns
+///
+/// * `Ok(GroupedInstances)` - The instances of the proof, grouped by what they are derived from.
+/// * `Err(ExternalEZKLError)` - If the proof or settings could not be parsed, or the number of
+///   instances of the proof does not match the settings.
+#[cfg_attr(feature = "ffi", export)]
+pub fn split_instances(
+    proof_json: String,
+    settings_json: String,
+) -> Result<GroupedInstances, ExternalEZKLError> {
+    check_input_sizes!(proof_json, settings_json);
+    let proof = deserialize_proof("proof_json", &proof_json)?;
+    let settings: GraphSettings = deserialize_json("settings_json", &settings_json)?;
+
+    let (num_inputs, num_params, num_outputs) = instance_layout(&settings);
+    let instances: Vec<String> = proof
+        .instances
+        .iter()
+        .flatten()
+        .map(|value| format!("{:?}", value))
+        .collect();
+    if instances.len() != num_inputs + num_params + num_outputs {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the proof has {} public instances, but the settings lay out {} inputs, {} params and {} outputs",
+            instances.len(),
+            num_inputs,
+            num_params,
+            num_outputs
+        )));
+    }
+
+    let mut instances = instances.into_iter();
+    Ok(GroupedInstances {
+        inputs: instances.by_ref().take(num_inputs).collect(),
+        params: instances.by_ref().take(num_params).collect(),
+        outputs: instances.collect(),
+    })
+}
+
+/// The KZG commitments of the inputs, params and outputs a circuit commits to with polycommit
+/// visibility, as recorded in a witness.
+///
+/// Each commitment is a compressed G1 point in hex, grouped per committed tensor. The groups are
+/// empty for values that are not committed to.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolyCommitments {
+    /// The commitments to the model inputs.
+    pub inputs: Vec<Vec<String>>,
+    /// The commitments to the model parameters.
+    pub params: Vec<Vec<String>>,
+    /// The commitments to the model outputs.
+    pub outputs: Vec<Vec<String>>,
+}
+
+impl PolyCommitments {
+    pub(crate) fn from_witness(witness: &GraphWitness) -> Self {
+        let encode = |processed: &Option<ModuleForwardResult>| -> Vec<Vec<String>> {
+            processed
+                .as_ref()
+                .and_then(|result| result.polycommit.as_ref())
+                .map(|tensors| {
+                    tensors
+                        .iter()
+                        .map(|points| points.iter().map(encode_point).collect())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        PolyCommitments {
+            inputs: encode(&witness.processed_inputs),
+            params: encode(&witness.processed_params),
+            outputs: encode(&witness.processed_outputs),
+        }
+    }
+
+    /// Returns `true` if the witness holds no commitments.
+    pub fn is_empty(&self) -> bool {
+        [&self.inputs, &self.params, &self.outputs]
+            .iter()
+            .all(|groups| groups.iter().all(Vec::is_empty))
+    }
+}
+
+/// Encodes a G1 point in its compressed form as hex.
+pub(crate) fn encode_point(point: &G1Affine) -> String {
+    hex::encode(point.to_bytes())
+}
+
+/// Decodes a G1 point from the hex of its compressed form, as produced by [`encode_point`].
+pub(crate) fn decode_point(field: &str, encoded: &str) -> Result<G1Affine, ExternalEZKLError> {
+    let invalid = || {
+        ExternalEZKLError::InvalidInput(format!(
+            "`{}` contains an invalid commitment: {}",
+            field, encoded
+        ))
+    };
+    let bytes = hex::decode(encoded.trim_start_matches("0x")).map_err(|_| invalid())?;
+    let mut repr = <G1Affine as GroupEncoding>::Repr::default();
+    if bytes.len() != repr.as_ref().len() {
+        return Err(invalid());
+    }
+    repr.as_mut().copy_from_slice(&bytes);
+    Option::<G1Affine>::from(G1Affine::from_bytes(&repr)).ok_or_else(invalid)
+}
+
+/// Reads the polycommit commitments recorded in a witness.
+///
+/// Circuits with `polycommit` input, param or output visibility commit to those values with KZG
+/// instead of exposing them as public instances. The commitments are computed during witness
+/// generation and are the same ones the proof carries, so apps can publish them and check a
+/// proof against them with `verify_with_commitments`.
+///
+/// # Arguments
+///
+/// * `witness_json` - A `String` containing the JSON representation of the witness.
+///
+/// # Returns
+///
+/// * `Ok(PolyCommitments)` - The commitments in the witness, empty if the circuit commits to nothing.
+/// * `Err(ExternalEZKLError)` - If the witness could not be parsed.
+#[cfg_attr(feature = "ffi", export)]
+pub fn witness_commitments(witness_json: String) -> Result<PolyCommitments, ExternalEZKLError> {
+    check_input_sizes!(witness_json);
+    let witness: GraphWitness = deserialize_json("witness_json", &witness_json)?;
+
+    Ok(PolyCommitments::from_witness(&witness))
+}
@@ -0,0 +1,64 @@
+use crate::config;
+use crate::provider::{ArtifactProvider, ProviderReader};
+use ezkl::EZKL_BUF_CAPACITY;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Where an artifact is read from.
+#[derive(Clone, Copy)]
+pub(crate) enum Source<'a> {
+    /// A file on disk.
+    Path(&'a Path),
+    /// An artifact already in memory.
+    Bytes(&'a [u8]),
+    /// An artifact read through an app-provided `ArtifactProvider`.
+    Provider {
+        provider: &'a Arc<dyn ArtifactProvider>,
+        name: &'a str,
+    },
+}
+
+impl Source<'_> {
+    /// Describes the source in error messages.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Source::Path(path) => format!("`{}`", path.display()),
+            Source::Bytes(_) => "the artifact".to_string(),
+            Source::Provider { name, .. } => format!("`{}` from the artifact provider", name),
+        }
+    }
+}
+
+/// The capacity of the buffered readers and writers used for artifacts, which is
+/// `EZKL_BUF_CAPACITY` unless overridden in the configuration.
+pub fn buffer_capacity() -> usize {
+    config::current()
+        .io_buffer_capacity
+        .map_or(*EZKL_BUF_CAPACITY, |capacity| capacity as usize)
+}
+
+/// Opens a buffered reader over an artifact, with the configured buffer capacity.
+///
+/// Keys and SRS are read in many small chunks, so reading them through a consistently sized
+/// buffer keeps the number of syscalls low. Provider artifacts are read in ranges of the
+/// buffer capacity.
+pub(crate) fn ezkl_reader(source: Source<'_>) -> std::io::Result<BufReader<Box<dyn Read + '_>>> {
+    let inner: Box<dyn Read + '_> = match source {
+        Source::Path(path) => Box::new(File::open(path)?),
+        Source::Bytes(bytes) => Box::new(bytes),
+        Source::Provider { provider, name } => {
+            Box::new(ProviderReader::open(provider.clone(), name)?)
+        }
+    };
+    Ok(BufReader::with_capacity(buffer_capacity(), inner))
+}
+
+/// Creates a file and a buffered writer over it, with the configured buffer capacity.
+pub(crate) fn ezkl_writer(path: &Path) -> std::io::Result<BufWriter<File>> {
+    Ok(BufWriter::with_capacity(
+        buffer_capacity(),
+        File::create(path)?,
+    ))
+}
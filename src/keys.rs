@@ -0,0 +1,301 @@
+use crate::files::{read_artifact, ArtifactMetadata, OutputFile, StagedFile};
+use crate::fingerprint;
+use crate::limits::check_input_sizes;
+use crate::logging::info;
+use crate::progress::{KeyGenStage, ProgressListener};
+use crate::serialization::{deserialize_circuit, deserialize_params_prover, deserialize_pk};
+use crate::trace::Interval;
+use crate::ExternalEZKLError;
+use ezkl::graph::GraphCircuit;
+use ezkl::pfsys::create_keys;
+use ezkl::Commitments;
+use halo2_proofs::halo2curves::bn256::{Bn256, Fr, G1Affine};
+use halo2_proofs::plonk::ProvingKey;
+use halo2_proofs::poly::commitment::CommitmentScheme;
+use halo2_proofs::poly::ipa::commitment::IPACommitmentScheme;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use halo2_proofs::SerdeFormat::RawBytes;
+use std::io::Write;
+use std::sync::Arc;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The number of bytes written between two progress reports while writing the proving key.
+const PROGRESS_INTERVAL_BYTES: u64 = 4 * 1024 * 1024;
+
+/// The options of key generation.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Clone, Default)]
+pub struct KeyGenOptions {
+    /// If `true`, selectors are not compressed into fixed columns, like the ezkl CLI flag of the same name.
+    pub disable_selector_compression: bool,
+    /// The listener receiving the progress of the stages, if any.
+    pub progress: Option<Arc<dyn ProgressListener>>,
+}
+
+/// The key files written by [`gen_keys_to_files`].
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyFiles {
+    /// The size and digest of the written Proving Key (PK).
+    pub pk: ArtifactMetadata,
+    /// The size and digest of the written Verification Key (VK).
+    pub vk: ArtifactMetadata,
+}
+
+impl KeyGenOptions {
+    fn report(&self, stage: KeyGenStage, completed: u64, total: u64) {
+        if let Some(progress) = &self.progress {
+            progress.on_progress(stage, completed, total);
+        }
+    }
+}
+
+/// Generates the proving and verification keys of a circuit and writes them directly to files.
+///
+/// The proving key is serialized while it is written, so the peak memory holds the proving key
+/// but never its serialized bytes. The keys match the ones generated by the ezkl CLI.
+///
+/// # Arguments
+///
+/// * `circuit_path` - The path of the compiled circuit.
+/// * `srs_path` - The path of the Structured Reference String (SRS), for the commitment scheme of the circuit.
+/// * `pk_out` - The path the Proving Key (PK) is written to.
+/// * `vk_out` - The path the Verification Key (VK) is written to.
+/// * `options` - A `KeyGenOptions` record with the key generation flags and the progress listener.
+///
+/// # Returns
+///
+/// * `Ok(KeyFiles)` - The sizes and digests of the written keys.
+/// * `Err(ExternalEZKLError)` - An error that occurred during key generation; no key file is left behind.
+#[cfg_attr(feature = "ffi", export)]
+pub fn gen_keys_to_files(
+    circuit_path: String,
+    srs_path: String,
+    pk_out: String,
+    vk_out: String,
+    options: KeyGenOptions,
+) -> Result<KeyFiles, ExternalEZKLError> {
+    let pk_output = OutputFile::create(&pk_out)?;
+    let vk_output = OutputFile::create(&vk_out)?;
+
+    let circuit = deserialize_circuit(&read_artifact(&circuit_path)?)?;
+    options.report(KeyGenStage::LoadCircuit, 1, 1);
+    let srs = read_artifact(&srs_path)?;
+
+    match Commitments::from(circuit.settings().run_args.commitment) {
+        Commitments::KZG => {
+            gen_keys::<KZGCommitmentScheme<Bn256>>(circuit, srs, pk_output, vk_output, &options)
+        }
+        Commitments::IPA => {
+            gen_keys::<IPACommitmentScheme<G1Affine>>(circuit, srs, pk_output, vk_output, &options)
+        }
+    }
+}
+
+fn gen_keys<Scheme: CommitmentScheme<Scalar = Fr, Curve = G1Affine>>(
+    circuit: GraphCircuit,
+    srs: Vec<u8>,
+    pk_output: OutputFile,
+    vk_output: OutputFile,
+    options: &KeyGenOptions,
+) -> Result<KeyFiles, ExternalEZKLError> {
+    let params =
+        deserialize_params_prover::<Scheme>(Some(&srs), circuit.settings().run_args.logrows)?;
+    drop(srs);
+    options.report(KeyGenStage::LoadSrs, 1, 1);
+
+    let pk = {
+        let _interval = Interval::begin("create_keys");
+        create_keys::<Scheme, GraphCircuit>(&circuit, &params, options.disable_selector_compression)
+            .map_err(|e| ExternalEZKLError::InternalError(e.to_string()))?
+    };
+    drop(params);
+    options.report(KeyGenStage::CreateKeys, 1, 1);
+
+    // Both keys are moved in place only once both were written, so that a failure never leaves
+    // a vk without its pk behind
+    let vk = vk_output.stage_with(|mut writer| {
+        pk.get_vk()
+            .write(&mut writer, RawBytes)
+            .map_err(|e| ExternalEZKLError::InternalError(e.to_string()))
+    })?;
+    options.report(KeyGenStage::WriteVk, 1, 1);
+
+    let pk = write_pk(pk, pk_output, options)?;
+    let pk = pk.commit()?;
+    let vk = vk.commit()?;
+    info!(
+        "generated a {} byte proving key and a {} byte verification key",
+        pk.byte_length, vk.byte_length
+    );
+    Ok(KeyFiles { pk, vk })
+}
+
+/// Re-derives a proving key with selector compression enabled, to shrink keys generated without it.
+///
+/// Compressing the selectors changes the fixed columns of the circuit, and with them the
+/// verification key, unless the key was already generated with compression. The re-derived key
+/// is only returned if its verification key equals the original one, so it can replace the
+/// original key without redeploying verifiers. The size difference is logged.
+///
+/// # Arguments
+///
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)` - The re-derived proving key.
+/// * `Err(ExternalEZKLError)` - `FingerprintMismatch` if the re-derived key would change the
+///   verification key, or an error that occurred while deserializing or deriving the key.
+#[cfg_attr(feature = "ffi", export)]
+pub fn optimize_pk(
+    compiled_circuit: Vec<u8>,
+    pk: Vec<u8>,
+    srs: Vec<u8>,
+) -> Result<Vec<u8>, ExternalEZKLError> {
+    check_input_sizes!(compiled_circuit, pk, srs);
+    let circuit = deserialize_circuit(&compiled_circuit)?;
+    match Commitments::from(circuit.settings().run_args.commitment) {
+        Commitments::KZG => optimize::<KZGCommitmentScheme<Bn256>>(circuit, &pk, &srs),
+        Commitments::IPA => optimize::<IPACommitmentScheme<G1Affine>>(circuit, &pk, &srs),
+    }
+}
+
+fn optimize<Scheme: CommitmentScheme<Scalar = Fr, Curve = G1Affine>>(
+    circuit: GraphCircuit,
+    serialized_pk: &[u8],
+    srs: &[u8],
+) -> Result<Vec<u8>, ExternalEZKLError> {
+    let original = deserialize_pk::<Scheme, GraphCircuit>(serialized_pk, circuit.params())?;
+    let original_vk = fingerprint::of_vk(original.get_vk());
+    drop(original);
+
+    let params =
+        deserialize_params_prover::<Scheme>(Some(srs), circuit.settings().run_args.logrows)?;
+    let pk = create_keys::<Scheme, GraphCircuit>(&circuit, &params, false)
+        .map_err(|e| ExternalEZKLError::InternalError(e.to_string()))?;
+    fingerprint::check(
+        "verification key of the optimized proving key",
+        Some(&original_vk),
+        &fingerprint::of_vk(pk.get_vk()),
+    )?;
+
+    let mut optimized = Vec::with_capacity(pk.bytes_length(RawBytes));
+    pk.write(&mut optimized, RawBytes)
+        .map_err(|e| ExternalEZKLError::InternalError(e.to_string()))?;
+    info!(
+        "optimized the proving key from {} to {} bytes ({:+} bytes)",
+        serialized_pk.len(),
+        optimized.len(),
+        optimized.len() as i64 - serialized_pk.len() as i64
+    );
+    Ok(optimized)
+}
+
+/// The structure of a proving key, to predict the memory needed to prove with it.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PkInfo {
+    /// The log₂ of the number of rows of the circuit the key was generated for.
+    pub k: u32,
+    /// The number of fixed columns, including the compressed selectors.
+    pub num_fixed_columns: u64,
+    /// The number of advice columns.
+    pub num_advice_columns: u64,
+    /// The number of instance columns.
+    pub num_instance_columns: u64,
+    /// The number of columns taking part in the permutation argument.
+    pub num_permutation_columns: u64,
+    /// The size of the serialized proving key in bytes.
+    pub serialized_size: u64,
+}
+
+/// Reads the structure of a proving key: its `k` and its column counts.
+///
+/// The key is deserialized to read its constraint system and dropped before returning.
+///
+/// # Arguments
+///
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(PkInfo)` - The structure of the proving key.
+/// * `Err(ExternalEZKLError)` - If the circuit or the proving key could not be deserialized.
+#[cfg_attr(feature = "ffi", export)]
+pub fn pk_info(compiled_circuit: Vec<u8>, pk: Vec<u8>) -> Result<PkInfo, ExternalEZKLError> {
+    check_input_sizes!(compiled_circuit, pk);
+    let circuit = deserialize_circuit(&compiled_circuit)?;
+    let deserialized = match Commitments::from(circuit.settings().run_args.commitment) {
+        Commitments::KZG => {
+            deserialize_pk::<KZGCommitmentScheme<Bn256>, GraphCircuit>(&pk, circuit.params())?
+        }
+        Commitments::IPA => {
+            deserialize_pk::<IPACommitmentScheme<G1Affine>, GraphCircuit>(&pk, circuit.params())?
+        }
+    };
+
+    let vk = deserialized.get_vk();
+    let cs = vk.cs();
+    Ok(PkInfo {
+        k: vk.get_domain().k(),
+        num_fixed_columns: cs.num_fixed_columns() as u64,
+        num_advice_columns: cs.num_advice_columns() as u64,
+        num_instance_columns: cs.num_instance_columns() as u64,
+        num_permutation_columns: cs.permutation().get_columns().len() as u64,
+        serialized_size: pk.len() as u64,
+    })
+}
+
+/// Streams the proving key into its file, reporting the written bytes.
+fn write_pk(
+    pk: ProvingKey<G1Affine>,
+    output: OutputFile,
+    options: &KeyGenOptions,
+) -> Result<StagedFile, ExternalEZKLError> {
+    let total = pk.bytes_length(RawBytes) as u64;
+    let staged = output.stage_with(|writer| {
+        let mut writer = ProgressWriter {
+            inner: writer,
+            options,
+            total,
+            written: 0,
+            reported: 0,
+        };
+        pk.write(&mut writer, RawBytes)
+            .map_err(|e| ExternalEZKLError::InternalError(e.to_string()))
+    })?;
+    let byte_length = staged.metadata().byte_length;
+    options.report(KeyGenStage::WritePk, byte_length, byte_length);
+    Ok(staged)
+}
+
+/// A writer reporting the progress of writing the proving key every few megabytes.
+struct ProgressWriter<'a> {
+    inner: &'a mut dyn Write,
+    options: &'a KeyGenOptions,
+    total: u64,
+    written: u64,
+    reported: u64,
+}
+
+impl Write for ProgressWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.written += written as u64;
+        if self.written - self.reported >= PROGRESS_INTERVAL_BYTES && self.written < self.total {
+            self.reported = self.written;
+            self.options
+                .report(KeyGenStage::WritePk, self.written, self.total);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
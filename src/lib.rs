@@ -1,23 +1,186 @@
+#[cfg(feature = "prover")]
+mod batch;
+#[cfg(feature = "benchmarks")]
+mod benchmarks;
+mod bundle;
+mod chain;
+mod clock;
+#[cfg(feature = "prover")]
+mod command;
+mod compat;
+mod compression;
+mod config;
+mod diagnostics;
 mod error;
+mod evm;
+mod executor;
+#[cfg(feature = "fetcher")]
+mod fetch;
+mod files;
+mod fingerprint;
+#[cfg(feature = "prover")]
 mod gen_witness;
+#[cfg(feature = "self-check")]
+mod health_check;
+mod identify;
+mod inspect;
+mod io;
+#[cfg(feature = "prover")]
+mod keys;
+mod limits;
+mod logging;
+mod metadata;
+mod migration;
+#[cfg(feature = "prover")]
+mod mock;
+#[cfg(feature = "prover")]
+mod non_finite;
+mod normalize;
+mod progress;
+#[cfg(feature = "prover")]
 mod prove;
+mod provider;
+#[cfg(feature = "prover")]
+mod proving_request;
+#[cfg(feature = "prover")]
+mod quantize;
+#[cfg(feature = "prover")]
+mod raw_input;
+#[cfg(feature = "prover")]
+mod self_test;
+#[cfg(feature = "prover")]
+mod sensitive;
 mod serialization;
+#[cfg(feature = "prover")]
+mod session;
+mod settings;
+mod srs;
+mod store;
+mod trace;
 mod verify;
+// The JavaScript exports keep their own namespace, as they share the names of the native functions
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "prover")]
+mod witness_cache;
 
-pub use gen_witness::gen_witness;
-pub use prove::{prove, prove_advanced};
-pub use verify::verify;
+#[cfg(feature = "prover")]
+pub use batch::{gen_witness_batch, prove_batch, BatchItemResult};
+#[cfg(feature = "benchmarks")]
+pub use benchmarks::{compare_commitments, CommitmentComparison, CommitmentMeasurement};
+pub use bundle::{create_bundle, verify_bundle};
+pub use chain::witness_to_input;
+#[cfg(feature = "prover")]
+pub use command::{execute, supported_commands, SUPPORTED_COMMANDS};
+pub use compat::{VerifyOptions, VerifyStrictness};
+pub use config::{initialize, EzklConfig};
+pub use diagnostics::set_diagnostics;
+pub use evm::{
+    encode_evm_calldata, estimate_evm_gas, instances_from_bytes, proof_instances_bytes, GasEstimate,
+};
+pub use executor::ThreadPriority;
+#[cfg(feature = "fetcher")]
+pub use fetch::fetch_artifact;
+pub use files::{cleanup_artifacts, verify_from_file, ArtifactMetadata};
+#[cfg(feature = "prover")]
+pub use files::{
+    gen_witness_to_file, gen_witness_to_file_from_store, prove_to_file, prove_to_file_from_store,
+};
+pub use fingerprint::{
+    circuit_fingerprint, pk_fingerprint, proof_digest, verify_with_fingerprint, vk_fingerprint,
+};
+#[cfg(feature = "prover")]
+pub use fingerprint::{prove_with_fingerprints, ExpectedFingerprints};
+#[cfg(feature = "prover")]
+pub use gen_witness::{
+    gen_witness, gen_witness_advanced, gen_witness_detailed, gen_witness_detailed_with_options,
+    gen_witness_simple, witness_dynamic_range, DynamicRange, WitnessOptions, WitnessResult,
+};
+#[cfg(feature = "self-check")]
+pub use health_check::{quick_health_check, HealthReport};
+pub use identify::{identify_and_verify, IdentifyResult, VkCandidate};
+pub use inspect::{
+    circuit_info, graph_summary, input_template, inspect_proof, split_instances,
+    witness_commitments, CircuitInfo, CommitmentsWrapper, GraphSummary, GroupedInstances,
+    LayerSummary, OpCount, PolyCommitments, ProofInfo, ProofSplitCommitWrapper,
+    TranscriptTypeWrapper,
+};
+#[cfg(feature = "prover")]
+pub use keys::{gen_keys_to_files, optimize_pk, pk_info, KeyFiles, KeyGenOptions, PkInfo};
+pub use limits::ResourceLimits;
+pub use logging::{EzklLogger, LogLevel};
+pub use metadata::{attach_metadata, read_metadata};
+pub use migration::migrate_artifact;
+#[cfg(feature = "prover")]
+pub use mock::{mock, ConstraintFailure, ConstraintFailureKind, MockReport};
+#[cfg(feature = "prover")]
+pub use non_finite::SanitizedInput;
+pub use normalize::{convert_felt, normalize_felt, normalize_proof, FeltEncoding};
+pub use progress::{KeyGenStage, ProgressListener};
+#[cfg(feature = "prover")]
+pub use prove::{
+    default_prove_options, prove, prove_advanced, prove_async, prove_bytes, prove_detailed,
+    prove_from_input, prove_from_input_detailed, CheckModeWrapper, InputProofResult,
+    ProofTypeWrapper, ProveMetrics, ProveOptions, ProveResult, VerifiedAgainst,
+};
+#[cfg(feature = "prover")]
+pub use provider::{gen_witness_from_provider, prove_from_provider};
+pub use provider::{verify_from_provider, ArtifactProvider};
+#[cfg(feature = "prover")]
+pub use proving_request::{export_proving_request, prove_request};
+#[cfg(feature = "prover")]
+pub use quantize::{quantize_preview, InputQuantization, QuantizationReport};
+#[cfg(feature = "prover")]
+pub use raw_input::{gen_witness_from_raw, DType, RawTensor};
+#[cfg(feature = "prover")]
+pub use self_test::{self_test, SelfTestReport, SelfTestStage, SelfTestStep};
+#[cfg(feature = "prover")]
+pub use session::{handle_memory_warning, EzklProver};
+pub use settings::{
+    parse_settings, settings_compatible, CompatReport, LookupSettings, RunArgsWrapper,
+    SettingsDifference, SettingsInfo, VariableBinding, VisibilityWrapper,
+};
+pub use srs::{
+    effective_srs_k, inspect_srs, inspect_srs_file, split_srs, SrsHeader, SrsParts, SRS_HEADER_LEN,
+};
+pub use store::{ArtifactKind, ArtifactStore};
+pub use trace::{set_trace_listener, TraceListener};
+pub use verify::{
+    prepare_vk, verify, verify_advanced, verify_async, verify_bytes, verify_detailed,
+    verify_flexible, verify_hashed_outputs, verify_output_claim, verify_with_commitments,
+    ClaimResult, OutputClaim, VerifyFailureCategory, VerifyOutcome, VerifyReport,
+};
+
+pub use error::{error_to_json, EZKLError, ErrorReport};
 
 pub(crate) use error::EZKLError as ExternalEZKLError;
 pub(crate) use ezkl::EZKLError as InnerEZKLError;
+#[cfg(feature = "prover")]
 pub(crate) use halo2_proofs::poly::ipa::strategy::AccumulatorStrategy as IPAAccumulatorStrategy;
 pub(crate) use halo2_proofs::poly::ipa::strategy::SingleStrategy as IPASingleStrategy;
+#[cfg(feature = "prover")]
 pub(crate) use halo2_proofs::poly::kzg::strategy::AccumulatorStrategy as KZGAccumulatorStrategy;
 pub(crate) use halo2_proofs::poly::kzg::strategy::SingleStrategy as KZGSingleStrategy;
 
-uniffi::setup_scaffolding!();
+// The namespace is pinned so that the exported C symbols stay stable across releases
+#[cfg(feature = "ffi")]
+uniffi::setup_scaffolding!("ios_ezkl");
 
 // This module is used for testing purposes only
 pub mod testing {
-    pub use crate::serialization::deserialize_params_prover;
+    pub use crate::diagnostics::{allocated_bytes, measure_peak_bytes};
+    #[cfg(feature = "test-hooks")]
+    pub use crate::files::{
+        fail_artifact_write_after, fail_next_artifact_write, on_coordinated_read,
+    };
+    pub use crate::io::buffer_capacity;
+    #[cfg(all(feature = "prover", feature = "test-hooks"))]
+    pub use crate::limits::{hold_proving_slot, HeldProvingSlot};
+    #[cfg(feature = "prover")]
+    pub use crate::raw_input::raw_graph_data;
+    #[cfg(feature = "prover")]
+    pub use crate::sensitive::wiped_buffers;
+    pub use crate::serialization::{
+        deserialize_json_file, deserialize_params_prover, serialize_proof,
+    };
 }
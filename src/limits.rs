@@ -0,0 +1,205 @@
+#[cfg(feature = "prover")]
+use crate::clock::Instant;
+use crate::config;
+#[cfg(feature = "prover")]
+use crate::logging::warn;
+use crate::ExternalEZKLError;
+#[cfg(feature = "prover")]
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+/// The limits on the resources the library may use at once.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// The maximum number of proofs generated concurrently. Each proof holds its own proving key
+    /// and SRS in memory, so this defaults to 1.
+    pub max_concurrent_proofs: u32,
+    /// If `true`, prove calls beyond the limit wait for a running proof to finish; otherwise they
+    /// fail immediately with `Busy`.
+    pub queue_when_busy: bool,
+    /// The maximum size in bytes of a string or byte argument, such as a witness or proving key.
+    /// Calls with a larger argument fail with `InvalidInput` before doing any work; artifacts that
+    /// large should be passed by path to the `_to_file` functions instead. Defaults to 512 MiB.
+    #[cfg_attr(feature = "ffi", uniffi(default = 536870912))]
+    pub max_input_bytes: u64,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        ResourceLimits {
+            max_concurrent_proofs: 1,
+            queue_when_busy: true,
+            max_input_bytes: DEFAULT_MAX_INPUT_BYTES,
+        }
+    }
+}
+
+/// The default of [`ResourceLimits::max_input_bytes`].
+const DEFAULT_MAX_INPUT_BYTES: u64 = 512 * 1024 * 1024;
+
+/// The size in bytes of a string or byte argument of an exported function.
+pub(crate) trait InputSize {
+    fn input_size(&self) -> u64;
+}
+
+impl InputSize for String {
+    fn input_size(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+impl InputSize for Vec<u8> {
+    fn input_size(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+impl InputSize for Vec<String> {
+    fn input_size(&self) -> u64 {
+        self.iter().map(InputSize::input_size).sum()
+    }
+}
+
+impl InputSize for Vec<Vec<u8>> {
+    fn input_size(&self) -> u64 {
+        self.iter().map(InputSize::input_size).sum()
+    }
+}
+
+impl<T: InputSize> InputSize for Option<T> {
+    fn input_size(&self) -> u64 {
+        self.as_ref().map_or(0, InputSize::input_size)
+    }
+}
+
+/// Fails with `InvalidInput` if an argument is larger than [`ResourceLimits::max_input_bytes`].
+///
+/// # Arguments
+///
+/// * `arguments` - The name and size in bytes of each argument to check.
+pub(crate) fn check_input_limits(arguments: &[(&str, u64)]) -> Result<(), ExternalEZKLError> {
+    let max_input_bytes = config::current().limits.max_input_bytes;
+    match arguments.iter().find(|(_, size)| *size > max_input_bytes) {
+        Some((name, size)) => Err(ExternalEZKLError::InvalidInput(format!(
+            "`{}` is {} bytes, more than the limit of {} bytes set by `max_input_bytes` of the resource limits; pass artifacts this large by path to the `_to_file` functions instead",
+            name, size, max_input_bytes
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Checks the sizes of the named string and byte arguments of an exported function with
+/// [`check_input_limits`], returning early with its error.
+macro_rules! check_input_sizes {
+    ($($argument:ident),+ $(,)?) => {
+        $crate::limits::check_input_limits(&[$((
+            stringify!($argument),
+            $crate::limits::InputSize::input_size(&$argument),
+        )),+])?
+    };
+}
+pub(crate) use check_input_sizes;
+
+#[cfg(feature = "prover")]
+static RUNNING_PROOFS: Mutex<u32> = Mutex::new(0);
+#[cfg(feature = "prover")]
+static PROOF_FINISHED: Condvar = Condvar::new();
+
+/// A slot in the proving semaphore, released when dropped.
+///
+/// Only proving is gated; witness generation and verification are comparatively cheap.
+#[cfg(feature = "prover")]
+pub(crate) struct ProvingPermit {
+    _private: (),
+}
+
+#[cfg(feature = "prover")]
+impl ProvingPermit {
+    /// Acquires a proving slot according to the configured [`ResourceLimits`].
+    pub(crate) fn acquire() -> Result<Self, ExternalEZKLError> {
+        let limits = config::current().limits;
+        let max_concurrent_proofs = limits.max_concurrent_proofs.max(1);
+
+        let mut running = RUNNING_PROOFS.lock().map_err(|_| {
+            ExternalEZKLError::InternalError("proving semaphore poisoned".to_string())
+        })?;
+        while *running >= max_concurrent_proofs {
+            if !limits.queue_when_busy {
+                return Err(ExternalEZKLError::Busy {
+                    running_proofs: *running,
+                });
+            }
+            running = PROOF_FINISHED.wait(running).map_err(|_| {
+                ExternalEZKLError::InternalError("proving semaphore poisoned".to_string())
+            })?;
+        }
+        *running += 1;
+
+        Ok(ProvingPermit { _private: () })
+    }
+}
+
+#[cfg(feature = "prover")]
+impl Drop for ProvingPermit {
+    fn drop(&mut self) {
+        if let Ok(mut running) = RUNNING_PROOFS.lock() {
+            *running = running.saturating_sub(1);
+        }
+        PROOF_FINISHED.notify_one();
+    }
+}
+
+/// A proving slot taken by a test, as if a proof was running, until it is dropped.
+#[cfg(all(feature = "prover", any(test, feature = "test-hooks")))]
+pub struct HeldProvingSlot {
+    _permit: ProvingPermit,
+}
+
+/// Takes a proving slot like a proving call, to test calls waiting for one.
+#[cfg(all(feature = "prover", any(test, feature = "test-hooks")))]
+pub fn hold_proving_slot() -> Result<HeldProvingSlot, ExternalEZKLError> {
+    Ok(HeldProvingSlot {
+        _permit: ProvingPermit::acquire()?,
+    })
+}
+
+/// The time budget of a call, checked at the boundaries of its stages.
+///
+/// A stage that is running can't be interrupted, so a call exceeding its budget returns at the
+/// next boundary, without leaving any work running in the background.
+#[cfg(feature = "prover")]
+pub(crate) struct TimeBudget {
+    start: Instant,
+    budget: Option<Duration>,
+}
+
+#[cfg(feature = "prover")]
+impl TimeBudget {
+    /// Starts the budget of a call, which is unlimited if `budget_ms` is `None`.
+    pub(crate) fn start(budget_ms: Option<u64>) -> Self {
+        TimeBudget {
+            start: Instant::now(),
+            budget: budget_ms.map(Duration::from_millis),
+        }
+    }
+
+    /// Fails with `TimedOut` if the call has exceeded its budget.
+    pub(crate) fn check(&self, stage: &str) -> Result<(), ExternalEZKLError> {
+        let elapsed = self.start.elapsed();
+        match self.budget {
+            Some(budget) if elapsed > budget => {
+                warn!(
+                    "aborting before `{}`: {} ms elapsed, the budget is {} ms",
+                    stage,
+                    elapsed.as_millis(),
+                    budget.as_millis()
+                );
+                Err(ExternalEZKLError::TimedOut {
+                    elapsed_ms: elapsed.as_millis() as u64,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
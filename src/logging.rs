@@ -0,0 +1,90 @@
+use log::{Log, Metadata, Record};
+use std::sync::{Arc, RwLock};
+
+// The macros the library logs with. Their arguments are only formatted if a logger is installed
+// and the message is at or above its level.
+pub(crate) use log::{debug, info, trace, warn};
+
+/// The severity of a log message.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<log::Level> for LogLevel {
+    fn from(level: log::Level) -> Self {
+        match level {
+            log::Level::Error => LogLevel::Error,
+            log::Level::Warn => LogLevel::Warn,
+            log::Level::Info => LogLevel::Info,
+            log::Level::Debug => LogLevel::Debug,
+            log::Level::Trace => LogLevel::Trace,
+        }
+    }
+}
+
+impl From<LogLevel> for log::LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::Trace => log::LevelFilter::Trace,
+        }
+    }
+}
+
+/// A logger implemented by the host application, receiving the log messages of the library.
+#[cfg_attr(feature = "ffi", uniffi::export(with_foreign))]
+pub trait EzklLogger: Send + Sync {
+    /// Called for every log message at or above the configured level.
+    fn log(&self, level: LogLevel, message: String);
+}
+
+static LOGGER: RwLock<Option<Arc<dyn EzklLogger>>> = RwLock::new(None);
+static BRIDGE: BridgeLogger = BridgeLogger;
+
+/// Forwards the records of the `log` facade to the installed foreign logger.
+struct BridgeLogger;
+
+impl Log for BridgeLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        if let Ok(logger) = LOGGER.read() {
+            if let Some(logger) = logger.as_ref() {
+                logger.log(record.level().into(), record.args().to_string());
+            }
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installs the foreign logger and the maximum level of the messages forwarded to it.
+///
+/// Passing `None` silences the library again.
+pub(crate) fn install(logger: Option<Arc<dyn EzklLogger>>, level: LogLevel) {
+    // Registering the bridge only fails if it is already registered, which is fine
+    let _ = log::set_logger(&BRIDGE);
+    let enabled = logger.is_some();
+    if let Ok(mut current) = LOGGER.write() {
+        *current = logger;
+    }
+    log::set_max_level(if enabled {
+        level.into()
+    } else {
+        log::LevelFilter::Off
+    });
+}
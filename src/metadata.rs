@@ -0,0 +1,66 @@
+use crate::limits::check_input_sizes;
+use crate::serialization::{deserialize_json, deserialize_proof, APP_METADATA_KEY};
+use crate::ExternalEZKLError;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The part of a proof JSON holding the app metadata.
+#[derive(Deserialize)]
+struct TaggedProof {
+    #[serde(default)]
+    app_metadata: HashMap<String, String>,
+}
+
+/// Attaches app metadata, such as a session id or model version, to a proof.
+///
+/// The metadata is stored under the top-level `app_metadata` key of the proof JSON, replacing any
+/// metadata attached before. Verification ignores the key and `proof_digest` doesn't cover it,
+/// so tagging a proof doesn't change whether it verifies or its digest.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof.
+/// * `metadata` - The key/value pairs to attach.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The proof JSON with the metadata attached.
+/// * `Err(ExternalEZKLError)` - If the proof could not be parsed.
+#[cfg_attr(feature = "ffi", export)]
+pub fn attach_metadata(
+    proof_json: String,
+    metadata: HashMap<String, String>,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(proof_json);
+    // Only tag proofs, which the verifier accepts once the metadata is removed
+    deserialize_proof("proof_json", &proof_json)?;
+    let mut proof: serde_json::Map<String, Value> = deserialize_json("proof_json", &proof_json)?;
+
+    // Sorted, so that equal metadata is always written the same way
+    let metadata: BTreeMap<String, String> = metadata.into_iter().collect();
+    proof.insert(
+        APP_METADATA_KEY.to_string(),
+        serde_json::to_value(metadata)?,
+    );
+    Ok(serde_json::to_string(&proof)?)
+}
+
+/// Reads the app metadata attached to a proof with [`attach_metadata`].
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof.
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, String>)` - The attached metadata, empty if there is none.
+/// * `Err(ExternalEZKLError)` - If the proof is not JSON or its metadata is not a map of strings.
+#[cfg_attr(feature = "ffi", export)]
+pub fn read_metadata(proof_json: String) -> Result<HashMap<String, String>, ExternalEZKLError> {
+    check_input_sizes!(proof_json);
+    let proof: TaggedProof = deserialize_json("proof_json", &proof_json)?;
+    Ok(proof.app_metadata)
+}
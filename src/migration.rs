@@ -0,0 +1,155 @@
+use crate::compression::json_text;
+use crate::limits::check_input_sizes;
+use crate::logging::info;
+use crate::serialization::deserialize_json;
+use crate::store::ArtifactKind;
+use crate::ExternalEZKLError;
+use ezkl::graph::GraphSettings;
+use serde_json::{Map, Value};
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// A serialization layout an artifact was shipped in by an earlier ezkl version, and how to
+/// re-serialize it in the layout that followed it.
+struct Migration {
+    kind: ArtifactKind,
+    /// A description of the layout, for errors and logs.
+    layout: &'static str,
+    /// Whether the bytes are in this layout.
+    detect: fn(&[u8]) -> bool,
+    /// Re-serializes bytes of this layout in the layout of the next migration of the same kind,
+    /// or in the current layout if there is none.
+    upgrade: fn(Vec<u8>) -> Result<Vec<u8>, ExternalEZKLError>,
+}
+
+/// The compatibility table of the layouts artifacts can be migrated from, oldest first.
+///
+/// When an ezkl bump changes a layout, the layout it replaces is appended with an upgrade to the
+/// new one, so artifacts of any layout in the table are migrated through every later step.
+const MIGRATIONS: &[Migration] = &[Migration {
+    kind: ArtifactKind::Settings,
+    layout: "settings of ezkl versions before IPA commitments",
+    detect: is_kzg_only_settings,
+    upgrade: add_commitment_to_settings,
+}];
+
+/// Re-serializes an artifact written by an earlier ezkl version in the layout of the pinned one,
+/// e.g. to keep the keys and settings cached by an installed app usable after an update.
+///
+/// The layouts that can be migrated are listed in a compatibility table. Artifacts already in the
+/// current layout are returned unchanged. Settings can be migrated so far. Every release of this
+/// crate has pinned ezkl v13.0.2, so no earlier key layout has been shipped to migrate from, and
+/// keys carry no marker of the layout they were written in; when a bump changes the layout of
+/// keys, the layout it replaces gets an entry like the settings one.
+///
+/// # Arguments
+///
+/// * `kind` - The kind of the artifact.
+/// * `bytes` - The serialized artifact.
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)` - The artifact in the current layout.
+/// * `Err(ExternalEZKLError)` - `Unsupported` if the layout of the artifact is not in the
+///   compatibility table, e.g. because it is too old, or the error of re-reading it.
+#[cfg_attr(feature = "ffi", export)]
+pub fn migrate_artifact(kind: ArtifactKind, bytes: Vec<u8>) -> Result<Vec<u8>, ExternalEZKLError> {
+    check_input_sizes!(bytes);
+    let steps: Vec<&Migration> = MIGRATIONS.iter().filter(|m| m.kind == kind).collect();
+    if let Some(first) = steps.iter().position(|m| (m.detect)(&bytes)) {
+        info!("migrating {} to the current layout", steps[first].layout);
+        let migrated = steps[first..]
+            .iter()
+            .try_fold(bytes, |bytes, m| (m.upgrade)(bytes))?;
+        return current_layout(kind, migrated);
+    }
+
+    match kind {
+        ArtifactKind::Settings => {
+            let text = json_text("bytes", bytes)?;
+            serde_json::from_str::<GraphSettings>(&text)
+                .map(|_| text.into_bytes())
+                .map_err(|e| {
+                    ExternalEZKLError::Unsupported {
+                        detail: format!(
+                            "the settings are neither in the current layout ({}) nor in a layout they can be migrated from",
+                            e
+                        ),
+                    }
+                })
+        }
+        kind => Err(ExternalEZKLError::Unsupported {
+            detail: format!("no layout of {:?} artifacts can be migrated", kind),
+        }),
+    }
+}
+
+/// Checks that a migrated artifact reads with the pinned ezkl and re-serializes it with it.
+fn current_layout(kind: ArtifactKind, bytes: Vec<u8>) -> Result<Vec<u8>, ExternalEZKLError> {
+    match kind {
+        ArtifactKind::Settings => {
+            let settings: GraphSettings = deserialize_json("bytes", &json_text("bytes", bytes)?)?;
+            Ok(serde_json::to_vec(&settings)?)
+        }
+        _ => Ok(bytes),
+    }
+}
+
+/// Parses settings JSON into an object that a migration can edit.
+fn settings_object(bytes: &[u8]) -> Option<Map<String, Value>> {
+    match serde_json::from_str(&json_text("bytes", bytes.to_vec()).ok()?).ok()? {
+        Value::Object(settings) => Some(settings),
+        _ => None,
+    }
+}
+
+/// Settings written before ezkl supported IPA commitments have no commitment in their run args,
+/// and name the module of polycommit visibility `kzg`.
+fn is_kzg_only_settings(bytes: &[u8]) -> bool {
+    let Some(settings) = settings_object(bytes) else {
+        return false;
+    };
+    let no_commitment = settings
+        .get("run_args")
+        .and_then(Value::as_object)
+        .is_some_and(|run_args| !run_args.contains_key("commitment"));
+    let kzg_module = settings
+        .get("module_sizes")
+        .and_then(Value::as_object)
+        .is_some_and(|module_sizes| module_sizes.contains_key("kzg"));
+    no_commitment || kzg_module
+}
+
+/// Upgrades settings written before IPA commitments, which could only use KZG.
+///
+/// The counters of dynamic lookups and shuffles and the rebasing flags were added later too;
+/// the circuits of these settings have none of them, so they are filled in as zero and `false`.
+fn add_commitment_to_settings(bytes: Vec<u8>) -> Result<Vec<u8>, ExternalEZKLError> {
+    let mut settings = settings_object(&bytes).ok_or_else(|| {
+        ExternalEZKLError::InvalidInput("the settings are not a JSON object".to_string())
+    })?;
+
+    if let Some(Value::Object(run_args)) = settings.get_mut("run_args") {
+        run_args
+            .entry("commitment")
+            .or_insert_with(|| Value::from("KZG"));
+        for flag in ["div_rebasing", "rebase_frac_zero_constants"] {
+            run_args.entry(flag).or_insert(Value::Bool(false));
+        }
+    }
+    if let Some(Value::Object(module_sizes)) = settings.get_mut("module_sizes") {
+        if let Some(kzg) = module_sizes.remove("kzg") {
+            module_sizes.entry("polycommit").or_insert(kzg);
+        }
+    }
+    for counter in [
+        "total_dynamic_col_size",
+        "num_dynamic_lookups",
+        "num_shuffles",
+        "total_shuffle_col_size",
+    ] {
+        settings.entry(counter).or_insert(Value::from(0));
+    }
+
+    Ok(serde_json::to_vec(&settings)?)
+}
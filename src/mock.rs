@@ -0,0 +1,208 @@
+use crate::limits::{check_input_sizes, ProvingPermit};
+use crate::serialization::{deserialize_circuit, deserialize_json};
+use crate::settings::check_logrows;
+use crate::trace::Interval;
+use crate::{ExternalEZKLError, InnerEZKLError};
+use ezkl::graph::GraphWitness;
+use halo2_proofs::dev::{FailureLocation, MockProver, VerifyFailure};
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The kind of constraint a circuit failed to satisfy, after the halo2 `VerifyFailure` variants.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintFailureKind {
+    /// A gate queries a cell that was never assigned.
+    CellNotAssigned,
+    /// A gate queries an instance cell that was never assigned.
+    InstanceCellNotAssigned,
+    /// A gate constraint does not evaluate to zero.
+    ConstraintNotSatisfied,
+    /// A gate constraint is poisoned, i.e. enabled on a row it cannot be evaluated on.
+    ConstraintPoisoned,
+    /// An input of a lookup is not in its table.
+    Lookup,
+    /// Two cells constrained to be equal hold different values.
+    Permutation,
+    /// A failure this version of the bindings does not know about.
+    Other,
+}
+
+/// A constraint the circuit failed to satisfy for a witness.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct ConstraintFailure {
+    /// The kind of the failure.
+    pub kind: ConstraintFailureKind,
+    /// The gate, constraint or lookup that failed, if the failure names one.
+    pub gate: Option<String>,
+    /// The region the failure occurred in, if it occurred inside a region.
+    pub region: Option<String>,
+    /// The row of the failure, relative to the start of the region if `region` is set and
+    /// absolute otherwise.
+    pub row_offset: Option<i64>,
+    /// The column of the failure, if the failure names one.
+    pub column: Option<String>,
+    /// The full description of the failure, as reported by halo2.
+    pub description: String,
+}
+
+/// The result of checking the constraints of a circuit with the mock prover.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct MockReport {
+    /// `true` if the witness satisfies every constraint of the circuit.
+    pub satisfied: bool,
+    /// The constraints the witness does not satisfy, empty if `satisfied` is `true`.
+    pub failures: Vec<ConstraintFailure>,
+}
+
+impl ConstraintFailure {
+    fn in_location(
+        kind: ConstraintFailureKind,
+        gate: Option<String>,
+        column: Option<String>,
+        location: &FailureLocation,
+        description: String,
+    ) -> Self {
+        let (region, row_offset) = match location {
+            FailureLocation::InRegion { region, offset } => {
+                (Some(region.to_string()), Some(*offset as i64))
+            }
+            FailureLocation::OutsideRegion { row } => (None, Some(*row as i64)),
+        };
+        ConstraintFailure {
+            kind,
+            gate,
+            region,
+            row_offset,
+            column,
+            description,
+        }
+    }
+}
+
+impl From<&VerifyFailure> for ConstraintFailure {
+    fn from(failure: &VerifyFailure) -> Self {
+        let description = failure.to_string();
+        match failure {
+            VerifyFailure::CellNotAssigned {
+                gate,
+                region,
+                column,
+                offset,
+                ..
+            } => ConstraintFailure {
+                kind: ConstraintFailureKind::CellNotAssigned,
+                gate: Some(gate.to_string()),
+                region: Some(region.to_string()),
+                row_offset: Some(*offset as i64),
+                column: Some(format!("{:?}", column)),
+                description,
+            },
+            VerifyFailure::InstanceCellNotAssigned {
+                gate,
+                region,
+                column,
+                row,
+                ..
+            } => ConstraintFailure {
+                kind: ConstraintFailureKind::InstanceCellNotAssigned,
+                gate: Some(gate.to_string()),
+                region: Some(region.to_string()),
+                row_offset: Some(*row as i64),
+                column: Some(format!("{:?}", column)),
+                description,
+            },
+            VerifyFailure::ConstraintNotSatisfied {
+                constraint,
+                location,
+                ..
+            } => ConstraintFailure::in_location(
+                ConstraintFailureKind::ConstraintNotSatisfied,
+                Some(constraint.to_string()),
+                None,
+                location,
+                description,
+            ),
+            VerifyFailure::ConstraintPoisoned { constraint } => ConstraintFailure {
+                kind: ConstraintFailureKind::ConstraintPoisoned,
+                gate: Some(constraint.to_string()),
+                region: None,
+                row_offset: None,
+                column: None,
+                description,
+            },
+            VerifyFailure::Lookup { name, location, .. } => ConstraintFailure::in_location(
+                ConstraintFailureKind::Lookup,
+                Some(name.to_string()),
+                None,
+                location,
+                description,
+            ),
+            VerifyFailure::Permutation { column, location } => ConstraintFailure::in_location(
+                ConstraintFailureKind::Permutation,
+                None,
+                Some(column.to_string()),
+                location,
+                description,
+            ),
+            // Newer halo2 versions add variants, such as shuffle failures
+            #[allow(unreachable_patterns)]
+            _ => ConstraintFailure {
+                kind: ConstraintFailureKind::Other,
+                gate: None,
+                region: None,
+                row_offset: None,
+                column: None,
+                description,
+            },
+        }
+    }
+}
+
+/// Checks that a witness satisfies every constraint of a circuit, without generating a proof.
+///
+/// This runs the halo2 mock prover, which is slower than proving for large circuits but
+/// reports exactly which gates, lookups and copy constraints fail and where, which is what you
+/// need to debug a circuit or a witness that fails to prove. No proving key or SRS is needed.
+///
+/// # Arguments
+///
+/// * `witness_json` - A `String` containing the JSON representation of the witness generated for the circuit input.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+///
+/// # Returns
+///
+/// * `Ok(MockReport)` - Whether the witness satisfies the circuit, and the failures if it does not.
+/// * `Err(ExternalEZKLError)` - If the witness or circuit could not be parsed, or the circuit could not be synthesized.
+#[cfg_attr(feature = "ffi", export)]
+pub fn mock(
+    witness_json: String,
+    compiled_circuit: Vec<u8>,
+) -> Result<MockReport, ExternalEZKLError> {
+    check_input_sizes!(witness_json, compiled_circuit);
+    let witness: GraphWitness = deserialize_json("witness_json", &witness_json)?;
+    drop(witness_json);
+    let mut circuit = deserialize_circuit(&compiled_circuit)?;
+    check_logrows(circuit.settings())?;
+
+    circuit.load_graph_witness(&witness)?;
+    let public_inputs = circuit.prepare_public_inputs(&witness)?;
+    let logrows = circuit.settings().run_args.logrows;
+
+    // The mock prover keeps every cell of the circuit in memory, so it counts as a proof
+    let _permit = ProvingPermit::acquire()?;
+    let _interval = Interval::begin("mock");
+    let prover =
+        MockProver::run(logrows, &circuit, vec![public_inputs]).map_err(InnerEZKLError::from)?;
+
+    let failures: Vec<ConstraintFailure> = match prover.verify() {
+        Ok(()) => vec![],
+        Err(failures) => failures.iter().map(ConstraintFailure::from).collect(),
+    };
+    Ok(MockReport {
+        satisfied: failures.is_empty(),
+        failures,
+    })
+}
@@ -0,0 +1,176 @@
+use crate::logging::warn;
+use crate::ExternalEZKLError;
+use ezkl::graph::input::{DataSource, FileSourceInner, GraphData};
+use ezkl::graph::{scale_to_multiplier, GraphSettings};
+use serde_json::Value;
+use std::borrow::Cow;
+
+/// An input value that was NaN or infinite, and was replaced because the witness options asked
+/// to sanitize the input.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct SanitizedInput {
+    /// The index of the input tensor.
+    pub input: u64,
+    /// The offset of the value in the flattened input tensor.
+    pub offset: u64,
+    /// The value that was replaced, NaN or ±infinity.
+    pub value: f64,
+    /// The value it was replaced with: zero for NaN, and the bound of the lookup range at the
+    /// input scale for infinities.
+    pub replacement: f64,
+}
+
+/// Checks that every float of the input data is finite.
+///
+/// Without `sanitize`, the first value that is NaN or infinite is rejected with `InvalidInput`,
+/// as it would otherwise be quantized to an arbitrary field element and only fail later as a
+/// constraint violation. With `sanitize`, NaN is replaced with zero and infinities are clamped
+/// to the lookup range of the circuit at the scale of the input, and the replacements are
+/// returned.
+pub(crate) fn check_finite_inputs(
+    data: &mut GraphData,
+    settings: &GraphSettings,
+    sanitize: bool,
+) -> Result<Vec<SanitizedInput>, ExternalEZKLError> {
+    let DataSource::File(rows) = &mut data.input_data else {
+        return Ok(vec![]);
+    };
+    let (min, max) = settings.run_args.lookup_range;
+    let mut sanitized = vec![];
+    for (input, row) in rows.iter_mut().enumerate() {
+        for (offset, element) in row.iter_mut().enumerate() {
+            let FileSourceInner::Float(value) = element else {
+                continue;
+            };
+            if value.is_finite() {
+                continue;
+            }
+            if !sanitize {
+                return Err(ExternalEZKLError::InvalidInput(format!(
+                    "input {} has the non-finite value {} at offset {}; set `sanitize` in the witness options to replace NaN with zero and clamp infinities",
+                    input, value, offset
+                )));
+            }
+            let scale = settings.model_input_scales.get(input).copied().unwrap_or(0);
+            let multiplier = scale_to_multiplier(scale);
+            let replacement = if value.is_nan() {
+                0.0
+            } else if value.is_sign_positive() {
+                max as f64 / multiplier
+            } else {
+                min as f64 / multiplier
+            };
+            warn!(
+                "replacing the non-finite value {} of input {} at offset {} with {}",
+                value, input, offset, replacement
+            );
+            sanitized.push(SanitizedInput {
+                input: input as u64,
+                offset: offset as u64,
+                value: *value,
+                replacement,
+            });
+            *element = FileSourceInner::Float(replacement);
+        }
+    }
+    Ok(sanitized)
+}
+
+/// Quotes the bare `NaN`, `Infinity` and `-Infinity` tokens written by e.g. Python's `json`
+/// module, which are not valid JSON, so they can be parsed as strings.
+pub(crate) fn quote_non_finite_literals(json: &str) -> Cow<'_, str> {
+    const LITERALS: [&str; 3] = ["-Infinity", "Infinity", "NaN"];
+    if !LITERALS.iter().any(|literal| json.contains(literal)) {
+        return Cow::Borrowed(json);
+    }
+
+    let mut quoted = String::with_capacity(json.len() + 16);
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut rest = json;
+    while let Some(c) = rest.chars().next() {
+        if !in_string {
+            if let Some(literal) = LITERALS.iter().find(|literal| rest.starts_with(**literal)) {
+                quoted.push('"');
+                quoted.push_str(literal);
+                quoted.push('"');
+                rest = &rest[literal.len()..];
+                continue;
+            }
+        }
+        if in_string && escaped {
+            escaped = false;
+        } else if in_string && c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            in_string = !in_string;
+        }
+        quoted.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    Cow::Owned(quoted)
+}
+
+/// Replaces the non-finite values of the inputs of parsed input data with zeros, so that it
+/// deserializes, and returns their positions and values to restore with [`restore_non_finite`].
+///
+/// JSON has no representation of NaN and infinities, so encoders write them as `null`, like
+/// `JSON.stringify` and `serde_json` do, or as strings such as `"NaN"` and `"-Infinity"`.
+pub(crate) fn take_non_finite(value: &mut Value) -> Vec<(usize, usize, f64)> {
+    let inputs = match value {
+        Value::Object(fields) => match fields.get_mut("input_data") {
+            Some(Value::Array(inputs)) => inputs,
+            _ => return vec![],
+        },
+        Value::Array(inputs) => inputs,
+        _ => return vec![],
+    };
+
+    let mut taken = vec![];
+    let mut take = |input: usize, row: &mut Vec<Value>| {
+        for (offset, element) in row.iter_mut().enumerate() {
+            let non_finite = match element {
+                Value::Null => Some(f64::NAN),
+                Value::String(text) => non_finite_from_str(text),
+                _ => None,
+            };
+            if let Some(non_finite) = non_finite {
+                taken.push((input, offset, non_finite));
+                *element = Value::from(0.0);
+            }
+        }
+    };
+    // A bare array holds a single flat input, unless each of its elements is an array
+    if inputs.iter().all(Value::is_array) {
+        for (input, row) in inputs.iter_mut().enumerate() {
+            if let Value::Array(row) = row {
+                take(input, row);
+            }
+        }
+    } else {
+        take(0, inputs);
+    }
+    taken
+}
+
+/// Puts the values taken by [`take_non_finite`] back into the deserialized input data.
+pub(crate) fn restore_non_finite(data: &mut GraphData, taken: Vec<(usize, usize, f64)>) {
+    let DataSource::File(rows) = &mut data.input_data else {
+        return;
+    };
+    for (input, offset, value) in taken {
+        if let Some(element) = rows.get_mut(input).and_then(|row| row.get_mut(offset)) {
+            *element = FileSourceInner::Float(value);
+        }
+    }
+}
+
+fn non_finite_from_str(text: &str) -> Option<f64> {
+    match text.trim().to_ascii_lowercase().as_str() {
+        "nan" | "+nan" | "-nan" => Some(f64::NAN),
+        "inf" | "+inf" | "infinity" | "+infinity" => Some(f64::INFINITY),
+        "-inf" | "-infinity" => Some(f64::NEG_INFINITY),
+        _ => None,
+    }
+}
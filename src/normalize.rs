@@ -0,0 +1,249 @@
+use crate::limits::check_input_sizes;
+use crate::serialization::serialize_proof;
+use crate::ExternalEZKLError;
+use ezkl::pfsys::Snark;
+use halo2_proofs::halo2curves::bn256::{Fr, G1Affine};
+use halo2_proofs::halo2curves::ff::PrimeField;
+use num_bigint::BigUint;
+use serde_json::{Map, Value};
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The encodings `normalize_proof` understands, for error messages.
+const SUPPORTED_ENCODINGS: &str = "the JSON written by ezkl's `Snark::save`, including layouts with limb encoded instances or a hex encoded proof, or the bincode encoding of a proof";
+
+/// Converts a proof saved by the ezkl CLI or other desktop tooling into the JSON this library emits.
+///
+/// The input may be JSON, including the layouts of older ezkl versions, which encoded the
+/// instances as four little-endian `u64` limbs and the proof as a hex string, or the bincode
+/// encoding of a proof.
+///
+/// # Arguments
+///
+/// * `bytes` - A `Vec<u8>` containing the proof file contents.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The proof as the JSON `String` accepted by `verify`.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` naming the supported encodings, and the ezkl version
+///   that wrote the proof if it is recorded, if the proof could not be converted.
+#[cfg_attr(feature = "ffi", export)]
+pub fn normalize_proof(bytes: Vec<u8>) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(bytes);
+    let text = std::str::from_utf8(&bytes)
+        .ok()
+        .map(|text| text.trim_start_matches('\u{feff}').trim());
+    let snark = match text {
+        Some(text) if text.starts_with('{') => from_json(text)?,
+        _ => bincode::deserialize::<Snark<Fr, G1Affine>>(&bytes).map_err(|e| {
+            ExternalEZKLError::InvalidInput(format!(
+                "unrecognized proof encoding ({}); expected {}",
+                e, SUPPORTED_ENCODINGS
+            ))
+        })?,
+    };
+    serialize_proof(&snark)
+}
+
+/// Parses a proof from JSON, rewriting older field layouts to the current one first.
+fn from_json(text: &str) -> Result<Snark<Fr, G1Affine>, ExternalEZKLError> {
+    let mut value: Value = serde_json::from_str(text).map_err(|e| {
+        ExternalEZKLError::InvalidInput(format!("the proof is not valid JSON: {}", e))
+    })?;
+    let version = value
+        .get("version")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    if let Some(fields) = value.as_object_mut() {
+        upgrade_instances(fields);
+        upgrade_proof(fields);
+    }
+
+    serde_json::from_value(value).map_err(|e| {
+        let written_by = version
+            .map(|version| format!(", written by ezkl {}", version))
+            .unwrap_or_default();
+        ExternalEZKLError::InvalidInput(format!(
+            "unrecognized proof layout{} ({}); expected {}",
+            written_by, e, SUPPORTED_ENCODINGS
+        ))
+    })
+}
+
+/// Rewrites instances encoded as four little-endian `u64` limbs to hex encoded field elements.
+fn upgrade_instances(fields: &mut Map<String, Value>) {
+    let Some(Value::Array(columns)) = fields.get_mut("instances") else {
+        return;
+    };
+    for instance in columns.iter_mut().filter_map(Value::as_array_mut).flatten() {
+        let Some(limbs) = instance.as_array() else {
+            continue;
+        };
+        let limbs: Option<Vec<u64>> = limbs.iter().map(Value::as_u64).collect();
+        if let Some(limbs) = limbs.filter(|limbs| limbs.len() == 4) {
+            let repr: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+            *instance = Value::String(hex::encode(repr));
+        }
+    }
+}
+
+/// Rewrites a proof given as a hex string, or only as `hex_proof`, to an array of bytes.
+fn upgrade_proof(fields: &mut Map<String, Value>) {
+    let hex_proof = match fields.get("proof") {
+        Some(Value::String(hex)) => Some(hex.clone()),
+        None => fields
+            .get("hex_proof")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        _ => None,
+    };
+    let Some(bytes) = hex_proof.and_then(|hex| hex::decode(hex.trim_start_matches("0x")).ok())
+    else {
+        return;
+    };
+    fields.insert(
+        "proof".to_string(),
+        Value::Array(bytes.into_iter().map(Value::from).collect()),
+    );
+}
+
+/// The string encodings of a field element.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeltEncoding {
+    /// The value in decimal, e.g. `"255"`.
+    Decimal,
+    /// The value as `0x`-prefixed big-endian hex, as in the instances of `inspect_proof`.
+    Hex,
+    /// A JSON array of the four little-endian `u64` limbs of the value, e.g. `"[255,0,0,0]"`, as
+    /// written by older ezkl versions.
+    Limbs,
+}
+
+/// Converts a field element between the decimal, hex and limb encodings.
+///
+/// # Arguments
+///
+/// * `value` - A `String` containing the field element in the `from` encoding.
+/// * `from` - The encoding of `value`.
+/// * `to` - The encoding to convert `value` to.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The field element in the `to` encoding.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` if `value` is not in the `from` encoding, or is not
+///   smaller than the modulus of the field.
+#[cfg_attr(feature = "ffi", export)]
+pub fn convert_felt(
+    value: String,
+    from: FeltEncoding,
+    to: FeltEncoding,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(value);
+    let felt = match from {
+        FeltEncoding::Decimal => BigUint::parse_bytes(value.trim().as_bytes(), 10)
+            .ok_or_else(|| invalid_felt(&value, "not a decimal number"))
+            .and_then(|integer| felt_from_integer(&value, &integer))?,
+        FeltEncoding::Hex => {
+            let digits = value.trim().strip_prefix("0x").unwrap_or(value.trim());
+            BigUint::parse_bytes(digits.as_bytes(), 16)
+                .ok_or_else(|| invalid_felt(&value, "not a hex number"))
+                .and_then(|integer| felt_from_integer(&value, &integer))?
+        }
+        FeltEncoding::Limbs => {
+            let limbs: [u64; 4] = serde_json::from_str(&value)
+                .map_err(|_| invalid_felt(&value, "not an array of four u64 limbs"))?;
+            felt_from_limbs(&value, limbs)?
+        }
+    };
+    Ok(encode_felt(&felt, to))
+}
+
+/// Normalizes a field element given in any of the encodings found in witness and proof files to
+/// `0x`-prefixed big-endian hex.
+///
+/// The encoding is detected from the JSON value: a `0x`-prefixed string is hex, a string of
+/// digits or a number is decimal, and an array of four numbers is limbs.
+///
+/// # Arguments
+///
+/// * `value_json` - A `String` containing the JSON representation of the field element.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The field element as `0x`-prefixed big-endian hex.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` if the encoding is not recognized or the value is
+///   not smaller than the modulus of the field.
+#[cfg_attr(feature = "ffi", export)]
+pub fn normalize_felt(value_json: String) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(value_json);
+    let value: Value = serde_json::from_str(&value_json)
+        .map_err(|_| invalid_felt(&value_json, "not valid JSON"))?;
+    let (value, from) =
+        match value {
+            Value::String(text) if text.trim().starts_with("0x") => (text, FeltEncoding::Hex),
+            Value::String(text) => (text, FeltEncoding::Decimal),
+            Value::Number(number) if number.is_u64() => (number.to_string(), FeltEncoding::Decimal),
+            Value::Array(_) => (value.to_string(), FeltEncoding::Limbs),
+            _ => return Err(invalid_felt(
+                &value_json,
+                "expected a hex or decimal string, an unsigned integer or an array of four limbs",
+            )),
+        };
+    convert_felt(value, from, FeltEncoding::Hex)
+}
+
+/// Parses a field element given as `0x`-prefixed big-endian hex, or as the 64 digit
+/// little-endian hex of its representation, as in the outputs of witness files.
+pub(crate) fn parse_felt(value: &str) -> Result<Fr, ExternalEZKLError> {
+    let value = value.trim();
+    if let Some(digits) = value.strip_prefix("0x") {
+        let integer = BigUint::parse_bytes(digits.as_bytes(), 16)
+            .ok_or_else(|| invalid_felt(value, "not a hex number"))?;
+        return felt_from_integer(value, &integer);
+    }
+    match hex::decode(value) {
+        Ok(bytes) if bytes.len() == 32 => felt_from_integer(value, &BigUint::from_bytes_le(&bytes)),
+        _ => Err(invalid_felt(
+            value,
+            "expected `0x`-prefixed hex or the 64 digit little-endian hex of a witness file",
+        )),
+    }
+}
+
+fn invalid_felt(value: &str, reason: &str) -> ExternalEZKLError {
+    ExternalEZKLError::InvalidInput(format!("invalid field element `{}`: {}", value, reason))
+}
+
+fn felt_from_integer(value: &str, integer: &BigUint) -> Result<Fr, ExternalEZKLError> {
+    let bytes = integer.to_bytes_le();
+    if bytes.len() > 32 {
+        return Err(invalid_felt(value, "not smaller than the field modulus"));
+    }
+    let mut repr = <Fr as PrimeField>::Repr::default();
+    repr.as_mut()[..bytes.len()].copy_from_slice(&bytes);
+    Option::from(Fr::from_repr(repr))
+        .ok_or_else(|| invalid_felt(value, "not smaller than the field modulus"))
+}
+
+fn felt_from_limbs(value: &str, limbs: [u64; 4]) -> Result<Fr, ExternalEZKLError> {
+    let bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+    felt_from_integer(value, &BigUint::from_bytes_le(&bytes))
+}
+
+pub(crate) fn encode_felt(felt: &Fr, encoding: FeltEncoding) -> String {
+    let repr = felt.to_repr();
+    match encoding {
+        FeltEncoding::Decimal => BigUint::from_bytes_le(repr.as_ref()).to_str_radix(10),
+        FeltEncoding::Hex => format!("{:?}", felt),
+        FeltEncoding::Limbs => {
+            let limbs: Vec<u64> = repr
+                .as_ref()
+                .chunks(8)
+                .map(|chunk| u64::from_le_bytes(chunk.try_into().expect("8 byte chunk")))
+                .collect();
+            Value::from(limbs).to_string()
+        }
+    }
+}
@@ -0,0 +1,20 @@
+/// A stage of a long running call reporting its progress: the stages of key generation, in the
+/// order they are run, and the download of `fetch_artifact`.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyGenStage {
+    LoadCircuit,
+    LoadSrs,
+    CreateKeys,
+    WriteVk,
+    WritePk,
+    Download,
+}
+
+/// A listener implemented by the host application, receiving the progress of long running calls.
+#[cfg_attr(feature = "ffi", uniffi::export(with_foreign))]
+pub trait ProgressListener: Send + Sync {
+    /// Called when a stage makes progress. `completed` reaches `total` when the stage ends; stages
+    /// without finer grained progress only report their end, with both set to 1.
+    fn on_progress(&self, stage: KeyGenStage, completed: u64, total: u64);
+}
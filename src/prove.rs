@@ -1,4 +1,20 @@
-use crate::serialization::{deserialize_circuit, deserialize_params_prover, deserialize_pk};
+use crate::clock::Instant;
+use crate::compression::{json_output, json_text};
+use crate::diagnostics::CallScope;
+use crate::executor::{run_on_worker, WorkKind};
+use crate::gen_witness::{PolycommitSrs, WitnessGenerator, WitnessOptions};
+use crate::inspect::{CommitmentsWrapper, ProofSplitCommitWrapper, TranscriptTypeWrapper};
+use crate::limits::{check_input_sizes, ProvingPermit, TimeBudget};
+use crate::logging::info;
+use crate::sensitive::Sensitive;
+use crate::serialization::{
+    deserialize_circuit, deserialize_json, deserialize_params_prover, deserialize_pk,
+    serialize_proof,
+};
+use crate::settings::{check_logrows, check_variables, VariableBinding};
+use crate::srs::{stitch_srs, SrsUsage};
+use crate::trace::Interval;
+use crate::verify::{verify_with_params, VerifierParamsRef};
 use crate::ExternalEZKLError;
 use crate::{IPAAccumulatorStrategy, IPASingleStrategy, KZGAccumulatorStrategy, KZGSingleStrategy};
 use ezkl::circuit::CheckMode;
@@ -7,21 +23,80 @@ use ezkl::pfsys::evm::aggregation_kzg::PoseidonTranscript;
 use ezkl::pfsys::{
     create_proof_circuit, ProofSplitCommit, ProofType, Snark, StrategyType, TranscriptType,
 };
-use ezkl::{Commitments, EZKLError as InnerEZKLError};
+use ezkl::Commitments;
 use halo2_proofs::halo2curves::bn256::{Bn256, Fr, G1Affine};
-use halo2_proofs::plonk::Circuit;
-use halo2_proofs::poly::ipa::commitment::IPACommitmentScheme;
+use halo2_proofs::plonk::{Circuit, ProvingKey, VerifyingKey};
+use halo2_proofs::poly::ipa::commitment::{IPACommitmentScheme, ParamsIPA};
 use halo2_proofs::poly::ipa::multiopen::{ProverIPA, VerifierIPA};
-use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
 use halo2_proofs::poly::kzg::multiopen::{ProverSHPLONK, VerifierSHPLONK};
 use snark_verifier::loader::native::NativeLoader;
 use snark_verifier::system::halo2::transcript::evm::EvmTranscript;
 use snark_verifier::system::halo2::{compile, Config};
+use snark_verifier::verifier::plonk::PlonkProtocol;
+use std::borrow::Borrow;
+use std::sync::Mutex;
+use std::time::Duration;
+#[cfg(feature = "ffi")]
 use uniffi::export;
 
+/// The options of a proving call.
+///
+/// The simple proving functions use the values returned by [`default_prove_options`]. Every field
+/// has a default in the Swift bindings; uniffi can't declare the defaults of the enum fields, so
+/// `gen-bindings` adds them.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProveOptions {
+    /// The proof type. `Single` proofs use the EVM transcript and can be verified on-chain,
+    /// `ForAggr` proofs use the Poseidon transcript and can be aggregated.
+    pub proof_type: ProofTypeWrapper,
+    /// The check mode. `Safe` checks the constraints of the circuit before proving, `Unchecked`
+    /// skips the checks, which is only useful for debugging.
+    pub check_mode: CheckModeWrapper,
+    /// An SRS extension created by `split_srs`, in which case the SRS passed to the call is the
+    /// matching verifier SRS.
+    #[cfg_attr(feature = "ffi", uniffi(default = None))]
+    pub srs_extension: Option<Vec<u8>>,
+    /// The values the variables of the circuit, such as `batch_size`, are expected to have been
+    /// compiled with. The call fails if one differs, as the compiled circuit cannot be resized.
+    #[cfg_attr(feature = "ffi", uniffi(default = []))]
+    pub expected_variables: Vec<VariableBinding>,
+    /// The time the call may take in milliseconds, including waiting for a proving slot, or `None`
+    /// for no limit. The budget is checked between the stages of proving, so a call exceeding it
+    /// fails with `TimedOut` once the running stage completes.
+    #[cfg_attr(feature = "ffi", uniffi(default = None))]
+    pub time_budget_ms: Option<u64>,
+    /// If `true`, the detailed proving calls return the proof gzip-compressed in `proof_gzip`
+    /// instead of as a JSON string.
+    #[cfg_attr(feature = "ffi", uniffi(default = false))]
+    pub compress_output: bool,
+    /// A Verification Key (VK) in binary form, such as the one shipped with the app, to verify
+    /// the proof against before it is returned. The call fails with `ExternalVerificationFailed`
+    /// if the proof doesn't verify against it.
+    #[cfg_attr(feature = "ffi", uniffi(default = None))]
+    pub verify_with: Option<Vec<u8>>,
+    /// The location of the split output commitments to record in the proof, as reported by
+    /// `inspect_proof`, or `None` to derive it from the witness. The call fails with
+    /// `InvalidInput` if the witness locates the output commitments elsewhere.
+    #[cfg_attr(feature = "ffi", uniffi(default = None))]
+    pub split_commit: Option<ProofSplitCommitWrapper>,
+}
+
+/// Returns the options used by `prove` and the other proving functions without options.
+///
+/// # Returns
+///
+/// * `ProveOptions` - A `Single` proof, using the EVM transcript, proven in `Safe` mode.
+#[cfg_attr(feature = "ffi", export)]
+pub fn default_prove_options() -> ProveOptions {
+    ProveOptions::default()
+}
+
 /// Proves a circuit using the provided witness, compiled circuit, proving key, and SRS.
 ///
-/// This function abstracts away configuration details by using default proving configurations.
+/// This function abstracts away configuration details by using the options returned by
+/// [`default_prove_options`].
 ///
 /// # Arguments
 ///
@@ -34,7 +109,7 @@ use uniffi::export;
 ///
 /// * `Ok(String)` - The generated proof as a JSON `String`.
 /// * `Err(ExternalEZKLError)` - An error that occurred during the proving process.
-#[export]
+#[cfg_attr(feature = "ffi", export)]
 pub fn prove(
     witness_json: String,
     compiled_circuit: Vec<u8>,
@@ -46,11 +121,43 @@ pub fn prove(
         compiled_circuit,
         pk,
         srs,
-        ProofTypeWrapper::Single,
-        CheckModeWrapper::SAFE,
+        ProveOptions::default(),
     )
 }
 
+/// Proves a circuit like [`prove_advanced`], on a worker thread instead of the calling thread.
+///
+/// The worker thread is named `ezkl-prove` and runs with the `prove_thread_priority` of the
+/// configuration, `Utility` by default, so that long proofs are neither throttled nor compete
+/// with the UI.
+///
+/// # Arguments
+///
+/// * `witness_json` - A `String` containing the JSON representation of the witness generated for the circuit input.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `options` - A `ProveOptions` record with the proof type, check mode, SRS extension and expected variables to prove with.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated proof as a JSON `String`.
+/// * `Err(ExternalEZKLError)` - An error that occurred during the proving process.
+#[cfg_attr(feature = "ffi", export)]
+pub async fn prove_async(
+    witness_json: String,
+    compiled_circuit: Vec<u8>,
+    pk: Vec<u8>,
+    srs: Vec<u8>,
+    options: ProveOptions,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(witness_json, compiled_circuit, pk, srs);
+    run_on_worker(WorkKind::Prove, move || {
+        prove_advanced(witness_json, compiled_circuit, pk, srs, options)
+    })
+    .await
+}
+
 /// Proves a circuit using the provided witness, compiled circuit, proving key, and SRS.
 ///
 /// This function is used for advanced proving configurations.
@@ -61,36 +168,353 @@ pub fn prove(
 /// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
 /// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
 /// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
-/// * `proof_type` - A `ProofTypeWrapper` enum value representing the proof type to be used for proving. Default is `Single`. For aggregation proofs, use `ForAggr`.
-/// * `check_mode` - A `CheckModeWrapper` enum value representing the check mode to be used for proving. Default is `SAFE`. For unsafe proving useful for debugging, use `UNSAFE`.
+/// * `options` - A `ProveOptions` record with the proof type, check mode, SRS extension and expected variables to prove with.
 ///
 /// # Returns
 ///
 /// * `Ok(String)` - The generated proof as a JSON `String`.
 /// * `Err(ExternalEZKLError)` - An error that occurred during the proving process.
-#[export]
+#[cfg_attr(feature = "ffi", export)]
 pub fn prove_advanced(
     witness_json: String,
     compiled_circuit: Vec<u8>,
     pk: Vec<u8>,
     srs: Vec<u8>,
-    proof_type: ProofTypeWrapper,
-    check_mode: CheckModeWrapper,
+    options: ProveOptions,
 ) -> Result<String, ExternalEZKLError> {
-    let proof = prove_internal(
+    check_input_sizes!(witness_json, compiled_circuit, pk, srs);
+    if options.compress_output {
+        return Err(ExternalEZKLError::InvalidInput(
+            "`compress_output` is only supported by `prove_detailed`, which returns the compressed proof".to_string(),
+        ));
+    }
+    // Started before waiting for a proving slot, which counts towards the budget
+    let budget = TimeBudget::start(options.time_budget_ms);
+    let _permit = ProvingPermit::acquire()?;
+    let snark = prove_internal(
         witness_json,
         &compiled_circuit,
         &pk,
         Some(&srs),
-        proof_type.into(),
-        check_mode.into(),
+        &options,
+        &budget,
+    )?;
+
+    serialize_proof(&snark)
+}
+
+/// Metrics collected while generating a proof.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct ProveMetrics {
+    /// The total time spent proving, in milliseconds.
+    pub elapsed_ms: u64,
+    /// The part of `elapsed_ms` spent deserializing the circuit, proving key and SRS.
+    pub load_ms: u64,
+    /// The peak number of bytes allocated during the call, if diagnostics are enabled.
+    pub peak_memory_bytes: Option<u64>,
+    /// The commitment scheme of the proven circuit.
+    pub commitment: CommitmentsWrapper,
+    /// The part of `elapsed_ms` spent compiling the protocol of a `ForAggr` proof, or `None` if
+    /// the call reused the protocol compiled by an earlier call of the session or did not need one.
+    pub protocol_compile_ms: Option<u64>,
+    /// The `k` of the given SRS.
+    pub srs_original_k: u32,
+    /// The `k` the SRS was used with, the logrows of the circuit.
+    pub srs_used_k: u32,
+    /// Whether the SRS was larger than the circuit needs and was downsized to `srs_used_k`; proofs
+    /// made with a downsized SRS verify with the original SRS.
+    pub downsized: bool,
+    /// Whether the proof was verified before it was returned.
+    pub post_prove_verified: bool,
+    /// The verification key the proof was verified against before it was returned.
+    pub verified_against: VerifiedAgainst,
+}
+
+/// The verification key a proof was verified against before a proving call returned it.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifiedAgainst {
+    /// The vk of the proving key, which `Safe` mode verifies every proof against. It shows the
+    /// proof is valid, not that it verifies against a vk shipped separately from the proving key.
+    ProverVk,
+    /// The vk given in `verify_with` of the options.
+    ExternalVk,
+    /// The proof was not verified, as it was generated in `Unchecked` mode.
+    None,
+}
+
+/// The result of a detailed proving call.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct ProveResult {
+    /// The generated proof as a JSON `String`, empty if the options asked for a compressed proof.
+    pub proof_json: String,
+    /// The generated proof as gzip-compressed JSON, if the options asked for it.
+    pub proof_gzip: Option<Vec<u8>>,
+    /// The transcript the proof was created with, `EVM` for `Single` and `Poseidon` for `ForAggr` proofs.
+    pub transcript: TranscriptTypeWrapper,
+    /// Metrics collected while generating the proof.
+    pub metrics: ProveMetrics,
+}
+
+/// Proves a circuit using the provided witness, compiled circuit, proving key, and SRS, and reports metrics about the run.
+///
+/// Behaves like [`prove_advanced`], but additionally returns the time spent proving and, when
+/// enabled via `set_diagnostics`, the peak memory usage of the call.
+///
+/// # Arguments
+///
+/// * `witness_json` - A `String` containing the JSON representation of the witness generated for the circuit input.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `options` - A `ProveOptions` record with the proof type, check mode, SRS extension and expected variables to prove with.
+///
+/// # Returns
+///
+/// * `Ok(ProveResult)` - The generated proof and the metrics of the run.
+/// * `Err(ExternalEZKLError)` - An error that occurred during the proving process.
+#[cfg_attr(feature = "ffi", export)]
+pub fn prove_detailed(
+    witness_json: String,
+    compiled_circuit: Vec<u8>,
+    pk: Vec<u8>,
+    srs: Vec<u8>,
+    options: ProveOptions,
+) -> Result<ProveResult, ExternalEZKLError> {
+    check_input_sizes!(witness_json, compiled_circuit, pk, srs);
+    let budget = TimeBudget::start(options.time_budget_ms);
+    let data: GraphWitness = deserialize_json("witness_json", &witness_json)?;
+
+    let _permit = ProvingPermit::acquire()?;
+    prove_with_metrics(data, &options, &budget, || {
+        let start_time = Instant::now();
+        let prover = Prover::new(
+            &compiled_circuit,
+            &pk,
+            Some(&srs),
+            options.srs_extension.as_deref(),
+        )?;
+        Ok((prover, start_time.elapsed()))
+    })
+}
+
+/// Proves a parsed witness and reports the metrics of the run, for [`prove_detailed`] and the
+/// proving sessions.
+///
+/// The caller holds a proving slot; `load` returns the artifacts to prove with and the time it
+/// spent loading them, zero if they were already loaded.
+pub(crate) fn prove_with_metrics<P: Borrow<Prover>>(
+    data: GraphWitness,
+    options: &ProveOptions,
+    budget: &TimeBudget,
+    load: impl FnOnce() -> Result<(P, Duration), ExternalEZKLError>,
+) -> Result<ProveResult, ExternalEZKLError> {
+    let scope = CallScope::begin();
+    let start_time = Instant::now();
+
+    budget.check("load_artifacts")?;
+    let (prover, load_time) = load()?;
+    let prover = prover.borrow();
+    check_variables(prover.circuit(), &options.expected_variables)?;
+    let (mut snark, protocol_compile_time) = prover.prove_measured(
+        data,
+        options.proof_type.into(),
+        options.check_mode.into(),
+        budget,
+    )?;
+    record_split_commit(&mut snark, options.split_commit)?;
+    let verified_against = prover.post_prove_check(
+        &snark,
+        options.check_mode.into(),
+        options.verify_with.as_deref(),
+    )?;
+    let transcript = snark.transcript_type.into();
+    let (proof_json, proof_gzip) = json_output(serialize_proof(&snark)?, options.compress_output)?;
+
+    let metrics = ProveMetrics {
+        elapsed_ms: start_time.elapsed().as_millis() as u64,
+        load_ms: load_time.as_millis() as u64,
+        peak_memory_bytes: scope.peak_bytes(),
+        commitment: prover.commitment().into(),
+        protocol_compile_ms: protocol_compile_time.map(|time| time.as_millis() as u64),
+        srs_original_k: prover.srs_usage().original_k,
+        srs_used_k: prover.srs_usage().used_k,
+        downsized: prover.srs_usage().downsized(),
+        post_prove_verified: verified_against != VerifiedAgainst::None,
+        verified_against,
+    };
+    info!(
+        "proving took {} ms, of which loading took {} ms (peak memory: {:?} bytes)",
+        metrics.elapsed_ms, metrics.load_ms, metrics.peak_memory_bytes
     );
 
-    match proof {
-        Ok(snark) => serde_json::to_string(&snark).map_err(|e| e.into()),
-        Err(e) => Err(e),
+    Ok(ProveResult {
+        proof_json,
+        proof_gzip,
+        transcript,
+        metrics,
+    })
+}
+
+/// Proves a circuit like [`prove_detailed`], taking the witness as plain or gzip-compressed JSON.
+///
+/// # Arguments
+///
+/// * `witness` - The JSON representation of the witness, either as UTF-8 text or gzip-compressed.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `options` - A `ProveOptions` record with the proof type, check mode, SRS extension and expected variables to prove with.
+///
+/// # Returns
+///
+/// * `Ok(ProveResult)` - The generated proof and the metrics of the run.
+/// * `Err(ExternalEZKLError)` - An error that occurred during the proving process.
+#[cfg_attr(feature = "ffi", export)]
+pub fn prove_bytes(
+    witness: Vec<u8>,
+    compiled_circuit: Vec<u8>,
+    pk: Vec<u8>,
+    srs: Vec<u8>,
+    options: ProveOptions,
+) -> Result<ProveResult, ExternalEZKLError> {
+    check_input_sizes!(witness, compiled_circuit, pk, srs);
+    let witness_json = json_text("witness", witness)?;
+    prove_detailed(witness_json, compiled_circuit, pk, srs, options)
+}
+
+/// Generates a witness for the input data and proves it, deserializing the circuit and SRS once
+/// for both steps.
+///
+/// Behaves like `gen_witness` followed by [`prove_async`], but the witness
+/// never leaves Rust. Witness generation checks the `expected_variables` of the options, and proving
+/// runs on a worker thread like [`prove_async`].
+///
+/// # Arguments
+///
+/// * `input_json` - A `String` containing the JSON representation of the input data for the circuit.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `options` - A `ProveOptions` record with the proof type, check mode, SRS extension and expected variables to prove with.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated proof as a JSON `String`.
+/// * `Err(ExternalEZKLError)` - An error that occurred during witness generation or proving.
+#[cfg_attr(feature = "ffi", export)]
+pub async fn prove_from_input(
+    input_json: String,
+    compiled_circuit: Vec<u8>,
+    pk: Vec<u8>,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+    options: ProveOptions,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(input_json, compiled_circuit, pk, vk, srs);
+    let result =
+        prove_from_input_detailed(input_json, compiled_circuit, pk, vk, srs, options, false)
+            .await?;
+    Ok(result.proof_json)
+}
+
+/// The result of proving from input data.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct InputProofResult {
+    /// The generated proof as a JSON `String`.
+    pub proof_json: String,
+    /// The witness the proof was generated from as a JSON `String`, if it was requested.
+    pub witness_json: Option<String>,
+}
+
+/// Generates a witness and proves it like [`prove_from_input`], optionally returning the witness.
+///
+/// # Arguments
+///
+/// * `input_json` - A `String` containing the JSON representation of the input data for the circuit.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `options` - A `ProveOptions` record with the proof type, check mode, SRS extension and expected variables to prove with.
+/// * `return_witness` - If `true`, the witness is returned with the proof.
+///
+/// # Returns
+///
+/// * `Ok(InputProofResult)` - The generated proof, and the witness if it was requested.
+/// * `Err(ExternalEZKLError)` - An error that occurred during witness generation or proving.
+#[cfg_attr(feature = "ffi", export)]
+pub async fn prove_from_input_detailed(
+    input_json: String,
+    compiled_circuit: Vec<u8>,
+    pk: Vec<u8>,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+    options: ProveOptions,
+    return_witness: bool,
+) -> Result<InputProofResult, ExternalEZKLError> {
+    check_input_sizes!(input_json, compiled_circuit, pk, vk, srs);
+    if options.compress_output {
+        return Err(ExternalEZKLError::InvalidInput(
+            "`compress_output` is only supported by `prove_detailed`, which returns the compressed proof".to_string(),
+        ));
     }
-    .map_err(|e| e.into())
+    let budget = TimeBudget::start(options.time_budget_ms);
+    let permit = ProvingPermit::acquire()?;
+    let input_json = Sensitive::new(input_json);
+
+    budget.check("load_artifacts")?;
+    let prover = Prover::new(
+        &compiled_circuit,
+        &pk,
+        Some(&srs),
+        options.srs_extension.as_deref(),
+    )?;
+    // The serialized artifacts would otherwise be held for the whole call
+    drop(compiled_circuit);
+    drop(pk);
+    drop(srs);
+
+    let generator = WitnessGenerator::for_prover(&prover, &vk)?;
+    let witness_options = WitnessOptions {
+        expected_variables: options.expected_variables.clone(),
+        ..WitnessOptions::default()
+    };
+    let witness = generator.generate(&input_json, &witness_options).await?;
+    drop(generator);
+    drop(input_json);
+    let witness_json = if return_witness {
+        Some(witness.as_json()?)
+    } else {
+        None
+    };
+
+    let proof_json = run_on_worker(WorkKind::Prove, move || {
+        let _permit = permit;
+        let mut snark = prover.prove_within(
+            witness,
+            options.proof_type.into(),
+            options.check_mode.into(),
+            &budget,
+        )?;
+        record_split_commit(&mut snark, options.split_commit)?;
+        prover.post_prove_check(
+            &snark,
+            options.check_mode.into(),
+            options.verify_with.as_deref(),
+        )?;
+        serialize_proof(&snark)
+    })
+    .await?;
+
+    Ok(InputProofResult {
+        proof_json,
+        witness_json,
+    })
 }
 
 pub(crate) fn prove_internal(
@@ -98,48 +522,379 @@ pub(crate) fn prove_internal(
     compiled_circuit: &[u8],
     serialized_pk: &[u8],
     serialised_srs: Option<&[u8]>,
-    proof_type: ProofType,
-    check_mode: CheckMode,
-) -> Result<Snark<Fr, G1Affine>, InnerEZKLError> {
-    let data: GraphWitness = serde_json::from_str(&witness_json)?;
-    //
-    // match (witness_json, witness_path) {
-    //     (Some(json), None) =>
-    //     (None, Some(path)) => GraphWitness::from_path(path)?,
-    //     _ => {
-    //         return Err(InnerEZKLError::IoError(std::io::Error::new(
-    //             std::io::ErrorKind::InvalidInput,
-    //             "Witness must be provided in one of the two ways: json or path",
-    //         )))
-    //     }
-    // };
-
-    let mut circuit: GraphCircuit = deserialize_circuit(compiled_circuit)?;
-
-    circuit.load_graph_witness(&data)?;
-
-    let pretty_public_inputs = circuit.pretty_public_inputs(&data)?;
-    let public_inputs = circuit.prepare_public_inputs(&data)?;
-
-    let circuit_settings = circuit.settings().clone();
-
-    let strategy: StrategyType = proof_type.into();
-    let transcript: TranscriptType = proof_type.into();
-    let proof_split_commits: Option<ProofSplitCommit> = data.into();
-
-    let commitment = circuit_settings.run_args.commitment.into();
-    let logrows = circuit_settings.run_args.logrows;
-    // creates and verifies the proof
-    let mut snark = match commitment {
-        Commitments::KZG => {
-            let pk = deserialize_pk::<KZGCommitmentScheme<Bn256>, GraphCircuit>(
-                serialized_pk,
-                circuit.params(),
-            )?;
-
-            let params =
-                deserialize_params_prover::<KZGCommitmentScheme<Bn256>>(serialised_srs, logrows)?;
-            match strategy {
+    options: &ProveOptions,
+    budget: &TimeBudget,
+) -> Result<Snark<Fr, G1Affine>, ExternalEZKLError> {
+    let witness_json = Sensitive::new(witness_json);
+    let data: Sensitive<GraphWitness> =
+        Sensitive::new(deserialize_json("witness_json", &witness_json)?);
+    drop(witness_json);
+
+    prove_witness(
+        data,
+        compiled_circuit,
+        serialized_pk,
+        serialised_srs,
+        options,
+        budget,
+    )
+}
+
+/// Proves an already parsed witness, see [`prove_internal`].
+///
+/// The budget is started by the caller before it waits for a proving slot, so that the wait
+/// counts towards it.
+pub(crate) fn prove_witness(
+    data: Sensitive<GraphWitness>,
+    compiled_circuit: &[u8],
+    serialized_pk: &[u8],
+    serialised_srs: Option<&[u8]>,
+    options: &ProveOptions,
+    budget: &TimeBudget,
+) -> Result<Snark<Fr, G1Affine>, ExternalEZKLError> {
+    budget.check("load_artifacts")?;
+    let prover = Prover::new(
+        compiled_circuit,
+        serialized_pk,
+        serialised_srs,
+        options.srs_extension.as_deref(),
+    )?;
+    prove_loaded(&prover, data.into_inner(), options, budget)
+}
+
+/// Proves a parsed witness with already loaded artifacts, applying the options other than the
+/// SRS extension, which the prover was loaded with.
+pub(crate) fn prove_loaded(
+    prover: &Prover,
+    data: GraphWitness,
+    options: &ProveOptions,
+    budget: &TimeBudget,
+) -> Result<Snark<Fr, G1Affine>, ExternalEZKLError> {
+    check_variables(prover.circuit(), &options.expected_variables)?;
+    let mut snark = prover.prove_within(
+        data,
+        options.proof_type.into(),
+        options.check_mode.into(),
+        budget,
+    )?;
+    record_split_commit(&mut snark, options.split_commit)?;
+    prover.post_prove_check(
+        &snark,
+        options.check_mode.into(),
+        options.verify_with.as_deref(),
+    )?;
+    Ok(snark)
+}
+
+/// Records the split commitment requested by the options in a proof, checking it against the
+/// split derived from the witness, if any.
+pub(crate) fn record_split_commit(
+    snark: &mut Snark<Fr, G1Affine>,
+    requested: Option<ProofSplitCommitWrapper>,
+) -> Result<(), ExternalEZKLError> {
+    let Some(requested) = requested else {
+        return Ok(());
+    };
+    if let Some(derived) = snark.split.as_ref() {
+        let derived = ProofSplitCommitWrapper::from_split(derived)?;
+        if derived != requested {
+            return Err(ExternalEZKLError::InvalidInput(format!(
+                "the split commitment {}..{} does not match the witness, which places the output commitments at {}..{}",
+                requested.start, requested.end, derived.start, derived.end
+            )));
+        }
+    }
+    snark.split = Some(requested.to_split()?);
+    Ok(())
+}
+
+/// Moves the processed inputs, params and outputs, which locate the split commitments in the
+/// proof, into a new witness, leaving the private inputs and outputs to be wiped.
+///
+/// The polycommit commitments are the first points of the proof, ordered inputs, params, then
+/// outputs, so all three are needed: the split covers the output commitments and starts after
+/// the input and param ones. Circuits that do not commit to their outputs have no split.
+fn processed_only(witness: &mut GraphWitness) -> GraphWitness {
+    let mut processed = GraphWitness::new(vec![], vec![]);
+    processed.processed_inputs = witness.processed_inputs.take();
+    processed.processed_params = witness.processed_params.take();
+    processed.processed_outputs = witness.processed_outputs.take();
+    processed
+}
+
+/// The proving key and SRS of a circuit, for the commitment scheme of the circuit.
+#[allow(clippy::upper_case_acronyms)]
+enum ProvingArtifacts {
+    KZG {
+        pk: ProvingKey<G1Affine>,
+        params: ParamsKZG<Bn256>,
+    },
+    IPA {
+        pk: ProvingKey<G1Affine>,
+        params: ParamsIPA<G1Affine>,
+    },
+}
+
+/// A circuit, proving key and SRS deserialized once, to generate any number of proofs with.
+pub(crate) struct Prover {
+    circuit: GraphCircuit,
+    artifacts: ProvingArtifacts,
+    /// The protocol compiled for accumulator proofs, with the number of instances it was compiled for.
+    protocol: Mutex<Option<(usize, PlonkProtocol<G1Affine>)>>,
+    srs_usage: SrsUsage,
+}
+
+impl Prover {
+    /// Deserializes the artifacts needed for proving.
+    ///
+    /// A KZG SRS can be given as a verifier SRS plus an extension created by `split_srs`.
+    pub(crate) fn new(
+        compiled_circuit: &[u8],
+        serialized_pk: &[u8],
+        serialised_srs: Option<&[u8]>,
+        serialised_srs_extension: Option<&[u8]>,
+    ) -> Result<Self, ExternalEZKLError> {
+        let circuit: GraphCircuit = deserialize_circuit(compiled_circuit)?;
+        Self::from_circuit(
+            circuit,
+            serialized_pk,
+            serialised_srs,
+            serialised_srs_extension,
+        )
+    }
+
+    /// Deserializes the proving key and SRS for an already deserialized circuit.
+    pub(crate) fn from_circuit(
+        circuit: GraphCircuit,
+        serialized_pk: &[u8],
+        serialised_srs: Option<&[u8]>,
+        serialised_srs_extension: Option<&[u8]>,
+    ) -> Result<Self, ExternalEZKLError> {
+        let circuit_settings = circuit.settings();
+        check_logrows(circuit_settings)?;
+        let logrows = circuit_settings.run_args.logrows;
+        let commitment = Commitments::from(circuit_settings.run_args.commitment);
+
+        let stitched_srs = match (serialised_srs, serialised_srs_extension) {
+            (Some(srs), Some(extension)) if commitment == Commitments::KZG => {
+                Some(stitch_srs(srs, extension, logrows)?)
+            }
+            (_, Some(_)) => {
+                return Err(ExternalEZKLError::InvalidInput(
+                    "an SRS extension requires a KZG circuit and a verifier SRS".to_string(),
+                ))
+            }
+            _ => None,
+        };
+        let serialised_srs = stitched_srs.as_deref().or(serialised_srs);
+
+        let artifacts = match commitment {
+            Commitments::KZG => ProvingArtifacts::KZG {
+                pk: deserialize_pk::<KZGCommitmentScheme<Bn256>, GraphCircuit>(
+                    serialized_pk,
+                    circuit.params(),
+                )?,
+                params: deserialize_params_prover::<KZGCommitmentScheme<Bn256>>(
+                    serialised_srs,
+                    logrows,
+                )?,
+            },
+            Commitments::IPA => ProvingArtifacts::IPA {
+                pk: deserialize_pk::<IPACommitmentScheme<G1Affine>, GraphCircuit>(
+                    serialized_pk,
+                    circuit.params(),
+                )?,
+                params: deserialize_params_prover::<IPACommitmentScheme<G1Affine>>(
+                    serialised_srs,
+                    logrows,
+                )?,
+            },
+        };
+
+        // Loading the params succeeded, so the SRS was given
+        let srs_usage = SrsUsage::read(serialised_srs.unwrap_or_default(), logrows)?;
+
+        Ok(Prover {
+            circuit,
+            artifacts,
+            protocol: Mutex::new(None),
+            srs_usage,
+        })
+    }
+
+    /// The size of the SRS and the size it was downsized to for the circuit.
+    pub(crate) fn srs_usage(&self) -> SrsUsage {
+        self.srs_usage
+    }
+
+    /// Returns the commitment scheme of the circuit.
+    pub(crate) fn commitment(&self) -> Commitments {
+        Commitments::from(self.circuit.settings().run_args.commitment)
+    }
+
+    /// The circuit proofs are generated for.
+    pub(crate) fn circuit(&self) -> &GraphCircuit {
+        &self.circuit
+    }
+
+    /// A copy of the SRS, for witness generation to commit to polycommit visibility modules with.
+    pub(crate) fn polycommit_srs(&self) -> PolycommitSrs {
+        match &self.artifacts {
+            ProvingArtifacts::KZG { params, .. } => PolycommitSrs::KZG(params.clone()),
+            ProvingArtifacts::IPA { params, .. } => PolycommitSrs::IPA(params.clone()),
+        }
+    }
+
+    /// Verifies a proof against `verify_with`, such as the vk shipped with the app, if it is given,
+    /// and returns what the proof was verified against.
+    ///
+    /// Proofs generated in `Safe` mode are already verified against the vk of the proving key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(VerifiedAgainst)` - The vk the proof was verified against, if any.
+    /// * `Err(ExternalEZKLError)` - `ExternalVerificationFailed` if the proof doesn't verify
+    ///   against `verify_with`, or `verify_with` can't be read for the circuit.
+    pub(crate) fn post_prove_check(
+        &self,
+        snark: &Snark<Fr, G1Affine>,
+        check_mode: CheckMode,
+        verify_with: Option<&[u8]>,
+    ) -> Result<VerifiedAgainst, ExternalEZKLError> {
+        let Some(vk) = verify_with else {
+            return Ok(match check_mode {
+                CheckMode::SAFE => VerifiedAgainst::ProverVk,
+                CheckMode::UNSAFE => VerifiedAgainst::None,
+            });
+        };
+
+        let params = match &self.artifacts {
+            ProvingArtifacts::KZG { params, .. } => VerifierParamsRef::KZG(params),
+            ProvingArtifacts::IPA { params, .. } => VerifierParamsRef::IPA(params),
+        };
+        let verified = verify_with_params(
+            snark.clone(),
+            self.circuit.settings().clone(),
+            vk,
+            params,
+            false,
+        );
+        match verified {
+            Ok((Ok(()), _)) => Ok(VerifiedAgainst::ExternalVk),
+            Ok((Err(e), _)) => Err(ExternalEZKLError::ExternalVerificationFailed {
+                detail: e.to_string(),
+            }),
+            Err(ExternalEZKLError::LoadVk { detail }) => {
+                Err(ExternalEZKLError::ExternalVerificationFailed {
+                    detail: format!("the vk could not be read for the circuit: {}", detail),
+                })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The verification key of the proving key.
+    pub(crate) fn vk(&self) -> &VerifyingKey<G1Affine> {
+        match &self.artifacts {
+            ProvingArtifacts::KZG { pk, .. } | ProvingArtifacts::IPA { pk, .. } => pk.get_vk(),
+        }
+    }
+
+    /// Returns the protocol compiled for accumulator proofs with the given number of instances,
+    /// compiling it on first use, and the time spent compiling it if it was compiled by this call.
+    ///
+    /// Only the most recently compiled protocol is kept, so a different number of instances
+    /// replaces it.
+    fn protocol(
+        &self,
+        num_instance: usize,
+        compile: impl FnOnce() -> PlonkProtocol<G1Affine>,
+    ) -> Result<(PlonkProtocol<G1Affine>, Option<Duration>), ExternalEZKLError> {
+        let mut cached = self.protocol.lock().map_err(|_| {
+            ExternalEZKLError::InternalError("protocol cache lock poisoned".to_string())
+        })?;
+        match cached.as_ref() {
+            Some((cached_instances, protocol)) if *cached_instances == num_instance => {
+                Ok((protocol.clone(), None))
+            }
+            _ => {
+                let _interval = Interval::begin("compile_protocol");
+                let start_time = Instant::now();
+                let protocol = compile();
+                let compile_time = start_time.elapsed();
+                *cached = Some((num_instance, protocol.clone()));
+                Ok((protocol, Some(compile_time)))
+            }
+        }
+    }
+
+    /// Generates a proof for the given witness.
+    ///
+    /// A `Single` proof uses the EVM transcript and a `ForAggr` proof the Poseidon transcript, so
+    /// the same prover can generate proofs of both types in any order.
+    ///
+    /// The inputs and outputs of the witness are wiped once proving completes, if enabled.
+    pub(crate) fn prove(
+        &self,
+        data: GraphWitness,
+        proof_type: ProofType,
+        check_mode: CheckMode,
+    ) -> Result<Snark<Fr, G1Affine>, ExternalEZKLError> {
+        self.prove_within(data, proof_type, check_mode, &TimeBudget::start(None))
+    }
+
+    /// Generates a proof like [`Prover::prove`], checking the time budget before each stage.
+    pub(crate) fn prove_within(
+        &self,
+        data: GraphWitness,
+        proof_type: ProofType,
+        check_mode: CheckMode,
+        budget: &TimeBudget,
+    ) -> Result<Snark<Fr, G1Affine>, ExternalEZKLError> {
+        self.prove_measured(data, proof_type, check_mode, budget)
+            .map(|(snark, _)| snark)
+    }
+
+    /// Generates a proof like [`Prover::prove_within`], also returning the time spent compiling
+    /// the protocol of a `ForAggr` proof, if it was not compiled by an earlier call.
+    pub(crate) fn prove_measured(
+        &self,
+        data: GraphWitness,
+        proof_type: ProofType,
+        check_mode: CheckMode,
+        budget: &TimeBudget,
+    ) -> Result<(Snark<Fr, G1Affine>, Option<Duration>), ExternalEZKLError> {
+        let mut data = Sensitive::new(data);
+        budget.check("load_witness")?;
+        let mut circuit = self.circuit.clone();
+
+        let load_interval = Interval::begin("load_witness");
+        circuit.load_graph_witness(&data)?;
+
+        let pretty_public_inputs = circuit.pretty_public_inputs(&data)?;
+        let public_inputs = circuit.prepare_public_inputs(&data)?;
+        drop(load_interval);
+        // A witness of different dimensions would otherwise fail deep inside the transcript
+        let expected_instances: usize = circuit.settings().total_instances().iter().sum();
+        if public_inputs.len() != expected_instances {
+            return Err(ExternalEZKLError::InvalidInput(format!(
+                "the witness has {} public instances, but the circuit settings expect {}",
+                public_inputs.len(),
+                expected_instances
+            )));
+        }
+
+        let strategy: StrategyType = proof_type.into();
+        let transcript: TranscriptType = proof_type.into();
+        let proof_split_commits: Option<ProofSplitCommit> = processed_only(&mut data).into();
+
+        let commitment = circuit.settings().run_args.commitment.into();
+        let mut protocol_compile_time = None;
+        budget.check("create_proof")?;
+        let _interval = Interval::begin("create_proof");
+        // creates and verifies the proof
+        let mut snark = match &self.artifacts {
+            ProvingArtifacts::KZG { pk, params } => match strategy {
                 StrategyType::Single => create_proof_circuit::<
                     KZGCommitmentScheme<Bn256>,
                     _,
@@ -152,8 +907,8 @@ pub(crate) fn prove_internal(
                 >(
                     circuit,
                     vec![public_inputs],
-                    &params,
-                    &pk,
+                    params,
+                    pk,
                     check_mode,
                     commitment,
                     transcript,
@@ -161,11 +916,14 @@ pub(crate) fn prove_internal(
                     None,
                 ),
                 StrategyType::Accum => {
-                    let protocol = Some(compile(
-                        &params,
-                        pk.get_vk(),
-                        Config::kzg().with_num_instance(vec![public_inputs.len()]),
-                    ));
+                    let (protocol, compile_time) = self.protocol(public_inputs.len(), || {
+                        compile(
+                            params,
+                            pk.get_vk(),
+                            Config::kzg().with_num_instance(vec![public_inputs.len()]),
+                        )
+                    })?;
+                    protocol_compile_time = compile_time;
 
                     create_proof_circuit::<
                         KZGCommitmentScheme<Bn256>,
@@ -179,28 +937,17 @@ pub(crate) fn prove_internal(
                     >(
                         circuit,
                         vec![public_inputs],
-                        &params,
-                        &pk,
+                        params,
+                        pk,
                         check_mode,
                         commitment,
                         transcript,
                         proof_split_commits,
-                        protocol,
+                        Some(protocol),
                     )
                 }
-            }
-        }
-        Commitments::IPA => {
-            let pk = deserialize_pk::<IPACommitmentScheme<G1Affine>, GraphCircuit>(
-                serialized_pk,
-                circuit.params(),
-            )?;
-
-            let params = deserialize_params_prover::<IPACommitmentScheme<G1Affine>>(
-                serialised_srs,
-                circuit_settings.run_args.logrows,
-            )?;
-            match strategy {
+            },
+            ProvingArtifacts::IPA { pk, params } => match strategy {
                 StrategyType::Single => create_proof_circuit::<
                     IPACommitmentScheme<G1Affine>,
                     _,
@@ -213,8 +960,8 @@ pub(crate) fn prove_internal(
                 >(
                     circuit,
                     vec![public_inputs],
-                    &params,
-                    &pk,
+                    params,
+                    pk,
                     check_mode,
                     commitment,
                     transcript,
@@ -222,11 +969,14 @@ pub(crate) fn prove_internal(
                     None,
                 ),
                 StrategyType::Accum => {
-                    let protocol = Some(compile(
-                        &params,
-                        pk.get_vk(),
-                        Config::ipa().with_num_instance(vec![public_inputs.len()]),
-                    ));
+                    let (protocol, compile_time) = self.protocol(public_inputs.len(), || {
+                        compile(
+                            params,
+                            pk.get_vk(),
+                            Config::ipa().with_num_instance(vec![public_inputs.len()]),
+                        )
+                    })?;
+                    protocol_compile_time = compile_time;
                     create_proof_circuit::<
                         IPACommitmentScheme<G1Affine>,
                         _,
@@ -239,31 +989,29 @@ pub(crate) fn prove_internal(
                     >(
                         circuit,
                         vec![public_inputs],
-                        &params,
-                        &pk,
+                        params,
+                        pk,
                         check_mode,
                         commitment,
                         transcript,
                         proof_split_commits,
-                        protocol,
+                        Some(protocol),
                     )
                 }
-            }
-        }
-    }?;
+            },
+        }?;
 
-    snark.pretty_public_inputs = pretty_public_inputs;
+        snark.pretty_public_inputs = pretty_public_inputs;
 
-    // if let Some(proof_path) = proof_path {
-    //     snark.save(&proof_path)?;
-    // }
-
-    Ok(snark)
+        Ok((snark, protocol_compile_time))
+    }
 }
 
-#[derive(uniffi::Enum)]
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ProofTypeWrapper {
     // Single is the default mode, should mostly be used for production
+    #[default]
     Single,
     ForAggr,
 }
@@ -277,19 +1025,32 @@ impl From<ProofTypeWrapper> for ProofType {
     }
 }
 
-#[allow(clippy::upper_case_acronyms)]
-#[derive(uniffi::Enum)]
+// The variants are not named after `CheckMode::SAFE` and `CheckMode::UNSAFE`, as `unsafe` is a
+// keyword in Swift and would have to be escaped by every caller
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum CheckModeWrapper {
-    // SAFE is the default mode, should be always used for production
-    SAFE,
-    UNSAFE,
+    // Safe is the default mode, should be always used for production
+    #[default]
+    Safe,
+    // Unchecked skips the constraint checks, only useful for debugging
+    Unchecked,
 }
 
 impl From<CheckModeWrapper> for CheckMode {
     fn from(cm: CheckModeWrapper) -> Self {
         match cm {
-            CheckModeWrapper::SAFE => CheckMode::SAFE,
-            CheckModeWrapper::UNSAFE => CheckMode::UNSAFE,
+            CheckModeWrapper::Safe => CheckMode::SAFE,
+            CheckModeWrapper::Unchecked => CheckMode::UNSAFE,
+        }
+    }
+}
+
+impl From<CheckMode> for CheckModeWrapper {
+    fn from(cm: CheckMode) -> Self {
+        match cm {
+            CheckMode::SAFE => CheckModeWrapper::Safe,
+            CheckMode::UNSAFE => CheckModeWrapper::Unchecked,
         }
     }
 }
@@ -0,0 +1,247 @@
+#[cfg(feature = "prover")]
+use crate::gen_witness::{gen_witness_internal, WitnessOptions};
+use crate::io::Source;
+#[cfg(feature = "prover")]
+use crate::limits::{check_input_sizes, ProvingPermit, TimeBudget};
+#[cfg(feature = "prover")]
+use crate::prove::{prove_witness, ProveOptions};
+#[cfg(feature = "prover")]
+use crate::sensitive::Sensitive;
+use crate::serialization::deserialize_proof_from;
+#[cfg(feature = "prover")]
+use crate::serialization::{deserialize_json_from, serialize_proof};
+use crate::verify::verify_snark;
+use crate::ExternalEZKLError;
+#[cfg(feature = "prover")]
+use ezkl::graph::GraphWitness;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::Arc;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// Reads artifacts by name from app-managed storage, such as an encrypted container, for the
+/// `*_from_provider` functions.
+///
+/// Errors returned by the provider are reported with the name of the artifact being read.
+#[cfg_attr(feature = "ffi", uniffi::export(with_foreign))]
+pub trait ArtifactProvider: Send + Sync {
+    /// Returns the whole artifact.
+    fn read(&self, name: String) -> Result<Vec<u8>, ExternalEZKLError>;
+    /// Returns `len` bytes of the artifact starting at `offset`, or fewer at the end of the artifact.
+    fn read_range(&self, name: String, offset: u64, len: u64)
+        -> Result<Vec<u8>, ExternalEZKLError>;
+    /// Returns the size of the artifact in bytes.
+    fn size(&self, name: String) -> Result<u64, ExternalEZKLError>;
+}
+
+/// Names the artifact in an error returned by a provider.
+fn provider_error(name: &str, e: ExternalEZKLError) -> ExternalEZKLError {
+    ExternalEZKLError::InvalidInput(format!(
+        "the artifact provider failed to read `{}`: {}",
+        name, e
+    ))
+}
+
+/// Reads a whole artifact from a provider.
+pub(crate) fn read_artifact(
+    provider: &Arc<dyn ArtifactProvider>,
+    name: &str,
+) -> Result<Vec<u8>, ExternalEZKLError> {
+    provider
+        .read(name.to_string())
+        .map_err(|e| provider_error(name, e))
+}
+
+/// A reader over an artifact of a provider, reading it in ranges as it is consumed.
+pub(crate) struct ProviderReader {
+    provider: Arc<dyn ArtifactProvider>,
+    name: String,
+    position: u64,
+    size: u64,
+}
+
+impl ProviderReader {
+    pub(crate) fn open(
+        provider: Arc<dyn ArtifactProvider>,
+        name: &str,
+    ) -> std::io::Result<ProviderReader> {
+        let size = provider
+            .size(name.to_string())
+            .map_err(|e| std::io::Error::other(provider_error(name, e).to_string()))?;
+        Ok(ProviderReader {
+            provider,
+            name: name.to_string(),
+            position: 0,
+            size,
+        })
+    }
+}
+
+impl Read for ProviderReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = self.size.saturating_sub(self.position);
+        let len = remaining.min(buf.len() as u64);
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let bytes = self
+            .provider
+            .read_range(self.name.clone(), self.position, len)
+            .map_err(|e| std::io::Error::other(provider_error(&self.name, e).to_string()))?;
+        if bytes.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!(
+                    "the artifact provider returned no bytes for `{}` at offset {} of {}",
+                    self.name, self.position, self.size
+                ),
+            ));
+        }
+        let read = bytes.len().min(len as usize);
+        buf[..read].copy_from_slice(&bytes[..read]);
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for ProviderReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.size.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        self.position = position.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+/// Generates a witness like `gen_witness`, reading the circuit, vk and SRS from an artifact provider.
+///
+/// # Arguments
+///
+/// * `input_json` - A `String` containing the JSON representation of the input data for the circuit.
+/// * `provider` - The `ArtifactProvider` to read the artifacts from.
+/// * `circuit_name` - The name of the compiled circuit.
+/// * `vk_name` - The name of the Verification Key (VK).
+/// * `srs_name` - The name of the Structured Reference String (SRS).
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated witness as a JSON `String`.
+/// * `Err(ExternalEZKLError)` - If the provider failed to read an artifact, or an error that occurred during witness generation.
+#[cfg(feature = "prover")]
+#[cfg_attr(feature = "ffi", export)]
+pub async fn gen_witness_from_provider(
+    input_json: String,
+    provider: Arc<dyn ArtifactProvider>,
+    circuit_name: String,
+    vk_name: String,
+    srs_name: String,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(input_json);
+    let compiled_circuit = read_artifact(&provider, &circuit_name)?;
+    let vk = read_artifact(&provider, &vk_name)?;
+    let srs = read_artifact(&provider, &srs_name)?;
+
+    let witness = gen_witness_internal(
+        &compiled_circuit,
+        input_json,
+        Some(&vk),
+        Some(&srs),
+        &WitnessOptions::default(),
+    )
+    .await?;
+    Ok(witness.as_json()?)
+}
+
+/// Generates a proof like `prove`, reading the witness, circuit, pk and SRS from an artifact provider.
+///
+/// The witness is parsed while it is read in ranges, so the whole witness JSON is never held in memory.
+///
+/// # Arguments
+///
+/// * `provider` - The `ArtifactProvider` to read the artifacts from.
+/// * `witness_name` - The name of the witness JSON generated for the circuit input.
+/// * `circuit_name` - The name of the compiled circuit.
+/// * `pk_name` - The name of the Proving Key (PK).
+/// * `srs_name` - The name of the Structured Reference String (SRS).
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated proof as a JSON `String`.
+/// * `Err(ExternalEZKLError)` - If the provider failed to read an artifact, or an error that occurred during proving.
+#[cfg(feature = "prover")]
+#[cfg_attr(feature = "ffi", export)]
+pub fn prove_from_provider(
+    provider: Arc<dyn ArtifactProvider>,
+    witness_name: String,
+    circuit_name: String,
+    pk_name: String,
+    srs_name: String,
+) -> Result<String, ExternalEZKLError> {
+    let data: Sensitive<GraphWitness> = Sensitive::new(deserialize_json_from(
+        "witness_name",
+        Source::Provider {
+            provider: &provider,
+            name: &witness_name,
+        },
+    )?);
+    let compiled_circuit = read_artifact(&provider, &circuit_name)?;
+    let pk = read_artifact(&provider, &pk_name)?;
+    let srs = read_artifact(&provider, &srs_name)?;
+
+    let options = ProveOptions::default();
+    let budget = TimeBudget::start(options.time_budget_ms);
+    let _permit = ProvingPermit::acquire()?;
+    let snark = prove_witness(data, &compiled_circuit, &pk, Some(&srs), &options, &budget)?;
+    serialize_proof(&snark)
+}
+
+/// Verifies a proof like `verify`, reading the proof, settings, vk and SRS from an artifact provider.
+///
+/// # Arguments
+///
+/// * `provider` - The `ArtifactProvider` to read the artifacts from.
+/// * `proof_name` - The name of the proof JSON.
+/// * `settings_name` - The name of the circuit settings JSON.
+/// * `vk_name` - The name of the Verification Key (VK).
+/// * `srs_name` - The name of the Structured Reference String (SRS).
+///
+/// # Returns
+///
+/// * `Ok(bool)` - `true` if the proof is valid.
+/// * `Err(ExternalEZKLError)` - If the provider failed to read an artifact, or an error that occurred during verification.
+#[cfg_attr(feature = "ffi", export)]
+pub fn verify_from_provider(
+    provider: Arc<dyn ArtifactProvider>,
+    proof_name: String,
+    settings_name: String,
+    vk_name: String,
+    srs_name: String,
+) -> Result<bool, ExternalEZKLError> {
+    let proof = deserialize_proof_from(
+        "proof_name",
+        Source::Provider {
+            provider: &provider,
+            name: &proof_name,
+        },
+    )?;
+    let settings_json =
+        String::from_utf8(read_artifact(&provider, &settings_name)?).map_err(|e| {
+            ExternalEZKLError::InvalidInput(format!(
+                "`{}` from the artifact provider is not UTF-8: {}",
+                settings_name, e
+            ))
+        })?;
+    let vk = read_artifact(&provider, &vk_name)?;
+    let srs = read_artifact(&provider, &srs_name)?;
+
+    verify_snark(proof, settings_json, &vk, Some(&srs), false, false)?.into_validity()
+}
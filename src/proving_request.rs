@@ -0,0 +1,128 @@
+use crate::fingerprint::{check, of_circuit, of_settings};
+use crate::limits::{check_input_sizes, ProvingPermit};
+use crate::logging::warn;
+use crate::prove::Prover;
+use crate::serialization::{deserialize_circuit, deserialize_json, serialize_proof};
+use crate::ExternalEZKLError;
+use ezkl::circuit::CheckMode;
+use ezkl::graph::GraphWitness;
+use ezkl::pfsys::ProofType;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The version of the proving request format written by [`export_proving_request`].
+const PROVING_REQUEST_VERSION: u32 = 1;
+
+/// A witness together with the identifiers of the circuit it was generated for, to prove it on
+/// another device.
+#[derive(Serialize, Deserialize)]
+struct ProvingRequest {
+    version: u32,
+    /// The version of the bindings that generated the witness.
+    crate_version: String,
+    /// The fingerprint of the compiled circuit, as returned by `circuit_fingerprint`.
+    circuit_fingerprint: String,
+    /// The fingerprint of the circuit settings.
+    settings_hash: String,
+    /// The witness, embedded verbatim.
+    witness: Box<RawValue>,
+}
+
+/// Packs a witness with the fingerprints of the circuit it was generated for, so that it can be
+/// proven on another device with [`prove_request`].
+///
+/// This splits proving across two devices: the witness is generated where the private input
+/// lives, and the proof is created on a more powerful paired device. The witness contains the
+/// private inputs and outputs, so the request must be sent over a channel you trust.
+///
+/// # Arguments
+///
+/// * `witness_json` - A `String` containing the JSON representation of the witness.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit the witness was generated for.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The JSON representation of the proving request.
+/// * `Err(ExternalEZKLError)` - If the witness or the circuit could not be parsed.
+#[cfg_attr(feature = "ffi", export)]
+pub fn export_proving_request(
+    witness_json: String,
+    compiled_circuit: Vec<u8>,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(witness_json, compiled_circuit);
+    // Only checked to be a witness, it is embedded as is
+    let _: GraphWitness = deserialize_json("witness_json", &witness_json)?;
+    let circuit = deserialize_circuit(&compiled_circuit)?;
+
+    let request = ProvingRequest {
+        version: PROVING_REQUEST_VERSION,
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        circuit_fingerprint: of_circuit(&circuit)?,
+        settings_hash: of_settings(circuit.settings())?,
+        witness: deserialize_json("witness_json", &witness_json)?,
+    };
+    Ok(serde_json::to_string(&request)?)
+}
+
+/// Proves the witness of a request created by [`export_proving_request`], after checking that
+/// the request was made for the supplied circuit.
+///
+/// Requests made by a different version of the bindings are accepted with a warning, as long as
+/// the circuit fingerprints match.
+///
+/// # Arguments
+///
+/// * `request_json` - A `String` containing the JSON representation of the proving request.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated proof as a JSON `String`.
+/// * `Err(ExternalEZKLError)` - `FingerprintMismatch` if the request was made for a different
+///   circuit or settings, or an error that occurred during proving.
+#[cfg_attr(feature = "ffi", export)]
+pub fn prove_request(
+    request_json: String,
+    compiled_circuit: Vec<u8>,
+    pk: Vec<u8>,
+    srs: Vec<u8>,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(request_json, compiled_circuit, pk, srs);
+    let request: ProvingRequest = deserialize_json("request_json", &request_json)?;
+    drop(request_json);
+    if request.version != PROVING_REQUEST_VERSION {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "unsupported proving request version {}, expected {}",
+            request.version, PROVING_REQUEST_VERSION
+        )));
+    }
+    if request.crate_version != env!("CARGO_PKG_VERSION") {
+        warn!(
+            "the proving request was made by version {} of the bindings, proving with {}",
+            request.crate_version,
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+
+    let _permit = ProvingPermit::acquire()?;
+    let prover = Prover::new(&compiled_circuit, &pk, Some(&srs), None)?;
+    check(
+        "circuit",
+        Some(&request.circuit_fingerprint),
+        &of_circuit(prover.circuit())?,
+    )?;
+    check(
+        "settings",
+        Some(&request.settings_hash),
+        &of_settings(prover.circuit().settings())?,
+    )?;
+
+    let data: GraphWitness = deserialize_json("request_json", request.witness.get())?;
+    drop(request);
+    let snark = prover.prove(data, ProofType::Single, CheckMode::SAFE)?;
+    serialize_proof(&snark)
+}
@@ -0,0 +1,110 @@
+use crate::gen_witness::deserialize_graph_data;
+use crate::limits::check_input_sizes;
+use crate::serialization::deserialize_circuit;
+use crate::ExternalEZKLError;
+use ezkl::fieldutils::felt_to_integer_rep;
+use ezkl::graph::input::{DataSource, FileSourceInner};
+use ezkl::graph::scale_to_multiplier;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The precision lost by quantizing the inputs of a circuit.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct QuantizationReport {
+    /// The quantization of each input, in the order of the circuit inputs.
+    pub inputs: Vec<InputQuantization>,
+}
+
+/// The precision lost by quantizing a single input tensor.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct InputQuantization {
+    /// The fixed-point scale the input is quantized with.
+    pub scale: i32,
+    /// The largest absolute difference between a value and its requantized value.
+    pub max_abs_error: f64,
+    /// The mean absolute difference between the values and their requantized values.
+    pub mean_abs_error: f64,
+    /// The values as seen by the circuit, i.e. quantized and dequantized again.
+    pub requantized: Vec<f64>,
+}
+
+/// Quantizes the inputs with the input scales of a circuit and dequantizes them again, to show
+/// the precision lost before proving.
+///
+/// The forward pass is not run, so this is cheap enough to call while the user edits the input.
+///
+/// # Arguments
+///
+/// * `input_json` - A `String` containing the JSON representation of the input data for the circuit.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+///
+/// # Returns
+///
+/// * `Ok(QuantizationReport)` - The requantized values and the quantization error of each input.
+/// * `Err(ExternalEZKLError)` - If the input could not be parsed, references a remote data source,
+///   or does not have as many inputs as the circuit.
+#[cfg_attr(feature = "ffi", export)]
+pub fn quantize_preview(
+    input_json: String,
+    compiled_circuit: Vec<u8>,
+) -> Result<QuantizationReport, ExternalEZKLError> {
+    check_input_sizes!(input_json, compiled_circuit);
+    let data = deserialize_graph_data(&input_json)?;
+    let circuit = deserialize_circuit(&compiled_circuit)?;
+    let scales = &circuit.settings().model_input_scales;
+
+    let DataSource::File(rows) = &data.input_data else {
+        return Err(ExternalEZKLError::InvalidInput(
+            "only inline input data can be previewed".to_string(),
+        ));
+    };
+    if rows.len() != scales.len() {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the input has {} tensors, but the circuit has {} inputs",
+            rows.len(),
+            scales.len()
+        )));
+    }
+
+    let inputs = rows
+        .iter()
+        .zip(scales)
+        .map(|(values, &scale)| quantize(values, scale))
+        .collect();
+    Ok(QuantizationReport { inputs })
+}
+
+/// Round-trips the values of one input through the fixed-point representation at `scale`.
+fn quantize(values: &[FileSourceInner], scale: i32) -> InputQuantization {
+    let multiplier = scale_to_multiplier(scale);
+    let mut max_abs_error: f64 = 0.0;
+    let mut total_abs_error = 0.0;
+    let requantized: Vec<f64> = values
+        .iter()
+        .map(|value| match value {
+            FileSourceInner::Float(value) => {
+                let requantized = (value * multiplier).round() / multiplier;
+                let error = (requantized - value).abs();
+                max_abs_error = max_abs_error.max(error);
+                total_abs_error += error;
+                requantized
+            }
+            // Booleans and field elements are taken as already quantized values
+            FileSourceInner::Bool(value) => f64::from(u8::from(*value)) / multiplier,
+            FileSourceInner::Field(value) => felt_to_integer_rep(*value) as f64 / multiplier,
+        })
+        .collect();
+
+    InputQuantization {
+        scale,
+        max_abs_error,
+        mean_abs_error: if values.is_empty() {
+            0.0
+        } else {
+            total_abs_error / values.len() as f64
+        },
+        requantized,
+    }
+}
@@ -0,0 +1,128 @@
+use crate::gen_witness::{WitnessGenerator, WitnessOptions};
+use crate::limits::check_input_sizes;
+use crate::sensitive::Sensitive;
+use crate::ExternalEZKLError;
+use ezkl::graph::input::{DataSource, FileSourceInner, GraphData};
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The element type of a raw tensor, stored little-endian.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DType {
+    /// 32-bit floats.
+    F32,
+    /// 64-bit floats.
+    F64,
+    /// 64-bit signed integers, converted to `f64`, so values beyond ±2⁵³ lose precision.
+    I64,
+    /// Bytes, e.g. the channels of an 8-bit image.
+    U8,
+}
+
+impl DType {
+    /// The size of one element in bytes.
+    fn size(self) -> usize {
+        match self {
+            DType::F32 => 4,
+            DType::F64 | DType::I64 => 8,
+            DType::U8 => 1,
+        }
+    }
+
+    /// Reads one little-endian element of this type.
+    fn read(self, bytes: &[u8]) -> f64 {
+        match self {
+            DType::F32 => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            DType::F64 => f64::from_le_bytes(bytes.try_into().unwrap()),
+            DType::I64 => i64::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            DType::U8 => bytes[0] as f64,
+        }
+    }
+}
+
+/// An input tensor in raw form, e.g. the pixels of a camera frame or the samples of an audio
+/// buffer, passed without encoding it as JSON.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct RawTensor {
+    /// The dimensions of the tensor, in row-major order.
+    pub shape: Vec<u64>,
+    /// The element type of `data`.
+    pub dtype: DType,
+    /// The elements of the tensor, little-endian and in row-major order.
+    pub data: Vec<u8>,
+}
+
+/// Converts raw input tensors into the input data of a circuit, as if they were given as JSON
+/// floats.
+pub(crate) fn raw_graph_data(inputs: Vec<RawTensor>) -> Result<GraphData, ExternalEZKLError> {
+    let mut rows = Vec::with_capacity(inputs.len());
+    for (index, tensor) in inputs.into_iter().enumerate() {
+        let data = Sensitive::new(tensor.data);
+        let len = tensor
+            .shape
+            .iter()
+            .try_fold(1usize, |len, &dim| {
+                len.checked_mul(usize::try_from(dim).ok()?)
+            })
+            .ok_or_else(|| {
+                ExternalEZKLError::InvalidInput(format!(
+                    "the shape {:?} of input {} is too large",
+                    tensor.shape, index
+                ))
+            })?;
+        let expected = len.checked_mul(tensor.dtype.size());
+        if expected != Some(data.len()) {
+            return Err(ExternalEZKLError::InvalidInput(format!(
+                "input {} of shape {:?} and type {:?} needs {} bytes, but has {}",
+                index,
+                tensor.shape,
+                tensor.dtype,
+                len.saturating_mul(tensor.dtype.size()),
+                data.len()
+            )));
+        }
+        rows.push(
+            data.chunks_exact(tensor.dtype.size())
+                .map(|bytes| FileSourceInner::Float(tensor.dtype.read(bytes)))
+                .collect::<Vec<_>>(),
+        );
+    }
+    Ok(GraphData::new(DataSource::File(rows)))
+}
+
+/// Generates a witness like `gen_witness`, from input tensors in raw form instead of JSON.
+///
+/// The elements are quantized with the input scales of the circuit like JSON floats are, so
+/// this produces the same witness as `gen_witness` with the same values, without building and
+/// parsing a JSON number for every element.
+///
+/// # Arguments
+///
+/// * `inputs` - The input tensors of the circuit, in the order of the circuit inputs.
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated witness as a JSON `String`.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` if the length of a tensor's data doesn't match its
+///   shape and type or a float element is NaN or infinite, or an error that occurred during
+///   witness generation.
+#[cfg_attr(feature = "ffi", export)]
+pub async fn gen_witness_from_raw(
+    inputs: Vec<RawTensor>,
+    compiled_circuit: Vec<u8>,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+) -> Result<String, ExternalEZKLError> {
+    check_input_sizes!(compiled_circuit, vk, srs);
+    let data = Sensitive::new(raw_graph_data(inputs)?);
+    let generated = WitnessGenerator::new(&compiled_circuit, Some(&vk), Some(&srs))?
+        .generate_from_data(data, &WitnessOptions::default())
+        .await?;
+
+    Ok(generated.witness.as_json()?)
+}
@@ -0,0 +1,194 @@
+use crate::clock::Instant;
+use crate::gen_witness::{WitnessGenerator, WitnessOptions};
+use crate::limits::{check_input_sizes, ProvingPermit};
+use crate::logging::{info, warn};
+use crate::prove::Prover;
+use crate::serialization::{deserialize_circuit, deserialize_vk, serialize_proof};
+use crate::verify::verify_internal;
+use crate::ExternalEZKLError;
+use ezkl::circuit::CheckMode;
+use ezkl::graph::GraphCircuit;
+use ezkl::pfsys::ProofType;
+use halo2_proofs::halo2curves::bn256::Bn256;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// A stage of the end-to-end self test, in the order they are run.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfTestStage {
+    LoadCircuit,
+    LoadVk,
+    LoadPkAndSrs,
+    GenWitness,
+    Prove,
+    Verify,
+}
+
+/// The outcome of a single stage of the self test.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct SelfTestStep {
+    /// The stage that was run.
+    pub stage: SelfTestStage,
+    /// The time the stage took in milliseconds.
+    pub elapsed_ms: u64,
+    /// The error message if the stage failed.
+    pub error: Option<String>,
+}
+
+/// The report of a self test.
+///
+/// The test stops at the first failing stage, so the stages after it are not listed.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct SelfTestReport {
+    /// The stages that were run, in order.
+    pub steps: Vec<SelfTestStep>,
+    /// `true` if every stage succeeded.
+    pub passed: bool,
+    /// The stage that failed, if any.
+    pub failed_stage: Option<SelfTestStage>,
+}
+
+/// Collects the outcomes of the stages as they are run.
+#[derive(Default)]
+struct SelfTestRun {
+    steps: Vec<SelfTestStep>,
+    failed_stage: Option<SelfTestStage>,
+}
+
+impl SelfTestRun {
+    /// Records the outcome of a stage started at `started`, returning its value if it succeeded.
+    fn record<T>(
+        &mut self,
+        stage: SelfTestStage,
+        started: Instant,
+        result: Result<T, ExternalEZKLError>,
+    ) -> Option<T> {
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        let (value, error) = match result {
+            Ok(value) => (Some(value), None),
+            Err(e) => {
+                warn!("self test stage {:?} failed: {}", stage, e);
+                self.failed_stage = Some(stage);
+                (None, Some(e.to_string()))
+            }
+        };
+        self.steps.push(SelfTestStep {
+            stage,
+            elapsed_ms,
+            error,
+        });
+        value
+    }
+
+    fn into_report(self) -> SelfTestReport {
+        SelfTestReport {
+            passed: self.failed_stage.is_none(),
+            failed_stage: self.failed_stage,
+            steps: self.steps,
+        }
+    }
+}
+
+/// Runs the whole pipeline once on a sample input to check that the artifacts work together,
+/// e.g. after downloading them on first launch.
+///
+/// The proof is created with `Unchecked` checks to keep the test cheap; it is still verified.
+///
+/// # Arguments
+///
+/// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+/// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `sample_input_json` - A `String` containing the JSON representation of a sample input.
+///
+/// # Returns
+///
+/// * `Ok(SelfTestReport)` - The outcome and timing of each stage, up to the first failure.
+/// * `Err(ExternalEZKLError)` - Stage failures are reported in the report rather than as errors.
+#[cfg_attr(feature = "ffi", export)]
+pub async fn self_test(
+    compiled_circuit: Vec<u8>,
+    pk: Vec<u8>,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+    sample_input_json: String,
+) -> Result<SelfTestReport, ExternalEZKLError> {
+    check_input_sizes!(compiled_circuit, pk, vk, srs, sample_input_json);
+    let mut run = SelfTestRun::default();
+    // The outcome of every stage is recorded in `run`, including the one that stopped the test
+    let _ = run_stages(
+        &mut run,
+        &compiled_circuit,
+        &pk,
+        &vk,
+        &srs,
+        &sample_input_json,
+    )
+    .await;
+    let report = run.into_report();
+    info!("self test passed: {}", report.passed);
+    Ok(report)
+}
+
+/// Runs the stages in order, returning `None` as soon as one of them fails.
+async fn run_stages(
+    run: &mut SelfTestRun,
+    compiled_circuit: &[u8],
+    pk: &[u8],
+    vk: &[u8],
+    srs: &[u8],
+    sample_input_json: &str,
+) -> Option<()> {
+    let started = Instant::now();
+    let loaded = deserialize_circuit(compiled_circuit)
+        .map_err(ExternalEZKLError::from)
+        .and_then(|circuit| {
+            let settings_json = circuit.settings().as_json()?;
+            Ok((circuit, settings_json))
+        });
+    let (circuit, settings_json) = run.record(SelfTestStage::LoadCircuit, started, loaded)?;
+
+    let started = Instant::now();
+    let loaded_vk = run.record(
+        SelfTestStage::LoadVk,
+        started,
+        deserialize_vk::<KZGCommitmentScheme<Bn256>, GraphCircuit>(vk, circuit.settings().clone())
+            .map_err(ExternalEZKLError::from),
+    )?;
+
+    let started = Instant::now();
+    let prover = run.record(
+        SelfTestStage::LoadPkAndSrs,
+        started,
+        Prover::from_circuit(circuit.clone(), pk, Some(srs), None),
+    )?;
+
+    let started = Instant::now();
+    let witness = match WitnessGenerator::from_loaded(circuit, Some(loaded_vk), Some(srs)) {
+        Ok(generator) => {
+            generator
+                .generate(sample_input_json, &WitnessOptions::default())
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    let witness = run.record(SelfTestStage::GenWitness, started, witness)?;
+
+    let started = Instant::now();
+    let proof = ProvingPermit::acquire()
+        .and_then(|_permit| prover.prove(witness, ProofType::Single, CheckMode::UNSAFE))
+        .and_then(serialize_proof);
+    let proof_json = run.record(SelfTestStage::Prove, started, proof)?;
+
+    let started = Instant::now();
+    let verification = verify_internal(proof_json, settings_json, vk, Some(srs), false, false)
+        .and_then(|verification| verification.into_validity());
+    run.record(SelfTestStage::Verify, started, verification)?;
+
+    Some(())
+}
@@ -0,0 +1,138 @@
+use crate::config;
+use ezkl::graph::input::{DataSource, FileSourceInner, GraphData};
+use ezkl::graph::GraphWitness;
+use ezkl::tensor::Tensor;
+use halo2_proofs::halo2curves::bn256::Fr;
+use halo2_proofs::halo2curves::ff::Field;
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{compiler_fence, AtomicU64, Ordering};
+use zeroize::Zeroize;
+
+/// The number of buffers wiped so far, to check the wiping paths in tests.
+static WIPED_BUFFERS: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the number of buffers wiped since the library was loaded.
+pub fn wiped_buffers() -> u64 {
+    WIPED_BUFFERS.load(Ordering::SeqCst)
+}
+
+/// Returns `true` if private inputs should be wiped after use.
+pub(crate) fn enabled() -> bool {
+    config::current().zeroize_inputs
+}
+
+/// A buffer holding private inputs that can be overwritten in place.
+pub(crate) trait Wipe {
+    fn wipe(&mut self);
+}
+
+impl Wipe for String {
+    fn wipe(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl Wipe for Vec<u8> {
+    fn wipe(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl Wipe for GraphData {
+    /// Overwrites the inline values of the input data.
+    fn wipe(&mut self) {
+        if let DataSource::File(rows) = &mut self.input_data {
+            for value in rows.iter_mut().flatten() {
+                // SAFETY: `value` is a valid, aligned and exclusive reference
+                unsafe { std::ptr::write_volatile(value, FileSourceInner::Float(0.0)) };
+            }
+            compiler_fence(Ordering::SeqCst);
+        }
+    }
+}
+
+impl Wipe for Vec<Tensor<Fr>> {
+    /// Overwrites the quantized input tensors.
+    fn wipe(&mut self) {
+        for tensor in self.iter_mut() {
+            overwrite(tensor, Fr::ZERO);
+        }
+    }
+}
+
+impl Wipe for GraphWitness {
+    /// Overwrites the inputs, outputs and their human readable form.
+    ///
+    /// The processed inputs, params and outputs are kept, as they only hold hashes and commitments.
+    fn wipe(&mut self) {
+        for values in self.inputs.iter_mut().chain(self.outputs.iter_mut()) {
+            overwrite(values, Fr::ZERO);
+        }
+        if let Some(pretty) = self.pretty_elements.as_mut() {
+            pretty
+                .rescaled_inputs
+                .iter_mut()
+                .chain(pretty.rescaled_outputs.iter_mut())
+                .chain(pretty.inputs.iter_mut())
+                .chain(pretty.outputs.iter_mut())
+                .flatten()
+                .for_each(Zeroize::zeroize);
+        }
+    }
+}
+
+/// A buffer holding private inputs that is wiped when dropped, on both success and error paths,
+/// if enabled.
+pub(crate) struct Sensitive<T: Wipe> {
+    value: T,
+    wipe: bool,
+}
+
+impl<T: Wipe> Sensitive<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Sensitive {
+            value,
+            wipe: enabled(),
+        }
+    }
+
+    /// Returns the value without wiping it, to hand it to a function that wipes it itself.
+    pub(crate) fn into_inner(self) -> T {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, so the value is moved out exactly once
+        unsafe { std::ptr::read(&this.value) }
+    }
+}
+
+impl<T: Wipe> Deref for Sensitive<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Wipe> DerefMut for Sensitive<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: Wipe> Drop for Sensitive<T> {
+    fn drop(&mut self) {
+        if self.wipe {
+            self.value.wipe();
+            WIPED_BUFFERS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Overwrites every element with `value` in a way the compiler can't optimize away.
+fn overwrite<T: Copy>(elements: &mut [T], value: T) {
+    for element in elements.iter_mut() {
+        // SAFETY: `element` is a valid, aligned and exclusive reference to a `Copy` value
+        unsafe { std::ptr::write_volatile(element, value) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
@@ -1,19 +1,377 @@
-use crate::InnerEZKLError;
+use crate::compression::decoding_reader;
+use crate::io::{ezkl_reader, Source};
+use crate::logging::{debug, info, warn};
+use crate::settings::rows_of;
+use crate::trace::Interval;
+use crate::{ExternalEZKLError, InnerEZKLError};
 use ezkl::graph::GraphCircuit;
 use ezkl::pfsys::srs::SrsError;
-use ezkl::pfsys::PfsysError;
-use ezkl::EZKL_BUF_CAPACITY;
+use ezkl::pfsys::{PfsysError, PrettyElements, ProofSplitCommit, Snark, TranscriptType};
+use ezkl::Commitments;
 use halo2_proofs::arithmetic::CurveAffine;
+use halo2_proofs::halo2curves::bn256::{Fr, G1Affine};
 use halo2_proofs::halo2curves::ff::{FromUniformBytes, PrimeField};
 use halo2_proofs::halo2curves::serde::SerdeObject;
 use halo2_proofs::plonk::{Circuit, ProvingKey, VerifyingKey};
 use halo2_proofs::poly::commitment::{CommitmentScheme, Params};
-use halo2_proofs::SerdeFormat::RawBytes;
-use std::io::BufReader;
-use uniffi::deps::log::{debug, info};
+use halo2_proofs::SerdeFormat::{RawBytes, RawBytesUnchecked};
+use serde::de::DeserializeOwned;
+use serde::ser::{Error as _, SerializeSeq};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use snark_verifier::verifier::plonk::PlonkProtocol;
+use std::path::Path;
+
+/// The number of characters shown on each side of a JSON syntax error.
+const JSON_EXCERPT_RADIUS: usize = 20;
+
+/// The magic bytes starting a verification key prepared by `prepare_vk`, with its format version.
+const PREPARED_VK_MAGIC: &[u8; 8] = b"EZKLPVK\x01";
+/// The length of the header of a prepared verification key: the magic bytes and the SHA-256
+/// digest of the vk that follows.
+const PREPARED_VK_HEADER_LEN: usize = PREPARED_VK_MAGIC.len() + 32;
+
+/// The top-level key of a proof JSON holding the metadata attached by the app.
+pub(crate) const APP_METADATA_KEY: &str = "app_metadata";
+
+/// Deserializes a JSON argument, reporting the argument name and error location on failure.
+///
+/// # Arguments
+///
+/// * `field` - The name of the argument, used in the error.
+/// * `json` - The JSON representation of the value.
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized value.
+/// * `Err(ExternalEZKLError)` - A `JsonError` with the line, column and an excerpt of the offending region.
+pub(crate) fn deserialize_json<T: DeserializeOwned>(
+    field: &str,
+    json: &str,
+) -> Result<T, ExternalEZKLError> {
+    serde_json::from_str(json).map_err(|e| json_error(field, json, e))
+}
+
+fn json_error(field: &str, json: &str, e: serde_json::Error) -> ExternalEZKLError {
+    let excerpt = json_excerpt(json, e.line(), e.column());
+    ExternalEZKLError::JsonError {
+        field: field.to_string(),
+        line: e.line() as u64,
+        column: e.column() as u64,
+        message: if excerpt.is_empty() {
+            e.to_string()
+        } else {
+            format!("{} (near `{}`)", e, excerpt)
+        },
+    }
+}
+
+/// Deserializes a proof like [`deserialize_json`], ignoring top-level fields added by newer ezkl
+/// versions that the pinned version rejects.
+///
+/// Proofs are parsed strictly first, so the lenient path only costs a second parse for proofs
+/// that would otherwise be rejected.
+pub(crate) fn deserialize_proof(
+    field: &str,
+    json: &str,
+) -> Result<Snark<Fr, G1Affine>, ExternalEZKLError> {
+    match serde_json::from_str(json) {
+        Ok(proof) => Ok(proof),
+        Err(e) if unknown_field(&e.to_string()).is_some() => {
+            let value: Value = deserialize_json(field, json)?;
+            without_unknown_fields(field, value)
+        }
+        Err(e) => Err(json_error(field, json, e)),
+    }
+}
+
+/// Deserializes a proof file like [`deserialize_json_file`], ignoring top-level fields added by
+/// newer ezkl versions like [`deserialize_proof`].
+pub(crate) fn deserialize_proof_file(
+    field: &str,
+    path: &str,
+) -> Result<Snark<Fr, G1Affine>, ExternalEZKLError> {
+    deserialize_proof_from(field, Source::Path(Path::new(path)))
+}
+
+/// Deserializes a proof like [`deserialize_proof_file`], streaming it from any source.
+pub(crate) fn deserialize_proof_from(
+    field: &str,
+    source: Source<'_>,
+) -> Result<Snark<Fr, G1Affine>, ExternalEZKLError> {
+    match deserialize_json_from(field, source) {
+        Err(ExternalEZKLError::JsonError { message, .. }) if unknown_field(&message).is_some() => {
+            let value: Value = deserialize_json_from(field, source)?;
+            without_unknown_fields(field, value)
+        }
+        result => result,
+    }
+}
+
+/// Returns the name of the field a serde "unknown field" error is about.
+fn unknown_field(message: &str) -> Option<&str> {
+    let rest = &message[message.find("unknown field `")? + "unknown field `".len()..];
+    Some(&rest[..rest.find('`')?])
+}
+
+/// Deserializes a proof, removing the top-level fields it is rejected for one at a time.
+fn without_unknown_fields(
+    field: &str,
+    mut value: Value,
+) -> Result<Snark<Fr, G1Affine>, ExternalEZKLError> {
+    // The app metadata is not part of the proof, so it is removed without a warning
+    if let Some(object) = value.as_object_mut() {
+        object.remove(APP_METADATA_KEY);
+    }
+    let mut ignored = vec![];
+    loop {
+        let e = match serde_json::from_value(value.clone()) {
+            Ok(proof) => {
+                if !ignored.is_empty() {
+                    warn!(
+                        "ignored fields unknown to this ezkl version in `{}`: {}",
+                        field,
+                        ignored.join(", ")
+                    );
+                }
+                return Ok(proof);
+            }
+            Err(e) => e,
+        };
+        let removed = unknown_field(&e.to_string())
+            .and_then(|name| value.as_object_mut()?.remove_entry(name))
+            .map(|(name, _)| name);
+        match removed {
+            Some(name) => ignored.push(name),
+            None => {
+                return Err(ExternalEZKLError::JsonError {
+                    field: field.to_string(),
+                    line: 0,
+                    column: 0,
+                    message: e.to_string(),
+                })
+            }
+        }
+    }
+}
+
+/// Deserializes a JSON file while it is read, so its text is never held in memory at once.
+///
+/// Gzip-compressed files are decompressed transparently.
+///
+/// # Arguments
+///
+/// * `field` - The name of the argument holding the path, used in the error.
+/// * `path` - The path of the JSON file.
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized value.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` if the file could not be read, or a `JsonError` with the line and column of the offending region.
+pub fn deserialize_json_file<T: DeserializeOwned>(
+    field: &str,
+    path: &str,
+) -> Result<T, ExternalEZKLError> {
+    deserialize_json_from(field, Source::Path(Path::new(path)))
+}
+
+/// Deserializes a JSON artifact like [`deserialize_json_file`], streaming it from any source.
+pub(crate) fn deserialize_json_from<T: DeserializeOwned>(
+    field: &str,
+    source: Source<'_>,
+) -> Result<T, ExternalEZKLError> {
+    let read_error = |e: std::io::Error| {
+        ExternalEZKLError::InvalidInput(format!("failed to read {}: {}", source.describe(), e))
+    };
+    let reader = ezkl_reader(source).map_err(read_error)?;
+    let reader = decoding_reader(reader).map_err(read_error)?;
+    serde_json::from_reader(reader).map_err(|e| {
+        if e.is_io() {
+            return read_error(e.into());
+        }
+        ExternalEZKLError::JsonError {
+            field: field.to_string(),
+            line: e.line() as u64,
+            column: e.column() as u64,
+            message: e.to_string(),
+        }
+    })
+}
+
+/// Returns the text around the given 1-based line and column.
+///
+/// An unexpected end of input is reported past the last line when the JSON ends with a newline,
+/// so positions past the last line are clamped to the end of the last non-blank line.
+fn json_excerpt(json: &str, line: usize, column: usize) -> String {
+    let Some((text, column)) = json
+        .lines()
+        .nth(line.saturating_sub(1))
+        .map(|text| (text, column))
+        .or_else(|| {
+            let last = json.lines().rev().find(|text| !text.trim().is_empty())?;
+            Some((last, usize::MAX))
+        })
+    else {
+        return String::new();
+    };
+    let chars: Vec<char> = text.chars().collect();
+    let end = column.min(chars.len());
+    let start = end.saturating_sub(JSON_EXCERPT_RADIUS);
+    let end = (end + JSON_EXCERPT_RADIUS).min(chars.len());
+    chars[start..end]
+        .iter()
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// A proof with the fields of `Snark`, in the same order, whose instances are hex encoded
+/// straight into the output.
+///
+/// It is built by destructuring a `Snark` without a rest pattern, so a field added to `Snark` by
+/// an ezkl bump fails to compile here instead of being left out of the JSON.
+#[derive(Serialize)]
+struct ProofJson<'a> {
+    protocol: &'a Option<PlonkProtocol<G1Affine>>,
+    instances: HexInstances<'a>,
+    proof: &'a Vec<u8>,
+    hex_proof: &'a Option<String>,
+    transcript_type: &'a TranscriptType,
+    split: &'a Option<ProofSplitCommit>,
+    pretty_public_inputs: &'a Option<PrettyElements>,
+    timestamp: &'a Option<u128>,
+    commitment: &'a Option<Commitments>,
+}
+
+/// The instances of a proof, serialized as serde serializes field elements: the hex encoding of
+/// their 32 byte representation.
+struct HexInstances<'a>(&'a [Vec<Fr>]);
+
+impl Serialize for HexInstances<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|column| HexColumn(column)))
+    }
+}
+
+struct HexColumn<'a>(&'a [Fr]);
+
+impl Serialize for HexColumn<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        let mut hex = [0u8; 64];
+        for instance in self.0 {
+            hex::encode_to_slice(instance.to_repr().as_ref(), &mut hex)
+                .map_err(S::Error::custom)?;
+            seq.serialize_element(std::str::from_utf8(&hex).map_err(S::Error::custom)?)?;
+        }
+        seq.end()
+    }
+}
+
+/// Serializes a proof to JSON, producing the same output as `serde_json::to_string`.
+///
+/// Proofs of circuits with many public values are dominated by their instances, which serde
+/// formats into a temporary string each. Here the instances are hex encoded on the stack and
+/// written directly into a buffer preallocated for the whole proof.
+///
+/// # Arguments
+///
+/// * `snark` - The proof to serialize.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The JSON representation of the proof.
+/// * `Err(ExternalEZKLError)` - If the proof could not be serialized.
+pub fn serialize_proof(snark: &Snark<Fr, G1Affine>) -> Result<String, ExternalEZKLError> {
+    let Snark {
+        protocol,
+        instances,
+        proof,
+        hex_proof,
+        transcript_type,
+        split,
+        pretty_public_inputs,
+        timestamp,
+        commitment,
+    } = snark;
+    let proof_json = ProofJson {
+        protocol,
+        instances: HexInstances(instances),
+        proof,
+        hex_proof,
+        transcript_type,
+        split,
+        pretty_public_inputs,
+        timestamp,
+        commitment,
+    };
+
+    // Each instance is a quoted hex string of its 32 byte representation, followed by a comma,
+    // and each byte of the proof at most three digits followed by a comma
+    const INSTANCE_JSON_LEN: usize = 2 * 32 + 3;
+    const PROOF_BYTE_JSON_LEN: usize = 4;
+    const OTHER_FIELDS_JSON_LEN: usize = 1024;
+    let num_instances: usize = instances.iter().map(Vec::len).sum();
+    let capacity = num_instances * INSTANCE_JSON_LEN
+        + 3 * instances.len()
+        + proof.len() * PROOF_BYTE_JSON_LEN
+        + hex_proof.as_ref().map_or(0, String::len)
+        + OTHER_FIELDS_JSON_LEN;
+    let mut json = Vec::with_capacity(capacity);
+    serde_json::to_writer(&mut json, &proof_json)?;
+    String::from_utf8(json).map_err(|e| ExternalEZKLError::InternalError(e.to_string()))
+}
+
+/// The smallest possible compiled circuit, used to reject empty buffers.
+const MIN_CIRCUIT_LEN: usize = 1;
+/// The smallest possible key: every key starts with the domain size and the number of fixed columns.
+const MIN_KEY_LEN: usize = 8;
+/// The smallest possible SRS: `k` followed by at least one (compressed) G1 point.
+const MIN_SRS_LEN: usize = 4 + 32;
+
+/// Checks that an artifact buffer is not obviously empty or truncated.
+///
+/// # Arguments
+///
+/// * `artifact` - The name of the artifact, used in the error.
+/// * `bytes` - The serialized artifact.
+/// * `min_len` - The smallest length a valid serialized artifact can have.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the buffer is at least `min_len` bytes long.
+/// * `Err(String)` - A message naming the artifact and the actual length of the buffer.
+fn check_min_len(artifact: &str, bytes: &[u8], min_len: usize) -> Result<(), String> {
+    if bytes.is_empty() {
+        Err(format!(
+            "{} buffer is empty — did the file download complete?",
+            artifact
+        ))
+    } else if bytes.len() < min_len {
+        Err(format!(
+            "{} is only {} bytes, expected at least {}",
+            artifact,
+            bytes.len(),
+            min_len
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn invalid_input(message: String) -> InnerEZKLError {
+    InnerEZKLError::IoError(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        message,
+    ))
+}
 
 /// Deserializes a compiled circuit from a byte slice.
 ///
+/// The circuit is read the way `GraphCircuit::load` reads the `.ezkl` files written by the ezkl
+/// CLI `compile-circuit` and `GraphCircuit::save`, i.e. as bincode with fixed-size integers.
+///
 /// # Arguments
 ///
 /// * `compiled_circuit` - A byte slice containing the serialized circuit.
@@ -23,16 +381,22 @@ use uniffi::deps::log::{debug, info};
 /// * `Ok(GraphCircuit)` - The deserialized circuit.
 /// * `Err(InnerEZKLError)` - If deserialization fails.
 pub(crate) fn deserialize_circuit(compiled_circuit: &[u8]) -> Result<GraphCircuit, InnerEZKLError> {
-    // Deserialize the circuit using `bincode`
-    let circuit: GraphCircuit = bincode::deserialize(compiled_circuit).map_err(|e| {
-        ezkl::EZKLError::IoError(std::io::Error::new(std::io::ErrorKind::InvalidInput, e))
-    })?;
-    Ok(circuit)
+    check_min_len("compiled circuit", compiled_circuit, MIN_CIRCUIT_LEN).map_err(invalid_input)?;
+
+    let _interval = Interval::begin("deserialize_circuit");
+    let reader = ezkl_reader(Source::Bytes(compiled_circuit)).map_err(InnerEZKLError::IoError)?;
+    bincode::deserialize_from(reader).map_err(|e| {
+        invalid_input(format!(
+            "the compiled circuit could not be read as an `.ezkl` file: {}",
+            e
+        ))
+    })
 }
 
 /// Deserializes a verification key from a byte slice.
 ///
-/// Currently only supports `RawBytes` format, which is the EZKL default format.
+/// Supports the `RawBytes` format, which is the EZKL default format, and verification keys
+/// prepared by `prepare_vk`, whose points are read without validating them again.
 ///
 /// # Arguments
 ///
@@ -52,21 +416,56 @@ where
     Scheme::Curve: SerdeObject + CurveAffine,
     Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
 {
+    let _interval = Interval::begin("deserialize_vk");
+    // The points of a prepared vk were validated when it was prepared
+    let (serialised_vk, format) = match prepared_vk(serialised_vk).map_err(PfsysError::LoadVk)? {
+        Some(vk) => (vk, RawBytesUnchecked),
+        None => (serialised_vk, RawBytes),
+    };
+    check_min_len("verification key", serialised_vk, MIN_KEY_LEN).map_err(PfsysError::LoadVk)?;
+
     debug!("Deserializing verification key...");
     // Create a buffered reader over the serialized verification key
-    let cursor = std::io::Cursor::new(serialised_vk);
-    let mut reader = BufReader::with_capacity(*EZKL_BUF_CAPACITY, cursor);
+    let mut reader =
+        ezkl_reader(Source::Bytes(serialised_vk)).map_err(|e| PfsysError::LoadVk(e.to_string()))?;
     // Read the verification key from the buffer
-    let vk = VerifyingKey::<Scheme::Curve>::read::<_, C>(
-        &mut reader,
-        RawBytes, // Currently only supports RawBytes format
-        params,
-    )
-    .map_err(|e| PfsysError::LoadVk(format!("{}", e)))?;
+    let vk = VerifyingKey::<Scheme::Curve>::read::<_, C>(&mut reader, format, params)
+        .map_err(|e| PfsysError::LoadVk(format!("{}", e)))?;
     info!("Deserialized verification key");
     Ok(vk)
 }
 
+/// Prefixes a validated verification key in the `RawBytes` format with the header of a prepared vk.
+pub(crate) fn prepared_vk_bytes(vk: &[u8]) -> Vec<u8> {
+    let mut prepared = Vec::with_capacity(PREPARED_VK_HEADER_LEN + vk.len());
+    prepared.extend_from_slice(PREPARED_VK_MAGIC);
+    prepared.extend_from_slice(&Sha256::digest(vk));
+    prepared.extend_from_slice(vk);
+    prepared
+}
+
+/// Returns the vk following the header of a prepared verification key, or `None` if the bytes
+/// are not a prepared vk.
+///
+/// The digest in the header is checked, so that a prepared vk corrupted in storage fails to load
+/// instead of being read without validation.
+pub(crate) fn prepared_vk(serialised_vk: &[u8]) -> Result<Option<&[u8]>, String> {
+    if !serialised_vk.starts_with(PREPARED_VK_MAGIC) {
+        return Ok(None);
+    }
+    if serialised_vk.len() < PREPARED_VK_HEADER_LEN {
+        return Err("the prepared verification key is truncated".to_string());
+    }
+    let (digest, vk) = serialised_vk[PREPARED_VK_MAGIC.len()..].split_at(32);
+    if Sha256::digest(vk).as_slice() != digest {
+        return Err(
+            "the prepared verification key is corrupted, its digest doesn't match; prepare it again"
+                .to_string(),
+        );
+    }
+    Ok(Some(vk))
+}
+
 /// Deserializes a proving key from a byte slice.
 ///
 /// Currently only supports `RawBytes` format, which is the EZKL default format.
@@ -89,10 +488,13 @@ where
     Scheme::Curve: SerdeObject + CurveAffine,
     Scheme::Scalar: PrimeField + SerdeObject + FromUniformBytes<64>,
 {
+    check_min_len("proving key", serialised_pk, MIN_KEY_LEN).map_err(PfsysError::LoadPk)?;
+
+    let _interval = Interval::begin("deserialize_pk");
     debug!("Deserializing proving key...");
     // Create a buffered reader over the serialized proving key
-    let cursor = std::io::Cursor::new(serialised_pk);
-    let mut reader = BufReader::with_capacity(*EZKL_BUF_CAPACITY, cursor);
+    let mut reader =
+        ezkl_reader(Source::Bytes(serialised_pk)).map_err(|e| PfsysError::LoadPk(e.to_string()))?;
     // Read the proving key from the buffer
     let pk = ProvingKey::<Scheme::Curve>::read::<_, C>(
         &mut reader,
@@ -127,9 +529,13 @@ pub fn deserialize_params_prover<Scheme: CommitmentScheme>(
         ))
     })?;
 
+    check_min_len("SRS", serialized_srs, MIN_SRS_LEN).map_err(invalid_input)?;
+    rows_of(logrows).map_err(invalid_input)?;
+
+    let _interval = Interval::begin("load_srs");
     // Create a buffered reader over the serialized SRS
-    let cursor = std::io::Cursor::new(serialized_srs);
-    let mut reader = BufReader::new(cursor);
+    let mut reader = ezkl_reader(Source::Bytes(serialized_srs))
+        .map_err(|e| SrsError::ReadError(e.to_string()))?;
     // Read the parameters from the buffer
     let mut params: Scheme::ParamsProver = Params::<'_, Scheme::Curve>::read(&mut reader)
         .map_err(|e| SrsError::ReadError(e.to_string()))?;
@@ -164,9 +570,13 @@ pub(crate) fn deserialize_params_verifier<Scheme: CommitmentScheme>(
         ))
     })?;
 
+    check_min_len("SRS", serialized_srs, MIN_SRS_LEN).map_err(invalid_input)?;
+    rows_of(logrows).map_err(invalid_input)?;
+
+    let _interval = Interval::begin("load_srs");
     // Create a buffered reader over the serialized SRS
-    let cursor = std::io::Cursor::new(serialized_srs);
-    let mut reader = BufReader::new(cursor);
+    let mut reader = ezkl_reader(Source::Bytes(serialized_srs))
+        .map_err(|e| SrsError::ReadError(e.to_string()))?;
     // Read the parameters from the buffer
     let mut params: Scheme::ParamsVerifier = Params::<'_, Scheme::Curve>::read(&mut reader)
         .map_err(|e| SrsError::ReadError(e.to_string()))?;
@@ -0,0 +1,301 @@
+use crate::clock::Instant;
+use crate::executor::{run_on_worker, WorkKind};
+use crate::limits::{check_input_sizes, ProvingPermit, TimeBudget};
+use crate::logging::info;
+use crate::prove::{prove_with_metrics, ProveOptions, ProveResult, Prover};
+use crate::provider::{read_artifact, ArtifactProvider};
+use crate::serialization::deserialize_json;
+use crate::store::{ArtifactKind, ArtifactStore};
+use crate::ExternalEZKLError;
+use ezkl::graph::GraphWitness;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The sessions that are alive, released by [`handle_memory_warning`].
+static SESSIONS: Mutex<Vec<Weak<EzklProver>>> = Mutex::new(vec![]);
+
+/// Where a session reads its artifacts from whenever it loads them.
+enum SessionSources {
+    /// Artifacts passed by the app, which the session holds for its whole lifetime.
+    Bytes {
+        compiled_circuit: Vec<u8>,
+        pk: Vec<u8>,
+        srs: Vec<u8>,
+    },
+    /// Artifacts of an artifact store, read and checked again on every load.
+    Store {
+        store: Arc<ArtifactStore>,
+        circuit_fingerprint: String,
+    },
+    /// Artifacts of an artifact provider, read again on every load.
+    Provider {
+        provider: Arc<dyn ArtifactProvider>,
+        circuit_name: String,
+        pk_name: String,
+        srs_name: String,
+    },
+}
+
+impl SessionSources {
+    /// Deserializes the artifacts, reading them from their source first if the session doesn't
+    /// hold them.
+    fn load(&self) -> Result<Prover, ExternalEZKLError> {
+        match self {
+            SessionSources::Bytes {
+                compiled_circuit,
+                pk,
+                srs,
+            } => Prover::new(compiled_circuit, pk, Some(srs), None),
+            SessionSources::Store {
+                store,
+                circuit_fingerprint,
+            } => Prover::new(
+                &store.read(ArtifactKind::CompiledCircuit, circuit_fingerprint)?,
+                &store.read(ArtifactKind::ProvingKey, circuit_fingerprint)?,
+                Some(&store.read(ArtifactKind::Srs, circuit_fingerprint)?),
+                None,
+            ),
+            SessionSources::Provider {
+                provider,
+                circuit_name,
+                pk_name,
+                srs_name,
+            } => Prover::new(
+                &read_artifact(provider, circuit_name)?,
+                &read_artifact(provider, pk_name)?,
+                Some(&read_artifact(provider, srs_name)?),
+                None,
+            ),
+        }
+    }
+}
+
+/// A proving session for one circuit, which deserializes the circuit, proving key and SRS once
+/// and reuses them for every proof.
+///
+/// Creating a session is cheap; the artifacts are deserialized by [`EzklProver::preload`], or by
+/// the first proof if the session was not preloaded.
+#[cfg_attr(feature = "ffi", derive(uniffi::Object))]
+pub struct EzklProver {
+    sources: SessionSources,
+    prover: Mutex<Option<Arc<Prover>>>,
+}
+
+impl EzklProver {
+    /// Creates a session reading its artifacts from the given sources, and registers it to be
+    /// released on memory warnings.
+    fn with_sources(sources: SessionSources) -> Arc<Self> {
+        let session = Arc::new(EzklProver {
+            sources,
+            prover: Mutex::new(None),
+        });
+        if let Ok(mut sessions) = SESSIONS.lock() {
+            sessions.retain(|session| session.strong_count() > 0);
+            sessions.push(Arc::downgrade(&session));
+        }
+        session
+    }
+
+    /// Returns the loaded artifacts, loading them first if needed, and the time spent loading.
+    fn loaded(&self) -> Result<(Arc<Prover>, Duration), ExternalEZKLError> {
+        let mut prover = self.prover.lock().map_err(|_| {
+            ExternalEZKLError::InternalError("proving session lock poisoned".to_string())
+        })?;
+        if let Some(prover) = prover.as_ref() {
+            return Ok((prover.clone(), Duration::ZERO));
+        }
+
+        let start_time = Instant::now();
+        let loaded = Arc::new(self.sources.load()?);
+        let load_time = start_time.elapsed();
+        info!("loading the proving artifacts took {:?}", load_time);
+
+        *prover = Some(loaded.clone());
+        Ok((loaded, load_time))
+    }
+}
+
+#[cfg_attr(feature = "ffi", export)]
+impl EzklProver {
+    /// Creates a proving session without deserializing anything yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `compiled_circuit` - A `Vec<u8>` containing the compiled circuit in binary form.
+    /// * `pk` - A `Vec<u8>` containing the Proving Key (PK) in binary form.
+    /// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+    #[cfg_attr(feature = "ffi", uniffi::constructor)]
+    pub fn new(compiled_circuit: Vec<u8>, pk: Vec<u8>, srs: Vec<u8>) -> Arc<Self> {
+        EzklProver::with_sources(SessionSources::Bytes {
+            compiled_circuit,
+            pk,
+            srs,
+        })
+    }
+
+    /// Creates a proving session for a circuit whose artifacts are kept in an artifact store.
+    ///
+    /// The circuit, proving key and SRS are read and checked against their stored digests right
+    /// away, but deserialized only when the session is preloaded or proves. The session doesn't
+    /// hold on to them, but reads them again whenever it loads them, so that
+    /// [`EzklProver::release_caches`] frees all of their memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The `ArtifactStore` holding the artifacts of the circuit.
+    /// * `circuit_fingerprint` - The fingerprint of the circuit the artifacts are stored under.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(EzklProver)` - The proving session.
+    /// * `Err(ExternalEZKLError)` - If an artifact is missing or corrupted.
+    #[cfg_attr(feature = "ffi", uniffi::constructor)]
+    pub fn from_store(
+        store: Arc<ArtifactStore>,
+        circuit_fingerprint: String,
+    ) -> Result<Arc<Self>, ExternalEZKLError> {
+        for kind in [
+            ArtifactKind::CompiledCircuit,
+            ArtifactKind::ProvingKey,
+            ArtifactKind::Srs,
+        ] {
+            store.read(kind, &circuit_fingerprint)?;
+        }
+        Ok(EzklProver::with_sources(SessionSources::Store {
+            store,
+            circuit_fingerprint,
+        }))
+    }
+
+    /// Creates a proving session for a circuit whose artifacts are read from an artifact provider.
+    ///
+    /// Nothing is read until the session is preloaded or proves. Like a session created with
+    /// [`EzklProver::from_store`], the session reads the artifacts again whenever it loads them.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The `ArtifactProvider` to read the artifacts from.
+    /// * `circuit_name` - The name of the compiled circuit.
+    /// * `pk_name` - The name of the Proving Key (PK).
+    /// * `srs_name` - The name of the Structured Reference String (SRS).
+    #[cfg_attr(feature = "ffi", uniffi::constructor)]
+    pub fn from_provider(
+        provider: Arc<dyn ArtifactProvider>,
+        circuit_name: String,
+        pk_name: String,
+        srs_name: String,
+    ) -> Arc<Self> {
+        EzklProver::with_sources(SessionSources::Provider {
+            provider,
+            circuit_name,
+            pk_name,
+            srs_name,
+        })
+    }
+
+    /// Deserializes the circuit, proving key and SRS ahead of time, e.g. while the app is idle,
+    /// so that the next proof does not have to. Does nothing if they are already loaded.
+    ///
+    /// The artifacts are deserialized on a worker thread like `prove_async`, so the thread
+    /// polling the future is not blocked while they load.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the artifacts are loaded.
+    /// * `Err(ExternalEZKLError)` - If the artifacts could not be deserialized.
+    pub async fn preload(self: Arc<Self>) -> Result<(), ExternalEZKLError> {
+        run_on_worker(WorkKind::Prove, move || self.loaded().map(|_| ())).await
+    }
+
+    /// Returns `true` if the artifacts are loaded and proofs can start right away.
+    pub fn is_ready(&self) -> bool {
+        self.prover
+            .lock()
+            .map(|prover| prover.is_some())
+            .unwrap_or(false)
+    }
+
+    /// Drops the deserialized circuit, proving key and SRS, and the protocol compiled for
+    /// accumulator proofs, e.g. on a memory warning.
+    ///
+    /// The session stays usable: the next proof loads the artifacts again from the sources the
+    /// session was created with, and reports the time spent in `load_ms`. A proof that is running
+    /// keeps its artifacts until it finishes. The artifacts passed to [`EzklProver::new`] are held
+    /// by the session, so only sessions created from a store or provider free those as well.
+    pub fn release_caches(&self) {
+        if let Ok(mut prover) = self.prover.lock() {
+            if prover.take().is_some() {
+                info!("released the proving artifacts of a session");
+            }
+        }
+    }
+
+    /// Generates a proof for the given witness with the default proving configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `witness_json` - A `String` containing the JSON representation of the witness generated for the circuit input.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ProveResult)` - The generated proof and the metrics of the run; `load_ms` is zero if the session was ready.
+    /// * `Err(ExternalEZKLError)` - An error that occurred during the proving process.
+    pub fn prove(&self, witness_json: String) -> Result<ProveResult, ExternalEZKLError> {
+        self.prove_with_options(witness_json, ProveOptions::default())
+    }
+
+    /// Generates a proof for the given witness with the given proving configuration.
+    ///
+    /// The configuration can change from call to call, e.g. to generate an EVM transcript proof
+    /// for on-chain verification and a Poseidon transcript proof for aggregation from the same
+    /// witness, without reloading the artifacts.
+    ///
+    /// # Arguments
+    ///
+    /// * `witness_json` - A `String` containing the JSON representation of the witness generated for the circuit input.
+    /// * `options` - A `ProveOptions` record with the proof type, check mode and expected variables to prove with.
+    ///   The SRS of a session is fixed when it is created, so `srs_extension` must be `None`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ProveResult)` - The generated proof, tagged with its transcript, and the metrics of the run.
+    /// * `Err(ExternalEZKLError)` - An error that occurred during the proving process.
+    pub fn prove_with_options(
+        &self,
+        witness_json: String,
+        options: ProveOptions,
+    ) -> Result<ProveResult, ExternalEZKLError> {
+        check_input_sizes!(witness_json);
+        if options.srs_extension.is_some() {
+            return Err(ExternalEZKLError::InvalidInput(
+                "the SRS of a proving session is fixed when it is created; pass the full SRS to the session instead of an SRS extension".to_string(),
+            ));
+        }
+        let budget = TimeBudget::start(options.time_budget_ms);
+        let data: GraphWitness = deserialize_json("witness_json", &witness_json)?;
+
+        let _permit = ProvingPermit::acquire()?;
+        prove_with_metrics(data, &options, &budget, || self.loaded())
+    }
+}
+
+/// Releases the cached state of every proving session, to call when iOS sends a memory warning.
+///
+/// Calls [`EzklProver::release_caches`] on every session that is alive. The witness cache is kept
+/// on disk and holds no memory, and witness generation keeps nothing between calls, so nothing
+/// else is released.
+#[cfg_attr(feature = "ffi", export)]
+pub fn handle_memory_warning() {
+    let sessions: Vec<Arc<EzklProver>> = match SESSIONS.lock() {
+        Ok(mut sessions) => {
+            sessions.retain(|session| session.strong_count() > 0);
+            sessions.iter().filter_map(Weak::upgrade).collect()
+        }
+        Err(_) => return,
+    };
+    for session in sessions {
+        session.release_caches();
+    }
+}
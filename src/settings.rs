@@ -0,0 +1,502 @@
+use crate::inspect::CommitmentsWrapper;
+use crate::limits::check_input_sizes;
+use crate::serialization::deserialize_json;
+use crate::ExternalEZKLError;
+#[cfg(feature = "prover")]
+use ezkl::graph::GraphCircuit;
+use ezkl::graph::{GraphSettings, Visibility};
+use ezkl::{Commitments, RunArgs};
+use halo2_proofs::halo2curves::bn256::Fr;
+use halo2_proofs::halo2curves::bn256::G1Affine;
+use halo2_proofs::halo2curves::ff::PrimeField;
+use halo2_proofs::plonk::VerifyingKey;
+use serde::Serialize;
+use serde_json::Value;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The visibility of the inputs, parameters or outputs of a circuit.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub enum VisibilityWrapper {
+    /// Known only to the prover.
+    Private,
+    /// Part of the public instances of the proof.
+    Public,
+    /// Committed to with a Poseidon hash, which is public if `hash_is_public`.
+    Hashed {
+        hash_is_public: bool,
+        outlets: Vec<u64>,
+    },
+    /// Committed to with a polynomial commitment.
+    Polycommit,
+    /// Baked into the circuit as constants.
+    Fixed,
+}
+
+impl From<&Visibility> for VisibilityWrapper {
+    fn from(visibility: &Visibility) -> Self {
+        match visibility {
+            Visibility::Private => VisibilityWrapper::Private,
+            Visibility::Public => VisibilityWrapper::Public,
+            Visibility::Hashed {
+                hash_is_public,
+                outlets,
+            } => VisibilityWrapper::Hashed {
+                hash_is_public: *hash_is_public,
+                outlets: outlets.iter().map(|&outlet| outlet as u64).collect(),
+            },
+            Visibility::KZGCommit => VisibilityWrapper::Polycommit,
+            Visibility::Fixed => VisibilityWrapper::Fixed,
+        }
+    }
+}
+
+impl From<VisibilityWrapper> for Visibility {
+    fn from(visibility: VisibilityWrapper) -> Self {
+        match visibility {
+            VisibilityWrapper::Private => Visibility::Private,
+            VisibilityWrapper::Public => Visibility::Public,
+            VisibilityWrapper::Hashed {
+                hash_is_public,
+                outlets,
+            } => Visibility::Hashed {
+                hash_is_public,
+                outlets: outlets.into_iter().map(|outlet| outlet as usize).collect(),
+            },
+            VisibilityWrapper::Polycommit => Visibility::KZGCommit,
+            VisibilityWrapper::Fixed => Visibility::Fixed,
+        }
+    }
+}
+
+/// The commonly tuned run arguments of a circuit.
+///
+/// Converting to ezkl's `RunArgs` keeps the defaults for every field not listed here.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RunArgsWrapper {
+    /// The log₂ of the number of rows of the circuit.
+    pub logrows: u32,
+    /// The visibility of the model inputs.
+    pub input_visibility: VisibilityWrapper,
+    /// The visibility of the model parameters.
+    pub param_visibility: VisibilityWrapper,
+    /// The visibility of the model outputs.
+    pub output_visibility: VisibilityWrapper,
+    /// The fixed-point scale (log₂ of the denominator) of the model inputs.
+    pub input_scale: i32,
+    /// The fixed-point scale (log₂ of the denominator) of the model parameters.
+    pub param_scale: i32,
+    /// The smallest value covered by the lookup tables.
+    pub lookup_range_min: i64,
+    /// The largest value covered by the lookup tables.
+    pub lookup_range_max: i64,
+    /// The number of inner columns used for the lookup tables.
+    pub num_inner_cols: u32,
+    /// The polynomial commitment scheme, if set.
+    pub commitment: Option<CommitmentsWrapper>,
+}
+
+impl From<&RunArgs> for RunArgsWrapper {
+    fn from(run_args: &RunArgs) -> Self {
+        RunArgsWrapper {
+            logrows: run_args.logrows,
+            input_visibility: (&run_args.input_visibility).into(),
+            param_visibility: (&run_args.param_visibility).into(),
+            output_visibility: (&run_args.output_visibility).into(),
+            input_scale: run_args.input_scale as i32,
+            param_scale: run_args.param_scale as i32,
+            lookup_range_min: run_args.lookup_range.0 as i64,
+            lookup_range_max: run_args.lookup_range.1 as i64,
+            num_inner_cols: run_args.num_inner_cols as u32,
+            commitment: run_args.commitment.map(CommitmentsWrapper::from),
+        }
+    }
+}
+
+impl From<RunArgsWrapper> for RunArgs {
+    fn from(run_args: RunArgsWrapper) -> Self {
+        RunArgs {
+            logrows: run_args.logrows,
+            input_visibility: run_args.input_visibility.into(),
+            param_visibility: run_args.param_visibility.into(),
+            output_visibility: run_args.output_visibility.into(),
+            input_scale: run_args.input_scale as _,
+            param_scale: run_args.param_scale as _,
+            lookup_range: (
+                run_args.lookup_range_min as _,
+                run_args.lookup_range_max as _,
+            ),
+            num_inner_cols: run_args.num_inner_cols as usize,
+            commitment: run_args.commitment.map(Into::into),
+            ..RunArgs::default()
+        }
+    }
+}
+
+/// Information about circuit settings, including the run arguments they were generated with.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone)]
+pub struct SettingsInfo {
+    /// The run arguments of the circuit.
+    pub run_args: RunArgsWrapper,
+    /// The number of rows used by the circuit.
+    pub num_rows: u64,
+    /// The total number of cells assigned by the circuit.
+    pub total_assignments: u64,
+    /// The total number of public instances of a proof of the circuit.
+    pub total_instances: u64,
+    /// The polynomial commitment scheme of the circuit, `KZG` if the run arguments don't set one.
+    pub commitment: CommitmentsWrapper,
+    /// The visibility of the model parameters. `Fixed` params are baked into the vk, so proofs
+    /// of the circuit carry no param instances.
+    pub param_visibility: VisibilityWrapper,
+}
+
+impl From<&GraphSettings> for SettingsInfo {
+    fn from(settings: &GraphSettings) -> Self {
+        SettingsInfo {
+            run_args: (&settings.run_args).into(),
+            num_rows: settings.num_rows as u64,
+            total_assignments: settings.total_assignments as u64,
+            total_instances: settings.total_instances().iter().sum::<usize>() as u64,
+            commitment: Commitments::from(settings.run_args.commitment).into(),
+            param_visibility: (&settings.run_args.param_visibility).into(),
+        }
+    }
+}
+
+/// Reads circuit settings, including the run arguments they were generated with.
+///
+/// # Arguments
+///
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+///
+/// # Returns
+///
+/// * `Ok(SettingsInfo)` - Information about the settings.
+/// * `Err(ExternalEZKLError)` - If the settings could not be parsed.
+#[cfg_attr(feature = "ffi", export)]
+pub fn parse_settings(settings_json: String) -> Result<SettingsInfo, ExternalEZKLError> {
+    check_input_sizes!(settings_json);
+    let settings: GraphSettings = deserialize_json("settings_json", &settings_json)?;
+    Ok(SettingsInfo::from(&settings))
+}
+
+/// The value of a symbolic dimension of a model, such as `batch_size`.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct VariableBinding {
+    /// The name of the variable, as listed in `run_args.variables` of the circuit settings.
+    pub name: String,
+    /// The value of the variable.
+    pub value: u64,
+}
+
+/// Checks the expected variables passed to a witness or prove call against the variables of a
+/// circuit.
+///
+/// The variables cannot be overridden per call. ezkl substitutes them into the ONNX graph when
+/// the model is loaded, so the compiled circuit only holds the resulting fixed shapes, and the
+/// proving and verifying keys are generated for that layout. A different value would need the
+/// ONNX model, a recompilation and new keys, none of which a witness or prove call receives. A
+/// binding can therefore only restate the compiled value; a different value is reported with the
+/// input shapes it would have to change.
+///
+/// # Arguments
+///
+/// * `circuit` - The compiled circuit.
+/// * `bindings` - The variable values requested by the caller.
+///
+/// # Returns
+///
+/// * `Ok(())` - If every binding names a variable of the circuit and matches its compiled value.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` naming the unknown or mismatched variable.
+#[cfg(feature = "prover")]
+pub(crate) fn check_variables(
+    circuit: &GraphCircuit,
+    bindings: &[VariableBinding],
+) -> Result<(), ExternalEZKLError> {
+    let variables = &circuit.settings().run_args.variables;
+    for binding in bindings {
+        let Some((_, compiled)) = variables.iter().find(|(name, _)| *name == binding.name) else {
+            let names: Vec<&str> = variables.iter().map(|(name, _)| name.as_str()).collect();
+            return Err(ExternalEZKLError::InvalidInput(format!(
+                "unknown variable `{}`, the circuit defines {:?}",
+                binding.name, names
+            )));
+        };
+        if *compiled as u64 != binding.value {
+            return Err(ExternalEZKLError::InvalidInput(format!(
+                "the circuit was compiled with {}={}, which fixes its input shapes to {:?}; recompile the model with {}={} to use inputs of a different shape",
+                binding.name,
+                compiled,
+                circuit.model().graph.input_shapes()?,
+                binding.name,
+                binding.value
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The lookup settings of a circuit, which fix its lookup tables and range checks.
+///
+/// These are compiled into the circuit, so a witness call can only restate them; see
+/// `check_range` in `WitnessOptions` for the lookup setting that can be tuned at witness time.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct LookupSettings {
+    /// The smallest value the lookup tables cover, the first bound of `run_args.lookup_range`.
+    pub lookup_range_min: i64,
+    /// The largest value the lookup tables cover, the second bound of `run_args.lookup_range`.
+    pub lookup_range_max: i64,
+    /// The number of inner columns the lookups and range checks are laid out in.
+    pub num_inner_cols: u64,
+}
+
+/// Checks the lookup settings passed to a witness call against those of a circuit.
+///
+/// # Arguments
+///
+/// * `circuit` - The compiled circuit.
+/// * `expected` - The lookup settings requested by the caller, if any.
+///
+/// # Returns
+///
+/// * `Ok(())` - If no lookup settings were requested or they match those of the circuit.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` if the requested settings are invalid, or differ
+///   from the compiled ones and would require recompiling the model.
+#[cfg(feature = "prover")]
+pub(crate) fn check_lookup_settings(
+    circuit: &GraphCircuit,
+    expected: Option<&LookupSettings>,
+) -> Result<(), ExternalEZKLError> {
+    let Some(expected) = expected else {
+        return Ok(());
+    };
+    if expected.lookup_range_min > expected.lookup_range_max {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the lookup range [{}, {}] is empty",
+            expected.lookup_range_min, expected.lookup_range_max
+        )));
+    }
+    if expected.num_inner_cols == 0 {
+        return Err(ExternalEZKLError::InvalidInput(
+            "`num_inner_cols` must be at least 1".to_string(),
+        ));
+    }
+
+    let run_args = &circuit.settings().run_args;
+    let (min, max) = run_args.lookup_range;
+    if i128::from(min) != i128::from(expected.lookup_range_min)
+        || i128::from(max) != i128::from(expected.lookup_range_max)
+    {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the circuit was compiled with the lookup range [{}, {}], which sizes its lookup tables; recompile the model with the lookup range [{}, {}] to use it",
+            min, max, expected.lookup_range_min, expected.lookup_range_max
+        )));
+    }
+    if run_args.num_inner_cols as u64 != expected.num_inner_cols {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the circuit was compiled with num_inner_cols={}, which lays out its lookups; recompile the model with num_inner_cols={} to use it",
+            run_args.num_inner_cols, expected.num_inner_cols
+        )));
+    }
+    Ok(())
+}
+
+/// The largest logrows of a circuit over the BN254 scalar field, whose multiplicative group has
+/// no larger power of two subgroup to evaluate the circuit's domain over.
+pub(crate) const MAX_LOGROWS: u32 = Fr::S;
+
+/// Returns the number of rows of a circuit with `2^logrows` rows.
+///
+/// The logrows of a circuit is read from its settings, which may be corrupted, so it is checked
+/// before it is used as a shift amount: `1 << logrows` panics or wraps for large values.
+///
+/// # Arguments
+///
+/// * `logrows` - The logrows of the circuit.
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of rows, if `logrows` is between 1 and [`MAX_LOGROWS`].
+/// * `Err(String)` - A message stating the supported range.
+pub(crate) fn rows_of(logrows: u32) -> Result<usize, String> {
+    let out_of_range = || {
+        format!(
+            "logrows = {} is out of range, circuits have between 1 and {} logrows",
+            logrows, MAX_LOGROWS
+        )
+    };
+    if logrows == 0 || logrows > MAX_LOGROWS {
+        return Err(out_of_range());
+    }
+    1usize.checked_shl(logrows).ok_or_else(out_of_range)
+}
+
+/// Checks that the logrows of the circuit settings is in range, see [`rows_of`].
+pub(crate) fn check_logrows(settings: &GraphSettings) -> Result<(), ExternalEZKLError> {
+    rows_of(settings.run_args.logrows)
+        .map(|_| ())
+        .map_err(ExternalEZKLError::InvalidInput)
+}
+
+/// Whether witness generation for a circuit needs the SRS.
+///
+/// Only circuits with polycommit visibility do, to commit to the committed values.
+pub(crate) fn requires_srs_for_witness(settings: &GraphSettings) -> bool {
+    settings.module_requires_polycommit()
+}
+
+/// Whether witness generation for a circuit needs the vk.
+///
+/// Only circuits with polycommit visibility do, as the commitments skip the blinding rows,
+/// whose number is read from the constraint system in the vk.
+pub(crate) fn requires_vk_for_witness(settings: &GraphSettings) -> bool {
+    settings.module_requires_polycommit()
+}
+
+/// Checks that a verification key was generated for the circuit described by the settings.
+///
+/// A vk doesn't record the settings it was generated from, so it is compared on what it does
+/// record: the number of rows of its domain and its fixed column commitments. A vk generated for
+/// a differently configured build of the circuit would otherwise only surface as an opaque
+/// synthesis error or an invalid proof.
+///
+/// # Arguments
+///
+/// * `vk` - The verification key, deserialized against the settings.
+/// * `settings` - The settings of the compiled circuit.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the vk matches the settings.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` describing the first disagreement.
+pub(crate) fn check_vk_matches_settings(
+    vk: &VerifyingKey<G1Affine>,
+    settings: &GraphSettings,
+) -> Result<(), ExternalEZKLError> {
+    let vk_logrows = vk.get_domain().k();
+    if vk_logrows != settings.run_args.logrows {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the verification key was generated for logrows={} but the circuit was compiled with logrows={}; regenerate the keys from this circuit",
+            vk_logrows, settings.run_args.logrows
+        )));
+    }
+    let vk_fixed_columns = vk.fixed_commitments().len();
+    let circuit_fixed_columns = vk.cs().num_fixed_columns();
+    if vk_fixed_columns != circuit_fixed_columns {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the verification key commits to {} fixed columns but the circuit has {} (with {} instance columns, {:?} commitments); regenerate the keys from this circuit",
+            vk_fixed_columns,
+            circuit_fixed_columns,
+            settings.total_instances().len(),
+            CommitmentsWrapper::from(Commitments::from(settings.run_args.commitment)),
+        )));
+    }
+    Ok(())
+}
+
+/// Settings fields that don't change the circuit, so proofs verify regardless of their values.
+const COSMETIC_SETTINGS_FIELDS: [&str; 6] = [
+    "version",
+    "timestamp",
+    "check_mode",
+    "input_types",
+    "output_types",
+    "run_args.check_mode",
+];
+
+/// A field that differs between two settings.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SettingsDifference {
+    /// The path of the field, e.g. `run_args.logrows`.
+    pub field: String,
+    /// The JSON representation of the value in the first settings.
+    pub a: String,
+    /// The JSON representation of the value in the second settings.
+    pub b: String,
+    /// Whether a proof for one settings fails to verify against the other.
+    pub breaks_verification: bool,
+}
+
+/// The result of comparing two settings.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Serialize, Debug, Clone)]
+pub struct CompatReport {
+    /// `true` if no difference breaks verification.
+    pub compatible: bool,
+    /// Every differing field.
+    pub differences: Vec<SettingsDifference>,
+}
+
+/// Compares two circuit settings and reports whether they describe the same circuit for
+/// verification purposes, e.g. when the settings served by a backend and those bundled with
+/// an app may be of different versions.
+///
+/// # Arguments
+///
+/// * `a_json` - A `String` containing the JSON representation of the first circuit settings.
+/// * `b_json` - A `String` containing the JSON representation of the second circuit settings.
+///
+/// # Returns
+///
+/// * `Ok(CompatReport)` - The differing fields, split into verification-breaking and cosmetic ones.
+/// * `Err(ExternalEZKLError)` - If either settings could not be parsed.
+#[cfg_attr(feature = "ffi", export)]
+pub fn settings_compatible(
+    a_json: String,
+    b_json: String,
+) -> Result<CompatReport, ExternalEZKLError> {
+    check_input_sizes!(a_json, b_json);
+    let a: GraphSettings = deserialize_json("a_json", &a_json)?;
+    let b: GraphSettings = deserialize_json("b_json", &b_json)?;
+
+    // Both settings are re-serialized, so fields missing from older settings get their defaults
+    let mut differences = vec![];
+    diff_values(
+        "",
+        &serde_json::to_value(&a)?,
+        &serde_json::to_value(&b)?,
+        &mut differences,
+    );
+    Ok(CompatReport {
+        compatible: !differences.iter().any(|d| d.breaks_verification),
+        differences,
+    })
+}
+
+/// Collects the differences between two values, descending into objects.
+///
+/// Arrays such as the instance shapes are compared as a whole.
+fn diff_values(path: &str, a: &Value, b: &Value, differences: &mut Vec<SettingsDifference>) {
+    match (a, b) {
+        (Value::Object(a_fields), Value::Object(b_fields)) => {
+            let b_only = b_fields.keys().filter(|key| !a_fields.contains_key(*key));
+            for key in a_fields.keys().chain(b_only) {
+                let field = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                diff_values(
+                    &field,
+                    a_fields.get(key).unwrap_or(&Value::Null),
+                    b_fields.get(key).unwrap_or(&Value::Null),
+                    differences,
+                );
+            }
+        }
+        _ if a != b => differences.push(SettingsDifference {
+            field: path.to_string(),
+            a: a.to_string(),
+            b: b.to_string(),
+            breaks_verification: !COSMETIC_SETTINGS_FIELDS.contains(&path),
+        }),
+        _ => {}
+    }
+}
@@ -0,0 +1,370 @@
+use crate::inspect::CommitmentsWrapper;
+use crate::limits::check_input_sizes;
+use crate::logging::info;
+use crate::serialization::{deserialize_json, deserialize_params_prover};
+use crate::settings::check_logrows;
+use crate::ExternalEZKLError;
+use ezkl::graph::GraphSettings;
+use halo2_proofs::halo2curves::bn256::{Bn256, G1Affine, G2Affine};
+use halo2_proofs::halo2curves::group::prime::PrimeCurveAffine;
+use halo2_proofs::halo2curves::group::GroupEncoding;
+use halo2_proofs::halo2curves::pairing::Engine;
+use halo2_proofs::halo2curves::serde::SerdeObject;
+use halo2_proofs::poly::commitment::Params;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use std::io::Read;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The size of the `k` field at the start of an SRS file.
+const K_LEN: usize = 4;
+/// The size of a G1 point in the uncompressed raw format KZG parameters are stored in.
+const KZG_G1_LEN: u64 = 64;
+/// The size of a G2 point in the uncompressed raw format KZG parameters are stored in.
+const KZG_G2_LEN: u64 = 128;
+/// The size of a G1 point in the compressed format IPA parameters are stored in.
+const IPA_G1_LEN: u64 = 32;
+/// The number of bytes needed to read `k` and guess the commitment scheme.
+pub const SRS_HEADER_LEN: usize = K_LEN + KZG_G1_LEN as usize;
+/// The largest `k` an SRS file can reasonably have.
+const MAX_SRS_K: u32 = 32;
+
+/// Information read from the start of an SRS file.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SrsHeader {
+    /// The log₂ of the number of points of the SRS.
+    pub k: u32,
+    /// The expected size of the complete SRS file in bytes, for the guessed commitment scheme
+    /// (KZG if it could not be guessed).
+    pub estimated_full_size: u64,
+    /// The commitment scheme the SRS appears to be for, if it could be determined.
+    pub commitment_guess: Option<CommitmentsWrapper>,
+}
+
+impl SrsHeader {
+    /// Reads the header from the first bytes of an SRS file.
+    pub(crate) fn read(prefix: &[u8]) -> Result<Self, ExternalEZKLError> {
+        let k_bytes: [u8; K_LEN] = prefix
+            .get(..K_LEN)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                ExternalEZKLError::InvalidInput(format!(
+                    "an SRS starts with at least {} bytes, got {}",
+                    K_LEN,
+                    prefix.len()
+                ))
+            })?;
+        let k = u32::from_le_bytes(k_bytes);
+        if k == 0 || k > MAX_SRS_K {
+            return Err(ExternalEZKLError::InvalidInput(format!(
+                "the data does not look like an SRS: k = {} is out of range",
+                k
+            )));
+        }
+
+        let commitment_guess = guess_commitment(&prefix[K_LEN..]);
+        let estimated_full_size =
+            full_srs_size(commitment_guess.unwrap_or(CommitmentsWrapper::KZG), k);
+
+        Ok(SrsHeader {
+            k,
+            estimated_full_size,
+            commitment_guess,
+        })
+    }
+}
+
+/// The size of an SRS and the size it was downsized to when it was loaded for a circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SrsUsage {
+    /// The `k` of the SRS as given.
+    pub(crate) original_k: u32,
+    /// The `k` the SRS was used with, after downsizing it to the requested size.
+    pub(crate) used_k: u32,
+}
+
+impl SrsUsage {
+    /// Reads how an SRS is used when loaded for `requested_k`, which downsizes larger SRSs.
+    pub(crate) fn read(srs: &[u8], requested_k: u32) -> Result<Self, ExternalEZKLError> {
+        let original_k = SrsHeader::read(srs)?.k;
+        Ok(SrsUsage {
+            original_k,
+            used_k: requested_k.min(original_k),
+        })
+    }
+
+    /// Whether the SRS was larger than needed and was downsized.
+    pub(crate) fn downsized(&self) -> bool {
+        self.used_k < self.original_k
+    }
+}
+
+/// Returns the `k` an SRS is used with for a circuit.
+///
+/// An SRS larger than the circuit needs is downsized to its logrows when it is loaded, so a
+/// proof can be created and verified with any SRS at least as large as the circuit. Proofs made
+/// with a downsized SRS verify with the original SRS, and vice versa.
+///
+/// # Arguments
+///
+/// * `srs` - A `Vec<u8>` containing the SRS, or at least its first `SRS_HEADER_LEN` bytes.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+///
+/// # Returns
+///
+/// * `Ok(u32)` - The logrows of the circuit, which the SRS is downsized to if it is larger.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` if the SRS is too small for the circuit or does not look like an SRS.
+#[cfg_attr(feature = "ffi", export)]
+pub fn effective_srs_k(srs: Vec<u8>, settings_json: String) -> Result<u32, ExternalEZKLError> {
+    check_input_sizes!(srs, settings_json);
+    let settings: GraphSettings = deserialize_json("settings_json", &settings_json)?;
+    check_logrows(&settings)?;
+    let logrows = settings.run_args.logrows;
+    let usage = SrsUsage::read(&srs, logrows)?;
+    if usage.original_k < logrows {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the SRS has k = {}, but the circuit needs logrows {}",
+            usage.original_k, logrows
+        )));
+    }
+    Ok(usage.used_k)
+}
+
+/// Returns the size in bytes of a complete SRS with `2^k` points for the commitment scheme.
+pub(crate) fn full_srs_size(commitment: CommitmentsWrapper, k: u32) -> u64 {
+    let n = 1u64 << k;
+    match commitment {
+        // k, then g and g_lagrange, then g2 and s_g2
+        CommitmentsWrapper::KZG => K_LEN as u64 + 2 * n * KZG_G1_LEN + 2 * KZG_G2_LEN,
+        // k, then g and g_lagrange, then w and u
+        CommitmentsWrapper::IPA => K_LEN as u64 + 2 * n * IPA_G1_LEN + 2 * IPA_G1_LEN,
+    }
+}
+
+/// Guesses the commitment scheme from the first point of the SRS.
+///
+/// A KZG SRS starts with the G1 generator in raw form, an IPA SRS with a compressed random point.
+fn guess_commitment(points: &[u8]) -> Option<CommitmentsWrapper> {
+    if let Some(raw) = points.get(..KZG_G1_LEN as usize) {
+        if G1Affine::from_raw_bytes(raw) == Some(<G1Affine as PrimeCurveAffine>::generator()) {
+            return Some(CommitmentsWrapper::KZG);
+        }
+    }
+
+    let compressed = points.get(..IPA_G1_LEN as usize)?;
+    let mut repr = <G1Affine as GroupEncoding>::Repr::default();
+    repr.as_mut().copy_from_slice(compressed);
+    Option::<G1Affine>::from(G1Affine::from_bytes(&repr)).map(|_| CommitmentsWrapper::IPA)
+}
+
+/// Reads the size and commitment scheme of an SRS from its first bytes, without loading it.
+///
+/// Only the first `SRS_HEADER_LEN` bytes are needed, so apps can pick the right SRS for a
+/// circuit without reading whole files.
+///
+/// # Arguments
+///
+/// * `srs_prefix` - A `Vec<u8>` containing the start of the SRS; any longer prefix, including the whole SRS, works too.
+///
+/// # Returns
+///
+/// * `Ok(SrsHeader)` - The information read from the SRS header.
+/// * `Err(ExternalEZKLError)` - If the data is too short or does not look like an SRS.
+#[cfg_attr(feature = "ffi", export)]
+pub fn inspect_srs(srs_prefix: Vec<u8>) -> Result<SrsHeader, ExternalEZKLError> {
+    check_input_sizes!(srs_prefix);
+    SrsHeader::read(&srs_prefix)
+}
+
+/// Reads the size and commitment scheme of an SRS file, reading only its first bytes.
+///
+/// # Arguments
+///
+/// * `srs_path` - The path of the SRS file.
+///
+/// # Returns
+///
+/// * `Ok(SrsHeader)` - The information read from the SRS header.
+/// * `Err(ExternalEZKLError)` - If the file could not be read or does not look like an SRS.
+#[cfg_attr(feature = "ffi", export)]
+pub fn inspect_srs_file(srs_path: String) -> Result<SrsHeader, ExternalEZKLError> {
+    let io_error = |e: std::io::Error| {
+        ExternalEZKLError::InvalidInput(format!("failed to read `{}`: {}", srs_path, e))
+    };
+
+    let file = std::fs::File::open(&srs_path).map_err(io_error)?;
+    let mut prefix = Vec::with_capacity(SRS_HEADER_LEN);
+    file.take(SRS_HEADER_LEN as u64)
+        .read_to_end(&mut prefix)
+        .map_err(io_error)?;
+
+    SrsHeader::read(&prefix)
+}
+
+/// A KZG SRS split into a small part sufficient for verification and an extension with the
+/// remaining points needed for proving.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct SrsParts {
+    /// A complete SRS of size `2^verifier_k`, usable on its own for verification.
+    pub verifier_srs: Vec<u8>,
+    /// `k` of the full SRS, followed by the G1 points missing from the verifier SRS, the Lagrange
+    /// basis of the full SRS and its G2 points.
+    pub extension: Vec<u8>,
+}
+
+/// Splits a KZG SRS into a verifier SRS, to ship with the app, and an extension, to download only
+/// when proving is needed.
+///
+/// # Arguments
+///
+/// * `srs` - A `Vec<u8>` containing the complete KZG SRS.
+/// * `verifier_k` - The log₂ of the number of points of the verifier SRS; `1` is enough to verify.
+///
+/// # Returns
+///
+/// * `Ok(SrsParts)` - The verifier SRS and the extension.
+/// * `Err(ExternalEZKLError)` - If the SRS is not a complete KZG SRS larger than `2^verifier_k`.
+#[cfg_attr(feature = "ffi", export)]
+pub fn split_srs(srs: Vec<u8>, verifier_k: u32) -> Result<SrsParts, ExternalEZKLError> {
+    check_input_sizes!(srs);
+    let header = SrsHeader::read(&srs)?;
+    let expected_len = full_srs_size(CommitmentsWrapper::KZG, header.k);
+    if header.commitment_guess != Some(CommitmentsWrapper::KZG) || srs.len() as u64 != expected_len
+    {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "only a complete KZG SRS can be split: expected {} bytes for k = {}, got {}",
+            expected_len,
+            header.k,
+            srs.len()
+        )));
+    }
+    if verifier_k == 0 || verifier_k >= header.k {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the verifier SRS must be smaller than the SRS: verifier_k = {}, k = {}",
+            verifier_k, header.k
+        )));
+    }
+
+    // Downsizing recomputes the Lagrange basis for the smaller domain
+    let params = deserialize_params_prover::<KZGCommitmentScheme<Bn256>>(Some(&srs), verifier_k)?;
+    let mut verifier_srs =
+        Vec::with_capacity(full_srs_size(CommitmentsWrapper::KZG, verifier_k) as usize);
+    params
+        .write(&mut verifier_srs)
+        .map_err(|e| ExternalEZKLError::InternalError(e.to_string()))?;
+
+    let mut extension = srs[..K_LEN].to_vec();
+    extension.extend_from_slice(&srs[g1_offset(1 << verifier_k)..]);
+
+    Ok(SrsParts {
+        verifier_srs,
+        extension,
+    })
+}
+
+/// Returns the offset of the `index`-th G1 point of a KZG SRS.
+fn g1_offset(index: u64) -> usize {
+    K_LEN + (index * KZG_G1_LEN) as usize
+}
+
+/// Reassembles a complete KZG SRS from a verifier SRS and an extension created by [`split_srs`].
+///
+/// # Arguments
+///
+/// * `verifier_srs` - The verifier SRS.
+/// * `extension` - The extension of the same SRS.
+/// * `logrows` - The logrows of the circuit the SRS is for.
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)` - The complete SRS.
+/// * `Err(ExternalEZKLError)` - If the parts are truncated, belong to different SRSs, or are too
+///   small for the circuit.
+// Only used by proving, which verify-only builds leave out
+#[cfg_attr(not(feature = "prover"), allow(dead_code))]
+pub(crate) fn stitch_srs(
+    verifier_srs: &[u8],
+    extension: &[u8],
+    logrows: u32,
+) -> Result<Vec<u8>, ExternalEZKLError> {
+    let verifier_k = SrsHeader::read(verifier_srs)?.k;
+    let k = SrsHeader::read(extension)?.k;
+    if k < logrows {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the extended SRS has k = {}, but the circuit needs logrows {}",
+            k, logrows
+        )));
+    }
+    if k <= verifier_k {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the SRS extension has k = {}, which does not extend the verifier SRS with k = {}",
+            k, verifier_k
+        )));
+    }
+
+    let verifier_len = full_srs_size(CommitmentsWrapper::KZG, verifier_k);
+    if verifier_srs.len() as u64 != verifier_len {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the verifier SRS with k = {} should be {} bytes, got {}",
+            verifier_k,
+            verifier_len,
+            verifier_srs.len()
+        )));
+    }
+    let (n, verifier_n) = (1u64 << k, 1u64 << verifier_k);
+    let extension_len = K_LEN as u64 + (2 * n - verifier_n) * KZG_G1_LEN + 2 * KZG_G2_LEN;
+    if extension.len() as u64 != extension_len {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the SRS extension from k = {} to k = {} should be {} bytes, got {}",
+            verifier_k,
+            k,
+            extension_len,
+            extension.len()
+        )));
+    }
+
+    let g2_len = 2 * KZG_G2_LEN as usize;
+    let g2_points = &verifier_srs[verifier_srs.len() - g2_len..];
+    if g2_points != &extension[extension.len() - g2_len..] {
+        return Err(ExternalEZKLError::InvalidInput(
+            "the SRS extension belongs to a different SRS: the G2 points differ".to_string(),
+        ));
+    }
+
+    // The first point of the extension must be the next power of tau after the last verifier point
+    let last_verifier_point = &verifier_srs[g1_offset(verifier_n - 1)..g1_offset(verifier_n)];
+    let first_extension_point = &extension[K_LEN..g1_offset(1)];
+    if !continues(last_verifier_point, first_extension_point, g2_points) {
+        return Err(ExternalEZKLError::InvalidInput(
+            "the SRS extension does not continue the verifier SRS".to_string(),
+        ));
+    }
+
+    let mut srs = Vec::with_capacity(full_srs_size(CommitmentsWrapper::KZG, k) as usize);
+    srs.extend_from_slice(&extension[..K_LEN]);
+    srs.extend_from_slice(&verifier_srs[K_LEN..g1_offset(verifier_n)]);
+    srs.extend_from_slice(&extension[K_LEN..]);
+    info!(
+        "stitched an SRS with k = {} from a verifier SRS with k = {}",
+        k, verifier_k
+    );
+    Ok(srs)
+}
+
+/// Checks that `next = tau * previous` using the pairing `e(next, g2) == e(previous, tau * g2)`.
+fn continues(previous: &[u8], next: &[u8], g2_points: &[u8]) -> bool {
+    let (g2, s_g2) = g2_points.split_at(KZG_G2_LEN as usize);
+    match (
+        G1Affine::from_raw_bytes(previous),
+        G1Affine::from_raw_bytes(next),
+        G2Affine::from_raw_bytes(g2),
+        G2Affine::from_raw_bytes(s_g2),
+    ) {
+        (Some(previous), Some(next), Some(g2), Some(s_g2)) => {
+            Bn256::pairing(&next, &g2) == Bn256::pairing(&previous, &s_g2)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,221 @@
+use crate::files::{io_error, read_artifact, ArtifactMetadata, OutputFile};
+use crate::ExternalEZKLError;
+use sha2::{Digest, Sha256};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// The extension of the file holding the SHA-256 digest of a stored artifact.
+const DIGEST_EXTENSION: &str = "sha256";
+
+/// The kind of an artifact kept in an [`ArtifactStore`].
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArtifactKind {
+    CompiledCircuit,
+    Settings,
+    ProvingKey,
+    VerificationKey,
+    Srs,
+}
+
+impl ArtifactKind {
+    /// The file name of the artifact inside the directory of its circuit, matching the ezkl CLI defaults.
+    fn file_name(self) -> &'static str {
+        match self {
+            ArtifactKind::CompiledCircuit => "network.ezkl",
+            ArtifactKind::Settings => "settings.json",
+            ArtifactKind::ProvingKey => "pk.key",
+            ArtifactKind::VerificationKey => "vk.key",
+            ArtifactKind::Srs => "kzg.srs",
+        }
+    }
+}
+
+/// A directory of circuit artifacts at deterministic paths, keyed by the circuit fingerprint.
+///
+/// Each artifact is stored at `<base_dir>/<circuit_fingerprint>/<file name>` next to a file with
+/// its SHA-256 digest, which is checked whenever the artifact is loaded. Constructing the store
+/// from the current sandbox location, e.g. after an app group migration, keeps every path valid.
+#[cfg_attr(feature = "ffi", derive(uniffi::Object))]
+#[derive(Debug)]
+pub struct ArtifactStore {
+    base_dir: PathBuf,
+}
+
+impl ArtifactStore {
+    fn artifact_path(
+        &self,
+        kind: ArtifactKind,
+        circuit_fingerprint: &str,
+    ) -> Result<PathBuf, ExternalEZKLError> {
+        // Fingerprints are hex digests; anything else could escape the base directory
+        if circuit_fingerprint.is_empty()
+            || !circuit_fingerprint.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(ExternalEZKLError::InvalidInput(format!(
+                "`{}` is not a circuit fingerprint; use the value returned by `circuit_fingerprint`",
+                circuit_fingerprint
+            )));
+        }
+        Ok(self
+            .base_dir
+            .join(circuit_fingerprint.to_ascii_lowercase())
+            .join(kind.file_name()))
+    }
+
+    /// Reads an artifact, failing if it is missing or its content doesn't match its stored digest.
+    pub(crate) fn read(
+        &self,
+        kind: ArtifactKind,
+        circuit_fingerprint: &str,
+    ) -> Result<Vec<u8>, ExternalEZKLError> {
+        let path = self.artifact_path(kind, circuit_fingerprint)?;
+        let digest_path = digest_path(&path);
+        let bytes = read_artifact(&path.display().to_string())?;
+        let expected = std::fs::read_to_string(&digest_path)
+            .map_err(|e| io_error("read", &digest_path.display().to_string(), e))?;
+
+        let actual = hex::encode(Sha256::digest(&bytes));
+        if !expected.trim().eq_ignore_ascii_case(&actual) {
+            return Err(ExternalEZKLError::FingerprintMismatch {
+                artifact: format!("stored {:?}", kind),
+                expected: expected.trim().to_string(),
+                actual,
+            });
+        }
+        Ok(bytes)
+    }
+}
+
+/// Returns the path of the file holding the digest of the artifact at the given path.
+fn digest_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(DIGEST_EXTENSION);
+    PathBuf::from(name)
+}
+
+#[cfg_attr(feature = "ffi", export)]
+impl ArtifactStore {
+    /// Creates a store rooted at the given directory, which is created when the first artifact is stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_dir` - The directory holding the artifacts, e.g. inside the app group container.
+    #[cfg_attr(feature = "ffi", uniffi::constructor)]
+    pub fn new(base_dir: String) -> Arc<Self> {
+        Arc::new(ArtifactStore {
+            base_dir: base_dir.into(),
+        })
+    }
+
+    /// Returns the path an artifact is stored at, whether or not it exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the artifact.
+    /// * `circuit_fingerprint` - The fingerprint of the circuit, as returned by `circuit_fingerprint`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - The path of the artifact.
+    /// * `Err(ExternalEZKLError)` - `InvalidInput` if the fingerprint is not a hex string.
+    pub fn path_for(
+        &self,
+        kind: ArtifactKind,
+        circuit_fingerprint: String,
+    ) -> Result<String, ExternalEZKLError> {
+        Ok(self
+            .artifact_path(kind, &circuit_fingerprint)?
+            .display()
+            .to_string())
+    }
+
+    /// Returns `true` if the artifact and its digest are stored.
+    pub fn exists(&self, kind: ArtifactKind, circuit_fingerprint: String) -> bool {
+        self.artifact_path(kind, &circuit_fingerprint)
+            .map(|path| path.is_file() && digest_path(&path).is_file())
+            .unwrap_or(false)
+    }
+
+    /// Stores an artifact and its digest, replacing any previous version.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the artifact.
+    /// * `circuit_fingerprint` - The fingerprint of the circuit, as returned by `circuit_fingerprint`.
+    /// * `bytes` - A `Vec<u8>` containing the artifact.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ArtifactMetadata)` - The size and digest of the stored artifact.
+    /// * `Err(ExternalEZKLError)` - If the fingerprint is invalid or the artifact could not be written.
+    pub fn store(
+        &self,
+        kind: ArtifactKind,
+        circuit_fingerprint: String,
+        bytes: Vec<u8>,
+    ) -> Result<ArtifactMetadata, ExternalEZKLError> {
+        let path = self.artifact_path(kind, &circuit_fingerprint)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| io_error("create", &dir.display().to_string(), e))?;
+        }
+
+        let metadata = OutputFile::create(&path.display().to_string())?.write_bytes(&bytes)?;
+        OutputFile::create(&digest_path(&path).display().to_string())?
+            .write_bytes(metadata.sha256.as_bytes())?;
+        Ok(metadata)
+    }
+
+    /// Loads an artifact after checking it against its stored digest.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the artifact.
+    /// * `circuit_fingerprint` - The fingerprint of the circuit, as returned by `circuit_fingerprint`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<u8>)` - The artifact.
+    /// * `Err(ExternalEZKLError)` - `FingerprintMismatch` if the artifact was modified or corrupted
+    ///   since it was stored, or `InvalidInput` if it is not stored.
+    pub fn load(
+        &self,
+        kind: ArtifactKind,
+        circuit_fingerprint: String,
+    ) -> Result<Vec<u8>, ExternalEZKLError> {
+        self.read(kind, &circuit_fingerprint)
+    }
+
+    /// Removes an artifact and its digest.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the artifact.
+    /// * `circuit_fingerprint` - The fingerprint of the circuit, as returned by `circuit_fingerprint`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the artifact was stored.
+    /// * `Err(ExternalEZKLError)` - If the fingerprint is invalid or the artifact could not be removed.
+    pub fn evict(
+        &self,
+        kind: ArtifactKind,
+        circuit_fingerprint: String,
+    ) -> Result<bool, ExternalEZKLError> {
+        let path = self.artifact_path(kind, &circuit_fingerprint)?;
+        let mut removed = false;
+        for path in [digest_path(&path), path] {
+            match std::fs::remove_file(&path) {
+                Ok(()) => removed = true,
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(io_error("remove", &path.display().to_string(), e)),
+            }
+        }
+        Ok(removed)
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+#[cfg(feature = "ffi")]
+use uniffi::export;
+
+/// A listener implemented by the host application, receiving interval markers around the
+/// stages of the library, e.g. to forward them to `os_signpost`.
+///
+/// The stages are `deserialize_circuit`, `deserialize_vk`, `deserialize_pk`, `load_srs`,
+/// `forward` (witness generation), `load_witness`, `create_proof` (which includes the circuit
+/// synthesis), `compile_protocol` (nested in `create_proof` for `ForAggr` proofs) and `verify_proof`.
+#[cfg_attr(feature = "ffi", uniffi::export(with_foreign))]
+pub trait TraceListener: Send + Sync {
+    /// Called when a stage begins. Returns an identifier passed to the matching `end_interval`.
+    fn begin_interval(&self, name: String) -> u64;
+    /// Called when the stage started by the `begin_interval` call returning `id` ends.
+    fn end_interval(&self, id: u64);
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static LISTENER: RwLock<Option<Arc<dyn TraceListener>>> = RwLock::new(None);
+
+/// Installs the listener receiving interval markers around the stages of the library.
+///
+/// # Arguments
+///
+/// * `listener` - The listener to install, or `None` to stop emitting markers.
+#[cfg_attr(feature = "ffi", export)]
+pub fn set_trace_listener(listener: Option<Arc<dyn TraceListener>>) {
+    let enabled = listener.is_some();
+    if let Ok(mut current) = LISTENER.write() {
+        *current = listener;
+    }
+    ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// An interval reported to the installed listener, ended when dropped.
+///
+/// Without a listener, beginning an interval only reads an atomic flag.
+pub(crate) struct Interval {
+    active: Option<(Arc<dyn TraceListener>, u64)>,
+}
+
+impl Interval {
+    /// Begins an interval for the named stage.
+    pub(crate) fn begin(name: &str) -> Self {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return Interval { active: None };
+        }
+        let listener = LISTENER.read().ok().and_then(|listener| listener.clone());
+        Interval {
+            active: listener.map(|listener| {
+                let id = listener.begin_interval(name.to_string());
+                (listener, id)
+            }),
+        }
+    }
+}
+
+impl Drop for Interval {
+    fn drop(&mut self) {
+        if let Some((listener, id)) = self.active.take() {
+            listener.end_interval(id);
+        }
+    }
+}
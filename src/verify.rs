@@ -1,28 +1,47 @@
-use crate::serialization::{deserialize_params_verifier, deserialize_vk};
+use crate::clock::Instant;
+use crate::compat::{cli_compatible_settings, VerifyOptions, VerifyStrictness};
+use crate::compression::json_text;
+use crate::executor::{run_on_worker, WorkKind};
+use crate::inspect::{decode_point, instance_layout, CommitmentsWrapper};
+use crate::limits::check_input_sizes;
+use crate::logging::debug;
+use crate::normalize::{encode_felt, parse_felt, FeltEncoding};
+use crate::serialization::{
+    deserialize_json, deserialize_params_verifier, deserialize_proof, deserialize_vk, prepared_vk,
+    prepared_vk_bytes,
+};
+use crate::settings::{check_logrows, check_vk_matches_settings, rows_of};
+use crate::srs::{full_srs_size, SrsHeader, SrsUsage};
+use crate::trace::Interval;
 use crate::{ExternalEZKLError, IPASingleStrategy, KZGSingleStrategy};
-use ezkl::graph::{GraphCircuit, GraphSettings};
+use ezkl::circuit::modules::poseidon::spec::{PoseidonSpec, POSEIDON_RATE, POSEIDON_WIDTH};
+use ezkl::circuit::modules::poseidon::PoseidonChip;
+use ezkl::circuit::modules::Module;
+use ezkl::fieldutils::felt_to_integer_rep;
+use ezkl::graph::{scale_to_multiplier, GraphCircuit, GraphSettings, Visibility};
 use ezkl::pfsys::evm::aggregation_kzg::PoseidonTranscript;
-use ezkl::pfsys::{verify_proof_circuit, Snark, TranscriptType};
+use ezkl::pfsys::{swap_proof_commitments_polycommit, verify_proof_circuit, Snark, TranscriptType};
 use ezkl::{Commitments, EZKLError as InnerEZKLError};
 use halo2_proofs::halo2curves::bn256::{Bn256, Fr, G1Affine};
 use halo2_proofs::halo2curves::ff::{FromUniformBytes, WithSmallOrderMulGroup};
 use halo2_proofs::halo2curves::serde::SerdeObject;
 use halo2_proofs::plonk;
 use halo2_proofs::plonk::Circuit;
-use halo2_proofs::poly::commitment::{CommitmentScheme, Verifier};
+use halo2_proofs::poly::commitment::{CommitmentScheme, Params as _, Verifier};
 use halo2_proofs::poly::ipa::commitment::{IPACommitmentScheme, ParamsIPA};
 use halo2_proofs::poly::ipa::multiopen::VerifierIPA;
 use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
 use halo2_proofs::poly::kzg::multiopen::VerifierSHPLONK;
 use halo2_proofs::poly::VerificationStrategy;
 use halo2_proofs::transcript::{EncodedChallenge, TranscriptReadBuffer};
+use halo2_proofs::SerdeFormat::RawBytes;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use snark_verifier::loader::native::NativeLoader;
 use snark_verifier::system::halo2::transcript::evm::EvmTranscript;
 use std::io::Cursor;
-use std::time::Instant;
-use uniffi::deps::log::info;
+use std::time::Duration;
+#[cfg(feature = "ffi")]
 use uniffi::export;
 
 /// Verifies a proof using the provided proof data, circuit settings, verification key, and SRS.
@@ -38,32 +57,771 @@ use uniffi::export;
 ///
 /// * `Ok(bool)` - `true` if the proof is valid, `false` if the proof is invalid.
 /// * `Err(ExternalEZKLError)` - An error that occurred during verification.
-#[export]
+#[cfg_attr(feature = "ffi", export)]
 pub fn verify(
     proof_json: String,
     settings_json: String,
     vk: Vec<u8>,
     srs: Vec<u8>,
 ) -> Result<bool, ExternalEZKLError> {
-    verify_internal(proof_json, settings_json, &vk, Some(&srs), false).map_err(|e| e.into())
+    check_input_sizes!(proof_json, settings_json, vk, srs);
+    let verification = verify_internal(proof_json, settings_json, &vk, Some(&srs), false, false)?;
+    verification.into_validity()
 }
 
+/// Verifies a proof like [`verify`], on a worker thread instead of the calling thread.
+///
+/// The worker thread is named `ezkl-verify` and runs with the `verify_thread_priority` of the
+/// configuration, `UserInitiated` by default, as the user usually waits for the result.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof to be verified.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(bool)` - `true` if the proof is valid, `false` if the proof is invalid.
+/// * `Err(ExternalEZKLError)` - An error that occurred during verification.
+#[cfg_attr(feature = "ffi", export)]
+pub async fn verify_async(
+    proof_json: String,
+    settings_json: String,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+) -> Result<bool, ExternalEZKLError> {
+    check_input_sizes!(proof_json, settings_json, vk, srs);
+    run_on_worker(WorkKind::Verify, move || {
+        verify(proof_json, settings_json, vk, srs)
+    })
+    .await
+}
+
+/// Verifies a proof like [`verify`], taking the proof as plain or gzip-compressed JSON.
+///
+/// # Arguments
+///
+/// * `proof` - The JSON representation of the proof, either as UTF-8 text or gzip-compressed.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(bool)` - `true` if the proof is valid, `false` if the proof is invalid.
+/// * `Err(ExternalEZKLError)` - An error that occurred during verification.
+#[cfg_attr(feature = "ffi", export)]
+pub fn verify_bytes(
+    proof: Vec<u8>,
+    settings_json: String,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+) -> Result<bool, ExternalEZKLError> {
+    check_input_sizes!(proof, settings_json, vk, srs);
+    verify(json_text("proof", proof)?, settings_json, vk, srs)
+}
+
+/// Verifies a proof like [`verify`], using only as much of the SRS as the commitment scheme needs.
+///
+/// KZG proofs are verified with a reduced SRS if the given one is smaller than the circuit, as
+/// the SHPLONK verifier only needs its first G1 point and its G2 points. IPA proofs require the
+/// full SRS for the circuit.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof to be verified.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - An optional `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(bool)` - `true` if the proof is valid, `false` if the proof is invalid.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` stating the required SRS if it is missing or too small, or an error that occurred during verification.
+#[cfg_attr(feature = "ffi", export)]
+pub fn verify_flexible(
+    proof_json: String,
+    settings_json: String,
+    vk: Vec<u8>,
+    srs: Option<Vec<u8>>,
+) -> Result<bool, ExternalEZKLError> {
+    check_input_sizes!(proof_json, settings_json, vk, srs);
+    let settings: GraphSettings = deserialize_json("settings_json", &settings_json)?;
+    check_logrows(&settings)?;
+    let logrows = settings.run_args.logrows;
+
+    let reduced_srs = match Commitments::from(settings.run_args.commitment) {
+        Commitments::KZG => {
+            let srs = srs.as_deref().ok_or_else(|| {
+                ExternalEZKLError::InvalidInput(
+                    "KZG verification requires an SRS, but a reduced SRS of any size is sufficient"
+                        .to_string(),
+                )
+            })?;
+            SrsHeader::read(srs)?.k < logrows
+        }
+        Commitments::IPA => {
+            let expected = full_srs_size(CommitmentsWrapper::IPA, logrows);
+            let provided = srs.as_ref().map_or(0, |srs| srs.len() as u64);
+            if provided < expected {
+                return Err(ExternalEZKLError::InvalidInput(format!(
+                    "IPA verification requires the full SRS for logrows {}: expected at least {} bytes, got {}",
+                    logrows, expected, provided
+                )));
+            }
+            false
+        }
+    };
+
+    let verification = verify_internal(
+        proof_json,
+        settings_json,
+        &vk,
+        srs.as_deref(),
+        reduced_srs,
+        false,
+    )?;
+    verification.into_validity()
+}
+
+/// Verifies a proof like [`verify`], and checks that it was made with the given polycommit
+/// commitments.
+///
+/// The values a circuit commits to with polycommit visibility are not public instances: the
+/// proof carries their KZG commitments as its first points, and [`verify`] only checks that the
+/// proof is consistent with the commitments it carries. This swaps the expected commitments,
+/// e.g. those published from `witness_commitments`, into the proof before verifying it, so the
+/// proof only verifies if it was made with exactly those commitments.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof to be verified.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `commitments` - The expected commitments as hex compressed G1 points, in the order of the inputs, params and outputs they commit to.
+///
+/// # Returns
+///
+/// * `Ok(bool)` - `true` if the proof is valid for the given commitments, `false` otherwise.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` if no or malformed commitments are given, or an error that occurred during verification.
+#[cfg_attr(feature = "ffi", export)]
+pub fn verify_with_commitments(
+    proof_json: String,
+    settings_json: String,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+    commitments: Vec<String>,
+) -> Result<bool, ExternalEZKLError> {
+    check_input_sizes!(proof_json, settings_json, vk, srs, commitments);
+    if commitments.is_empty() {
+        return Err(ExternalEZKLError::InvalidInput(
+            "`commitments` is empty; use `verify` for circuits without polycommit visibility"
+                .to_string(),
+        ));
+    }
+    let commitments = commitments
+        .iter()
+        .map(|commitment| decode_point("commitments", commitment))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let proof = deserialize_proof("proof_json", &proof_json)?;
+    drop(proof_json);
+    let proof = swap_proof_commitments_polycommit(&proof, &commitments)?;
+
+    let verification = verify_snark(proof, settings_json, &vk, Some(&srs), false, false)?;
+    verification.into_validity()
+}
+
+/// The overall outcome of a verification.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyOutcome {
+    /// The proof is valid.
+    Valid,
+    /// The proof does not verify against the verification key.
+    InvalidProof,
+    /// The proof's public instances do not match what the circuit expects.
+    Mismatch,
+}
+
+/// The check that failed during verification, derived from the underlying halo2 error.
+#[cfg_attr(feature = "ffi", derive(uniffi::Enum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyFailureCategory {
+    /// The public instances are malformed or too large for the circuit.
+    Instances,
+    /// The proof bytes could not be read as a transcript, e.g. because they are truncated.
+    MalformedTranscript,
+    /// The multi-opening argument of the proof is invalid.
+    OpeningArgument,
+    /// The final (pairing) check of the proof failed.
+    FinalCheck,
+    /// Any other failure.
+    Other,
+}
+
+impl From<&plonk::Error> for VerifyFailureCategory {
+    fn from(e: &plonk::Error) -> Self {
+        match e {
+            plonk::Error::InvalidInstances | plonk::Error::InstanceTooLarge => {
+                VerifyFailureCategory::Instances
+            }
+            plonk::Error::Transcript(_) => VerifyFailureCategory::MalformedTranscript,
+            plonk::Error::Opening => VerifyFailureCategory::OpeningArgument,
+            plonk::Error::ConstraintSystemFailure => VerifyFailureCategory::FinalCheck,
+            _ => VerifyFailureCategory::Other,
+        }
+    }
+}
+
+/// A detailed report about a verification.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone)]
+pub struct VerifyReport {
+    /// The overall outcome of the verification.
+    pub outcome: VerifyOutcome,
+    /// The check that failed, if the proof is not valid.
+    pub failure: Option<VerifyFailureCategory>,
+    /// A description of the failure, if the proof is not valid.
+    pub failure_message: Option<String>,
+    /// The number of public instances per column expected by the circuit settings.
+    pub expected_instances: Vec<u64>,
+    /// The number of public instances per column provided by the proof.
+    pub provided_instances: Vec<u64>,
+    /// The time spent verifying the proof, in milliseconds.
+    pub elapsed_ms: u64,
+    /// The `k` of the given SRS, or `None` if verification stopped before loading it.
+    pub srs_original_k: Option<u32>,
+    /// The `k` the SRS was used with, the logrows of the circuit, or `None` if verification
+    /// stopped before loading it.
+    pub srs_used_k: Option<u32>,
+    /// Whether the SRS was larger than the circuit needs and was downsized to `srs_used_k`.
+    pub downsized: bool,
+}
+
+/// Verifies a proof and reports in detail why verification failed, if it did.
+///
+/// Unlike [`verify`], an invalid proof is not an error: the outcome and the failing check are
+/// reported in the returned `VerifyReport`. Errors are only returned if the inputs could not be loaded.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof to be verified.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(VerifyReport)` - The detailed outcome of the verification.
+/// * `Err(ExternalEZKLError)` - An error that occurred while loading the inputs.
+#[cfg_attr(feature = "ffi", export)]
+pub fn verify_detailed(
+    proof_json: String,
+    settings_json: String,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+) -> Result<VerifyReport, ExternalEZKLError> {
+    check_input_sizes!(proof_json, settings_json, vk, srs);
+    let verification = verify_internal(proof_json, settings_json, &vk, Some(&srs), false, true)?;
+    Ok(VerifyReport::from(verification))
+}
+
+/// Verifies a proof like [`verify_detailed`], with options.
+///
+/// With `CliCompatible` strictness, settings written by the ezkl CLI or Python bindings of other
+/// ezkl versions are rewritten to the layout of the pinned version before verifying, so that
+/// they verify as they do with the tool that wrote them. The proof and the verification itself
+/// are the same in both modes.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof to be verified.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `options` - A `VerifyOptions` record with the strictness to read the settings with.
+///
+/// # Returns
+///
+/// * `Ok(VerifyReport)` - The detailed outcome of the verification.
+/// * `Err(ExternalEZKLError)` - An error that occurred while loading the inputs.
+#[cfg_attr(feature = "ffi", export)]
+pub fn verify_advanced(
+    proof_json: String,
+    settings_json: String,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+    options: VerifyOptions,
+) -> Result<VerifyReport, ExternalEZKLError> {
+    check_input_sizes!(proof_json, settings_json, vk, srs);
+    let settings_json = match options.verify_strictness {
+        VerifyStrictness::Strict => settings_json,
+        VerifyStrictness::CliCompatible => cli_compatible_settings(&settings_json)?,
+    };
+    let verification = verify_internal(proof_json, settings_json, &vk, Some(&srs), false, true)?;
+    Ok(VerifyReport::from(verification))
+}
+
+/// Validates a verification key once and returns it in a prepared format that loads faster.
+///
+/// Reading a vk validates that each of its points is on the curve. A prepared vk carries a small
+/// header marking it as validated, with a SHA-256 digest of the vk, and every function taking a
+/// vk reads its points without validating them again. The digest only detects corruption, so
+/// prepare vks ahead of time, e.g. when bundling them, and don't load prepared vks from
+/// untrusted sources.
+///
+/// # Arguments
+///
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form, or an already prepared vk.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)` - The prepared verification key.
+/// * `Err(ExternalEZKLError)` - `LoadVk` if the vk is invalid, or `InvalidInput` if it doesn't
+///   match the settings.
+#[cfg_attr(feature = "ffi", export)]
+pub fn prepare_vk(vk: Vec<u8>, settings_json: String) -> Result<Vec<u8>, ExternalEZKLError> {
+    check_input_sizes!(vk, settings_json);
+    let settings: GraphSettings = deserialize_json("settings_json", &settings_json)?;
+    check_logrows(&settings)?;
+    // An already prepared vk is validated again like any other
+    let raw_vk = prepared_vk(&vk)
+        .map_err(|detail| ExternalEZKLError::LoadVk { detail })?
+        .unwrap_or(&vk);
+    let verifying_key =
+        deserialize_vk::<KZGCommitmentScheme<Bn256>, GraphCircuit>(raw_vk, settings.clone())?;
+    check_vk_matches_settings(&verifying_key, &settings)?;
+
+    let mut raw_vk = Vec::new();
+    verifying_key
+        .write(&mut raw_vk, RawBytes)
+        .map_err(|e| ExternalEZKLError::InternalError(e.to_string()))?;
+    Ok(prepared_vk_bytes(&raw_vk))
+}
+
+/// The comparison of one model output of a proof with its expected value.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputClaim {
+    /// The expected value.
+    pub expected: f64,
+    /// The value the proof attests to, dequantized with the output scale.
+    pub actual: f64,
+    /// The fixed-point scale of the output.
+    pub scale: i32,
+    /// Whether `actual` is within the tolerance of `expected`.
+    pub passed: bool,
+}
+
+/// The result of checking the outputs a proof attests to against expected values.
+#[cfg_attr(feature = "ffi", derive(uniffi::Record))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimResult {
+    /// Whether the proof is valid.
+    pub proof_valid: bool,
+    /// The comparison of each output value, in the order of the flattened model outputs.
+    pub outputs: Vec<OutputClaim>,
+    /// Whether the proof is valid and every output is within the tolerance of its expected value.
+    pub passed: bool,
+}
+
+/// Verifies a proof and checks that the model outputs it attests to equal the expected values.
+///
+/// The outputs are dequantized with the output scales of the circuit. As the expected values
+/// were not quantized, an output passes if it is within `tolerance` plus half a quantization
+/// step of the expected value.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof to be verified.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `expected_outputs` - The expected value of each output, in the order of the flattened model outputs.
+/// * `tolerance` - The absolute difference allowed on top of the quantization error.
+///
+/// # Returns
+///
+/// * `Ok(ClaimResult)` - Whether the proof is valid, and the comparison of each output.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` if the outputs of the circuit are not public, the
+///   number of expected outputs does not match the circuit or the tolerance is negative, or an
+///   error that occurred while loading the inputs.
+#[cfg_attr(feature = "ffi", export)]
+pub fn verify_output_claim(
+    proof_json: String,
+    settings_json: String,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+    expected_outputs: Vec<f64>,
+    tolerance: f64,
+) -> Result<ClaimResult, ExternalEZKLError> {
+    check_input_sizes!(proof_json, settings_json, vk, srs);
+    if tolerance.is_nan() || tolerance < 0.0 {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "the tolerance must be a non-negative number, got {}",
+            tolerance
+        )));
+    }
+    let settings: GraphSettings = deserialize_json("settings_json", &settings_json)?;
+    if !matches!(settings.run_args.output_visibility, Visibility::Public) {
+        return Err(ExternalEZKLError::InvalidInput(
+            "the outputs of the circuit are not public instances of its proofs".to_string(),
+        ));
+    }
+
+    // The public outputs are the last instances, with the shapes of the last instance shapes
+    let num_outputs = settings.model_output_scales.len();
+    let output_shapes = &settings.model_instance_shapes[settings
+        .model_instance_shapes
+        .len()
+        .saturating_sub(num_outputs)..];
+    let scales: Vec<i32> = output_shapes
+        .iter()
+        .zip(&settings.model_output_scales)
+        .flat_map(|(shape, &scale)| std::iter::repeat(scale).take(shape.iter().product()))
+        .collect();
+    if expected_outputs.len() != scales.len() {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "{} expected outputs were given, but the circuit has {}",
+            expected_outputs.len(),
+            scales.len()
+        )));
+    }
+
+    let proof = deserialize_proof("proof_json", &proof_json)?;
+    drop(proof_json);
+    let instances: Vec<Fr> = proof.instances.iter().flatten().copied().collect();
+    let verification = verify_snark(proof, settings_json, &vk, Some(&srs), false, false)?;
+    let proof_valid = verification.result.is_ok()
+        && verification.expected_instances == verification.provided_instances;
+
+    let (num_input_instances, num_param_instances, _) = instance_layout(&settings);
+    let output_instances = instances
+        .iter()
+        .skip(num_input_instances + num_param_instances);
+    let outputs: Vec<OutputClaim> = output_instances
+        .zip(&expected_outputs)
+        .zip(&scales)
+        .map(|((instance, &expected), &scale)| {
+            let multiplier = scale_to_multiplier(scale);
+            let actual = felt_to_integer_rep(*instance) as f64 / multiplier;
+            OutputClaim {
+                expected,
+                actual,
+                scale,
+                passed: (actual - expected).abs() <= tolerance + 0.5 / multiplier,
+            }
+        })
+        .collect();
+
+    // A proof with too few instances can't attest to every output
+    let complete = outputs.len() == expected_outputs.len();
+    let passed = proof_valid && complete && outputs.iter().all(|output| output.passed);
+    Ok(ClaimResult {
+        proof_valid,
+        outputs,
+        passed,
+    })
+}
+
+/// Verifies a proof of a circuit with hashed outputs together with the outputs it was created
+/// for.
+///
+/// With `hashed` output visibility, the proof only carries the Poseidon hash of the outputs, so
+/// the app sends the outputs alongside the proof. This verifies the proof and recomputes the
+/// hash of the claimed outputs the way the circuit does, to check that they are the outputs the
+/// proof attests to.
+///
+/// # Arguments
+///
+/// * `proof_json` - A `String` containing the JSON representation of the proof to be verified.
+/// * `settings_json` - A `String` containing the JSON representation of the circuit settings.
+/// * `vk` - A `Vec<u8>` containing the Verification Key (VK) in binary form.
+/// * `srs` - A `Vec<u8>` containing the Structured Reference String (SRS) in binary form.
+/// * `claimed_outputs` - The field elements of the outputs, flattened, either `0x`-prefixed
+///   big-endian hex or as in the `outputs` of the witness the proof was created from.
+///
+/// # Returns
+///
+/// * `Ok(bool)` - `true` if the proof is valid and attests to the claimed outputs, `false` if
+///   the proof is invalid.
+/// * `Err(ExternalEZKLError)` - `OutputMismatch` if the proof is valid but the claimed outputs
+///   don't hash to its output hash, `InvalidInput` if the outputs of the circuit are not hashed
+///   publicly, no output is claimed or a claimed output is not a field element, `Unsupported`
+///   if the circuit has several outputs, or an error that occurred while loading the inputs.
+#[cfg_attr(feature = "ffi", export)]
+pub fn verify_hashed_outputs(
+    proof_json: String,
+    settings_json: String,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+    claimed_outputs: Vec<String>,
+) -> Result<bool, ExternalEZKLError> {
+    check_input_sizes!(proof_json, settings_json, vk, srs, claimed_outputs);
+    let settings: GraphSettings = deserialize_json("settings_json", &settings_json)?;
+    if !matches!(
+        settings.run_args.output_visibility,
+        Visibility::Hashed {
+            hash_is_public: true,
+            ..
+        }
+    ) {
+        return Err(ExternalEZKLError::InvalidInput(
+            "the outputs of the circuit are not hashed into public instances of its proofs"
+                .to_string(),
+        ));
+    }
+    // Each output is hashed on its own, and the settings don't record the output shapes
+    // needed to split the claimed outputs between them
+    let num_outputs = settings.model_output_scales.len();
+    if num_outputs != 1 {
+        return Err(ExternalEZKLError::Unsupported {
+            detail: format!(
+                "the circuit has {} outputs, only circuits with a single output are supported",
+                num_outputs
+            ),
+        });
+    }
+    if claimed_outputs.is_empty() {
+        return Err(ExternalEZKLError::InvalidInput(
+            "no outputs are claimed".to_string(),
+        ));
+    }
+    let claimed: Vec<Fr> = claimed_outputs
+        .iter()
+        .map(|output| parse_felt(output))
+        .collect::<Result<_, _>>()?;
+
+    let proof = deserialize_proof("proof_json", &proof_json)?;
+    drop(proof_json);
+    let instances: Vec<Fr> = proof.instances.iter().flatten().copied().collect();
+    let verification = verify_snark(proof, settings_json, &vk, Some(&srs), false, false)?;
+    if verification.result.is_err()
+        || verification.expected_instances != verification.provided_instances
+    {
+        return Ok(false);
+    }
+
+    let (num_input_instances, num_param_instances, _) = instance_layout(&settings);
+    let expected = instances[num_input_instances + num_param_instances];
+    let hashes = PoseidonChip::<PoseidonSpec, POSEIDON_WIDTH, POSEIDON_RATE>::run(claimed)
+        .map_err(|e| ExternalEZKLError::InternalError(e.to_string()))?;
+    let actual = hashes[0][0];
+    if actual != expected {
+        return Err(ExternalEZKLError::OutputMismatch {
+            expected: encode_felt(&expected, FeltEncoding::Hex),
+            actual: encode_felt(&actual, FeltEncoding::Hex),
+        });
+    }
+    Ok(true)
+}
+
+/// The result of running the verifier, before it is mapped to the exported types.
+pub(crate) struct Verification {
+    pub(crate) expected_instances: Vec<usize>,
+    pub(crate) provided_instances: Vec<usize>,
+    /// The time spent verifying the proof, if it was measured.
+    pub(crate) elapsed: Option<Duration>,
+    pub(crate) result: Result<(), plonk::Error>,
+    /// How the SRS was loaded, `None` if verification stopped before loading it.
+    pub(crate) srs_usage: Option<SrsUsage>,
+}
+
+impl Verification {
+    /// Maps the result to the return value of [`verify`], where an invalid proof is an error.
+    pub(crate) fn into_validity(self) -> Result<bool, ExternalEZKLError> {
+        if self.expected_instances != self.provided_instances {
+            return Err(ExternalEZKLError::InvalidInput(format!(
+                "the proof has {:?} public instances per column, but the settings expect {:?}",
+                self.provided_instances, self.expected_instances
+            )));
+        }
+        self.result
+            .map_err(|e| InnerEZKLError::from(e).into())
+            .map(|_| true)
+    }
+}
+
+impl From<Verification> for VerifyReport {
+    fn from(verification: Verification) -> Self {
+        let failure = verification
+            .result
+            .as_ref()
+            .err()
+            .map(VerifyFailureCategory::from);
+        let outcome = match failure {
+            None => VerifyOutcome::Valid,
+            Some(VerifyFailureCategory::Instances) => VerifyOutcome::Mismatch,
+            Some(_) if verification.expected_instances != verification.provided_instances => {
+                VerifyOutcome::Mismatch
+            }
+            Some(_) => VerifyOutcome::InvalidProof,
+        };
+
+        VerifyReport {
+            outcome,
+            failure,
+            failure_message: verification.result.err().map(|e| e.to_string()),
+            expected_instances: to_u64(&verification.expected_instances),
+            provided_instances: to_u64(&verification.provided_instances),
+            elapsed_ms: verification
+                .elapsed
+                .map_or(0, |elapsed| elapsed.as_millis() as u64),
+            srs_original_k: verification.srs_usage.map(|usage| usage.original_k),
+            srs_used_k: verification.srs_usage.map(|usage| usage.used_k),
+            downsized: verification
+                .srs_usage
+                .is_some_and(|usage| usage.downsized()),
+        }
+    }
+}
+
+fn to_u64(counts: &[usize]) -> Vec<u64> {
+    counts.iter().map(|&count| count as u64).collect()
+}
+
+/// Verifies a proof, measuring the time spent in the verifier only if `timed` is set.
 pub(crate) fn verify_internal(
     proof_json: String,
     settings_json: String,
     serialised_vk: &[u8],
     serialised_srs: Option<&[u8]>,
     reduced_srs: bool,
-) -> Result<bool, InnerEZKLError> {
-    let circuit_settings = GraphSettings::from_json(&settings_json)?;
+    timed: bool,
+) -> Result<Verification, ExternalEZKLError> {
+    let proof = deserialize_proof("proof_json", &proof_json)?;
+    drop(proof_json);
+    verify_snark(
+        proof,
+        settings_json,
+        serialised_vk,
+        serialised_srs,
+        reduced_srs,
+        timed,
+    )
+}
+
+/// Verifies an already parsed proof, see [`verify_internal`].
+pub(crate) fn verify_snark(
+    proof: Snark<Fr, G1Affine>,
+    settings_json: String,
+    serialised_vk: &[u8],
+    serialised_srs: Option<&[u8]>,
+    reduced_srs: bool,
+    timed: bool,
+) -> Result<Verification, ExternalEZKLError> {
+    let circuit_settings: GraphSettings = deserialize_json("settings_json", &settings_json)?;
+    check_logrows(&circuit_settings)?;
 
     let logrows = circuit_settings.run_args.logrows;
     let commitment = circuit_settings.run_args.commitment.into();
+    // The circuit exposes all of its public instances in a single column
+    let expected_instances = vec![circuit_settings.total_instances().iter().sum()];
 
-    match commitment {
-        Commitments::KZG => {
-            let proof: Snark<Fr, G1Affine> = serde_json::from_str(&proof_json)?;
+    let provided_instances: Vec<usize> = proof.instances.iter().map(Vec::len).collect();
+
+    // Reject proofs with the wrong number of instances before loading the SRS and the vk,
+    // as some halo2 verifier code paths panic on them instead of returning an error
+    if provided_instances != expected_instances {
+        return Ok(Verification {
+            expected_instances,
+            provided_instances,
+            elapsed: timed.then_some(Duration::ZERO),
+            result: Err(plonk::Error::InvalidInstances),
+            srs_usage: None,
+        });
+    }
+
+    // only need G_0 for the verification with shplonk, so a reduced SRS is downsized to k = 1
+    let srs_k = match commitment {
+        Commitments::KZG if reduced_srs => 1,
+        _ => logrows,
+    };
+    let params = VerifierParams::load(commitment, serialised_srs, srs_k)?;
+    let (result, elapsed) = verify_with_params(
+        proof,
+        circuit_settings,
+        serialised_vk,
+        params.as_params_ref(),
+        timed,
+    )?;
 
+    // Loading the params succeeded, so the SRS was given
+    let srs_usage = SrsUsage::read(serialised_srs.unwrap_or_default(), srs_k)?;
+
+    Ok(Verification {
+        expected_instances,
+        provided_instances,
+        elapsed,
+        result,
+        srs_usage: Some(srs_usage),
+    })
+}
+
+/// The verifier parameters of either commitment scheme, loaded from an SRS.
+pub(crate) enum VerifierParams {
+    KZG(ParamsKZG<Bn256>),
+    IPA(ParamsIPA<G1Affine>),
+}
+
+impl VerifierParams {
+    /// Loads the verifier parameters for the commitment scheme, downsized to `k` if the SRS is larger.
+    pub(crate) fn load(
+        commitment: Commitments,
+        serialised_srs: Option<&[u8]>,
+        k: u32,
+    ) -> Result<Self, ExternalEZKLError> {
+        Ok(match commitment {
+            Commitments::KZG => VerifierParams::KZG(deserialize_params_verifier::<
+                KZGCommitmentScheme<Bn256>,
+            >(serialised_srs, k)?),
+            Commitments::IPA => VerifierParams::IPA(deserialize_params_verifier::<
+                IPACommitmentScheme<G1Affine>,
+            >(serialised_srs, k)?),
+        })
+    }
+
+    /// Borrows the parameters, to verify with.
+    pub(crate) fn as_params_ref(&self) -> VerifierParamsRef<'_> {
+        match self {
+            VerifierParams::KZG(params) => VerifierParamsRef::KZG(params),
+            VerifierParams::IPA(params) => VerifierParamsRef::IPA(params),
+        }
+    }
+}
+
+/// Verifier parameters of either commitment scheme, borrowed from [`VerifierParams`] or from the
+/// parameters of a prover, which verify proofs as well.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy)]
+pub(crate) enum VerifierParamsRef<'a> {
+    KZG(&'a ParamsKZG<Bn256>),
+    IPA(&'a ParamsIPA<G1Affine>),
+}
+
+/// Verifies a proof with already loaded verifier parameters, see [`verify_snark`].
+///
+/// # Returns
+///
+/// * `Ok((Result<(), plonk::Error>, Option<Duration>))` - The result of the verification and the
+///   time it took, if `timed` is set.
+/// * `Err(ExternalEZKLError)` - If the vk could not be loaded or the parameters are for another commitment scheme.
+pub(crate) fn verify_with_params(
+    proof: Snark<Fr, G1Affine>,
+    circuit_settings: GraphSettings,
+    serialised_vk: &[u8],
+    params: VerifierParamsRef<'_>,
+    timed: bool,
+) -> Result<(Result<(), plonk::Error>, Option<Duration>), ExternalEZKLError> {
+    let logrows = circuit_settings.run_args.logrows;
+    let commitment = Commitments::from(circuit_settings.run_args.commitment);
+    let verified = match (commitment, params) {
+        (Commitments::KZG, VerifierParamsRef::KZG(params)) => {
             //     (None, Some(proof_path)) => Snark::load::<KZGCommitmentScheme<Bn256>>(&proof_path),
             //     (Some(proof_json), None) => Ok(serde_json::from_str(proof_json)?),
             //     _ => {
@@ -73,86 +831,85 @@ pub(crate) fn verify_internal(
             //         )))
             //     }
             // }?;
-            let params: ParamsKZG<Bn256> = if reduced_srs {
-                // only need G_0 for the verification with shplonk
-                deserialize_params_verifier::<KZGCommitmentScheme<Bn256>>(serialised_srs, 1)?
-            } else {
-                deserialize_params_verifier::<KZGCommitmentScheme<Bn256>>(serialised_srs, logrows)?
-            };
             match proof.transcript_type {
-                TranscriptType::EVM => verify_commitment::<
-                    KZGCommitmentScheme<Bn256>,
-                    VerifierSHPLONK<'_, Bn256>,
-                    _,
-                    KZGSingleStrategy<_>,
-                    EvmTranscript<G1Affine, _, _, _>,
-                    GraphCircuit,
-                    _,
-                >(
-                    proof_json,
-                    circuit_settings,
-                    serialised_vk,
-                    &params,
-                    logrows,
-                ),
-                TranscriptType::Poseidon => verify_commitment::<
-                    KZGCommitmentScheme<Bn256>,
-                    VerifierSHPLONK<'_, Bn256>,
-                    _,
-                    KZGSingleStrategy<_>,
-                    PoseidonTranscript<NativeLoader, _>,
-                    GraphCircuit,
-                    _,
-                >(
-                    proof_json,
-                    circuit_settings,
-                    serialised_vk,
-                    &params,
-                    logrows,
-                ),
+                TranscriptType::EVM => {
+                    verify_commitment::<
+                        KZGCommitmentScheme<Bn256>,
+                        VerifierSHPLONK<'_, Bn256>,
+                        _,
+                        KZGSingleStrategy<_>,
+                        EvmTranscript<G1Affine, _, _, _>,
+                        GraphCircuit,
+                        _,
+                    >(proof, circuit_settings, serialised_vk, params, logrows, timed)
+                }
+                TranscriptType::Poseidon => {
+                    verify_commitment::<
+                        KZGCommitmentScheme<Bn256>,
+                        VerifierSHPLONK<'_, Bn256>,
+                        _,
+                        KZGSingleStrategy<_>,
+                        PoseidonTranscript<NativeLoader, _>,
+                        GraphCircuit,
+                        _,
+                    >(proof, circuit_settings, serialised_vk, params, logrows, timed)
+                }
             }
         }
-        Commitments::IPA => {
-            let proof: Snark<Fr, G1Affine> = serde_json::from_str(&proof_json)?;
-
-            let params: ParamsIPA<_> = deserialize_params_verifier::<IPACommitmentScheme<G1Affine>>(
-                serialised_srs,
-                logrows,
-            )?;
+        (Commitments::IPA, VerifierParamsRef::IPA(params)) => {
+            // IPA verification uses the whole SRS, so its domain must be exactly the circuit's
+            let logrows = ipa_params_k(params, logrows)?;
             match proof.transcript_type {
-                TranscriptType::EVM => verify_commitment::<
-                    IPACommitmentScheme<G1Affine>,
-                    VerifierIPA<_>,
-                    _,
-                    IPASingleStrategy<_>,
-                    EvmTranscript<G1Affine, _, _, _>,
-                    GraphCircuit,
-                    _,
-                >(
-                    proof_json,
-                    circuit_settings,
-                    serialised_vk,
-                    &params,
-                    logrows,
-                ),
-                TranscriptType::Poseidon => verify_commitment::<
-                    IPACommitmentScheme<G1Affine>,
-                    VerifierIPA<_>,
-                    _,
-                    IPASingleStrategy<_>,
-                    PoseidonTranscript<NativeLoader, _>,
-                    GraphCircuit,
-                    _,
-                >(
-                    proof_json,
-                    circuit_settings,
-                    serialised_vk,
-                    &params,
-                    logrows,
-                ),
+                TranscriptType::EVM => {
+                    verify_commitment::<
+                        IPACommitmentScheme<G1Affine>,
+                        VerifierIPA<_>,
+                        _,
+                        IPASingleStrategy<_>,
+                        EvmTranscript<G1Affine, _, _, _>,
+                        GraphCircuit,
+                        _,
+                    >(proof, circuit_settings, serialised_vk, params, logrows, timed)
+                }
+                TranscriptType::Poseidon => {
+                    verify_commitment::<
+                        IPACommitmentScheme<G1Affine>,
+                        VerifierIPA<_>,
+                        _,
+                        IPASingleStrategy<_>,
+                        PoseidonTranscript<NativeLoader, _>,
+                        GraphCircuit,
+                        _,
+                    >(proof, circuit_settings, serialised_vk, params, logrows, timed)
+                }
             }
         }
+        (commitment, _) => {
+            return Err(ExternalEZKLError::InvalidInput(format!(
+                "the circuit settings use {:?} commitments, but the SRS was loaded for the other scheme",
+                CommitmentsWrapper::from(commitment)
+            )))
+        }
+    }?;
+    Ok(verified)
+}
+
+/// Returns the k of downsized IPA verifier parameters, which bounds the rows of the verification.
+///
+/// # Returns
+///
+/// * `Ok(u32)` - The k of the parameters, equal to the logrows of the circuit.
+/// * `Err(ExternalEZKLError)` - `InvalidInput` with both values if the SRS was too small for the
+///   circuit, or the parameters were not downsized to its logrows.
+fn ipa_params_k(params: &ParamsIPA<G1Affine>, logrows: u32) -> Result<u32, ExternalEZKLError> {
+    if params.k() != logrows {
+        return Err(ExternalEZKLError::InvalidInput(format!(
+            "IPA verification requires an SRS of k = {} for the logrows of the circuit, but the SRS has k = {}",
+            logrows,
+            params.k()
+        )));
     }
+    Ok(params.k())
 }
 
 fn verify_commitment<
@@ -165,12 +922,13 @@ fn verify_commitment<
     C: Circuit<<Scheme as CommitmentScheme>::Scalar, Params = Params>,
     Params,
 >(
-    proof_json: String,
+    proof: Snark<Scheme::Scalar, Scheme::Curve>,
     settings: Params,
     serialized_vk: &[u8],
     params: &'a Scheme::ParamsVerifier,
     logrows: u32,
-) -> Result<bool, InnerEZKLError>
+    timed: bool,
+) -> Result<(Result<(), plonk::Error>, Option<Duration>), InnerEZKLError>
 where
     Scheme::Scalar: FromUniformBytes<64>
         + SerdeObject
@@ -180,21 +938,21 @@ where
     Scheme::Curve: SerdeObject + Serialize + DeserializeOwned,
     Scheme::ParamsVerifier: 'a,
 {
-    let proof: Snark<Scheme::Scalar, Scheme::Curve> = serde_json::from_str(&proof_json)?;
-
+    let max_rows = rows_of(logrows).map_err(|e| {
+        InnerEZKLError::IoError(std::io::Error::new(std::io::ErrorKind::InvalidInput, e))
+    })?;
     let strategy = Strategy::new(params);
     let vk = deserialize_vk::<Scheme, C>(serialized_vk, settings)?;
-    let now = Instant::now();
-
-    let result =
-        verify_proof_circuit::<V, _, _, _, TR>(&proof, params, &vk, strategy, 1 << logrows);
-
-    let elapsed = now.elapsed();
-    info!(
-        "verify took {}.{}",
-        elapsed.as_secs(),
-        elapsed.subsec_millis()
-    );
-    info!("verified: {}", result.is_ok());
-    result.map_err(|e: plonk::Error| e.into()).map(|_| true)
+    let start_time = timed.then(Instant::now);
+
+    let interval = Interval::begin("verify_proof");
+    let result = verify_proof_circuit::<V, _, _, _, TR>(&proof, params, &vk, strategy, max_rows);
+    drop(interval);
+
+    let elapsed = start_time.map(|start_time| start_time.elapsed());
+    match elapsed {
+        Some(elapsed) => debug!("verified: {} in {:?}", result.is_ok(), elapsed),
+        None => debug!("verified: {}", result.is_ok()),
+    }
+    Ok((result.map(|_| ()), elapsed))
 }
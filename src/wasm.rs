@@ -0,0 +1,80 @@
+use crate::ExternalEZKLError;
+use wasm_bindgen::prelude::*;
+
+/// Converts an error of the library into a JavaScript `Error` with its message.
+fn js_error(e: ExternalEZKLError) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+/// Generates a witness like [`crate::gen_witness`], for JavaScript.
+///
+/// # Arguments
+///
+/// * `input_json` - The JSON representation of the input data for the circuit.
+/// * `compiled_circuit` - The compiled circuit in binary form.
+/// * `vk` - The Verification Key (VK) in binary form.
+/// * `srs` - The Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated witness as JSON.
+/// * `Err(JsError)` - An error with the message of the error that occurred.
+#[cfg(feature = "prover")]
+#[wasm_bindgen(js_name = genWitness)]
+pub async fn gen_witness(
+    input_json: String,
+    compiled_circuit: Vec<u8>,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+) -> Result<String, JsError> {
+    crate::gen_witness(input_json, compiled_circuit, vk, srs)
+        .await
+        .map_err(js_error)
+}
+
+/// Generates a proof like [`crate::prove`], for JavaScript.
+///
+/// # Arguments
+///
+/// * `witness_json` - The JSON representation of the witness generated for the circuit input.
+/// * `compiled_circuit` - The compiled circuit in binary form.
+/// * `pk` - The Proving Key (PK) in binary form.
+/// * `srs` - The Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated proof as JSON.
+/// * `Err(JsError)` - An error with the message of the error that occurred.
+#[cfg(feature = "prover")]
+#[wasm_bindgen]
+pub fn prove(
+    witness_json: String,
+    compiled_circuit: Vec<u8>,
+    pk: Vec<u8>,
+    srs: Vec<u8>,
+) -> Result<String, JsError> {
+    crate::prove(witness_json, compiled_circuit, pk, srs).map_err(js_error)
+}
+
+/// Verifies a proof like [`crate::verify`], for JavaScript.
+///
+/// # Arguments
+///
+/// * `proof_json` - The JSON representation of the proof to be verified.
+/// * `settings_json` - The JSON representation of the circuit settings.
+/// * `vk` - The Verification Key (VK) in binary form.
+/// * `srs` - The Structured Reference String (SRS) in binary form.
+///
+/// # Returns
+///
+/// * `Ok(bool)` - `true` if the proof is valid, `false` if the proof is invalid.
+/// * `Err(JsError)` - An error with the message of the error that occurred.
+#[wasm_bindgen]
+pub fn verify(
+    proof_json: String,
+    settings_json: String,
+    vk: Vec<u8>,
+    srs: Vec<u8>,
+) -> Result<bool, JsError> {
+    crate::verify(proof_json, settings_json, vk, srs).map_err(js_error)
+}
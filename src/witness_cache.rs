@@ -0,0 +1,97 @@
+use crate::config;
+use crate::files::{io_error, OutputFile};
+use crate::logging::{debug, warn};
+use crate::sensitive::Sensitive;
+use crate::serialization::deserialize_json;
+use crate::ExternalEZKLError;
+use ezkl::graph::input::GraphData;
+use ezkl::graph::GraphWitness;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// A directory of previously generated witnesses, keyed by the artifacts and the input they were
+/// generated for.
+pub(crate) struct WitnessCache {
+    dir: PathBuf,
+}
+
+impl WitnessCache {
+    /// Returns the witness cache, if a directory was configured.
+    pub(crate) fn configured() -> Option<Self> {
+        config::current()
+            .witness_cache_dir
+            .map(|dir| WitnessCache { dir: dir.into() })
+    }
+
+    /// Computes the key of a witness from the fingerprint of the artifacts it is generated with
+    /// and its input.
+    ///
+    /// The input is hashed in its re-serialized form, so formatting differences between equal
+    /// inputs don't cause cache misses.
+    pub(crate) fn key(
+        &self,
+        artifacts: &str,
+        data: &GraphData,
+    ) -> Result<String, ExternalEZKLError> {
+        let input = Sensitive::new(serde_json::to_string(data)?);
+        let mut hasher = Sha256::new();
+        hasher.update(artifacts.as_bytes());
+        hasher.update(input.as_bytes());
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.witness.json", key))
+    }
+
+    /// Reads a cached witness, removing the entry if it can't be parsed.
+    pub(crate) fn load(&self, key: &str) -> Option<GraphWitness> {
+        let path = self.path(key);
+        let json = match fs::read_to_string(&path) {
+            Ok(json) => Sensitive::new(json),
+            Err(e) if e.kind() == ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!(
+                    "failed to read witness cache entry {}: {}",
+                    path.display(),
+                    e
+                );
+                return None;
+            }
+        };
+        match deserialize_json::<GraphWitness>("witness cache entry", &json) {
+            Ok(witness) => {
+                debug!("read witness from cache entry {}", path.display());
+                Some(witness)
+            }
+            Err(e) => {
+                warn!(
+                    "ignoring corrupt witness cache entry {}: {}",
+                    path.display(),
+                    e
+                );
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// Stores a witness, logging rather than failing if it can't be written.
+    pub(crate) fn store(&self, key: &str, witness: &GraphWitness) {
+        if let Err(e) = self.try_store(key, witness) {
+            warn!("failed to write witness cache entry: {}", e);
+        }
+    }
+
+    fn try_store(&self, key: &str, witness: &GraphWitness) -> Result<(), ExternalEZKLError> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| io_error("create", &self.dir.display().to_string(), e))?;
+
+        // Written like the `*_to_file` outputs, so concurrent readers never see a partial entry
+        let output = OutputFile::create(&self.path(key).display().to_string())?;
+        output.write_json(witness)?;
+        Ok(())
+    }
+}
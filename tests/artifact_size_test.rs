@@ -0,0 +1,96 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use halo2_proofs::halo2curves::bn256::Bn256;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use ios_ezkl::testing::deserialize_params_prover;
+use ios_ezkl::EZKLError;
+
+fn assert_size_error<T: std::fmt::Debug>(result: Result<T, EZKLError>, expected: &str) {
+    let message = match result {
+        Err(e) => e.to_string(),
+        Ok(value) => panic!("Expected an error, got {:?}", value),
+    };
+    assert!(
+        message.contains(expected),
+        "`{}` does not contain `{}`",
+        message,
+        expected
+    );
+}
+
+#[tokio::test]
+async fn test_compiled_circuit_too_small() {
+    let fixture = common::fixture();
+    let result = ios_ezkl::gen_witness(
+        fixture.input_json.clone(),
+        vec![],
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await;
+    assert_size_error(result, "compiled circuit buffer is empty");
+}
+
+#[tokio::test]
+async fn test_vk_too_small() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    let result = ios_ezkl::verify(
+        proof.clone(),
+        fixture.settings_json.clone(),
+        vec![],
+        fixture.srs.clone(),
+    );
+    assert_size_error(result, "verification key buffer is empty");
+
+    let result = ios_ezkl::verify(
+        proof,
+        fixture.settings_json.clone(),
+        vec![0],
+        fixture.srs.clone(),
+    );
+    assert_size_error(
+        result,
+        "verification key is only 1 bytes, expected at least",
+    );
+}
+
+#[tokio::test]
+async fn test_pk_too_small() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let result = ios_ezkl::prove(
+        witness.clone(),
+        fixture.compiled_circuit.clone(),
+        vec![],
+        fixture.srs.clone(),
+    );
+    assert_size_error(result, "proving key buffer is empty");
+
+    let result = ios_ezkl::prove(
+        witness,
+        fixture.compiled_circuit.clone(),
+        vec![0],
+        fixture.srs.clone(),
+    );
+    assert_size_error(result, "proving key is only 1 bytes, expected at least");
+}
+
+#[test]
+fn test_srs_too_small() {
+    let empty = deserialize_params_prover::<KZGCommitmentScheme<Bn256>>(Some(&[]), 17);
+    assert!(empty
+        .expect_err("An empty SRS was accepted")
+        .to_string()
+        .contains("SRS buffer is empty — did the file download complete?"));
+
+    let tiny = deserialize_params_prover::<KZGCommitmentScheme<Bn256>>(Some(&[0]), 17);
+    assert!(tiny
+        .expect_err("A 1-byte SRS was accepted")
+        .to_string()
+        .contains("SRS is only 1 bytes, expected at least"));
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use std::fs::{File, FileTimes};
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "test-hooks")]
+fn write_fixture_files() -> (String, String, String) {
+    let fixture = common::fixture();
+    let dir = common::scratch_dir();
+    let paths = ["network.ezkl", "vk.key", "kzg.srs"].map(|name| dir.join(name));
+    std::fs::write(&paths[0], &fixture.compiled_circuit).unwrap();
+    std::fs::write(&paths[1], &fixture.vk).unwrap();
+    std::fs::write(&paths[2], &fixture.srs).unwrap();
+    let [circuit, vk, srs] = paths.map(|path| path.display().to_string());
+    (circuit, vk, srs)
+}
+
+#[cfg(feature = "test-hooks")]
+fn dir_entries(dir: &std::path::Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+// Injects the failure with a test hook: `cargo test --features test-hooks --test atomic_write_test`
+#[cfg(feature = "test-hooks")]
+#[tokio::test]
+async fn test_interrupted_write_leaves_no_partial_file() {
+    let fixture = common::fixture();
+    let (circuit_path, vk_path, srs_path) = write_fixture_files();
+    let out_dir = common::scratch_dir().join("interrupted");
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let witness_path = out_dir.join("witness.json");
+
+    ios_ezkl::testing::fail_next_artifact_write();
+    let result = ios_ezkl::gen_witness_to_file(
+        fixture.input_json.clone(),
+        circuit_path.clone(),
+        vk_path.clone(),
+        srs_path.clone(),
+        witness_path.display().to_string(),
+    )
+    .await;
+    assert!(result.is_err());
+    assert!(!witness_path.exists());
+    assert!(dir_entries(&out_dir).is_empty());
+
+    // The next write succeeds and only leaves the final file
+    ios_ezkl::gen_witness_to_file(
+        fixture.input_json.clone(),
+        circuit_path,
+        vk_path,
+        srs_path,
+        witness_path.display().to_string(),
+    )
+    .await
+    .expect("Witness generation failed");
+    assert_eq!(dir_entries(&out_dir), vec!["witness.json".to_string()]);
+}
+
+#[test]
+fn test_cleanup_removes_only_stale_temp_files() {
+    let dir = common::scratch_dir().join("cleanup");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    // A temporary file of a killed call, one of a call still writing, and unrelated files
+    let stale = dir.join(format!("proof.json.tmp-{}", uuid::Uuid::new_v4()));
+    let fresh = dir.join(format!("proof.json.tmp-{}", uuid::Uuid::new_v4()));
+    std::fs::write(&stale, "{\"trunc").unwrap();
+    std::fs::write(&fresh, "{\"trunc").unwrap();
+    std::fs::write(dir.join("proof.json"), "{}").unwrap();
+    std::fs::write(dir.join("notes.tmp-old"), "").unwrap();
+    let two_hours_ago = SystemTime::now() - Duration::from_secs(2 * 60 * 60);
+    File::options()
+        .write(true)
+        .open(&stale)
+        .unwrap()
+        .set_times(FileTimes::new().set_modified(two_hours_ago))
+        .unwrap();
+
+    let removed = ios_ezkl::cleanup_artifacts(dir.display().to_string()).unwrap();
+    assert_eq!(removed, 1);
+    assert!(!stale.exists());
+    assert!(fresh.exists());
+    assert!(dir.join("proof.json").exists());
+    assert!(dir.join("notes.tmp-old").exists());
+
+    assert!(ios_ezkl::cleanup_artifacts(dir.join("missing").display().to_string()).is_err());
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{BatchItemResult, EZKLError};
+
+const MALFORMED_INPUT: &str = "{\"input_data\": [[0.1,";
+
+#[tokio::test]
+async fn test_batch_partial_success() {
+    let fixture = common::fixture();
+    let inputs = vec![
+        fixture.input_json.clone(),
+        MALFORMED_INPUT.to_string(),
+        fixture.input_json.clone(),
+    ];
+
+    let witnesses = ios_ezkl::gen_witness_batch(
+        inputs,
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Batch witness generation failed");
+    assert_eq!(witnesses.len(), 3);
+
+    let expected = common::witness().await;
+    let witnesses: Vec<Option<String>> = witnesses
+        .into_iter()
+        .map(|item| match item {
+            BatchItemResult::Success { json } => Some(json),
+            BatchItemResult::Failure { error } => {
+                assert!(
+                    matches!(error, EZKLError::JsonError { ref field, .. } if field == "input_json"),
+                    "Unexpected error: {:?}",
+                    error
+                );
+                None
+            }
+        })
+        .collect();
+    assert_eq!(witnesses[0].as_deref(), Some(expected.as_str()));
+    assert!(witnesses[1].is_none());
+    assert_eq!(witnesses[2].as_deref(), Some(expected.as_str()));
+
+    // Prove the two witnesses, with the failed item replaced by an invalid witness
+    let proofs = ios_ezkl::prove_batch(
+        vec![
+            expected.clone(),
+            "not a witness".to_string(),
+            expected.clone(),
+        ],
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Batch proving failed");
+    assert_eq!(proofs.len(), 3);
+    assert!(matches!(proofs[1], BatchItemResult::Failure { .. }));
+
+    for index in [0, 2] {
+        let BatchItemResult::Success { json } = &proofs[index] else {
+            panic!("Proof {} failed: {:?}", index, proofs[index]);
+        };
+        let valid = ios_ezkl::verify(
+            json.clone(),
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+        )
+        .expect("Verification failed");
+        assert!(valid, "Proof {} is invalid", index);
+    }
+}
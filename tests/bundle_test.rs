@@ -0,0 +1,108 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::EZKLError;
+
+#[tokio::test]
+async fn test_bundle_round_trip() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    let bundle = ios_ezkl::create_bundle(
+        proof.clone(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+    )
+    .expect("Failed to create bundle");
+    let parsed: serde_json::Value = serde_json::from_str(&bundle).unwrap();
+    assert_eq!(parsed["version"], 1);
+    assert_eq!(
+        parsed["vk_fingerprint"],
+        ios_ezkl::vk_fingerprint(fixture.vk.clone(), fixture.settings_json.clone()).unwrap()
+    );
+
+    let valid = ios_ezkl::verify_bundle(
+        bundle,
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        Some(fixture.settings_json.clone()),
+    )
+    .expect("Failed to verify bundle");
+    assert!(valid);
+}
+
+#[tokio::test]
+async fn test_bundle_rejects_other_settings() {
+    let fixture = common::fixture();
+    let bundle = ios_ezkl::create_bundle(
+        common::proof().await,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+    )
+    .unwrap();
+
+    let mut other_settings: serde_json::Value =
+        serde_json::from_str(&fixture.settings_json).unwrap();
+    other_settings["run_args"]["logrows"] =
+        (other_settings["run_args"]["logrows"].as_u64().unwrap() + 1).into();
+
+    let result = ios_ezkl::verify_bundle(
+        bundle,
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        Some(other_settings.to_string()),
+    );
+    match result {
+        Err(EZKLError::FingerprintMismatch { artifact, .. }) => assert_eq!(artifact, "settings"),
+        other => panic!("Expected a FingerprintMismatch, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_bundle_rejects_other_vk() {
+    let fixture = common::fixture();
+    let bundle = ios_ezkl::create_bundle(
+        common::proof().await,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+    )
+    .unwrap();
+
+    // A bundle created for another verification key
+    let mut tampered: serde_json::Value = serde_json::from_str(&bundle).unwrap();
+    tampered["vk_fingerprint"] = "00".repeat(32).into();
+
+    let result = ios_ezkl::verify_bundle(
+        tampered.to_string(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        None,
+    );
+    match result {
+        Err(EZKLError::FingerprintMismatch { artifact, .. }) => {
+            assert_eq!(artifact, "verification key")
+        }
+        other => panic!("Expected a FingerprintMismatch, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_bundle_with_invalid_proof_is_not_a_mismatch() {
+    let fixture = common::fixture();
+    let proof = common::tamper_instance(&common::proof().await);
+    let bundle =
+        ios_ezkl::create_bundle(proof, fixture.settings_json.clone(), fixture.vk.clone()).unwrap();
+
+    let result = ios_ezkl::verify_bundle(
+        bundle,
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        Some(fixture.settings_json.clone()),
+    );
+    assert!(
+        matches!(result, Ok(false) | Err(EZKLError::InternalError(_))),
+        "Expected the proof to be rejected, got {:?}",
+        result
+    );
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{witness_to_input, EZKLError};
+
+/// A witness of a hypothetical first-stage model whose outputs are the inputs of the sample circuit.
+fn first_stage_witness(witness_json: &str) -> String {
+    let mut witness: serde_json::Value = serde_json::from_str(witness_json).unwrap();
+    witness["outputs"] = witness["inputs"].clone();
+    witness.to_string()
+}
+
+#[tokio::test]
+async fn test_chained_input_proves_in_second_stage() {
+    let fixture = common::fixture();
+    let witness = first_stage_witness(&common::witness().await);
+
+    let input_json = witness_to_input(
+        witness.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.compiled_circuit.clone(),
+    )
+    .expect("Failed to convert the witness");
+
+    let second_witness = ios_ezkl::gen_witness(
+        input_json,
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed for the second stage");
+
+    // The second stage quantizes the chained values back to the outputs of the first stage
+    let first: serde_json::Value = serde_json::from_str(&witness).unwrap();
+    let second: serde_json::Value = serde_json::from_str(&second_witness).unwrap();
+    assert_eq!(first["outputs"], second["inputs"]);
+
+    let proof = ios_ezkl::prove(
+        second_witness,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Proving failed for the second stage");
+    let valid = ios_ezkl::verify(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed for the second stage");
+    assert!(valid);
+}
+
+#[tokio::test]
+async fn test_shape_mismatch_is_reported() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    // The sample circuit has 10 outputs but takes 784 inputs, so it can't be chained to itself
+    let err = witness_to_input(
+        witness,
+        fixture.compiled_circuit.clone(),
+        fixture.compiled_circuit.clone(),
+    )
+    .expect_err("Chaining circuits with mismatched shapes should fail");
+
+    match err {
+        EZKLError::InvalidInput(message) => {
+            assert!(message.contains("output 0 has 10 elements"), "{}", message);
+            assert!(message.contains("784 elements"), "{}", message);
+        }
+        other => panic!("Unexpected error: {}", other),
+    }
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{verify_advanced, EZKLError, VerifyOptions, VerifyReport, VerifyStrictness};
+
+/// The settings of the sample circuit with the lowercase spellings of the run args the ezkl CLI
+/// and Python bindings take.
+const CLI_SETTINGS_PATH: &str = "tests/cli-compat/settings.json";
+/// The settings of the sample circuit as written by an ezkl version before IPA commitments.
+const LEGACY_SETTINGS_PATH: &str = "tests/ezkl-sample/legacy/settings.json";
+
+fn options(verify_strictness: VerifyStrictness) -> VerifyOptions {
+    VerifyOptions { verify_strictness }
+}
+
+fn verify_with(
+    proof_json: &str,
+    settings_json: &str,
+    strictness: VerifyStrictness,
+) -> Result<VerifyReport, EZKLError> {
+    let fixture = common::fixture();
+    verify_advanced(
+        proof_json.to_string(),
+        settings_json.to_string(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        options(strictness),
+    )
+}
+
+/// The fields of a report that must agree, leaving out the time spent.
+fn result_of(report: &VerifyReport) -> String {
+    format!(
+        "{:?} {:?} {:?} {:?} {:?} {:?} {:?} {}",
+        report.outcome,
+        report.failure,
+        report.failure_message,
+        report.expected_instances,
+        report.provided_instances,
+        report.srs_original_k,
+        report.srs_used_k,
+        report.downsized
+    )
+}
+
+#[tokio::test]
+async fn test_cli_artifacts_verify_identically() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+    let tampered = common::tamper_instance(&proof);
+    let cli_settings = std::fs::read_to_string(CLI_SETTINGS_PATH).unwrap();
+    let legacy_settings = std::fs::read_to_string(LEGACY_SETTINGS_PATH).unwrap();
+
+    for canonical in [&proof, &tampered] {
+        let expected = verify_with(canonical, &fixture.settings_json, VerifyStrictness::Strict)
+            .expect("Verification failed");
+        for settings_json in [&fixture.settings_json, &cli_settings, &legacy_settings] {
+            let report = verify_with(canonical, settings_json, VerifyStrictness::CliCompatible)
+                .expect("Verification failed");
+            assert_eq!(result_of(&report), result_of(&expected));
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_strict_rejects_cli_artifacts() {
+    let proof = common::proof().await;
+    let cli_settings = std::fs::read_to_string(CLI_SETTINGS_PATH).unwrap();
+
+    let err = verify_with(&proof, &cli_settings, VerifyStrictness::Strict).unwrap_err();
+    match err {
+        EZKLError::JsonError { field, .. } => assert_eq!(field, "settings_json"),
+        err => panic!("{:?}", err),
+    }
+}
+
+#[tokio::test]
+async fn test_strict_matches_verify_detailed() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    let report = verify_with(&proof, &fixture.settings_json, VerifyStrictness::Strict).unwrap();
+    let detailed = ios_ezkl::verify_detailed(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .unwrap();
+    assert_eq!(result_of(&report), result_of(&detailed));
+    assert_eq!(
+        VerifyOptions::default().verify_strictness,
+        VerifyStrictness::Strict
+    );
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// The artifacts of the sample circuit, written to files for the CLI to read.
+struct ArtifactFiles {
+    dir: PathBuf,
+}
+
+impl ArtifactFiles {
+    fn write() -> Self {
+        let fixture = common::fixture();
+        let dir = common::scratch_dir().join("cli");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("settings.json"), &fixture.settings_json).unwrap();
+        std::fs::write(dir.join("vk.key"), &fixture.vk).unwrap();
+        std::fs::write(dir.join("pk.key"), &fixture.pk).unwrap();
+        ArtifactFiles { dir }
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+}
+
+fn cli(args: &[&Path]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ezkl-porter-cli"))
+        .args(args)
+        .output()
+        .expect("Failed to run the CLI")
+}
+
+fn stdout_json(output: &Output) -> serde_json::Value {
+    serde_json::from_slice(&output.stdout).expect("The CLI did not print JSON")
+}
+
+#[test]
+fn test_cli_proves_and_verifies_the_sample_circuit() {
+    let files = ArtifactFiles::write();
+    let circuit = Path::new(common::COMPILED_CIRCUIT_PATH);
+    let srs = Path::new(common::SRS_PATH);
+    let (witness, proof) = (files.path("witness.json"), files.path("proof.json"));
+
+    let output = cli(&[
+        "gen-witness".as_ref(),
+        "--input".as_ref(),
+        common::INPUT_JSON_PATH.as_ref(),
+        "--circuit".as_ref(),
+        circuit,
+        "--vk".as_ref(),
+        &files.path("vk.key"),
+        "--srs".as_ref(),
+        srs,
+    ]);
+    assert!(output.status.success(), "{:?}", output);
+    std::fs::write(&witness, &output.stdout).unwrap();
+
+    let output = cli(&[
+        "prove".as_ref(),
+        "--witness".as_ref(),
+        &witness,
+        "--circuit".as_ref(),
+        circuit,
+        "--pk".as_ref(),
+        &files.path("pk.key"),
+        "--srs".as_ref(),
+        srs,
+    ]);
+    assert!(output.status.success(), "{:?}", output);
+    std::fs::write(&proof, &output.stdout).unwrap();
+
+    let verify = |proof: &Path| {
+        cli(&[
+            "verify".as_ref(),
+            "--proof".as_ref(),
+            proof,
+            "--settings".as_ref(),
+            &files.path("settings.json"),
+            "--vk".as_ref(),
+            &files.path("vk.key"),
+            "--srs".as_ref(),
+            srs,
+        ])
+    };
+    let output = verify(&proof);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(stdout_json(&output), serde_json::json!({ "valid": true }));
+
+    let output = cli(&["inspect-proof".as_ref(), "--proof".as_ref(), &proof]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(stdout_json(&output)["instances"].is_array());
+
+    // A proof with tampered instances is reported with its own exit code
+    let tampered = files.path("tampered.json");
+    let proof_json = std::fs::read_to_string(&proof).unwrap();
+    std::fs::write(&tampered, common::tamper_instance(&proof_json)).unwrap();
+    let output = verify(&tampered);
+    assert_eq!(output.status.code(), Some(5), "{:?}", output);
+    assert_eq!(stdout_json(&output), serde_json::json!({ "valid": false }));
+}
+
+#[test]
+fn test_cli_prints_circuit_info() {
+    let output = cli(&[
+        "circuit-info".as_ref(),
+        "--circuit".as_ref(),
+        common::COMPILED_CIRCUIT_PATH.as_ref(),
+    ]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(stdout_json(&output)["logrows"].is_u64());
+}
+
+#[test]
+fn test_cli_maps_errors_to_exit_codes() {
+    let files = ArtifactFiles::write();
+    let not_json = files.path("not.json");
+    std::fs::write(&not_json, "not json").unwrap();
+
+    let output = cli(&["inspect-proof".as_ref(), "--proof".as_ref(), &not_json]);
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["variant"], "JsonError");
+
+    let missing = files.path("missing.json");
+    let output = cli(&["inspect-proof".as_ref(), "--proof".as_ref(), &missing]);
+    assert_eq!(output.status.code(), Some(66), "{:?}", output);
+
+    let output = cli(&["aggregate".as_ref()]);
+    assert_eq!(output.status.code(), Some(64), "{:?}", output);
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::{json, Value};
+
+fn execute(command: Value) -> Value {
+    let response = ios_ezkl::execute(command.to_string()).expect("Failed to execute command");
+    serde_json::from_str(&response).expect("Response is not valid JSON")
+}
+
+#[test]
+fn test_all_supported_commands_validate_their_args() {
+    let commands = ios_ezkl::supported_commands();
+    assert_eq!(
+        commands,
+        vec!["verify", "gen_witness", "inspect_proof", "circuit_info"]
+    );
+
+    for cmd in commands {
+        let response = execute(json!({ "cmd": cmd, "args": { "unexpected": true } }));
+        assert_eq!(
+            response["error"]["kind"], "invalid_args",
+            "Unexpected response for `{}`: {}",
+            cmd, response
+        );
+    }
+}
+
+#[test]
+fn test_unknown_and_malformed_commands() {
+    let response = execute(json!({ "cmd": "prove", "args": {} }));
+    assert_eq!(response["error"]["kind"], "unknown_command");
+
+    let response = ios_ezkl::execute("not json".to_string()).unwrap();
+    let response: Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["error"]["kind"], "invalid_command");
+}
+
+#[tokio::test]
+async fn test_execute_dispatches_to_implementations() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    let info = execute(json!({
+        "cmd": "circuit_info",
+        "args": { "compiled_circuit": BASE64.encode(&fixture.compiled_circuit) }
+    }));
+    assert!(info["result"]["logrows"].as_u64().is_some(), "{}", info);
+
+    let inspected = execute(json!({ "cmd": "inspect_proof", "args": { "proof_json": proof } }));
+    assert_eq!(
+        inspected["result"]["instances"][0]
+            .as_array()
+            .unwrap()
+            .len() as u64,
+        info["result"]["total_instances"].as_u64().unwrap()
+    );
+
+    let witness = execute(json!({
+        "cmd": "gen_witness",
+        "args": {
+            "input_json": fixture.input_json,
+            "compiled_circuit": BASE64.encode(&fixture.compiled_circuit),
+            "vk": BASE64.encode(&fixture.vk),
+            "srs": BASE64.encode(&fixture.srs),
+        }
+    }));
+    assert!(witness["result"]["outputs"].is_array(), "{}", witness);
+
+    let verified = execute(json!({
+        "cmd": "verify",
+        "args": {
+            "proof_json": proof,
+            "settings_json": fixture.settings_json,
+            "vk": BASE64.encode(&fixture.vk),
+            "srs": BASE64.encode(&fixture.srs),
+        }
+    }));
+    assert_eq!(verified["result"], true);
+}
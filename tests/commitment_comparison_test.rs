@@ -0,0 +1,54 @@
+#![cfg(feature = "benchmarks")]
+
+mod common;
+
+use ios_ezkl::{compare_commitments, CommitmentsWrapper};
+
+#[tokio::test]
+#[ignore = "slow: generates IPA keys and proves the sample circuit with both schemes"]
+async fn test_compare_commitments_on_sample_circuit() {
+    let kzg = common::fixture();
+    let ipa = common::ipa_fixture();
+    let witness = common::witness().await;
+
+    let comparison = compare_commitments(
+        kzg.compiled_circuit.clone(),
+        witness,
+        kzg.srs.clone(),
+        ipa.srs.clone(),
+        kzg.pk.clone(),
+        ipa.pk.clone(),
+    )
+    .expect("Comparison failed");
+    println!("{:#?}", comparison);
+
+    assert_eq!(comparison.kzg.commitment, CommitmentsWrapper::KZG);
+    assert_eq!(comparison.ipa.commitment, CommitmentsWrapper::IPA);
+    assert!(comparison.kzg.verified);
+    assert!(comparison.ipa.verified);
+    assert!(comparison.num_rows > 0);
+    assert!(comparison.num_rows <= 1 << comparison.logrows);
+
+    // The IPA opening proof grows with the number of rows, the KZG one doesn't
+    assert!(comparison.kzg.proof_bytes > 0);
+    assert!(comparison.ipa.proof_bytes > comparison.kzg.proof_bytes);
+}
+
+#[tokio::test]
+#[ignore = "slow: generates IPA keys"]
+async fn test_mismatched_keys_are_rejected() {
+    let kzg = common::fixture();
+    let ipa = common::ipa_fixture();
+    let witness = common::witness().await;
+
+    // The pks are swapped, so the KZG pk is read as an IPA one and vice versa
+    let result = compare_commitments(
+        kzg.compiled_circuit.clone(),
+        witness,
+        kzg.srs.clone(),
+        ipa.srs.clone(),
+        ipa.pk.clone(),
+        kzg.pk.clone(),
+    );
+    assert!(result.is_err());
+}
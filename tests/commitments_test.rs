@@ -0,0 +1,57 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ezkl::Commitments;
+use ios_ezkl::{
+    circuit_info, default_prove_options, inspect_proof, parse_settings, prove_detailed,
+    CommitmentsWrapper,
+};
+
+#[test]
+fn test_commitment_conversions_round_trip() {
+    for commitment in [Commitments::KZG, Commitments::IPA] {
+        let wrapped = CommitmentsWrapper::from(commitment);
+        assert_eq!(Commitments::from(wrapped), commitment);
+    }
+    assert_eq!(
+        CommitmentsWrapper::from(Commitments::KZG),
+        CommitmentsWrapper::KZG
+    );
+    assert_eq!(
+        CommitmentsWrapper::from(Commitments::IPA),
+        CommitmentsWrapper::IPA
+    );
+}
+
+#[test]
+fn test_circuit_and_settings_report_commitment() {
+    for (fixture, expected) in [
+        (common::fixture(), CommitmentsWrapper::KZG),
+        (common::ipa_fixture(), CommitmentsWrapper::IPA),
+    ] {
+        let info = circuit_info(fixture.compiled_circuit.clone()).expect("Failed to read circuit");
+        assert_eq!(info.commitment, expected);
+
+        let settings =
+            parse_settings(fixture.settings_json.clone()).expect("Failed to parse settings");
+        assert_eq!(settings.commitment, expected);
+    }
+}
+
+#[tokio::test]
+async fn test_proof_and_metrics_report_commitment() {
+    let fixture = common::fixture();
+    let result = prove_detailed(
+        common::witness().await,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        default_prove_options(),
+    )
+    .expect("Proving failed");
+    assert_eq!(result.metrics.commitment, CommitmentsWrapper::KZG);
+
+    let info = inspect_proof(result.proof_json).expect("Failed to inspect proof");
+    assert_eq!(info.commitment, Some(CommitmentsWrapper::KZG));
+}
@@ -0,0 +1,338 @@
+#![allow(dead_code)]
+
+use ezkl::commands::DEFAULT_DISABLE_SELECTOR_COMPRESSION;
+use ezkl::graph::{GraphCircuit, Visibility};
+use ezkl::pfsys::{create_keys, save_pk, save_vk};
+use ezkl::Commitments;
+use ezkl::RunArgs;
+use halo2_proofs::halo2curves::bn256::{Bn256, G1Affine};
+use halo2_proofs::poly::commitment::{Params, ParamsProver};
+use halo2_proofs::poly::ipa::commitment::{IPACommitmentScheme, ParamsIPA};
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use ios_ezkl::testing::deserialize_params_prover;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+pub const INPUT_JSON_PATH: &str = "tests/ezkl-sample/input.json";
+pub const COMPILED_CIRCUIT_PATH: &str = "tests/ezkl-sample/network.ezkl";
+pub const SRS_PATH: &str = "tests/ezkl-sample/kzg.srs";
+
+/// The artifacts of the sample circuit, with keys generated once per test binary.
+pub struct Fixture {
+    pub input_json: String,
+    pub compiled_circuit: Vec<u8>,
+    pub settings_json: String,
+    pub srs: Vec<u8>,
+    pub vk: Vec<u8>,
+    pub pk: Vec<u8>,
+}
+
+static FIXTURE: OnceLock<Fixture> = OnceLock::new();
+static IPA_FIXTURE: OnceLock<Fixture> = OnceLock::new();
+static POLYCOMMIT_FIXTURE: OnceLock<Fixture> = OnceLock::new();
+static HASHED_FIXTURE: OnceLock<Fixture> = OnceLock::new();
+static FIXED_PARAMS_FIXTURE: OnceLock<Fixture> = OnceLock::new();
+static PRIVATE_OUTPUTS_FIXTURE: OnceLock<Fixture> = OnceLock::new();
+static HASHED_OUTPUTS_FIXTURE: OnceLock<Fixture> = OnceLock::new();
+
+/// Returns a directory private to the running test binary.
+pub fn scratch_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ios-ezkl-tests-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("Failed to create scratch directory");
+    dir
+}
+
+pub fn fixture() -> &'static Fixture {
+    FIXTURE.get_or_init(|| {
+        let circuit = GraphCircuit::load(COMPILED_CIRCUIT_PATH.into()).unwrap();
+        let srs = std::fs::read(SRS_PATH).expect("Failed to read srs file");
+        let params = deserialize_params_prover::<KZGCommitmentScheme<Bn256>>(
+            Some(&srs),
+            circuit.settings().run_args.logrows,
+        )
+        .unwrap();
+        let pk = create_keys::<KZGCommitmentScheme<Bn256>, GraphCircuit>(
+            &circuit,
+            &params,
+            DEFAULT_DISABLE_SELECTOR_COMPRESSION.parse().unwrap(),
+        )
+        .unwrap();
+
+        let vk_path = scratch_dir().join("vk.key");
+        let pk_path = scratch_dir().join("pk.key");
+        save_vk::<G1Affine>(&vk_path, pk.get_vk()).unwrap();
+        save_pk::<G1Affine>(&pk_path, &pk).unwrap();
+
+        Fixture {
+            input_json: std::fs::read_to_string(INPUT_JSON_PATH).expect("Failed to read input"),
+            compiled_circuit: std::fs::read(COMPILED_CIRCUIT_PATH)
+                .expect("Failed to read circuit file"),
+            settings_json: circuit.settings().as_json().unwrap(),
+            srs,
+            vk: std::fs::read(vk_path).expect("Failed to read vk file"),
+            pk: std::fs::read(pk_path).expect("Failed to read pk file"),
+        }
+    })
+}
+
+/// The artifacts of the sample circuit switched to the IPA commitment scheme, with a freshly
+/// generated IPA SRS and keys.
+pub fn ipa_fixture() -> &'static Fixture {
+    IPA_FIXTURE.get_or_init(|| {
+        let mut circuit = GraphCircuit::load(COMPILED_CIRCUIT_PATH.into()).unwrap();
+        circuit.core.settings.run_args.commitment = Some(Commitments::IPA);
+
+        let params = ParamsIPA::<G1Affine>::new(circuit.settings().run_args.logrows);
+        let mut srs = vec![];
+        params.write(&mut srs).unwrap();
+        let pk = create_keys::<IPACommitmentScheme<G1Affine>, GraphCircuit>(
+            &circuit,
+            &params,
+            DEFAULT_DISABLE_SELECTOR_COMPRESSION.parse().unwrap(),
+        )
+        .unwrap();
+
+        let vk_path = scratch_dir().join("ipa-vk.key");
+        let pk_path = scratch_dir().join("ipa-pk.key");
+        save_vk::<G1Affine>(&vk_path, pk.get_vk()).unwrap();
+        save_pk::<G1Affine>(&pk_path, &pk).unwrap();
+
+        Fixture {
+            input_json: std::fs::read_to_string(INPUT_JSON_PATH).expect("Failed to read input"),
+            compiled_circuit: bincode::serialize(&circuit).unwrap(),
+            settings_json: circuit.settings().as_json().unwrap(),
+            srs,
+            vk: std::fs::read(vk_path).expect("Failed to read vk file"),
+            pk: std::fs::read(pk_path).expect("Failed to read pk file"),
+        }
+    })
+}
+
+/// The artifacts of the sample circuit rebuilt with polycommit input and output visibility, so
+/// that its inputs and outputs are committed to with KZG instead of being public instances.
+pub fn polycommit_fixture() -> &'static Fixture {
+    POLYCOMMIT_FIXTURE.get_or_init(|| {
+        visibility_fixture("polycommit", |run_args| {
+            run_args.input_visibility = Visibility::KZGCommit;
+            run_args.output_visibility = Visibility::KZGCommit;
+        })
+    })
+}
+
+/// The artifacts of the sample circuit rebuilt with hashed input and output visibility, so that
+/// the public instances of its proofs are the Poseidon hashes of its inputs and outputs.
+pub fn hashed_fixture() -> &'static Fixture {
+    HASHED_FIXTURE.get_or_init(|| {
+        let hashed = || Visibility::Hashed {
+            hash_is_public: true,
+            outlets: vec![],
+        };
+        visibility_fixture("hashed", |run_args| {
+            run_args.input_visibility = hashed();
+            run_args.output_visibility = hashed();
+        })
+    })
+}
+
+/// The artifacts of the sample circuit rebuilt with fixed param visibility, so that its params
+/// are baked into the vk, and with public inputs, so that its proofs expose inputs and outputs
+/// but no params.
+pub fn fixed_params_fixture() -> &'static Fixture {
+    FIXED_PARAMS_FIXTURE.get_or_init(|| {
+        visibility_fixture("fixed-params", |run_args| {
+            run_args.input_visibility = Visibility::Public;
+            run_args.param_visibility = Visibility::Fixed;
+        })
+    })
+}
+
+/// The artifacts of the sample circuit rebuilt with hashed input and private output visibility,
+/// so that its proofs only attest to the execution of the model: their only public instance is
+/// the Poseidon hash of the input, and they expose no outputs.
+pub fn private_outputs_fixture() -> &'static Fixture {
+    PRIVATE_OUTPUTS_FIXTURE.get_or_init(|| {
+        visibility_fixture("private-outputs", |run_args| {
+            run_args.input_visibility = Visibility::Hashed {
+                hash_is_public: true,
+                outlets: vec![],
+            };
+            run_args.output_visibility = Visibility::Private;
+        })
+    })
+}
+
+/// The artifacts of the sample circuit rebuilt with hashed output visibility, so that the only
+/// public instance of its proofs is the Poseidon hash of its output, which the app reveals
+/// separately.
+pub fn hashed_outputs_fixture() -> &'static Fixture {
+    HASHED_OUTPUTS_FIXTURE.get_or_init(|| {
+        visibility_fixture("hashed-outputs", |run_args| {
+            run_args.output_visibility = Visibility::Hashed {
+                hash_is_public: true,
+                outlets: vec![],
+            };
+        })
+    })
+}
+
+/// Rebuilds the sample circuit with the visibilities set by `configure` and generates its keys.
+fn visibility_fixture(name: &str, configure: impl FnOnce(&mut RunArgs)) -> Fixture {
+    let compiled = GraphCircuit::load(COMPILED_CIRCUIT_PATH.into()).unwrap();
+    let mut run_args = compiled.settings().run_args.clone();
+    configure(&mut run_args);
+    let circuit = GraphCircuit::new(compiled.model().clone(), &run_args).unwrap();
+
+    let srs = std::fs::read(SRS_PATH).expect("Failed to read srs file");
+    let params = deserialize_params_prover::<KZGCommitmentScheme<Bn256>>(
+        Some(&srs),
+        circuit.settings().run_args.logrows,
+    )
+    .unwrap();
+    let pk = create_keys::<KZGCommitmentScheme<Bn256>, GraphCircuit>(
+        &circuit,
+        &params,
+        DEFAULT_DISABLE_SELECTOR_COMPRESSION.parse().unwrap(),
+    )
+    .unwrap();
+
+    let vk_path = scratch_dir().join(format!("{}-vk.key", name));
+    let pk_path = scratch_dir().join(format!("{}-pk.key", name));
+    save_vk::<G1Affine>(&vk_path, pk.get_vk()).unwrap();
+    save_pk::<G1Affine>(&pk_path, &pk).unwrap();
+
+    Fixture {
+        input_json: std::fs::read_to_string(INPUT_JSON_PATH).expect("Failed to read input"),
+        compiled_circuit: bincode::serialize(&circuit).unwrap(),
+        settings_json: circuit.settings().as_json().unwrap(),
+        srs,
+        vk: std::fs::read(vk_path).expect("Failed to read vk file"),
+        pk: std::fs::read(pk_path).expect("Failed to read pk file"),
+    }
+}
+
+/// Generates a proof for the given fixture with the default proving options.
+#[cfg(feature = "prover")]
+pub async fn proof_for(fixture: &Fixture) -> String {
+    let witness = ios_ezkl::gen_witness(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+    ios_ezkl::prove(
+        witness,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Proof generation failed")
+}
+
+/// Generates a witness for the sample input.
+#[cfg(feature = "prover")]
+pub async fn witness() -> String {
+    let fixture = fixture();
+    ios_ezkl::gen_witness(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed")
+}
+
+/// Generates a proof for the sample input with the default proving options.
+#[cfg(feature = "prover")]
+pub async fn proof() -> String {
+    let fixture = fixture();
+    ios_ezkl::prove(
+        witness().await,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Proof generation failed")
+}
+
+/// Generates a proof for the sample input directly with ezkl, for builds without the prover.
+#[cfg(not(feature = "prover"))]
+pub async fn proof() -> String {
+    use ezkl::circuit::region::RegionSettings;
+    use ezkl::circuit::CheckMode;
+    use ezkl::graph::input::GraphData;
+    use ezkl::pfsys::{create_proof_circuit, load_pk, TranscriptType};
+    use halo2_proofs::plonk::Circuit;
+    use halo2_proofs::poly::kzg::multiopen::{ProverSHPLONK, VerifierSHPLONK};
+    use halo2_proofs::poly::kzg::strategy::SingleStrategy;
+    use snark_verifier::system::halo2::transcript::evm::EvmTranscript;
+
+    let fixture = fixture();
+    let mut circuit = GraphCircuit::load(COMPILED_CIRCUIT_PATH.into()).unwrap();
+    let params = deserialize_params_prover::<KZGCommitmentScheme<Bn256>>(
+        Some(&fixture.srs),
+        circuit.settings().run_args.logrows,
+    )
+    .unwrap();
+    let pk = load_pk::<KZGCommitmentScheme<Bn256>, GraphCircuit>(
+        scratch_dir().join("pk.key"),
+        circuit.params(),
+    )
+    .unwrap();
+
+    let data: GraphData = serde_json::from_str(&fixture.input_json).unwrap();
+    let mut input = circuit.load_graph_input(&data).await.unwrap();
+    let witness = circuit
+        .forward::<KZGCommitmentScheme<Bn256>>(
+            &mut input,
+            Some(pk.get_vk()),
+            None,
+            RegionSettings::all_true(),
+        )
+        .unwrap();
+    circuit.load_graph_witness(&witness).unwrap();
+    let pretty_public_inputs = circuit.pretty_public_inputs(&witness).unwrap();
+    let public_inputs = circuit.prepare_public_inputs(&witness).unwrap();
+
+    let mut snark = create_proof_circuit::<
+        KZGCommitmentScheme<Bn256>,
+        _,
+        ProverSHPLONK<_>,
+        VerifierSHPLONK<_>,
+        SingleStrategy<_>,
+        _,
+        EvmTranscript<_, _, _, _>,
+        EvmTranscript<_, _, _, _>,
+    >(
+        circuit,
+        vec![public_inputs],
+        &params,
+        &pk,
+        CheckMode::SAFE,
+        Commitments::KZG,
+        TranscriptType::EVM,
+        None,
+        None,
+    )
+    .unwrap();
+    snark.pretty_public_inputs = pretty_public_inputs;
+    ios_ezkl::testing::serialize_proof(&snark).unwrap()
+}
+
+/// Alters the value of a public instance of a proof while keeping it a valid field element.
+pub fn tamper_instance(proof_json: &str) -> String {
+    let mut proof: serde_json::Value = serde_json::from_str(proof_json).unwrap();
+    let instance = proof["instances"][0][0].as_str().unwrap().to_string();
+    // Alter the least significant nibble, for both big endian (`0x`-prefixed) and little endian encodings
+    let mut chars: Vec<char> = instance.chars().collect();
+    let index = if instance.starts_with("0x") {
+        chars.len() - 1
+    } else {
+        0
+    };
+    chars[index] = if chars[index] == '0' { '1' } else { '0' };
+    proof["instances"][0][0] = serde_json::Value::String(chars.into_iter().collect());
+    proof.to_string()
+}
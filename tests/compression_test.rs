@@ -0,0 +1,115 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use ios_ezkl::{ProveOptions, WitnessOptions};
+use std::io::{Read, Write};
+
+fn gzip(json: &str) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(json.as_bytes()).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn gunzip(bytes: &[u8]) -> String {
+    let mut json = String::new();
+    GzDecoder::new(bytes).read_to_string(&mut json).unwrap();
+    json
+}
+
+fn verify_plain_and_compressed(proof_json: &str) -> (bool, bool) {
+    let fixture = common::fixture();
+    let plain = ios_ezkl::verify(
+        proof_json.to_string(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    );
+    let compressed = ios_ezkl::verify_bytes(
+        gzip(proof_json),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    );
+    (plain.unwrap_or(false), compressed.unwrap_or(false))
+}
+
+#[tokio::test]
+async fn test_compressed_and_plain_paths_verify_alike() {
+    let fixture = common::fixture();
+    let witness = ios_ezkl::gen_witness_detailed_with_options(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        WitnessOptions {
+            compress_output: true,
+            ..WitnessOptions::default()
+        },
+    )
+    .await
+    .expect("Witness generation failed");
+    assert!(witness.witness_json.is_empty());
+    let witness_gzip = witness.witness_gzip.expect("Missing compressed witness");
+    let witness_json = gunzip(&witness_gzip);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&witness_json).unwrap(),
+        serde_json::from_str::<serde_json::Value>(&common::witness().await).unwrap()
+    );
+
+    // The compressed witness is accepted as is, and the proof is returned compressed
+    let result = ios_ezkl::prove_bytes(
+        witness_gzip,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ProveOptions {
+            compress_output: true,
+            ..ProveOptions::default()
+        },
+    )
+    .expect("Proving failed");
+    assert!(result.proof_json.is_empty());
+    let proof_json = gunzip(&result.proof_gzip.expect("Missing compressed proof"));
+
+    assert_eq!(verify_plain_and_compressed(&proof_json), (true, true));
+    let tampered = common::tamper_instance(&proof_json);
+    assert_eq!(verify_plain_and_compressed(&tampered), (false, false));
+
+    // Compressed proof files are read transparently
+    let proof_path = common::scratch_dir().join("proof.json.gz");
+    let settings_path = common::scratch_dir().join("settings.json");
+    let vk_path = common::scratch_dir().join("vk.key");
+    let srs_path = common::scratch_dir().join("kzg.srs");
+    std::fs::write(&proof_path, gzip(&proof_json)).unwrap();
+    std::fs::write(&settings_path, &fixture.settings_json).unwrap();
+    std::fs::write(&vk_path, &fixture.vk).unwrap();
+    std::fs::write(&srs_path, &fixture.srs).unwrap();
+    assert!(ios_ezkl::verify_from_file(
+        proof_path.display().to_string(),
+        settings_path.display().to_string(),
+        vk_path.display().to_string(),
+        srs_path.display().to_string(),
+    )
+    .expect("Verification failed"));
+}
+
+#[test]
+fn test_compress_output_requires_a_detailed_call() {
+    let fixture = common::fixture();
+    let err = ios_ezkl::prove_advanced(
+        String::new(),
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ProveOptions {
+            compress_output: true,
+            ..ProveOptions::default()
+        },
+    )
+    .expect_err("A string result can't hold a compressed proof");
+    assert!(matches!(err, ios_ezkl::EZKLError::InvalidInput(_)));
+}
@@ -0,0 +1,78 @@
+use ios_ezkl::{EZKLError, EzklConfig, EzklLogger, LogLevel, ResourceLimits};
+use std::sync::{Arc, Barrier, Mutex};
+
+struct RecordingLogger {
+    messages: Mutex<Vec<(LogLevel, String)>>,
+}
+
+impl EzklLogger for RecordingLogger {
+    fn log(&self, level: LogLevel, message: String) {
+        self.messages.lock().unwrap().push((level, message));
+    }
+}
+
+// The configuration is process-wide, so all scenarios run sequentially in a single test
+#[test]
+fn test_initialize_concurrently() {
+    let logger: Arc<dyn EzklLogger> = Arc::new(RecordingLogger {
+        messages: Mutex::new(Vec::new()),
+    });
+    let config = EzklConfig {
+        logger: Some(logger),
+        log_level: LogLevel::Debug,
+        max_threads: Some(2),
+        witness_cache_dir: None,
+        stale_temp_file_age_secs: 60,
+        limits: ResourceLimits::default(),
+        zeroize_inputs: true,
+        io_buffer_capacity: None,
+        prove_thread_priority: None,
+        verify_thread_priority: None,
+        coordinated_reads: false,
+    };
+
+    // 1. Many threads initializing with an identical configuration all succeed
+    let threads = 8;
+    let barrier = Arc::new(Barrier::new(threads));
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let barrier = barrier.clone();
+            let config = config.clone();
+            std::thread::spawn(move || {
+                barrier.wait();
+                ios_ezkl::initialize(config)
+            })
+        })
+        .collect();
+    for handle in handles {
+        let result = handle.join().expect("Initialization thread panicked");
+        assert!(result.is_ok(), "Initialization failed: {:?}", result);
+    }
+
+    // 2. A conflicting configuration is rejected, also when racing
+    let barrier = Arc::new(Barrier::new(threads));
+    let handles: Vec<_> = (0..threads)
+        .map(|i| {
+            let barrier = barrier.clone();
+            let config = EzklConfig {
+                max_threads: Some(4 + i as u32),
+                ..config.clone()
+            };
+            std::thread::spawn(move || {
+                barrier.wait();
+                ios_ezkl::initialize(config)
+            })
+        })
+        .collect();
+    for handle in handles {
+        let result = handle.join().expect("Initialization thread panicked");
+        assert!(
+            matches!(result, Err(EZKLError::AlreadyInitialized)),
+            "Expected AlreadyInitialized, got {:?}",
+            result
+        );
+    }
+
+    // 3. The original configuration is still accepted
+    assert!(ios_ezkl::initialize(config).is_ok());
+}
@@ -0,0 +1,99 @@
+#![cfg(all(feature = "prover", feature = "test-hooks"))]
+
+mod common;
+
+use ios_ezkl::testing::on_coordinated_read;
+use ios_ezkl::{EZKLError, EzklConfig};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// The configuration is process-wide, so all scenarios run sequentially in a single test
+#[tokio::test]
+async fn test_coordinated_reads_retry_torn_reads() {
+    ios_ezkl::initialize(EzklConfig {
+        coordinated_reads: true,
+        ..EzklConfig::default()
+    })
+    .unwrap();
+
+    let fixture = common::fixture();
+    let dir = common::scratch_dir().join("coordinated");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = |name: &str| dir.join(name).display().to_string();
+    std::fs::write(path("proof.json"), common::proof().await).unwrap();
+    std::fs::write(path("settings.json"), &fixture.settings_json).unwrap();
+    std::fs::write(path("srs"), &fixture.srs).unwrap();
+    let vk_path = path("vk.key");
+    let half_written_vk = &fixture.vk[..fixture.vk.len() / 2];
+
+    let verify = || {
+        ios_ezkl::verify_from_file(
+            path("proof.json"),
+            path("settings.json"),
+            vk_path.clone(),
+            path("srs"),
+        )
+    };
+
+    // 1. A stable file is read once
+    std::fs::write(&vk_path, &fixture.vk).unwrap();
+    assert!(verify().expect("Verification failed"));
+
+    // 2. The vk is being rewritten by another process when the read starts, and is complete by
+    //    the time it is read again
+    std::fs::write(&vk_path, half_written_vk).unwrap();
+    let complete = (vk_path.clone(), fixture.vk.clone());
+    on_coordinated_read(&vk_path, 1, move || {
+        std::fs::write(&complete.0, &complete.1).unwrap()
+    });
+    assert!(verify().expect("Verification failed after the retry"));
+
+    // 3. The vk changes during both reads
+    let toggle = AtomicBool::new(false);
+    let rewrite = (
+        vk_path.clone(),
+        fixture.vk.clone(),
+        half_written_vk.to_vec(),
+    );
+    on_coordinated_read(&vk_path, 2, move || {
+        let bytes = if toggle.fetch_xor(true, Ordering::SeqCst) {
+            &rewrite.1
+        } else {
+            &rewrite.2
+        };
+        std::fs::write(&rewrite.0, bytes).unwrap()
+    });
+    let err = verify().unwrap_err();
+    assert!(
+        matches!(err, EZKLError::ConcurrentModification { ref path } if *path == vk_path),
+        "{:?}",
+        err
+    );
+
+    // 4. The vk is rewritten in place with content of the same length during both reads, and
+    //    its modification time is restored, so only its content tells the change apart
+    std::fs::write(&vk_path, &fixture.vk).unwrap();
+    let modified = std::fs::metadata(&vk_path).unwrap().modified().unwrap();
+    let mut corrupted_vk = fixture.vk.clone();
+    *corrupted_vk.last_mut().unwrap() ^= 1;
+    let toggle = AtomicBool::new(false);
+    let rewrite = (vk_path.clone(), fixture.vk.clone(), corrupted_vk);
+    on_coordinated_read(&vk_path, 2, move || {
+        let bytes = if toggle.fetch_xor(true, Ordering::SeqCst) {
+            &rewrite.1
+        } else {
+            &rewrite.2
+        };
+        std::fs::write(&rewrite.0, bytes).unwrap();
+        let file = std::fs::File::options()
+            .write(true)
+            .open(&rewrite.0)
+            .unwrap();
+        file.set_modified(modified).unwrap();
+    });
+    let err = verify().unwrap_err();
+    assert!(
+        matches!(err, EZKLError::ConcurrentModification { ref path } if *path == vk_path),
+        "{:?}",
+        err
+    );
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "prover")]
+#![cfg(feature = "diagnostics")]
+
+mod common;
+
+use ios_ezkl::ProveOptions;
+
+#[tokio::test]
+async fn test_peak_memory_is_tracked_per_call() {
+    let fixture = common::fixture();
+    ios_ezkl::set_diagnostics(true);
+
+    // 1. Generate a witness and prove it, both tracking their peak memory
+    let witness = ios_ezkl::gen_witness_detailed(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+    let proof = ios_ezkl::prove_detailed(
+        witness.witness_json,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ProveOptions::default(),
+    )
+    .expect("Proof generation failed");
+
+    let prove_peak = proof.metrics.peak_memory_bytes.expect("Peak not tracked");
+    assert!(prove_peak > 0);
+
+    // 2. A cheaper call afterwards must not inherit the peak reached while proving
+    let witness = ios_ezkl::gen_witness_detailed(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+    let witness_peak = witness.peak_memory_bytes.expect("Peak not tracked");
+    assert!(witness_peak > 0);
+    assert!(witness_peak < prove_peak);
+}
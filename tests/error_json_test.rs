@@ -0,0 +1,188 @@
+use ios_ezkl::{error_to_json, EZKLError, ErrorReport};
+
+fn to_json(error: EZKLError, stage: Option<&str>) -> String {
+    error_to_json(ErrorReport {
+        error,
+        stage: stage.map(str::to_string),
+    })
+    .expect("Failed to serialize error")
+}
+
+#[test]
+fn test_internal_error_json() {
+    assert_eq!(
+        to_json(EZKLError::InternalError("boom".to_string()), Some("prove")),
+        r#"{"variant":"InternalError","message":"Internal error: boom","detail":"boom","stage":"prove"}"#
+    );
+}
+
+#[test]
+fn test_invalid_input_json() {
+    assert_eq!(
+        to_json(
+            EZKLError::InvalidInput("SRS buffer is empty".to_string()),
+            None
+        ),
+        r#"{"variant":"InvalidInput","message":"Invalid input: SRS buffer is empty","detail":"SRS buffer is empty"}"#
+    );
+}
+
+#[test]
+fn test_already_initialized_json() {
+    assert_eq!(
+        to_json(EZKLError::AlreadyInitialized, None),
+        r#"{"variant":"AlreadyInitialized","message":"Already initialized with a different configuration"}"#
+    );
+}
+
+#[test]
+fn test_busy_json() {
+    assert_eq!(
+        to_json(EZKLError::Busy { running_proofs: 2 }, Some("prove")),
+        r#"{"variant":"Busy","message":"Busy: 2 proofs are already running","running_proofs":2,"stage":"prove"}"#
+    );
+}
+
+#[test]
+fn test_json_error_json() {
+    let error = EZKLError::JsonError {
+        field: "input_json".to_string(),
+        line: 1,
+        column: 7,
+        message: "expected value".to_string(),
+    };
+    assert_eq!(
+        to_json(error, Some("gen_witness")),
+        r#"{"variant":"JsonError","message":"Invalid JSON in `input_json` at line 1, column 7: expected value","field":"input_json","line":1,"column":7,"stage":"gen_witness"}"#
+    );
+}
+
+#[test]
+fn test_fingerprint_mismatch_json() {
+    let error = EZKLError::FingerprintMismatch {
+        artifact: "proving key".to_string(),
+        expected: "ab".to_string(),
+        actual: "cd".to_string(),
+    };
+    assert_eq!(
+        to_json(error, None),
+        r#"{"variant":"FingerprintMismatch","message":"Fingerprint mismatch: the proving key has fingerprint cd, but ab was expected","artifact":"proving key","expected":"ab","actual":"cd"}"#
+    );
+}
+
+#[test]
+fn test_timed_out_json() {
+    assert_eq!(
+        to_json(EZKLError::TimedOut { elapsed_ms: 1500 }, Some("prove")),
+        r#"{"variant":"TimedOut","message":"Timed out: the call exceeded its time budget after 1500 ms","elapsed_ms":1500,"stage":"prove"}"#
+    );
+}
+
+#[test]
+fn test_missing_input_json() {
+    assert_eq!(
+        to_json(EZKLError::MissingInput { node: 3 }, Some("gen_witness")),
+        r#"{"variant":"MissingInput","message":"Missing input: node 3 of the model has no input","node":3,"stage":"gen_witness"}"#
+    );
+}
+
+#[test]
+fn test_shape_mismatch_json() {
+    let error = EZKLError::ShapeMismatch {
+        node: 2,
+        detail: "expected [1, 3]".to_string(),
+    };
+    assert_eq!(
+        to_json(error, Some("gen_witness")),
+        r#"{"variant":"ShapeMismatch","message":"Shape mismatch at node 2: expected [1, 3]","node":2,"detail":"expected [1, 3]","stage":"gen_witness"}"#
+    );
+}
+
+#[test]
+fn test_load_vk_json() {
+    let error = EZKLError::LoadVk {
+        detail: "truncated".to_string(),
+    };
+    assert_eq!(
+        to_json(error, Some("verify")),
+        r#"{"variant":"LoadVk","message":"Failed to load the verification key: truncated","detail":"truncated","stage":"verify"}"#
+    );
+}
+
+#[test]
+fn test_load_pk_json() {
+    let error = EZKLError::LoadPk {
+        detail: "truncated".to_string(),
+    };
+    assert_eq!(
+        to_json(error, Some("prove")),
+        r#"{"variant":"LoadPk","message":"Failed to load the proving key: truncated","detail":"truncated","stage":"prove"}"#
+    );
+}
+
+#[test]
+fn test_srs_read_json() {
+    let error = EZKLError::SrsRead {
+        detail: "unexpected end of file".to_string(),
+    };
+    assert_eq!(
+        to_json(error, None),
+        r#"{"variant":"SrsRead","message":"Failed to read the SRS: unexpected end of file","detail":"unexpected end of file"}"#
+    );
+}
+
+#[test]
+fn test_concurrent_modification_json() {
+    let error = EZKLError::ConcurrentModification {
+        path: "/tmp/pk.key".to_string(),
+    };
+    assert_eq!(
+        to_json(error, None),
+        r#"{"variant":"ConcurrentModification","message":"Concurrent modification: `/tmp/pk.key` changed while it was read, twice","path":"/tmp/pk.key"}"#
+    );
+}
+
+#[test]
+fn test_unsupported_json() {
+    let error = EZKLError::Unsupported {
+        detail: "KZG commitments only".to_string(),
+    };
+    assert_eq!(
+        to_json(error, None),
+        r#"{"variant":"Unsupported","message":"Unsupported: KZG commitments only","detail":"KZG commitments only"}"#
+    );
+}
+
+#[test]
+fn test_external_verification_failed_json() {
+    let error = EZKLError::ExternalVerificationFailed {
+        detail: "constraint not satisfied".to_string(),
+    };
+    assert_eq!(
+        to_json(error, Some("prove")),
+        r#"{"variant":"ExternalVerificationFailed","message":"The proof does not verify against the given verification key: constraint not satisfied","detail":"constraint not satisfied","stage":"prove"}"#
+    );
+}
+
+#[test]
+fn test_network_json() {
+    let error = EZKLError::Network {
+        detail: "connection refused".to_string(),
+    };
+    assert_eq!(
+        to_json(error, Some("gen_witness")),
+        r#"{"variant":"Network","message":"Network error: connection refused","detail":"connection refused","stage":"gen_witness"}"#
+    );
+}
+
+#[test]
+fn test_output_mismatch_json() {
+    let error = EZKLError::OutputMismatch {
+        expected: "ab".to_string(),
+        actual: "cd".to_string(),
+    };
+    assert_eq!(
+        to_json(error, None),
+        r#"{"variant":"OutputMismatch","message":"Output mismatch: the claimed outputs hash to cd, but the proof attests to ab","expected":"ab","actual":"cd"}"#
+    );
+}
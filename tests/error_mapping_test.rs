@@ -0,0 +1,114 @@
+mod common;
+
+use ezkl::graph::errors::GraphError;
+use ezkl::pfsys::srs::SrsError;
+use ezkl::pfsys::PfsysError;
+use ezkl::EZKLError as InnerEZKLError;
+use ios_ezkl::{error_to_json, EZKLError, ErrorReport};
+
+#[test]
+fn test_graph_errors_keep_their_variant() {
+    let err = EZKLError::from(GraphError::MissingInput(3));
+    assert!(
+        matches!(err, EZKLError::MissingInput { node: 3 }),
+        "{:?}",
+        err
+    );
+
+    let err = EZKLError::from(GraphError::InvalidDims(5, "matmul".to_string()));
+    assert!(
+        matches!(err, EZKLError::ShapeMismatch { node: 5, ref detail } if detail == "matmul"),
+        "{:?}",
+        err
+    );
+
+    let err = EZKLError::from(GraphError::MissingBatchSize);
+    assert!(matches!(err, EZKLError::InternalError(_)), "{:?}", err);
+}
+
+#[test]
+fn test_pfsys_and_srs_errors_keep_their_variant() {
+    let err = EZKLError::from(PfsysError::LoadVk("truncated".to_string()));
+    assert!(
+        matches!(err, EZKLError::LoadVk { ref detail } if detail == "truncated"),
+        "{:?}",
+        err
+    );
+
+    let err = EZKLError::from(PfsysError::LoadPk("truncated".to_string()));
+    assert!(
+        matches!(err, EZKLError::LoadPk { ref detail } if detail == "truncated"),
+        "{:?}",
+        err
+    );
+
+    let err = EZKLError::from(SrsError::ReadError("unexpected end of file".to_string()));
+    assert!(
+        matches!(err, EZKLError::SrsRead { ref detail } if detail == "unexpected end of file"),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn test_wrapped_errors_keep_their_variant() {
+    let err = EZKLError::from(InnerEZKLError::from(PfsysError::LoadVk(
+        "truncated".to_string(),
+    )));
+    assert!(matches!(err, EZKLError::LoadVk { .. }), "{:?}", err);
+
+    let err = EZKLError::from(InnerEZKLError::from(GraphError::MissingInput(0)));
+    assert!(
+        matches!(err, EZKLError::MissingInput { node: 0 }),
+        "{:?}",
+        err
+    );
+
+    let err = EZKLError::from(InnerEZKLError::from(SrsError::ReadError(
+        "bad point".to_string(),
+    )));
+    assert!(matches!(err, EZKLError::SrsRead { .. }), "{:?}", err);
+
+    // Errors without a dedicated variant remain internal errors with the original message
+    let err = EZKLError::from(InnerEZKLError::from(std::io::Error::new(
+        std::io::ErrorKind::Other,
+        "disk full",
+    )));
+    match err {
+        EZKLError::InternalError(message) => assert!(message.contains("disk full"), "{}", message),
+        other => panic!("Expected an InternalError, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_structured_error_json() {
+    let json = error_to_json(ErrorReport {
+        error: EZKLError::ShapeMismatch {
+            node: 5,
+            detail: "matmul".to_string(),
+        },
+        stage: None,
+    })
+    .unwrap();
+    assert_eq!(
+        json,
+        r#"{"variant":"ShapeMismatch","message":"Shape mismatch at node 5: matmul","node":5,"detail":"matmul"}"#
+    );
+}
+
+#[tokio::test]
+async fn test_verify_with_corrupted_vk_is_a_load_vk_error() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    let mut vk = fixture.vk.clone();
+    vk.truncate(vk.len() / 2);
+    let err = ios_ezkl::verify(
+        proof,
+        fixture.settings_json.clone(),
+        vk,
+        fixture.srs.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, EZKLError::LoadVk { .. }), "{:?}", err);
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{
+    encode_evm_calldata, estimate_evm_gas, prove_advanced, CheckModeWrapper, EZKLError,
+    ProofTypeWrapper, ProveOptions,
+};
+
+#[tokio::test]
+async fn test_estimate_matches_encoded_calldata() {
+    let fixture = common::fixture();
+    let proof_json = common::proof().await;
+
+    let estimate = estimate_evm_gas(proof_json.clone(), fixture.settings_json.clone())
+        .expect("Failed to estimate gas");
+    let calldata = encode_evm_calldata(proof_json.clone()).expect("Failed to encode calldata");
+
+    assert_eq!(estimate.calldata_bytes, calldata.len() as u64);
+    let zero_bytes = calldata.iter().filter(|&&byte| byte == 0).count() as u64;
+    let non_zero_bytes = calldata.len() as u64 - zero_bytes;
+    assert_eq!(estimate.calldata_gas, 4 * zero_bytes + 16 * non_zero_bytes);
+    assert_eq!(
+        estimate.estimated_total_gas,
+        21_000 + estimate.calldata_gas + estimate.estimated_verification_gas
+    );
+
+    // Selector, two offsets, the padded proof with its length, and the instances with their count
+    let proof: serde_json::Value = serde_json::from_str(&proof_json).unwrap();
+    let proof_len = proof["proof"].as_array().unwrap().len();
+    let num_instances: usize = proof["instances"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|column| column.as_array().unwrap().len())
+        .sum();
+    assert_eq!(
+        calldata.len(),
+        4 + 32 * 3 + proof_len.div_ceil(32) * 32 + 32 * (1 + num_instances)
+    );
+}
+
+#[tokio::test]
+async fn test_poseidon_proofs_are_rejected() {
+    let fixture = common::fixture();
+    let proof_json = prove_advanced(
+        common::witness().await,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ProveOptions {
+            proof_type: ProofTypeWrapper::ForAggr,
+            check_mode: CheckModeWrapper::Unchecked,
+            ..ProveOptions::default()
+        },
+    )
+    .expect("Proving failed");
+
+    let err = estimate_evm_gas(proof_json, fixture.settings_json.clone())
+        .expect_err("Poseidon proofs can't be verified on-chain");
+    assert!(matches!(err, EZKLError::InvalidInput(_)));
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use ios_ezkl::{set_trace_listener, TraceListener};
+use std::sync::{Arc, Mutex};
+use std::thread::ThreadId;
+
+/// Records the thread each stage begins on.
+#[derive(Default)]
+struct ThreadRecorder {
+    stages: Mutex<Vec<(String, Option<String>, ThreadId)>>,
+}
+
+impl ThreadRecorder {
+    fn threads_of(&self, stage: &str) -> Vec<(Option<String>, ThreadId)> {
+        self.stages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(name, _, _)| name == stage)
+            .map(|(_, thread_name, id)| (thread_name.clone(), *id))
+            .collect()
+    }
+}
+
+impl TraceListener for ThreadRecorder {
+    fn begin_interval(&self, name: String) -> u64 {
+        let thread = std::thread::current();
+        self.stages
+            .lock()
+            .unwrap()
+            .push((name, thread.name().map(str::to_string), thread.id()));
+        0
+    }
+
+    fn end_interval(&self, _id: u64) {}
+}
+
+// The trace listener is global, so both functions are checked in a single test
+#[tokio::test]
+async fn test_async_functions_run_on_named_worker_threads() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+    #[cfg(feature = "prover")]
+    let witness = common::witness().await;
+
+    // Installed after the fixtures are generated on this thread
+    let recorder = Arc::new(ThreadRecorder::default());
+    set_trace_listener(Some(recorder.clone()));
+    let caller = std::thread::current().id();
+
+    let valid = ios_ezkl::verify_async(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Verification failed");
+    assert!(valid);
+
+    let verified_on = recorder.threads_of("verify_proof");
+    assert!(!verified_on.is_empty());
+    for (name, id) in verified_on {
+        assert_eq!(name.as_deref(), Some("ezkl-verify"));
+        assert_ne!(id, caller);
+    }
+
+    #[cfg(feature = "prover")]
+    {
+        let proof = ios_ezkl::prove_async(
+            witness,
+            fixture.compiled_circuit.clone(),
+            fixture.pk.clone(),
+            fixture.srs.clone(),
+            ios_ezkl::ProveOptions::default(),
+        )
+        .await
+        .expect("Proving failed");
+        assert!(ios_ezkl::verify(
+            proof,
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone()
+        )
+        .unwrap());
+
+        let proved_on = recorder.threads_of("create_proof");
+        assert!(!proved_on.is_empty());
+        for (name, id) in proved_on {
+            assert_eq!(name.as_deref(), Some("ezkl-prove"));
+            assert_ne!(id, caller);
+        }
+    }
+
+    set_trace_listener(None);
+}
+
+#[tokio::test]
+async fn test_async_errors_are_returned_from_the_worker() {
+    let fixture = common::fixture();
+    let err = ios_ezkl::verify_async(
+        "{}".to_string(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(err, ios_ezkl::EZKLError::JsonError { .. }),
+        "{:?}",
+        err
+    );
+}
@@ -0,0 +1,126 @@
+use ios_ezkl::{convert_felt, normalize_felt, EZKLError, FeltEncoding};
+
+const ENCODINGS: [FeltEncoding; 3] = [
+    FeltEncoding::Decimal,
+    FeltEncoding::Hex,
+    FeltEncoding::Limbs,
+];
+
+/// The modulus of the BN254 scalar field in each encoding.
+const MODULUS: [&str; 3] = [
+    "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+    "[4891460686036598785,2896914383306846353,13281191951274694749,3486998266802970665]",
+];
+
+/// Edge values in each encoding, in the order of `ENCODINGS`.
+fn edge_values() -> Vec<[&'static str; 3]> {
+    vec![
+        [
+            "0",
+            "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "[0,0,0,0]",
+        ],
+        [
+            "1",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "[1,0,0,0]",
+        ],
+        [
+            "18446744073709551616",
+            "0x0000000000000000000000000000000000000000000000010000000000000000",
+            "[0,1,0,0]",
+        ],
+        [
+            "21888242871839275222246405745257275088548364400416034343698204186575808495616",
+            "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000",
+            "[4891460686036598784,2896914383306846353,13281191951274694749,3486998266802970665]",
+        ],
+    ]
+}
+
+#[test]
+fn test_convert_felt_round_trips_across_encodings() {
+    for value in edge_values() {
+        for (from, input) in ENCODINGS.iter().zip(value) {
+            for (to, expected) in ENCODINGS.iter().zip(value) {
+                assert_eq!(
+                    convert_felt(input.to_string(), *from, *to).unwrap(),
+                    expected,
+                    "{} from {:?} to {:?}",
+                    input,
+                    from,
+                    to
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_convert_felt_accepts_short_hex() {
+    assert_eq!(
+        convert_felt("0xff".to_string(), FeltEncoding::Hex, FeltEncoding::Decimal).unwrap(),
+        "255"
+    );
+    assert_eq!(
+        convert_felt("ff".to_string(), FeltEncoding::Hex, FeltEncoding::Limbs).unwrap(),
+        "[255,0,0,0]"
+    );
+}
+
+#[test]
+fn test_convert_felt_rejects_values_outside_the_field() {
+    for (from, modulus) in ENCODINGS.iter().zip(MODULUS) {
+        let err = convert_felt(modulus.to_string(), *from, FeltEncoding::Hex).unwrap_err();
+        assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+    }
+
+    let too_wide = format!("0x1{}", "0".repeat(64));
+    for (value, from) in [
+        (too_wide.as_str(), FeltEncoding::Hex),
+        ("-1", FeltEncoding::Decimal),
+        ("12a", FeltEncoding::Decimal),
+        ("0xzz", FeltEncoding::Hex),
+        ("[1,0,0]", FeltEncoding::Limbs),
+        ("[1,0,0,-1]", FeltEncoding::Limbs),
+    ] {
+        let err = convert_felt(value.to_string(), from, FeltEncoding::Decimal).unwrap_err();
+        assert!(
+            matches!(err, EZKLError::InvalidInput(_)),
+            "{}: {:?}",
+            value,
+            err
+        );
+    }
+}
+
+#[test]
+fn test_normalize_felt_detects_the_encoding() {
+    for [decimal, hex, limbs] in edge_values() {
+        assert_eq!(normalize_felt(format!("\"{}\"", decimal)).unwrap(), hex);
+        assert_eq!(normalize_felt(format!("\"{}\"", hex)).unwrap(), hex);
+        assert_eq!(normalize_felt(limbs.to_string()).unwrap(), hex);
+    }
+    assert_eq!(
+        normalize_felt("255".to_string()).unwrap(),
+        convert_felt("255".to_string(), FeltEncoding::Decimal, FeltEncoding::Hex).unwrap()
+    );
+
+    for invalid in [
+        format!("\"{}\"", MODULUS[0]),
+        MODULUS[2].to_string(),
+        "-1".to_string(),
+        "1.5".to_string(),
+        "null".to_string(),
+        "not json".to_string(),
+    ] {
+        let err = normalize_felt(invalid.clone()).unwrap_err();
+        assert!(
+            matches!(err, EZKLError::InvalidInput(_)),
+            "{}: {:?}",
+            invalid,
+            err
+        );
+    }
+}
@@ -0,0 +1,292 @@
+#![cfg(feature = "fetcher")]
+
+mod common;
+
+use hyper::header::{CONTENT_LENGTH, CONTENT_RANGE, RANGE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server, StatusCode};
+use ios_ezkl::{fetch_artifact, EZKLError, KeyGenStage, ProgressListener};
+use sha2::{Digest, Sha256};
+use std::convert::Infallible;
+use std::net::{SocketAddr, TcpListener};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// A local HTTP server serving one artifact, which cuts off the responses to its first requests.
+struct TestServer {
+    addr: SocketAddr,
+    state: Arc<ServerState>,
+}
+
+struct ServerState {
+    bytes: Vec<u8>,
+    /// The number of body bytes sent before the connection is aborted, per request in order.
+    interruptions: Vec<usize>,
+    /// Whether `Range` requests are answered with partial content.
+    ranges: bool,
+    /// The `Range` header of every request received.
+    requests: Mutex<Vec<Option<String>>>,
+}
+
+impl TestServer {
+    fn start(bytes: Vec<u8>, interruptions: Vec<usize>, ranges: bool) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(ServerState {
+            bytes,
+            interruptions,
+            ranges,
+            requests: Mutex::new(vec![]),
+        });
+
+        let served = state.clone();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async move {
+                let make_service = make_service_fn(move |_| {
+                    let state = served.clone();
+                    async move {
+                        Ok::<_, Infallible>(service_fn(move |request| {
+                            serve(state.clone(), request)
+                        }))
+                    }
+                });
+                Server::from_tcp(listener)
+                    .unwrap()
+                    .serve(make_service)
+                    .await
+                    .unwrap();
+            });
+        });
+        TestServer { addr, state }
+    }
+
+    fn url(&self) -> String {
+        format!("http://{}/kzg.srs", self.addr)
+    }
+
+    fn ranges_requested(&self) -> Vec<Option<String>> {
+        self.state.requests.lock().unwrap().clone()
+    }
+}
+
+async fn serve(
+    state: Arc<ServerState>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let range = request
+        .headers()
+        .get(RANGE)
+        .and_then(|range| range.to_str().ok())
+        .map(str::to_string);
+    let index = {
+        let mut requests = state.requests.lock().unwrap();
+        requests.push(range.clone());
+        requests.len() - 1
+    };
+
+    let offset = range
+        .filter(|_| state.ranges)
+        .and_then(|range| {
+            range
+                .strip_prefix("bytes=")?
+                .strip_suffix('-')?
+                .parse()
+                .ok()
+        })
+        .unwrap_or(0);
+    let len = state.bytes.len();
+    if offset > len {
+        let response = Response::builder()
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .body(Body::empty())
+            .unwrap();
+        return Ok(response);
+    }
+
+    let content = state.bytes[offset..].to_vec();
+    let mut response = Response::builder().header(CONTENT_LENGTH, content.len());
+    response = if offset > 0 {
+        response.status(StatusCode::PARTIAL_CONTENT).header(
+            CONTENT_RANGE,
+            format!("bytes {}-{}/{}", offset, len - 1, len),
+        )
+    } else {
+        response.status(StatusCode::OK)
+    };
+
+    let body = match state.interruptions.get(index) {
+        Some(&cut) => {
+            let (mut sender, body) = Body::channel();
+            tokio::spawn(async move {
+                let cut = cut.min(content.len());
+                let _ = sender.send_data(content[..cut].to_vec().into()).await;
+                sender.abort();
+            });
+            body
+        }
+        None => Body::from(content),
+    };
+    Ok(response.body(body).unwrap())
+}
+
+fn artifact() -> Vec<u8> {
+    std::fs::read(common::SRS_PATH).expect("Failed to read srs file")
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn dest_path(name: &str) -> PathBuf {
+    let path = common::scratch_dir().join(name);
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(partial_path(&path));
+    path
+}
+
+fn partial_path(dest: &std::path::Path) -> PathBuf {
+    PathBuf::from(format!("{}.part", dest.display()))
+}
+
+fn fetch(server: &TestServer, sha256: String, dest: &std::path::Path) -> Result<(), EZKLError> {
+    fetch_artifact(server.url(), sha256, dest.display().to_string(), None)
+}
+
+#[test]
+fn test_fetch_verifies_and_moves_into_place() {
+    let bytes = artifact();
+    let server = TestServer::start(bytes.clone(), vec![], true);
+    let dest = dest_path("fetched.srs");
+
+    fetch(&server, sha256(&bytes).to_uppercase(), &dest).expect("Failed to fetch");
+    assert_eq!(std::fs::read(&dest).unwrap(), bytes);
+    assert!(!partial_path(&dest).exists());
+
+    // A second call finds the artifact in place and doesn't download it again
+    fetch(&server, sha256(&bytes), &dest).unwrap();
+    assert_eq!(server.ranges_requested().len(), 1);
+}
+
+#[test]
+fn test_fetch_resumes_after_interruptions() {
+    let bytes = artifact();
+    let server = TestServer::start(bytes.clone(), vec![1000, 5000], true);
+    let dest = dest_path("resumed.srs");
+
+    fetch(&server, sha256(&bytes), &dest).expect("Failed to fetch");
+    assert_eq!(std::fs::read(&dest).unwrap(), bytes);
+    assert_eq!(
+        server.ranges_requested(),
+        vec![
+            None,
+            Some("bytes=1000-".to_string()),
+            Some("bytes=6000-".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_fetch_resumes_across_calls() {
+    let bytes = artifact();
+    let server = TestServer::start(bytes.clone(), vec![1000, 1000, 1000], true);
+    let dest = dest_path("resumed_across_calls.srs");
+
+    // Every attempt of the first call is interrupted
+    let err = fetch(&server, sha256(&bytes), &dest).unwrap_err();
+    assert!(matches!(err, EZKLError::Network { .. }), "{:?}", err);
+    assert!(!dest.exists());
+    assert_eq!(std::fs::metadata(partial_path(&dest)).unwrap().len(), 3000);
+
+    // The next call continues where it stopped
+    fetch(&server, sha256(&bytes), &dest).expect("Failed to fetch");
+    assert_eq!(std::fs::read(&dest).unwrap(), bytes);
+    assert_eq!(
+        server.ranges_requested().last().unwrap().as_deref(),
+        Some("bytes=3000-")
+    );
+}
+
+#[test]
+fn test_fetch_restarts_without_range_support() {
+    let bytes = artifact();
+    let server = TestServer::start(bytes.clone(), vec![1000], false);
+    let dest = dest_path("restarted.srs");
+
+    fetch(&server, sha256(&bytes), &dest).expect("Failed to fetch");
+    assert_eq!(std::fs::read(&dest).unwrap(), bytes);
+}
+
+#[test]
+fn test_mismatched_digest_is_never_accepted() {
+    let bytes = artifact();
+    let server = TestServer::start(bytes.clone(), vec![], true);
+    let dest = dest_path("mismatched.srs");
+    let expected = sha256(b"another artifact");
+
+    let err = fetch(&server, expected.clone(), &dest).unwrap_err();
+    match err {
+        EZKLError::FingerprintMismatch {
+            expected: reported,
+            actual,
+            ..
+        } => {
+            assert_eq!(reported, expected);
+            assert_eq!(actual, sha256(&bytes));
+        }
+        err => panic!("{:?}", err),
+    }
+    assert!(!dest.exists());
+    assert!(!partial_path(&dest).exists());
+
+    // A stale partial file of another artifact is downloaded again from the start
+    std::fs::write(partial_path(&dest), b"stale bytes").unwrap();
+    fetch(&server, sha256(&bytes), &dest).expect("Failed to fetch");
+    assert_eq!(std::fs::read(&dest).unwrap(), bytes);
+
+    // An invalid digest is rejected before downloading
+    let err = fetch(
+        &server,
+        "not a digest".to_string(),
+        &dest_path("invalid.srs"),
+    )
+    .unwrap_err();
+    assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+}
+
+#[derive(Default)]
+struct RecordingListener {
+    events: Mutex<Vec<(KeyGenStage, u64, u64)>>,
+}
+
+impl ProgressListener for RecordingListener {
+    fn on_progress(&self, stage: KeyGenStage, completed: u64, total: u64) {
+        self.events.lock().unwrap().push((stage, completed, total));
+    }
+}
+
+#[test]
+fn test_fetch_reports_download_progress() {
+    let bytes = artifact();
+    let server = TestServer::start(bytes.clone(), vec![1000], true);
+    let dest = dest_path("progress.srs");
+    let listener = Arc::new(RecordingListener::default());
+
+    fetch_artifact(
+        server.url(),
+        sha256(&bytes),
+        dest.display().to_string(),
+        Some(listener.clone()),
+    )
+    .expect("Failed to fetch");
+
+    let events = listener.events.lock().unwrap();
+    assert!(events.len() > 1);
+    assert!(events
+        .iter()
+        .all(|(stage, _, _)| *stage == KeyGenStage::Download));
+    assert!(events.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    let len = bytes.len() as u64;
+    assert_eq!(events.last(), Some(&(KeyGenStage::Download, len, len)));
+}
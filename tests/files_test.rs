@@ -0,0 +1,108 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use sha2::{Digest, Sha256};
+
+fn write_fixture_files() -> (String, String, String, String) {
+    let fixture = common::fixture();
+    let dir = common::scratch_dir();
+    let paths = ["network.ezkl", "vk.key", "pk.key", "kzg.srs"].map(|name| dir.join(name));
+    std::fs::write(&paths[0], &fixture.compiled_circuit).unwrap();
+    std::fs::write(&paths[1], &fixture.vk).unwrap();
+    std::fs::write(&paths[2], &fixture.pk).unwrap();
+    std::fs::write(&paths[3], &fixture.srs).unwrap();
+    let [circuit, vk, pk, srs] = paths.map(|path| path.display().to_string());
+    (circuit, vk, pk, srs)
+}
+
+#[tokio::test]
+async fn test_file_based_flow_end_to_end() {
+    let fixture = common::fixture();
+    let (circuit_path, vk_path, pk_path, srs_path) = write_fixture_files();
+    let witness_path = common::scratch_dir().join("witness.json");
+    let proof_path = common::scratch_dir().join("proof.json");
+
+    // 1. Generate the witness into a file
+    let witness = ios_ezkl::gen_witness_to_file(
+        fixture.input_json.clone(),
+        circuit_path.clone(),
+        vk_path.clone(),
+        srs_path.clone(),
+        witness_path.display().to_string(),
+    )
+    .await
+    .expect("Witness generation failed");
+    let witness_bytes = std::fs::read(&witness_path).unwrap();
+    assert_eq!(witness.byte_length, witness_bytes.len() as u64);
+    assert_eq!(witness.sha256, hex::encode(Sha256::digest(&witness_bytes)));
+
+    // 2. Prove from the witness file into a proof file
+    let proof = ios_ezkl::prove_to_file(
+        witness_path.display().to_string(),
+        circuit_path,
+        pk_path,
+        srs_path.clone(),
+        proof_path.display().to_string(),
+    )
+    .expect("Proof generation failed");
+    let proof_json = std::fs::read_to_string(&proof_path).unwrap();
+    assert_eq!(proof.byte_length, proof_json.len() as u64);
+
+    // 3. The written proof verifies
+    let verified = ios_ezkl::verify(
+        proof_json,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    );
+    assert!(
+        verified.is_ok(),
+        "Proof verification failed: {:?}",
+        verified
+    );
+
+    // 4. The proof file also verifies without loading it into a string
+    let settings_path = common::scratch_dir().join("settings.json");
+    std::fs::write(&settings_path, &fixture.settings_json).unwrap();
+    let verified = ios_ezkl::verify_from_file(
+        proof_path.display().to_string(),
+        settings_path.display().to_string(),
+        vk_path,
+        srs_path,
+    )
+    .expect("Proof file verification failed");
+    assert!(verified);
+}
+
+#[tokio::test]
+async fn test_failed_write_leaves_no_file() {
+    let (circuit_path, _, pk_path, srs_path) = write_fixture_files();
+
+    // An unwritable output path is rejected up front
+    let result = ios_ezkl::prove_to_file(
+        "missing-witness.json".to_string(),
+        circuit_path.clone(),
+        pk_path.clone(),
+        srs_path.clone(),
+        common::scratch_dir()
+            .join("missing-dir/proof.json")
+            .display()
+            .to_string(),
+    );
+    assert!(result.is_err());
+
+    // A failure after the output was created removes the partial file
+    let witness_path = common::scratch_dir().join("invalid-witness.json");
+    std::fs::write(&witness_path, "{}").unwrap();
+    let proof_path = common::scratch_dir().join("partial-proof.json");
+    let result = ios_ezkl::prove_to_file(
+        witness_path.display().to_string(),
+        circuit_path,
+        pk_path,
+        srs_path,
+        proof_path.display().to_string(),
+    );
+    assert!(result.is_err());
+    assert!(!proof_path.exists());
+}
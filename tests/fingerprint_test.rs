@@ -0,0 +1,185 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ezkl::graph::GraphCircuit;
+use ios_ezkl::{EZKLError, ExpectedFingerprints, ProofTypeWrapper, ProveOptions};
+
+#[test]
+fn test_circuit_fingerprint_is_stable_across_round_trips() {
+    let fixture = common::fixture();
+    let fingerprint = ios_ezkl::circuit_fingerprint(fixture.compiled_circuit.clone())
+        .expect("Failed to fingerprint circuit");
+
+    // Re-serializing the circuit does not change the fingerprint
+    let circuit: GraphCircuit = bincode::deserialize(&fixture.compiled_circuit).unwrap();
+    let reserialized = bincode::serialize(&circuit).unwrap();
+    assert_eq!(
+        ios_ezkl::circuit_fingerprint(reserialized).unwrap(),
+        fingerprint
+    );
+
+    // Neither do bytes that are not part of the circuit
+    let mut padded = fixture.compiled_circuit.clone();
+    padded.extend_from_slice(&[0; 16]);
+    assert_eq!(ios_ezkl::circuit_fingerprint(padded).unwrap(), fingerprint);
+}
+
+#[test]
+fn test_circuit_fingerprint_differs_across_circuits() {
+    let fixture = common::fixture();
+    let fingerprint = ios_ezkl::circuit_fingerprint(fixture.compiled_circuit.clone()).unwrap();
+
+    // Only one circuit is bundled, so derive a second one with different settings
+    let mut circuit: GraphCircuit = bincode::deserialize(&fixture.compiled_circuit).unwrap();
+    circuit.core.settings.run_args.logrows += 1;
+    let other = bincode::serialize(&circuit).unwrap();
+
+    assert_ne!(ios_ezkl::circuit_fingerprint(other).unwrap(), fingerprint);
+}
+
+#[test]
+fn test_vk_and_pk_fingerprints_match() {
+    let fixture = common::fixture();
+    let vk_fingerprint =
+        ios_ezkl::vk_fingerprint(fixture.vk.clone(), fixture.settings_json.clone())
+            .expect("Failed to fingerprint vk");
+    let pk_fingerprint =
+        ios_ezkl::pk_fingerprint(fixture.pk.clone(), fixture.settings_json.clone())
+            .expect("Failed to fingerprint pk");
+
+    assert_eq!(vk_fingerprint, pk_fingerprint);
+    assert_eq!(vk_fingerprint.len(), 64);
+}
+
+#[tokio::test]
+async fn test_prove_and_verify_check_fingerprints() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+    let circuit_fingerprint =
+        ios_ezkl::circuit_fingerprint(fixture.compiled_circuit.clone()).unwrap();
+    let key_fingerprint =
+        ios_ezkl::vk_fingerprint(fixture.vk.clone(), fixture.settings_json.clone()).unwrap();
+    let wrong_fingerprint = "00".repeat(32);
+
+    let result = ios_ezkl::prove_with_fingerprints(
+        witness.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ExpectedFingerprints {
+            circuit: Some(circuit_fingerprint.clone()),
+            pk: Some(wrong_fingerprint.clone()),
+        },
+        ProveOptions::default(),
+    );
+    match result {
+        Err(EZKLError::FingerprintMismatch {
+            artifact, actual, ..
+        }) => {
+            assert_eq!(artifact, "proving key");
+            assert_eq!(actual, key_fingerprint);
+        }
+        other => panic!("Expected a FingerprintMismatch, got {:?}", other),
+    }
+
+    let proof = ios_ezkl::prove_with_fingerprints(
+        witness,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ExpectedFingerprints {
+            circuit: Some(circuit_fingerprint),
+            pk: Some(key_fingerprint.clone()),
+        },
+        ProveOptions::default(),
+    )
+    .expect("Proving with matching fingerprints failed");
+
+    assert!(matches!(
+        ios_ezkl::verify_with_fingerprint(
+            proof.clone(),
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+            wrong_fingerprint,
+        ),
+        Err(EZKLError::FingerprintMismatch { .. })
+    ));
+    assert!(ios_ezkl::verify_with_fingerprint(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        key_fingerprint,
+    )
+    .expect("Verification with a matching fingerprint failed"));
+}
+
+#[tokio::test]
+async fn test_prove_with_fingerprints_applies_the_options() {
+    let fixture = common::fixture();
+    let proof = ios_ezkl::prove_with_fingerprints(
+        common::witness().await,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ExpectedFingerprints::default(),
+        ProveOptions {
+            proof_type: ProofTypeWrapper::ForAggr,
+            ..ProveOptions::default()
+        },
+    )
+    .expect("Proving failed");
+    let proof: serde_json::Value = serde_json::from_str(&proof).unwrap();
+    assert_eq!(proof["transcript_type"], "Poseidon");
+}
+
+#[tokio::test]
+async fn test_proof_digest_ignores_formatting_and_cosmetic_fields() {
+    let proof = common::proof().await;
+    let digest = ios_ezkl::proof_digest(proof.clone()).expect("Failed to digest proof");
+    assert_eq!(digest.len(), 64);
+
+    // Reversing the order of the keys and pretty printing
+    let value: serde_json::Value = serde_json::from_str(&proof).unwrap();
+    let fields = value.as_object().unwrap();
+    let reordered = format!(
+        "{{\n{}\n}}",
+        fields
+            .iter()
+            .rev()
+            .map(|(key, value)| format!(
+                "  {:?}: {}",
+                key,
+                serde_json::to_string_pretty(value).unwrap()
+            ))
+            .collect::<Vec<_>>()
+            .join(",\n")
+    );
+    assert_eq!(ios_ezkl::proof_digest(reordered).unwrap(), digest);
+
+    // Stripping the human readable public inputs
+    let mut stripped = value.clone();
+    assert!(stripped
+        .as_object_mut()
+        .unwrap()
+        .remove("pretty_public_inputs")
+        .is_some());
+    assert_eq!(
+        ios_ezkl::proof_digest(stripped.to_string()).unwrap(),
+        digest
+    );
+}
+
+#[tokio::test]
+async fn test_proof_digest_changes_with_the_instances() {
+    let proof = common::proof().await;
+    let digest = ios_ezkl::proof_digest(proof.clone()).unwrap();
+
+    let tampered = common::tamper_instance(&proof);
+    assert_ne!(ios_ezkl::proof_digest(tampered).unwrap(), digest);
+
+    let err = ios_ezkl::proof_digest("{}".to_string()).unwrap_err();
+    assert!(matches!(err, EZKLError::JsonError { .. }), "{:?}", err);
+}
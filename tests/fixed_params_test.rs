@@ -0,0 +1,39 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{circuit_info, parse_settings, VisibilityWrapper};
+
+#[tokio::test]
+async fn test_prove_and_verify_with_fixed_params() {
+    let fixture = common::fixed_params_fixture();
+    let proof = common::proof_for(fixture).await;
+
+    let verify = |proof: String| {
+        ios_ezkl::verify(
+            proof,
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+        )
+        .expect("Verification failed to run")
+    };
+    assert!(verify(proof.clone()));
+    assert!(!verify(common::tamper_instance(&proof)));
+}
+
+#[test]
+fn test_param_visibility_is_reported() {
+    let fixed = common::fixed_params_fixture();
+    let info = circuit_info(fixed.compiled_circuit.clone()).unwrap();
+    assert_eq!(info.param_visibility, VisibilityWrapper::Fixed);
+    let settings = parse_settings(fixed.settings_json.clone()).unwrap();
+    assert_eq!(settings.param_visibility, VisibilityWrapper::Fixed);
+
+    // The sample circuit has private params
+    let sample = common::fixture();
+    let info = circuit_info(sample.compiled_circuit.clone()).unwrap();
+    assert_eq!(info.param_visibility, VisibilityWrapper::Private);
+    let settings = parse_settings(sample.settings_json.clone()).unwrap();
+    assert_eq!(settings.param_visibility, VisibilityWrapper::Private);
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use ios_ezkl::graph_summary;
+
+#[test]
+fn test_graph_summary_of_the_sample_network() {
+    let summary = graph_summary(common::fixture().compiled_circuit.clone()).unwrap();
+
+    assert_eq!(summary.layers.len() as u64, summary.num_nodes);
+    assert_eq!(
+        summary.op_counts.iter().map(|op| op.count).sum::<u64>(),
+        summary.num_nodes
+    );
+    assert!(summary
+        .op_counts
+        .windows(2)
+        .all(|pair| pair[0].op < pair[1].op));
+
+    // The sample network maps a 28x28 image to ten classes, through ReLU activations
+    let input = &summary.layers[0];
+    assert!(input.op.eq_ignore_ascii_case("input"), "{:?}", input);
+    assert_eq!(
+        input.output_shapes[0].iter().product::<u64>(),
+        784,
+        "{:?}",
+        input
+    );
+    assert!(summary
+        .layers
+        .iter()
+        .any(|layer| layer.output_shapes == [vec![1, 10]]));
+    assert!(
+        summary
+            .op_counts
+            .iter()
+            .any(|op| op.op.to_uppercase().contains("RELU")),
+        "{:?}",
+        summary.op_counts
+    );
+    assert!(summary.total_parameters > 0);
+
+    // Every node is listed once, in the order of its index
+    assert!(summary
+        .layers
+        .windows(2)
+        .all(|pair| pair[0].index < pair[1].index));
+}
+
+#[test]
+fn test_graph_summary_rejects_invalid_circuits() {
+    assert!(graph_summary(vec![1, 2, 3]).is_err());
+}
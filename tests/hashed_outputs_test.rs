@@ -0,0 +1,137 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use common::Fixture;
+use ios_ezkl::{verify_hashed_outputs, EZKLError};
+use serde_json::Value;
+
+/// Proves the sample input and returns the proof with the outputs of its witness.
+async fn proof_with_outputs(fixture: &Fixture) -> (String, Vec<String>) {
+    let witness = ios_ezkl::gen_witness(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+    let outputs: Value = serde_json::from_str::<Value>(&witness).unwrap()["outputs"].clone();
+    let outputs = outputs
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|tensor| tensor.as_array().unwrap())
+        .map(|output| output.as_str().unwrap().to_string())
+        .collect();
+    let proof = ios_ezkl::prove(
+        witness,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Proof generation failed");
+    (proof, outputs)
+}
+
+/// Converts a field element of a witness file, little-endian hex, to `0x`-prefixed big-endian hex.
+fn big_endian_hex(output: &str) -> String {
+    let bytes: Vec<u8> = hex::decode(output).unwrap().into_iter().rev().collect();
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn verify_outputs(
+    fixture: &Fixture,
+    proof: &str,
+    claimed_outputs: Vec<String>,
+) -> Result<bool, EZKLError> {
+    verify_hashed_outputs(
+        proof.to_string(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        claimed_outputs,
+    )
+}
+
+#[tokio::test]
+async fn test_revealed_outputs_match_the_proof() {
+    let fixture = common::hashed_outputs_fixture();
+    let (proof, outputs) = proof_with_outputs(fixture).await;
+
+    // The only instance is the hash of the output
+    let proof_value: Value = serde_json::from_str(&proof).unwrap();
+    let instances: Vec<&Value> = proof_value["instances"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|column| column.as_array().unwrap())
+        .collect();
+    assert_eq!(instances.len(), 1);
+
+    assert!(verify_outputs(fixture, &proof, outputs.clone()).unwrap());
+
+    // The outputs can be claimed in big-endian hex too
+    let hex_outputs = outputs
+        .iter()
+        .map(|output| big_endian_hex(output))
+        .collect();
+    assert!(verify_outputs(fixture, &proof, hex_outputs).unwrap());
+}
+
+#[tokio::test]
+async fn test_altered_outputs_are_a_mismatch() {
+    let fixture = common::hashed_outputs_fixture();
+    let (proof, outputs) = proof_with_outputs(fixture).await;
+
+    let mut altered = outputs.clone();
+    let one = format!("0x{:064x}", 1);
+    altered[0] = if big_endian_hex(&outputs[0]) == one {
+        format!("0x{:064x}", 2)
+    } else {
+        one
+    };
+    match verify_outputs(fixture, &proof, altered).unwrap_err() {
+        EZKLError::OutputMismatch { expected, actual } => {
+            assert!(expected.starts_with("0x"), "{}", expected);
+            assert_ne!(expected, actual);
+        }
+        err => panic!("{:?}", err),
+    }
+
+    // Dropping an output changes the hash as well
+    let err = verify_outputs(fixture, &proof, outputs[1..].to_vec()).unwrap_err();
+    assert!(matches!(err, EZKLError::OutputMismatch { .. }), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_invalid_proof_is_rejected_before_the_outputs() {
+    let fixture = common::hashed_outputs_fixture();
+    let (proof, outputs) = proof_with_outputs(fixture).await;
+
+    let tampered = common::tamper_instance(&proof);
+    assert!(!verify_outputs(fixture, &tampered, outputs).unwrap());
+}
+
+#[tokio::test]
+async fn test_circuits_without_hashed_outputs_are_rejected() {
+    let fixture = common::fixture();
+    let (proof, outputs) = proof_with_outputs(fixture).await;
+
+    let err = verify_outputs(fixture, &proof, outputs).unwrap_err();
+    assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+
+    let fixture = common::hashed_outputs_fixture();
+    let (proof, _) = proof_with_outputs(fixture).await;
+    let err = verify_outputs(fixture, &proof, vec!["not a felt".to_string()]).unwrap_err();
+    assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_empty_claimed_outputs_are_rejected() {
+    let fixture = common::hashed_outputs_fixture();
+    let (proof, _) = proof_with_outputs(fixture).await;
+
+    let err = verify_outputs(fixture, &proof, vec![]).unwrap_err();
+    assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+}
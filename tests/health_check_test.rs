@@ -0,0 +1,17 @@
+#![cfg(feature = "self-check")]
+
+use ios_ezkl::SelfTestStage;
+
+#[tokio::test]
+async fn test_quick_health_check_passes() {
+    let health = ios_ezkl::quick_health_check()
+        .await
+        .expect("Health check setup failed");
+
+    assert!(health.report.passed, "{:?}", health.report);
+    assert_eq!(health.report.failed_stage, None);
+    assert_eq!(
+        health.report.steps.last().map(|step| step.stage),
+        Some(SelfTestStage::Verify)
+    );
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{identify_and_verify, IdentifyResult, VkCandidate};
+
+fn candidate(label: &str, fixture: &common::Fixture) -> VkCandidate {
+    VkCandidate {
+        label: label.to_string(),
+        settings_json: fixture.settings_json.clone(),
+        vk: fixture.vk.clone(),
+    }
+}
+
+fn matched(label: &str) -> IdentifyResult {
+    IdentifyResult::Match {
+        label: label.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_identify_proofs_of_two_circuit_versions() {
+    let (v1, v2) = (common::fixture(), common::hashed_fixture());
+    let v1_proof = common::proof_for(v1).await;
+    let v2_proof = common::proof_for(v2).await;
+
+    for candidates in [
+        vec![candidate("v1", v1), candidate("v2", v2)],
+        vec![candidate("v2", v2), candidate("v1", v1)],
+    ] {
+        let identify = |proof: &String| {
+            identify_and_verify(proof.clone(), candidates.clone(), v1.srs.clone())
+                .expect("Identification failed")
+        };
+        assert_eq!(identify(&v1_proof), matched("v1"));
+        assert_eq!(identify(&v2_proof), matched("v2"));
+        assert_eq!(
+            identify(&common::tamper_instance(&v1_proof)),
+            IdentifyResult::NoMatch
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_identify_skips_structurally_different_candidates() {
+    let (v1, v2) = (common::fixture(), common::hashed_fixture());
+    let proof = common::proof_for(v1).await;
+
+    // The vk of a candidate with a different number of instances is never loaded
+    let unloadable = VkCandidate {
+        vk: vec![0; 16],
+        ..candidate("v2", v2)
+    };
+    let result = identify_and_verify(
+        proof.clone(),
+        vec![unloadable, candidate("v1", v1)],
+        v1.srs.clone(),
+    )
+    .unwrap();
+    assert_eq!(result, matched("v1"));
+
+    let result = identify_and_verify(proof, vec![candidate("v2", v2)], v1.srs.clone()).unwrap();
+    assert_eq!(result, IdentifyResult::NoMatch);
+}
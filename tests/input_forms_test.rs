@@ -0,0 +1,60 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::EZKLError;
+use serde_json::Value;
+
+async fn gen_witness(input_json: String) -> Result<String, EZKLError> {
+    let fixture = common::fixture();
+    ios_ezkl::gen_witness(
+        input_json,
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+}
+
+fn input_data() -> Value {
+    let input: Value =
+        serde_json::from_str(&common::fixture().input_json).expect("Failed to parse input");
+    input["input_data"].clone()
+}
+
+#[tokio::test]
+async fn test_gen_witness_accepts_array_of_arrays() {
+    let expected = gen_witness(common::fixture().input_json.clone())
+        .await
+        .expect("Failed to generate witness from GraphData");
+
+    let witness = gen_witness(input_data().to_string())
+        .await
+        .expect("Failed to generate witness from an array of arrays");
+
+    assert_eq!(witness, expected);
+}
+
+#[tokio::test]
+async fn test_gen_witness_accepts_flat_array() {
+    let expected = gen_witness(common::fixture().input_json.clone())
+        .await
+        .expect("Failed to generate witness from GraphData");
+
+    let flat = input_data()[0].to_string();
+    let witness = gen_witness(flat)
+        .await
+        .expect("Failed to generate witness from a flat array");
+
+    assert_eq!(witness, expected);
+}
+
+#[tokio::test]
+async fn test_gen_witness_rejects_array_of_objects() {
+    let result = gen_witness("[[{\"value\": 1.0}]]".to_string()).await;
+
+    match result {
+        Err(EZKLError::JsonError { field, .. }) => assert_eq!(field, "input_json"),
+        other => panic!("Expected a JsonError, got {:?}", other),
+    }
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{EZKLError, EzklConfig, ResourceLimits};
+
+const MAX_INPUT_BYTES: u64 = 32 * 1024 * 1024;
+
+fn initialize() {
+    ios_ezkl::initialize(EzklConfig {
+        limits: ResourceLimits {
+            max_input_bytes: MAX_INPUT_BYTES,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .expect("Initialization failed");
+}
+
+/// Returns zeroed bytes one byte over the limit, which the allocator maps lazily.
+fn oversized_bytes() -> Vec<u8> {
+    vec![0; MAX_INPUT_BYTES as usize + 1]
+}
+
+fn assert_rejected(err: EZKLError, argument: &str) {
+    match err {
+        EZKLError::InvalidInput(message) => {
+            assert!(message.contains(&format!("`{}`", argument)), "{}", message);
+            assert!(
+                message.contains(&(MAX_INPUT_BYTES + 1).to_string()),
+                "{}",
+                message
+            );
+            assert!(
+                message.contains(&MAX_INPUT_BYTES.to_string()),
+                "{}",
+                message
+            );
+            assert!(message.contains("_to_file"), "{}", message);
+        }
+        err => panic!("{:?}", err),
+    }
+}
+
+#[test]
+fn test_default_limit_is_finite() {
+    assert_eq!(ResourceLimits::default().max_input_bytes, 512 * 1024 * 1024);
+}
+
+#[test]
+fn test_oversized_witness_is_rejected_before_loading() {
+    initialize();
+    let witness_json = String::from_utf8(oversized_bytes()).unwrap();
+
+    // The empty proving key would fail to load if the witness were not rejected first
+    let err = ios_ezkl::prove(witness_json, vec![], vec![], vec![]).unwrap_err();
+    assert_rejected(err, "witness_json");
+}
+
+#[tokio::test]
+async fn test_oversized_bytes_are_rejected() {
+    initialize();
+    let fixture = common::fixture();
+
+    let err = ios_ezkl::verify(
+        "{}".to_string(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        oversized_bytes(),
+    )
+    .unwrap_err();
+    assert_rejected(err, "srs");
+
+    let err = ios_ezkl::gen_witness(
+        fixture.input_json.clone(),
+        oversized_bytes(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .unwrap_err();
+    assert_rejected(err, "compiled_circuit");
+}
+
+#[tokio::test]
+async fn test_arguments_within_the_limit_are_accepted() {
+    initialize();
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    assert!(ios_ezkl::verify(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed"));
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{gen_witness, input_template};
+
+#[tokio::test]
+async fn test_input_template_is_accepted_by_gen_witness() {
+    let fixture = common::fixture();
+
+    let template =
+        input_template(fixture.compiled_circuit.clone()).expect("Failed to create the template");
+
+    let witness = gen_witness(
+        template,
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("The template was not accepted");
+    let witness: serde_json::Value = serde_json::from_str(&witness).unwrap();
+    assert!(witness["inputs"][0]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|value| value == &witness["inputs"][0][0]));
+}
+
+#[test]
+fn test_input_template_matches_the_sample_input() {
+    let fixture = common::fixture();
+    let sample: serde_json::Value = serde_json::from_str(&fixture.input_json).unwrap();
+
+    let template = input_template(fixture.compiled_circuit.clone()).unwrap();
+    let template: serde_json::Value = serde_json::from_str(&template).unwrap();
+
+    let inputs = template["input_data"].as_array().unwrap();
+    let shapes = template["_shapes"].as_array().unwrap();
+    assert_eq!(inputs.len(), sample["input_data"].as_array().unwrap().len());
+    assert_eq!(inputs.len(), shapes.len());
+    for ((input, expected), described) in inputs
+        .iter()
+        .zip(sample["input_data"].as_array().unwrap())
+        .zip(shapes)
+    {
+        let input = input.as_array().unwrap();
+        assert_eq!(input.len(), expected.as_array().unwrap().len());
+        assert!(input.iter().all(|value| value.as_f64() == Some(0.0)));
+
+        let elements: u64 = described["shape"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|dim| dim.as_u64().unwrap())
+            .product();
+        assert_eq!(elements, input.len() as u64);
+        assert!(described["scale"].is_i64());
+    }
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{
+    convert_felt, encode_evm_calldata, inspect_proof, instances_from_bytes, proof_instances_bytes,
+    EZKLError, FeltEncoding,
+};
+
+#[tokio::test]
+async fn test_instance_bytes_match_evm_calldata() {
+    let proof_json = common::proof().await;
+
+    let words = proof_instances_bytes(proof_json.clone()).expect("Failed to read instances");
+    assert!(!words.is_empty());
+    assert!(words.iter().all(|word| word.len() == 32));
+
+    // The calldata ends with the instance count and the instances, word for word
+    let calldata = encode_evm_calldata(proof_json).expect("Failed to encode calldata");
+    let tail = &calldata[calldata.len() - 32 * (words.len() + 1)..];
+    assert_eq!(tail[..24], [0; 24]);
+    assert_eq!(
+        u64::from_be_bytes(tail[24..32].try_into().unwrap()),
+        words.len() as u64
+    );
+    assert_eq!(tail[32..], words.concat());
+}
+
+#[tokio::test]
+async fn test_instances_from_bytes_inverts_instance_bytes() {
+    let proof_json = common::proof().await;
+    let words = proof_instances_bytes(proof_json.clone()).unwrap();
+
+    let instances = instances_from_bytes(words.clone()).expect("Failed to convert instances");
+    let expected: Vec<String> = inspect_proof(proof_json)
+        .unwrap()
+        .instances
+        .into_iter()
+        .flatten()
+        .collect();
+    assert_eq!(instances, expected);
+
+    // Each word is the big-endian value of its instance
+    for (word, instance) in words.iter().zip(&instances) {
+        let hex = convert_felt(instance.clone(), FeltEncoding::Hex, FeltEncoding::Hex).unwrap();
+        assert_eq!(format!("0x{}", hex::encode(word)), hex);
+    }
+}
+
+#[test]
+fn test_instances_from_bytes_rejects_invalid_words() {
+    let mut one = vec![0; 32];
+    one[31] = 1;
+    assert_eq!(
+        instances_from_bytes(vec![one.clone()]).unwrap(),
+        vec![convert_felt("1".to_string(), FeltEncoding::Decimal, FeltEncoding::Hex).unwrap()]
+    );
+
+    // A word of the wrong length
+    let err = instances_from_bytes(vec![one, vec![1; 31]]).unwrap_err();
+    match err {
+        EZKLError::InvalidInput(message) => assert!(message.contains("instance 1"), "{}", message),
+        err => panic!("{:?}", err),
+    }
+
+    // A word not smaller than the modulus
+    let err = instances_from_bytes(vec![vec![0xff; 32]]).unwrap_err();
+    assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+}
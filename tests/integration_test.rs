@@ -1,3 +1,5 @@
+#![cfg(feature = "prover")]
+
 use ezkl::commands::DEFAULT_DISABLE_SELECTOR_COMPRESSION;
 use ezkl::graph::{GraphCircuit, GraphWitness};
 use ezkl::pfsys::{create_keys, save_pk, save_vk};
@@ -143,3 +145,171 @@ async fn test_end_to_end() {
         verify_result
     );
 }
+
+#[tokio::test]
+async fn test_end_to_end_from_input() {
+    setup_keys_once();
+
+    // 1. Read input JSON and file paths
+    let input_file = std::fs::read(INPUT_JSON_PATH).expect("Failed to read input JSON file");
+    let input_json = String::from_utf8(input_file).expect("Failed to parse input JSON file");
+    let compiled_circuit =
+        std::fs::read(COMPILED_CIRCUIT_PATH).expect("Failed to read circuit file");
+    let vk = std::fs::read(VK_PATH).expect("Failed to read vk file");
+    let srs = std::fs::read(SRS_PATH).expect("Failed to read srs file");
+    let pk = std::fs::read(PK_PATH).expect("Failed to read pk file");
+    let settings_file = std::fs::read(SETTINGS_PATH).expect("Failed to read settings file");
+    let settings = String::from_utf8(settings_file).expect("Failed to parse settings file");
+
+    // 2. Generate the witness and the proof in a single call
+    let proof_json = ios_ezkl::prove_from_input(
+        input_json.clone(),
+        compiled_circuit.clone(),
+        pk.clone(),
+        vk.clone(),
+        srs.clone(),
+        ios_ezkl::default_prove_options(),
+    )
+    .await
+    .expect("Proof generation failed");
+
+    // 3. Verify proof using verify
+    let verify_result = ios_ezkl::verify(proof_json, settings.clone(), vk.clone(), srs.clone());
+    assert!(
+        verify_result.is_ok(),
+        "Proof verification failed: {:?}",
+        verify_result
+    );
+
+    // 4. The detailed call returns the same witness as gen_witness on request
+    let result = ios_ezkl::prove_from_input_detailed(
+        input_json.clone(),
+        compiled_circuit.clone(),
+        pk,
+        vk.clone(),
+        srs.clone(),
+        ios_ezkl::default_prove_options(),
+        true,
+    )
+    .await
+    .expect("Proof generation failed");
+    let witness = ios_ezkl::gen_witness(input_json, compiled_circuit, vk.clone(), srs.clone())
+        .await
+        .expect("Witness generation failed");
+    assert_eq!(result.witness_json, Some(witness));
+    assert!(ios_ezkl::verify(result.proof_json, settings, vk, srs).is_ok());
+}
+
+#[tokio::test]
+async fn test_gen_witness_dynamic_range() {
+    setup_keys_once();
+
+    // 1. Read input JSON and file paths
+    let file = std::fs::read(INPUT_JSON_PATH).expect("Failed to read input JSON file");
+    let input_json = String::from_utf8(file).expect("Failed to parse input JSON file");
+    let compiled_circuit =
+        std::fs::read(COMPILED_CIRCUIT_PATH).expect("Failed to read circuit file");
+    let vk = std::fs::read(VK_PATH).expect("Failed to read vk file");
+    let srs = std::fs::read(SRS_PATH).expect("Failed to read srs file");
+
+    // 2. Generate the witness together with its dynamic range
+    let result = ios_ezkl::gen_witness_detailed(input_json, compiled_circuit, vk, srs)
+        .await
+        .expect("Witness generation failed");
+
+    // 3. The reported values must match what the CLI writes into the witness file
+    let witness: serde_json::Value =
+        serde_json::from_str(&result.witness_json).expect("Failed to parse witness JSON");
+    let range = &result.dynamic_range;
+    assert_eq!(
+        witness["max_lookup_inputs"].as_i64(),
+        Some(range.max_lookup_inputs)
+    );
+    assert_eq!(
+        witness["min_lookup_inputs"].as_i64(),
+        Some(range.min_lookup_inputs)
+    );
+    assert_eq!(
+        witness["max_range_size"].as_i64(),
+        Some(range.max_range_size)
+    );
+    assert!(range.min_lookup_inputs <= range.max_lookup_inputs);
+
+    // 4. The standalone helper must agree with the detailed result
+    let standalone = ios_ezkl::witness_dynamic_range(result.witness_json.clone())
+        .expect("Failed to read dynamic range");
+    assert_eq!(&standalone, range);
+}
+
+// An input that references an on-chain data source behind an unroutable RPC endpoint
+const REMOTE_INPUT_JSON: &str = r#"{
+    "input_data": {
+        "rpc": "http://10.255.255.1:8545",
+        "calls": [
+            {
+                "call_data": [["0x0000000000000000000000000000000000000000000000000000000000000000", 0]],
+                "address": "0x0000000000000000000000000000000000000000"
+            }
+        ]
+    }
+}"#;
+
+#[tokio::test]
+async fn test_gen_witness_offline_rejects_remote_source() {
+    setup_keys_once();
+
+    let compiled_circuit =
+        std::fs::read(COMPILED_CIRCUIT_PATH).expect("Failed to read circuit file");
+    let vk = std::fs::read(VK_PATH).expect("Failed to read vk file");
+    let srs = std::fs::read(SRS_PATH).expect("Failed to read srs file");
+
+    let options = ios_ezkl::WitnessOptions {
+        offline: true,
+        network_timeout_ms: None,
+        ..ios_ezkl::WitnessOptions::default()
+    };
+    let witness = ios_ezkl::gen_witness_advanced(
+        REMOTE_INPUT_JSON.to_string(),
+        compiled_circuit,
+        vk,
+        srs,
+        options,
+    )
+    .await;
+
+    match witness {
+        Err(ios_ezkl::EZKLError::InvalidInput(message)) => {
+            assert!(message.contains("remote data source"), "{}", message)
+        }
+        other => panic!("Expected an InvalidInput error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_gen_witness_remote_source_times_out() {
+    setup_keys_once();
+
+    let compiled_circuit =
+        std::fs::read(COMPILED_CIRCUIT_PATH).expect("Failed to read circuit file");
+    let vk = std::fs::read(VK_PATH).expect("Failed to read vk file");
+    let srs = std::fs::read(SRS_PATH).expect("Failed to read srs file");
+
+    let options = ios_ezkl::WitnessOptions {
+        offline: false,
+        network_timeout_ms: Some(200),
+        ..ios_ezkl::WitnessOptions::default()
+    };
+    let start = std::time::Instant::now();
+    let witness = ios_ezkl::gen_witness_advanced(
+        REMOTE_INPUT_JSON.to_string(),
+        compiled_circuit,
+        vk,
+        srs,
+        options,
+    )
+    .await;
+
+    // The fetch must fail promptly rather than hang on the unreachable endpoint
+    assert!(witness.is_err(), "Expected the remote fetch to fail");
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use ezkl::EZKL_BUF_CAPACITY;
+use ios_ezkl::testing::buffer_capacity;
+use ios_ezkl::{EZKLError, EzklConfig};
+
+// The configuration is process-wide, so all scenarios run sequentially in a single test
+#[test]
+fn test_configured_buffer_capacity_is_honored() {
+    // 1. Without configuration the buffers match the ones ezkl reads its own files with
+    assert_eq!(buffer_capacity(), *EZKL_BUF_CAPACITY);
+
+    // 2. A zero capacity is rejected
+    let result = ios_ezkl::initialize(EzklConfig {
+        io_buffer_capacity: Some(0),
+        ..Default::default()
+    });
+    assert!(matches!(result, Err(EZKLError::InvalidInput(_))));
+    assert_eq!(buffer_capacity(), *EZKL_BUF_CAPACITY);
+
+    // 3. The configured capacity is used by every reader and writer
+    ios_ezkl::initialize(EzklConfig {
+        io_buffer_capacity: Some(64 * 1024),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(buffer_capacity(), 64 * 1024);
+
+    // 4. Artifacts still read and write correctly with the configured capacity
+    let fixture = common::fixture();
+    assert!(ios_ezkl::circuit_info(fixture.compiled_circuit.clone()).is_ok());
+    let path = common::scratch_dir().join("buffered-proof.json");
+    std::fs::write(&path, b"{}").unwrap();
+    assert!(
+        ios_ezkl::testing::deserialize_json_file::<serde_json::Value>(
+            "proof",
+            path.to_str().unwrap()
+        )
+        .is_ok()
+    );
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::EZKLError;
+
+fn assert_json_error<T: std::fmt::Debug>(
+    result: Result<T, EZKLError>,
+    expected_field: &str,
+    expected_line: u64,
+    expected_column: Option<u64>,
+) {
+    match result {
+        Err(EZKLError::JsonError {
+            field,
+            line,
+            column,
+            message,
+        }) => {
+            assert_eq!(field, expected_field);
+            assert_eq!(line, expected_line);
+            match expected_column {
+                Some(expected_column) => assert_eq!(column, expected_column),
+                None => assert!(column > 0),
+            }
+            assert!(message.contains("near"), "{}", message);
+        }
+        other => panic!("Expected a JsonError, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_malformed_input_json() {
+    let fixture = common::fixture();
+    let result = ios_ezkl::gen_witness(
+        "{\n  \"input_data\": [[0.1, 0.2,]]\n}".to_string(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await;
+    assert_json_error(result, "input_json", 2, None);
+}
+
+#[test]
+fn test_malformed_witness_json() {
+    let fixture = common::fixture();
+    let result = ios_ezkl::prove(
+        "{\n\n  \"inputs\": oops\n}".to_string(),
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    );
+    assert_json_error(result, "witness_json", 3, None);
+}
+
+#[tokio::test]
+async fn test_malformed_settings_json() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+    let result = ios_ezkl::verify(
+        proof,
+        "{ \"run_args\": { \"logrows\": } }".to_string(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    );
+    assert_json_error(result, "settings_json", 1, None);
+}
+
+#[test]
+fn test_malformed_proof_json() {
+    let fixture = common::fixture();
+    let result = ios_ezkl::verify(
+        "{\n  \"instances\": [[\n".to_string(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    );
+    // serde_json reports the end of input past the trailing newline, at line 3, column 0
+    assert_json_error(result, "proof_json", 3, Some(0));
+
+    let result = ios_ezkl::inspect_proof("[}".to_string());
+    assert_json_error(result, "proof_json", 1, None);
+}
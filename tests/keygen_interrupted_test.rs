@@ -0,0 +1,39 @@
+// Injects the failure with a test hook: `cargo test --features test-hooks --test keygen_interrupted_test`.
+// The hook is global, so this is the only test in its binary.
+#![cfg(all(feature = "prover", feature = "test-hooks"))]
+
+mod common;
+
+use ios_ezkl::KeyGenOptions;
+
+#[test]
+fn test_failed_pk_write_leaves_no_key_file() {
+    let fixture = common::fixture();
+    let dir = common::scratch_dir().join("keygen-interrupted");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = |name: &str| dir.join(name).display().to_string();
+    std::fs::write(path("network.ezkl"), &fixture.compiled_circuit).unwrap();
+    std::fs::write(path("kzg.srs"), &fixture.srs).unwrap();
+
+    // The vk is written first, so the second write is the pk
+    ios_ezkl::testing::fail_artifact_write_after(1);
+    let result = ios_ezkl::gen_keys_to_files(
+        path("network.ezkl"),
+        path("kzg.srs"),
+        path("pk.key"),
+        path("vk.key"),
+        KeyGenOptions::default(),
+    );
+    assert!(result.is_err());
+
+    // Neither key nor any temporary file is left behind
+    let mut names: Vec<String> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec!["kzg.srs".to_string(), "network.ezkl".to_string()]
+    );
+}
@@ -0,0 +1,204 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ezkl::graph::GraphCircuit;
+use ezkl::pfsys::load_vk;
+use halo2_proofs::halo2curves::bn256::Bn256;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use ios_ezkl::{KeyGenOptions, KeyGenStage, ProgressListener};
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct RecordingListener {
+    events: Mutex<Vec<(KeyGenStage, u64, u64)>>,
+}
+
+impl ProgressListener for RecordingListener {
+    fn on_progress(&self, stage: KeyGenStage, completed: u64, total: u64) {
+        self.events.lock().unwrap().push((stage, completed, total));
+    }
+}
+
+#[tokio::test]
+async fn test_gen_keys_to_files_and_prove_with_them() {
+    let fixture = common::fixture();
+    let dir = common::scratch_dir().join("keygen");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = |name: &str| dir.join(name).display().to_string();
+    std::fs::write(path("network.ezkl"), &fixture.compiled_circuit).unwrap();
+    std::fs::write(path("kzg.srs"), &fixture.srs).unwrap();
+
+    let listener = Arc::new(RecordingListener::default());
+    let keys = ios_ezkl::gen_keys_to_files(
+        path("network.ezkl"),
+        path("kzg.srs"),
+        path("pk.key"),
+        path("vk.key"),
+        KeyGenOptions {
+            progress: Some(listener.clone()),
+            ..KeyGenOptions::default()
+        },
+    )
+    .expect("Key generation failed");
+
+    let pk = std::fs::read(path("pk.key")).unwrap();
+    assert_eq!(keys.pk.byte_length, pk.len() as u64);
+    assert_eq!(keys.pk.sha256, hex::encode(Sha256::digest(&pk)));
+
+    // Every stage reports its completion, in order, and the pk progress never goes backwards
+    let events = listener.events.lock().unwrap().clone();
+    let completed: Vec<KeyGenStage> = events
+        .iter()
+        .filter(|(_, completed, total)| completed == total)
+        .map(|(stage, _, _)| *stage)
+        .collect();
+    assert_eq!(
+        completed,
+        vec![
+            KeyGenStage::LoadCircuit,
+            KeyGenStage::LoadSrs,
+            KeyGenStage::CreateKeys,
+            KeyGenStage::WriteVk,
+            KeyGenStage::WritePk,
+        ]
+    );
+    let pk_progress: Vec<u64> = events
+        .iter()
+        .filter(|(stage, _, _)| *stage == KeyGenStage::WritePk)
+        .map(|(_, completed, _)| *completed)
+        .collect();
+    assert!(pk_progress.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(pk_progress.last(), Some(&keys.pk.byte_length));
+
+    // The generated keys work with the path-based prover
+    ios_ezkl::gen_witness_to_file(
+        fixture.input_json.clone(),
+        path("network.ezkl"),
+        path("vk.key"),
+        path("kzg.srs"),
+        path("witness.json"),
+    )
+    .await
+    .expect("Witness generation failed");
+    ios_ezkl::prove_to_file(
+        path("witness.json"),
+        path("network.ezkl"),
+        path("pk.key"),
+        path("kzg.srs"),
+        path("proof.json"),
+    )
+    .expect("Proving failed");
+
+    let valid = ios_ezkl::verify_from_file(
+        path("proof.json"),
+        {
+            std::fs::write(path("settings.json"), &fixture.settings_json).unwrap();
+            path("settings.json")
+        },
+        path("vk.key"),
+        path("kzg.srs"),
+    )
+    .expect("Verification failed");
+    assert!(valid);
+}
+
+#[tokio::test]
+async fn test_optimized_pk_proves_against_original_vk() {
+    let fixture = common::fixture();
+    let optimized = ios_ezkl::optimize_pk(
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("The fixture keys are already compressed");
+
+    let proof = ios_ezkl::prove(
+        common::witness().await,
+        fixture.compiled_circuit.clone(),
+        optimized,
+        fixture.srs.clone(),
+    )
+    .expect("Proving failed");
+    let valid = ios_ezkl::verify(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed");
+    assert!(valid);
+}
+
+#[test]
+fn test_optimize_pk_detects_vk_change() {
+    let fixture = common::fixture();
+    let dir = common::scratch_dir().join("keygen-uncompressed");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = |name: &str| dir.join(name).display().to_string();
+    std::fs::write(path("network.ezkl"), &fixture.compiled_circuit).unwrap();
+    std::fs::write(path("kzg.srs"), &fixture.srs).unwrap();
+    ios_ezkl::gen_keys_to_files(
+        path("network.ezkl"),
+        path("kzg.srs"),
+        path("pk.key"),
+        path("vk.key"),
+        KeyGenOptions {
+            disable_selector_compression: true,
+            ..KeyGenOptions::default()
+        },
+    )
+    .expect("Key generation failed");
+
+    let err = ios_ezkl::optimize_pk(
+        fixture.compiled_circuit.clone(),
+        std::fs::read(path("pk.key")).unwrap(),
+        fixture.srs.clone(),
+    )
+    .expect_err("Compressing the selectors changes the vk");
+    assert!(matches!(
+        err,
+        ios_ezkl::EZKLError::FingerprintMismatch { .. }
+    ));
+}
+
+#[test]
+fn test_pk_info_of_the_sample_pk() {
+    let fixture = common::fixture();
+    let info = ios_ezkl::pk_info(fixture.compiled_circuit.clone(), fixture.pk.clone())
+        .expect("Failed to read the pk");
+
+    // Known values of the sample circuit; update them together with the fixtures
+    assert_eq!(info.k, 17);
+    assert_eq!(info.num_instance_columns, 1);
+    assert_eq!(info.serialized_size, fixture.pk.len() as u64);
+
+    // The column counts agree with the constraint system of the vk
+    let circuit = GraphCircuit::load(common::COMPILED_CIRCUIT_PATH.into()).unwrap();
+    let vk_path = common::scratch_dir().join("pk-info-vk.key");
+    std::fs::write(&vk_path, &fixture.vk).unwrap();
+    let vk =
+        load_vk::<KZGCommitmentScheme<Bn256>, GraphCircuit>(vk_path, circuit.params()).unwrap();
+    let cs = vk.cs();
+    assert_eq!(info.num_fixed_columns, cs.num_fixed_columns() as u64);
+    assert_eq!(info.num_advice_columns, cs.num_advice_columns() as u64);
+    assert_eq!(
+        info.num_permutation_columns,
+        cs.permutation().get_columns().len() as u64
+    );
+    assert!(info.num_advice_columns >= circuit.settings().run_args.num_inner_cols as u64);
+}
+
+#[test]
+fn test_pk_info_rejects_a_truncated_pk() {
+    let fixture = common::fixture();
+    let mut pk = fixture.pk.clone();
+    pk.truncate(pk.len() / 2);
+    let err = ios_ezkl::pk_info(fixture.compiled_circuit.clone(), pk).unwrap_err();
+    assert!(
+        matches!(err, ios_ezkl::EZKLError::LoadPk { .. }),
+        "{:?}",
+        err
+    );
+}
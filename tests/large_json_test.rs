@@ -0,0 +1,47 @@
+#![cfg(feature = "prover")]
+#![cfg(feature = "diagnostics")]
+
+mod common;
+
+use ezkl::graph::GraphWitness;
+use ios_ezkl::testing::{deserialize_json_file, measure_peak_bytes};
+
+// Kept small enough for CI while dwarfing the buffers of the reader
+const ROWS: usize = 1_000;
+const ROW_LEN: usize = 100;
+
+#[tokio::test]
+async fn test_large_witness_is_parsed_without_holding_its_text() {
+    ios_ezkl::set_diagnostics(true);
+
+    // A synthetic witness of about 7 MB, mostly made of inputs
+    let mut witness: serde_json::Value = serde_json::from_str(&common::witness().await).unwrap();
+    let element = witness["outputs"][0][0].clone();
+    witness["inputs"] = vec![vec![element; ROW_LEN]; ROWS].into();
+    let path = common::scratch_dir().join("large-witness.json");
+    std::fs::write(&path, witness.to_string()).unwrap();
+    let file_len = std::fs::metadata(&path).unwrap().len();
+    drop(witness);
+
+    let (parsed, streaming_peak) = measure_peak_bytes(|| {
+        deserialize_json_file::<GraphWitness>("witness_path", &path.display().to_string())
+    });
+    assert_eq!(parsed.unwrap().inputs.len(), ROWS);
+
+    let (parsed, loaded_peak) = measure_peak_bytes(|| {
+        let text = std::fs::read_to_string(&path).unwrap();
+        serde_json::from_str::<GraphWitness>(&text).unwrap()
+    });
+    assert_eq!(parsed.inputs.len(), ROWS);
+
+    // Loading the text first needs at least the whole file on top of the parsed witness
+    let streaming_peak = streaming_peak.expect("Peak not tracked");
+    let loaded_peak = loaded_peak.expect("Peak not tracked");
+    println!(
+        "file: {} bytes, streaming peak: {} bytes, loaded peak: {} bytes",
+        file_len, streaming_peak, loaded_peak
+    );
+    assert!(loaded_peak >= file_len);
+    assert!(streaming_peak < file_len);
+    assert!(streaming_peak < loaded_peak);
+}
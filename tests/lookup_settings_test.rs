@@ -0,0 +1,154 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{EZKLError, LookupSettings, WitnessOptions};
+use serde_json::Value;
+
+async fn gen_witness(input_json: String, options: WitnessOptions) -> Result<String, EZKLError> {
+    let fixture = common::fixture();
+    ios_ezkl::gen_witness_advanced(
+        input_json,
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        options,
+    )
+    .await
+}
+
+/// The sample input with every value scaled far beyond the lookup range of the circuit.
+fn out_of_range_input() -> String {
+    let mut input: Value = serde_json::from_str(&common::fixture().input_json).unwrap();
+    for values in input["input_data"].as_array_mut().unwrap() {
+        for value in values.as_array_mut().unwrap() {
+            *value = Value::from(1000.0);
+        }
+    }
+    input.to_string()
+}
+
+/// The lookup settings the sample circuit was compiled with.
+fn compiled_lookup_settings() -> LookupSettings {
+    LookupSettings {
+        lookup_range_min: -32768,
+        lookup_range_max: 32768,
+        num_inner_cols: 2,
+    }
+}
+
+#[tokio::test]
+async fn test_check_range_is_tunable_at_witness_time() {
+    let fixture = common::fixture();
+
+    // 1. By default, values out of range fail witness generation
+    let err = gen_witness(out_of_range_input(), WitnessOptions::default())
+        .await
+        .unwrap_err();
+    assert!(!matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+
+    // 2. Without range checks the witness is generated, and the values out of range fail the
+    //    constraints of the circuit instead
+    let witness = gen_witness(
+        out_of_range_input(),
+        WitnessOptions {
+            check_range: false,
+            ..WitnessOptions::default()
+        },
+    )
+    .await
+    .expect("Witness generation without range checks failed");
+    let report = ios_ezkl::mock(witness, fixture.compiled_circuit.clone()).unwrap();
+    assert!(!report.satisfied);
+
+    // 3. Inputs in range produce the same witness either way
+    let checked = gen_witness(fixture.input_json.clone(), WitnessOptions::default())
+        .await
+        .unwrap();
+    let unchecked = gen_witness(
+        fixture.input_json.clone(),
+        WitnessOptions {
+            check_range: false,
+            ..WitnessOptions::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(checked, unchecked);
+}
+
+#[tokio::test]
+async fn test_compiled_lookup_settings_are_accepted() {
+    let fixture = common::fixture();
+    gen_witness(
+        fixture.input_json.clone(),
+        WitnessOptions {
+            lookup_settings: Some(compiled_lookup_settings()),
+            ..WitnessOptions::default()
+        },
+    )
+    .await
+    .expect("Witness generation with the compiled lookup settings failed");
+}
+
+#[tokio::test]
+async fn test_lookup_settings_requiring_recompilation_are_rejected() {
+    let fixture = common::fixture();
+    let cases = [
+        // A smaller lookup table, as to save memory
+        LookupSettings {
+            lookup_range_min: -1024,
+            lookup_range_max: 1024,
+            ..compiled_lookup_settings()
+        },
+        LookupSettings {
+            num_inner_cols: 1,
+            ..compiled_lookup_settings()
+        },
+    ];
+    for lookup_settings in cases {
+        let err = gen_witness(
+            fixture.input_json.clone(),
+            WitnessOptions {
+                lookup_settings: Some(lookup_settings.clone()),
+                ..WitnessOptions::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        match err {
+            EZKLError::InvalidInput(message) => {
+                assert!(message.contains("recompile the model"), "{}", message)
+            }
+            err => panic!("{:?} for {:?}", err, lookup_settings),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_lookup_settings_are_rejected() {
+    let fixture = common::fixture();
+    let cases = [
+        LookupSettings {
+            lookup_range_min: 1,
+            lookup_range_max: -1,
+            ..compiled_lookup_settings()
+        },
+        LookupSettings {
+            num_inner_cols: 0,
+            ..compiled_lookup_settings()
+        },
+    ];
+    for lookup_settings in cases {
+        let err = gen_witness(
+            fixture.input_json.clone(),
+            WitnessOptions {
+                lookup_settings: Some(lookup_settings),
+                ..WitnessOptions::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+    }
+}
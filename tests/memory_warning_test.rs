@@ -0,0 +1,35 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{handle_memory_warning, EzklProver};
+
+// Kept in its own test binary, as a memory warning releases the sessions of every test running
+// in the process
+#[tokio::test]
+async fn test_memory_warning_releases_every_session() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let session = || {
+        EzklProver::new(
+            fixture.compiled_circuit.clone(),
+            fixture.pk.clone(),
+            fixture.srs.clone(),
+        )
+    };
+    let (first, second) = (session(), session());
+    first.clone().preload().await.expect("Failed to preload");
+    second.clone().preload().await.expect("Failed to preload");
+    // A dropped session is not kept alive by the memory warning handler
+    drop(session());
+
+    handle_memory_warning();
+    assert!(!first.is_ready());
+    assert!(!second.is_ready());
+
+    let result = first.prove(witness).expect("Proving failed");
+    assert!(result.metrics.load_ms > 0);
+    assert!(first.is_ready());
+    assert!(!second.is_ready());
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{attach_metadata, proof_digest, read_metadata, EZKLError};
+use std::collections::HashMap;
+
+fn metadata(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_metadata_round_trips_without_changing_the_proof() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+    assert!(read_metadata(proof.clone()).unwrap().is_empty());
+
+    let tags = metadata(&[
+        ("session_id", "3f1c"),
+        ("model_version", "2.1.0"),
+        ("device_class", "iPhone15,2"),
+    ]);
+    let tagged = attach_metadata(proof.clone(), tags.clone()).expect("Failed to attach metadata");
+    assert_eq!(read_metadata(tagged.clone()).unwrap(), tags);
+
+    // Attaching again replaces the metadata
+    let retagged = attach_metadata(tagged.clone(), metadata(&[("session_id", "9a0d")])).unwrap();
+    assert_eq!(
+        read_metadata(retagged).unwrap(),
+        metadata(&[("session_id", "9a0d")])
+    );
+
+    let valid = ios_ezkl::verify(
+        tagged.clone(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed");
+    assert!(valid);
+    assert_eq!(
+        proof_digest(tagged.clone()).unwrap(),
+        proof_digest(proof.clone()).unwrap()
+    );
+
+    // The inspection helpers read tagged proofs like untagged ones
+    assert_eq!(
+        ios_ezkl::split_instances(tagged.clone(), fixture.settings_json.clone()).unwrap(),
+        ios_ezkl::split_instances(proof, fixture.settings_json.clone()).unwrap()
+    );
+    assert!(ios_ezkl::inspect_proof(tagged).is_ok());
+}
+
+#[test]
+fn test_metadata_errors() {
+    let err = attach_metadata("{}".to_string(), HashMap::new()).unwrap_err();
+    assert!(
+        matches!(err, EZKLError::JsonError { ref field, .. } if field == "proof_json"),
+        "{:?}",
+        err
+    );
+
+    let err = read_metadata(r#"{"app_metadata": {"count": 1}}"#.to_string()).unwrap_err();
+    assert!(matches!(err, EZKLError::JsonError { .. }), "{:?}", err);
+}
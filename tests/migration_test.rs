@@ -0,0 +1,50 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{migrate_artifact, settings_compatible, ArtifactKind, EZKLError};
+
+/// The sample settings in the layout of ezkl versions before IPA commitments.
+const LEGACY_SETTINGS_PATH: &str = "tests/ezkl-sample/legacy/settings.json";
+const SETTINGS_PATH: &str = "tests/ezkl-sample/settings.json";
+
+#[test]
+fn test_migrate_legacy_settings() {
+    let legacy = std::fs::read(LEGACY_SETTINGS_PATH).expect("Failed to read legacy settings");
+    let current = std::fs::read_to_string(SETTINGS_PATH).expect("Failed to read settings");
+
+    let migrated = migrate_artifact(ArtifactKind::Settings, legacy).expect("Failed to migrate");
+    let migrated = String::from_utf8(migrated).unwrap();
+
+    // The migrated settings describe the same circuit as those written by the pinned ezkl
+    let report = settings_compatible(migrated.clone(), current).unwrap();
+    assert!(report.compatible, "{:?}", report.differences);
+    assert!(report.differences.is_empty(), "{:?}", report.differences);
+
+    // Migrating again leaves the settings unchanged
+    let again = migrate_artifact(ArtifactKind::Settings, migrated.clone().into_bytes()).unwrap();
+    assert_eq!(String::from_utf8(again).unwrap(), migrated);
+}
+
+#[test]
+fn test_migrate_current_settings_is_unchanged() {
+    let current = std::fs::read(SETTINGS_PATH).expect("Failed to read settings");
+    let migrated = migrate_artifact(ArtifactKind::Settings, current.clone()).unwrap();
+    assert_eq!(migrated, current);
+}
+
+#[test]
+fn test_migrate_rejects_unknown_layouts() {
+    // Settings of a layout older than the compatibility table
+    let err = migrate_artifact(
+        ArtifactKind::Settings,
+        br#"{"model_instance_shapes":[[1,10]],"logrows":17}"#.to_vec(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, EZKLError::Unsupported { .. }), "{:?}", err);
+
+    // Verification keys carry no marker of the layout they were written in
+    let fixture = common::fixture();
+    let err = migrate_artifact(ArtifactKind::VerificationKey, fixture.vk.clone()).unwrap_err();
+    assert!(matches!(err, EZKLError::Unsupported { .. }), "{:?}", err);
+}
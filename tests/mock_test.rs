@@ -0,0 +1,62 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{mock, ConstraintFailureKind};
+
+#[tokio::test]
+async fn test_mock_valid_witness() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let report = mock(witness, fixture.compiled_circuit.clone()).expect("Mock failed to run");
+
+    assert!(report.satisfied);
+    assert!(report.failures.is_empty());
+}
+
+#[tokio::test]
+async fn test_mock_reports_structured_failures() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    // Replacing an output breaks the constraints tying the outputs to the computation
+    let mut corrupted: serde_json::Value = serde_json::from_str(&witness).unwrap();
+    let output = corrupted["outputs"][0][0].as_str().unwrap().to_string();
+    let replacement = corrupted["outputs"][0]
+        .as_array()
+        .unwrap()
+        .iter()
+        .chain(corrupted["inputs"][0].as_array().unwrap())
+        .find(|value| value.as_str() != Some(output.as_str()))
+        .cloned()
+        .expect("No distinct value to corrupt the witness with");
+    corrupted["outputs"][0][0] = replacement;
+
+    let report =
+        mock(corrupted.to_string(), fixture.compiled_circuit.clone()).expect("Mock failed to run");
+
+    assert!(!report.satisfied);
+    assert!(!report.failures.is_empty());
+    assert!(report
+        .failures
+        .iter()
+        .all(|failure| failure.kind != ConstraintFailureKind::Other));
+    assert!(report.failures.iter().any(|failure| failure
+        .region
+        .as_deref()
+        .is_some_and(|region| !region.is_empty())));
+    assert!(report
+        .failures
+        .iter()
+        .all(|failure| !failure.description.is_empty()));
+}
+
+#[test]
+fn test_mock_rejects_invalid_witness() {
+    let fixture = common::fixture();
+
+    let result = mock("{}".to_string(), fixture.compiled_circuit.clone());
+
+    assert!(matches!(result, Err(ios_ezkl::EZKLError::JsonError { .. })));
+}
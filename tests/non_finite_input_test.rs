@@ -0,0 +1,173 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{
+    gen_witness, gen_witness_detailed_with_options, gen_witness_from_raw, DType, EZKLError,
+    RawTensor, WitnessOptions, WitnessResult,
+};
+use serde_json::Value;
+
+fn sample_values(fixture: &common::Fixture) -> Vec<Value> {
+    let input: Value = serde_json::from_str(&fixture.input_json).unwrap();
+    input["input_data"][0].as_array().unwrap().clone()
+}
+
+/// The sample input with the elements at the given offsets replaced by the given JSON text.
+fn input_with(replacements: &[(usize, &str)]) -> String {
+    let mut values: Vec<String> = sample_values(common::fixture())
+        .iter()
+        .map(Value::to_string)
+        .collect();
+    for &(offset, replacement) in replacements {
+        values[offset] = replacement.to_string();
+    }
+    format!("{{\"input_data\":[[{}]]}}", values.join(","))
+}
+
+async fn witness_with_options(
+    input_json: String,
+    options: WitnessOptions,
+) -> Result<WitnessResult, EZKLError> {
+    let fixture = common::fixture();
+    gen_witness_detailed_with_options(
+        input_json,
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        options,
+    )
+    .await
+}
+
+fn sanitizing() -> WitnessOptions {
+    WitnessOptions {
+        sanitize: true,
+        ..WitnessOptions::default()
+    }
+}
+
+fn assert_rejected(result: Result<impl std::fmt::Debug, EZKLError>, offset: usize) {
+    match result.unwrap_err() {
+        EZKLError::InvalidInput(message) => {
+            assert!(message.contains("input 0"), "{}", message);
+            assert!(
+                message.contains(&format!("offset {}", offset)),
+                "{}",
+                message
+            );
+        }
+        err => panic!("{:?}", err),
+    }
+}
+
+#[tokio::test]
+async fn test_non_finite_json_inputs_are_rejected() {
+    // NaN and infinities as written by `JSON.stringify`, Swift's string strategy and Python
+    for (offset, non_finite) in [
+        (0, "null"),
+        (1, "\"NaN\""),
+        (2, "\"-Infinity\""),
+        (3, "NaN"),
+        (4, "Infinity"),
+        (5, "-Infinity"),
+    ] {
+        let result = witness_with_options(
+            input_with(&[(offset, non_finite)]),
+            WitnessOptions::default(),
+        )
+        .await;
+        assert_rejected(result, offset);
+    }
+}
+
+#[tokio::test]
+async fn test_non_finite_raw_inputs_are_rejected() {
+    let fixture = common::fixture();
+    let mut values: Vec<f32> = sample_values(fixture)
+        .iter()
+        .map(|value| value.as_f64().unwrap() as f32)
+        .collect();
+    values[2] = f32::NAN;
+    let tensor = RawTensor {
+        shape: vec![1, values.len() as u64],
+        dtype: DType::F32,
+        data: values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect(),
+    };
+
+    let result = gen_witness_from_raw(
+        vec![tensor],
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await;
+    assert_rejected(result, 2);
+}
+
+#[tokio::test]
+async fn test_sanitize_replaces_nan_with_zero() {
+    let fixture = common::fixture();
+    let sanitized = witness_with_options(input_with(&[(1, "NaN")]), sanitizing())
+        .await
+        .expect("Witness generation failed");
+    assert_eq!(sanitized.sanitized_inputs.len(), 1);
+    let replaced = &sanitized.sanitized_inputs[0];
+    assert_eq!((replaced.input, replaced.offset), (0, 1));
+    assert!(replaced.value.is_nan());
+    assert_eq!(replaced.replacement, 0.0);
+
+    let zeroed = gen_witness(
+        input_with(&[(1, "0.0")]),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+    assert_eq!(sanitized.witness_json, zeroed);
+}
+
+#[tokio::test]
+async fn test_sanitize_clamps_infinities_to_the_lookup_range() {
+    let settings: Value = serde_json::from_str(&common::fixture().settings_json).unwrap();
+    let (min, max) = (
+        settings["run_args"]["lookup_range"][0].as_f64().unwrap(),
+        settings["run_args"]["lookup_range"][1].as_f64().unwrap(),
+    );
+    let multiplier = 2f64.powi(settings["model_input_scales"][0].as_i64().unwrap() as i32);
+
+    // The clamped values may exceed the range checks of later operations, which are skipped
+    let options = WitnessOptions {
+        check_range: false,
+        ..sanitizing()
+    };
+    let result = witness_with_options(
+        input_with(&[(0, "\"Infinity\""), (3, "-Infinity")]),
+        options,
+    )
+    .await
+    .expect("Witness generation failed");
+
+    let replacements: Vec<(u64, f64, f64)> = result
+        .sanitized_inputs
+        .iter()
+        .map(|replaced| (replaced.offset, replaced.value, replaced.replacement))
+        .collect();
+    assert_eq!(
+        replacements,
+        vec![
+            (0, f64::INFINITY, max / multiplier),
+            (3, f64::NEG_INFINITY, min / multiplier),
+        ]
+    );
+
+    // Finite inputs are left alone
+    let result = witness_with_options(input_with(&[]), sanitizing())
+        .await
+        .expect("Witness generation failed");
+    assert!(result.sanitized_inputs.is_empty());
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ezkl::pfsys::Snark;
+use halo2_proofs::halo2curves::bn256::{Fr, G1Affine};
+use ios_ezkl::{normalize_proof, EZKLError};
+
+fn assert_verifies(proof_json: String) {
+    let fixture = common::fixture();
+    let valid = ios_ezkl::verify(
+        proof_json,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed");
+    assert!(valid);
+}
+
+#[tokio::test]
+async fn test_canonical_json_is_unchanged() {
+    let proof_json = common::proof().await;
+
+    let normalized = normalize_proof(proof_json.clone().into_bytes()).unwrap();
+    assert_eq!(normalized, proof_json);
+}
+
+#[tokio::test]
+async fn test_bincode_proof_is_converted() {
+    let proof_json = common::proof().await;
+    let snark: Snark<Fr, G1Affine> = serde_json::from_str(&proof_json).unwrap();
+
+    let normalized = normalize_proof(bincode::serialize(&snark).unwrap()).unwrap();
+    assert_eq!(normalized, proof_json);
+    assert_verifies(normalized);
+}
+
+#[tokio::test]
+async fn test_legacy_json_layout_is_converted() {
+    let proof_json = common::proof().await;
+
+    // Older layouts encoded the instances as little-endian limbs and only kept the hex proof
+    let mut legacy: serde_json::Value = serde_json::from_str(&proof_json).unwrap();
+    let proof_bytes: Vec<u8> = legacy["proof"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|byte| byte.as_u64().unwrap() as u8)
+        .collect();
+    legacy["hex_proof"] = format!("0x{}", hex::encode(proof_bytes)).into();
+    legacy.as_object_mut().unwrap().remove("proof");
+    for column in legacy["instances"].as_array_mut().unwrap() {
+        for instance in column.as_array_mut().unwrap() {
+            let repr = hex::decode(instance.as_str().unwrap()).unwrap();
+            let limbs: Vec<u64> = repr
+                .chunks(8)
+                .map(|limb| u64::from_le_bytes(limb.try_into().unwrap()))
+                .collect();
+            *instance = limbs.into();
+        }
+    }
+    let legacy = format!(
+        "\u{feff}{}\n",
+        serde_json::to_string_pretty(&legacy).unwrap()
+    );
+
+    let normalized = normalize_proof(legacy.into_bytes()).unwrap();
+    assert_eq!(
+        ios_ezkl::inspect_proof(normalized.clone())
+            .unwrap()
+            .instances,
+        ios_ezkl::inspect_proof(proof_json).unwrap().instances
+    );
+    assert_verifies(normalized);
+}
+
+#[test]
+fn test_unrecognized_encodings_name_the_version() {
+    let err = normalize_proof(br#"{"version": "0.1.0", "instances": "nope"}"#.to_vec())
+        .expect_err("The layout is unknown");
+    let EZKLError::InvalidInput(message) = err else {
+        panic!("Expected InvalidInput, got {:?}", err);
+    };
+    assert!(message.contains("ezkl 0.1.0"), "{}", message);
+
+    let err = normalize_proof(vec![0xde, 0xad, 0xbe, 0xef]).expect_err("The bytes are no proof");
+    assert!(matches!(err, EZKLError::InvalidInput(_)));
+}
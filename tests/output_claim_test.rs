@@ -0,0 +1,108 @@
+mod common;
+
+use ezkl::fieldutils::felt_to_integer_rep;
+use ezkl::pfsys::Snark;
+use halo2_proofs::halo2curves::bn256::{Fr, G1Affine};
+use ios_ezkl::{verify_output_claim, EZKLError};
+
+/// The outputs the proof attests to, dequantized with the output scale of the sample circuit.
+fn proven_outputs(proof_json: &str) -> Vec<f64> {
+    let proof: Snark<Fr, G1Affine> = serde_json::from_str(proof_json).unwrap();
+    // The sample circuit has private inputs, so all of its instances are outputs
+    proof.instances[0]
+        .iter()
+        .map(|instance| felt_to_integer_rep(*instance) as f64 / 128.0)
+        .collect()
+}
+
+fn claim(proof: String, expected: Vec<f64>, tolerance: f64) -> ios_ezkl::ClaimResult {
+    let fixture = common::fixture();
+    verify_output_claim(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        expected,
+        tolerance,
+    )
+    .expect("Failed to check the claim")
+}
+
+#[tokio::test]
+async fn test_output_claim_matches_within_quantization() {
+    let proof = common::proof().await;
+    let outputs = proven_outputs(&proof);
+
+    // Values within half a quantization step pass even without a tolerance
+    let expected: Vec<f64> = outputs.iter().map(|value| value + 0.003).collect();
+    let result = claim(proof.clone(), expected.clone(), 0.0);
+    assert!(result.proof_valid);
+    assert!(result.passed);
+    assert_eq!(result.outputs.len(), outputs.len());
+    for ((output, actual), expected) in result.outputs.iter().zip(&outputs).zip(&expected) {
+        assert_eq!(output.actual, *actual);
+        assert_eq!(output.expected, *expected);
+        assert_eq!(output.scale, 7);
+        assert!(output.passed);
+    }
+
+    // Larger differences pass with a matching tolerance
+    let expected: Vec<f64> = outputs.iter().map(|value| value - 0.05).collect();
+    assert!(claim(proof, expected, 0.05).passed);
+}
+
+#[tokio::test]
+async fn test_output_claim_reports_mismatching_outputs() {
+    let proof = common::proof().await;
+    let mut expected = proven_outputs(&proof);
+    expected[3] += 0.5;
+
+    let result = claim(proof, expected, 0.1);
+    assert!(result.proof_valid);
+    assert!(!result.passed);
+    let failed: Vec<usize> = result
+        .outputs
+        .iter()
+        .enumerate()
+        .filter(|(_, output)| !output.passed)
+        .map(|(index, _)| index)
+        .collect();
+    assert_eq!(failed, [3]);
+}
+
+#[tokio::test]
+async fn test_output_claim_of_invalid_proof_fails() {
+    let proof = common::proof().await;
+    let expected = proven_outputs(&proof);
+
+    let tampered = common::tamper_instance(&proof);
+    let result = claim(tampered, expected, 1.0);
+    assert!(!result.proof_valid);
+    assert!(!result.passed);
+}
+
+#[tokio::test]
+async fn test_output_claim_rejects_invalid_expectations() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+    let expected = proven_outputs(&proof);
+
+    let check = |expected: Vec<f64>, tolerance: f64| {
+        verify_output_claim(
+            proof.clone(),
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+            expected,
+            tolerance,
+        )
+    };
+    for (expected, tolerance) in [
+        (expected[1..].to_vec(), 0.0),
+        (expected.clone(), -1.0),
+        (expected.clone(), f64::NAN),
+    ] {
+        let err = check(expected, tolerance).unwrap_err();
+        assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+    }
+}
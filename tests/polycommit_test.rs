@@ -0,0 +1,154 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{
+    gen_witness_detailed, inspect_proof, prove, prove_advanced, verify, verify_with_commitments,
+    witness_commitments, EZKLError, ProofSplitCommitWrapper, ProveOptions,
+};
+
+fn flatten(groups: &[Vec<String>]) -> Vec<String> {
+    groups.iter().flatten().cloned().collect()
+}
+
+#[tokio::test]
+async fn test_polycommit_prove_and_verify_with_commitments() {
+    let fixture = common::polycommit_fixture();
+
+    let result = gen_witness_detailed(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+
+    let commitments = result.commitments.clone();
+    assert!(!flatten(&commitments.inputs).is_empty());
+    assert!(!flatten(&commitments.outputs).is_empty());
+    assert!(commitments.params.is_empty());
+    assert_eq!(
+        witness_commitments(result.witness_json.clone()).unwrap(),
+        commitments
+    );
+
+    let proof = prove(
+        result.witness_json,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Proof generation failed");
+
+    // The split locates the output commitments after the input commitments in the proof
+    let snark: serde_json::Value = serde_json::from_str(&proof).unwrap();
+    let start = flatten(&commitments.inputs).len() as u64;
+    let end = start + flatten(&commitments.outputs).len() as u64;
+    assert_eq!(snark["split"]["start"], start);
+    assert_eq!(snark["split"]["end"], end);
+
+    assert!(verify(
+        proof.clone(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed"));
+
+    let expected = [flatten(&commitments.inputs), flatten(&commitments.outputs)].concat();
+    assert!(verify_with_commitments(
+        proof.clone(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        expected.clone(),
+    )
+    .expect("Verification with commitments failed"));
+
+    // Swapping in commitments the proof was not made with must not verify
+    let mut swapped = expected.clone();
+    swapped.rotate_left(1);
+    let result = verify_with_commitments(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        swapped,
+    );
+    assert!(!matches!(result, Ok(true)));
+}
+
+#[tokio::test]
+async fn test_witness_without_polycommit_has_no_commitments() {
+    let witness = common::witness().await;
+
+    assert!(witness_commitments(witness).unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_verify_with_commitments_rejects_malformed_commitments() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    for commitments in [vec![], vec!["not hex".to_string()], vec!["00".to_string()]] {
+        let result = verify_with_commitments(
+            proof.clone(),
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+            commitments,
+        );
+        assert!(matches!(result, Err(EZKLError::InvalidInput(_))));
+    }
+}
+
+#[tokio::test]
+async fn test_inspect_proof_reports_split_commit() {
+    let fixture = common::polycommit_fixture();
+    let result = gen_witness_detailed(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+    let start = flatten(&result.commitments.inputs).len() as u64;
+    let end = start + flatten(&result.commitments.outputs).len() as u64;
+    let expected = ProofSplitCommitWrapper { start, end };
+
+    let prove_with = |split_commit: Option<ProofSplitCommitWrapper>| {
+        prove_advanced(
+            result.witness_json.clone(),
+            fixture.compiled_circuit.clone(),
+            fixture.pk.clone(),
+            fixture.srs.clone(),
+            ProveOptions {
+                split_commit,
+                ..ProveOptions::default()
+            },
+        )
+    };
+    let split = inspect_proof(prove_with(None).expect("Proof generation failed")).unwrap();
+    assert_eq!(split.split_commit, Some(expected));
+
+    // A proof of a circuit with public outputs has no split
+    let standard = inspect_proof(common::proof().await).unwrap();
+    assert_eq!(standard.split_commit, None);
+    assert_eq!(split.transcript, standard.transcript);
+    assert_eq!(split.commitment, standard.commitment);
+
+    // The split can be given explicitly, but must match the witness
+    let explicit = prove_with(Some(expected)).expect("Proof generation failed");
+    assert_eq!(
+        inspect_proof(explicit).unwrap().split_commit,
+        Some(expected)
+    );
+    let err = prove_with(Some(ProofSplitCommitWrapper {
+        start: end,
+        end: end + 1,
+    }))
+    .unwrap_err();
+    assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{CheckModeWrapper, EZKLError, ProveOptions, ProveResult, VerifiedAgainst};
+
+async fn prove(options: ProveOptions) -> Result<ProveResult, EZKLError> {
+    let fixture = common::fixture();
+    ios_ezkl::prove_detailed(
+        common::witness().await,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        options,
+    )
+}
+
+#[tokio::test]
+async fn test_metrics_report_what_the_proof_was_verified_against() {
+    let fixture = common::fixture();
+
+    // 1. Safe mode verifies against the vk of the proving key
+    let metrics = prove(ProveOptions::default()).await.unwrap().metrics;
+    assert!(metrics.post_prove_verified);
+    assert_eq!(metrics.verified_against, VerifiedAgainst::ProverVk);
+
+    // 2. Unchecked mode doesn't verify
+    let metrics = prove(ProveOptions {
+        check_mode: CheckModeWrapper::Unchecked,
+        ..ProveOptions::default()
+    })
+    .await
+    .unwrap()
+    .metrics;
+    assert!(!metrics.post_prove_verified);
+    assert_eq!(metrics.verified_against, VerifiedAgainst::None);
+
+    // 3. An external vk is verified against in either mode
+    for check_mode in [CheckModeWrapper::Safe, CheckModeWrapper::Unchecked] {
+        let metrics = prove(ProveOptions {
+            check_mode,
+            verify_with: Some(fixture.vk.clone()),
+            ..ProveOptions::default()
+        })
+        .await
+        .expect("The proof must verify against the vk of its circuit")
+        .metrics;
+        assert!(metrics.post_prove_verified);
+        assert_eq!(metrics.verified_against, VerifiedAgainst::ExternalVk);
+    }
+}
+
+#[tokio::test]
+async fn test_mismatched_external_vk_fails() {
+    // The vk of the same model compiled with hashed inputs and outputs, as if the app shipped a
+    // stale vk
+    let mismatched = common::hashed_fixture().vk.clone();
+
+    let err = prove(ProveOptions {
+        verify_with: Some(mismatched.clone()),
+        ..ProveOptions::default()
+    })
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(err, EZKLError::ExternalVerificationFailed { .. }),
+        "{:?}",
+        err
+    );
+
+    // The simple proving calls check it too
+    let fixture = common::fixture();
+    let err = ios_ezkl::prove_advanced(
+        common::witness().await,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ProveOptions {
+            verify_with: Some(mismatched),
+            ..ProveOptions::default()
+        },
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, EZKLError::ExternalVerificationFailed { .. }),
+        "{:?}",
+        err
+    );
+}
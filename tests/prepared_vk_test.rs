@@ -0,0 +1,116 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{prepare_vk, verify_detailed, EZKLError, TraceListener};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// The tests of this file verify one at a time, so the timer only sees the vk loads of its test
+static SERIAL: Mutex<()> = Mutex::new(());
+
+/// Records how long each `deserialize_vk` interval took.
+#[derive(Default)]
+struct VkLoadTimer {
+    started: Mutex<Vec<(String, Instant)>>,
+    vk_loads: Mutex<Vec<Duration>>,
+}
+
+impl TraceListener for VkLoadTimer {
+    fn begin_interval(&self, name: String) -> u64 {
+        let mut started = self.started.lock().unwrap();
+        started.push((name, Instant::now()));
+        started.len() as u64 - 1
+    }
+
+    fn end_interval(&self, id: u64) {
+        let (name, start) = self.started.lock().unwrap()[id as usize].clone();
+        if name == "deserialize_vk" {
+            self.vk_loads.lock().unwrap().push(start.elapsed());
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_prepared_vk_verifies_alike() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let prepared = prepare_vk(fixture.vk.clone(), fixture.settings_json.clone())
+        .expect("Failed to prepare vk");
+    assert_ne!(prepared, fixture.vk);
+
+    for proof in [proof.clone(), common::tamper_instance(&proof)] {
+        let raw = verify_detailed(
+            proof.clone(),
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+        )
+        .unwrap();
+        let fast = verify_detailed(
+            proof,
+            fixture.settings_json.clone(),
+            prepared.clone(),
+            fixture.srs.clone(),
+        )
+        .unwrap();
+        assert_eq!(fast.outcome, raw.outcome);
+        assert_eq!(fast.failure, raw.failure);
+    }
+
+    // Preparing a prepared vk yields the same vk
+    let again = prepare_vk(prepared.clone(), fixture.settings_json.clone()).unwrap();
+    assert_eq!(again, prepared);
+}
+
+#[tokio::test]
+async fn test_prepared_vk_loads_faster() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let prepared = prepare_vk(fixture.vk.clone(), fixture.settings_json.clone()).unwrap();
+
+    let timer = Arc::new(VkLoadTimer::default());
+    ios_ezkl::set_trace_listener(Some(timer.clone()));
+    let mut fastest_load = |vk: &Vec<u8>| {
+        timer.vk_loads.lock().unwrap().clear();
+        for _ in 0..5 {
+            ios_ezkl::verify(
+                proof.clone(),
+                fixture.settings_json.clone(),
+                vk.clone(),
+                fixture.srs.clone(),
+            )
+            .unwrap();
+        }
+        *timer.vk_loads.lock().unwrap().iter().min().unwrap()
+    };
+    let raw = fastest_load(&fixture.vk);
+    let fast = fastest_load(&prepared);
+    ios_ezkl::set_trace_listener(None);
+
+    println!("vk load: {:?} raw, {:?} prepared", raw, fast);
+    assert!(fast < raw, "{:?} >= {:?}", fast, raw);
+}
+
+#[tokio::test]
+async fn test_corrupted_prepared_vk_is_rejected() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let mut prepared = prepare_vk(fixture.vk.clone(), fixture.settings_json.clone()).unwrap();
+    let last = prepared.len() - 1;
+    prepared[last] ^= 1;
+
+    let err = prepare_vk(prepared.clone(), fixture.settings_json.clone()).unwrap_err();
+    assert!(matches!(err, EZKLError::LoadVk { .. }), "{:?}", err);
+    let err = ios_ezkl::verify(
+        proof,
+        fixture.settings_json.clone(),
+        prepared,
+        fixture.srs.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, EZKLError::LoadVk { .. }), "{:?}", err);
+}
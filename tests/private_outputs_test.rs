@@ -0,0 +1,77 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{
+    circuit_info, inspect_proof, split_instances, verify, verify_output_claim, EZKLError,
+};
+use serde_json::Value;
+
+#[tokio::test]
+async fn test_prove_and_verify_without_public_outputs() {
+    let fixture = common::private_outputs_fixture();
+    let proof = common::proof_for(fixture).await;
+
+    let verify = |proof: String| {
+        verify(
+            proof,
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+        )
+        .expect("Verification failed to run")
+    };
+    assert!(verify(proof.clone()));
+    assert!(!verify(common::tamper_instance(&proof)));
+
+    // The only instance is the hash of the input
+    let grouped = split_instances(proof.clone(), fixture.settings_json.clone()).unwrap();
+    assert_eq!(grouped.inputs.len(), 1);
+    assert!(grouped.params.is_empty());
+    assert!(grouped.outputs.is_empty());
+}
+
+#[tokio::test]
+async fn test_inspection_reports_empty_outputs() {
+    let fixture = common::private_outputs_fixture();
+    let proof = common::proof_for(fixture).await;
+
+    let info = inspect_proof(proof.clone()).unwrap();
+    assert_eq!(info.instances.iter().flatten().count(), 1);
+    if let Some(pretty) = info.pretty_public_inputs_json {
+        let pretty: Value = serde_json::from_str(&pretty).unwrap();
+        let fields = pretty
+            .as_object()
+            .expect("Pretty public inputs are not an object");
+        assert!(fields.values().all(Value::is_array), "{}", pretty);
+        for field in ["rescaled_outputs", "outputs"] {
+            let outputs = fields[field].as_array().unwrap();
+            assert!(outputs
+                .iter()
+                .all(|tensor| tensor.as_array().unwrap().is_empty()));
+        }
+    }
+
+    // An output claim can't be checked against a proof without public outputs
+    let err = verify_output_claim(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        vec![0.0],
+        0.0,
+    )
+    .unwrap_err();
+    assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+}
+
+#[test]
+fn test_outputs_public_is_reported() {
+    let private = common::private_outputs_fixture();
+    let info = circuit_info(private.compiled_circuit.clone()).unwrap();
+    assert!(!info.outputs_public);
+
+    let sample = common::fixture();
+    let info = circuit_info(sample.compiled_circuit.clone()).unwrap();
+    assert!(info.outputs_public);
+}
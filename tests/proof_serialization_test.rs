@@ -0,0 +1,48 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ezkl::pfsys::Snark;
+use halo2_proofs::halo2curves::bn256::{Fr, G1Affine};
+use ios_ezkl::testing::serialize_proof;
+
+/// The fixture proof with its instances replaced by `count` synthetic values, as for a circuit
+/// with a large public output.
+async fn large_instance_snark(count: u64) -> Snark<Fr, G1Affine> {
+    let mut snark: Snark<Fr, G1Affine> = serde_json::from_str(&common::proof().await).unwrap();
+    snark.instances = vec![(0..count)
+        .map(|i| Fr::from(i * 0x9e37_79b9_7f4a_7c15))
+        .collect()];
+    snark
+}
+
+#[tokio::test]
+async fn test_serialize_proof_matches_serde() {
+    let proof_json = common::proof().await;
+    let snark: Snark<Fr, G1Affine> = serde_json::from_str(&proof_json).unwrap();
+    assert_eq!(
+        serialize_proof(&snark).unwrap(),
+        serde_json::to_string(&snark).unwrap()
+    );
+
+    // Also without any instances
+    let mut empty = snark;
+    empty.instances = vec![];
+    assert_eq!(
+        serialize_proof(&empty).unwrap(),
+        serde_json::to_string(&empty).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_serialize_large_instance_proof_in_one_allocation() {
+    let snark = large_instance_snark(100_000).await;
+
+    let actual = serialize_proof(&snark).unwrap();
+    assert_eq!(actual, serde_json::to_string(&snark).unwrap());
+
+    // The buffer is sized for the whole proof up front: had it been grown, its capacity would be
+    // far larger than the output, not just by the slack left for the proof bytes and other fields
+    assert!(actual.capacity() >= actual.len());
+    assert!(actual.capacity() <= actual.len() + 4 * snark.proof.len() + 2048);
+}
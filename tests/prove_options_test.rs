@@ -0,0 +1,87 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{CheckModeWrapper, ProofTypeWrapper, ProveOptions};
+
+#[test]
+fn test_default_prove_options() {
+    let defaults = ios_ezkl::default_prove_options();
+    assert_eq!(
+        defaults,
+        ProveOptions {
+            proof_type: ProofTypeWrapper::Single,
+            check_mode: CheckModeWrapper::Safe,
+            srs_extension: None,
+            expected_variables: vec![],
+            time_budget_ms: None,
+            compress_output: false,
+            verify_with: None,
+            split_commit: None,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_prove_matches_prove_advanced_with_defaults() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let simple = ios_ezkl::prove(
+        witness.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Proving failed");
+    let advanced = ios_ezkl::prove_advanced(
+        witness,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ios_ezkl::default_prove_options(),
+    )
+    .expect("Proving with the default options failed");
+
+    // Proofs are randomized, but both are proofs of the same kind over the same instances
+    let simple_info = ios_ezkl::inspect_proof(simple.clone()).unwrap();
+    let advanced_info = ios_ezkl::inspect_proof(advanced.clone()).unwrap();
+    assert_eq!(simple_info.transcript, advanced_info.transcript);
+    assert_eq!(simple_info.instances, advanced_info.instances);
+
+    for proof in [simple, advanced] {
+        let valid = ios_ezkl::verify(
+            proof,
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+        )
+        .unwrap();
+        assert!(valid);
+    }
+}
+
+#[test]
+fn test_option_enums_default_and_convert() {
+    use ezkl::circuit::CheckMode;
+    use ezkl::pfsys::ProofType;
+
+    assert_eq!(ProofTypeWrapper::default(), ProofTypeWrapper::Single);
+    assert_eq!(CheckModeWrapper::default(), CheckModeWrapper::Safe);
+
+    assert!(matches!(
+        CheckMode::from(CheckModeWrapper::Safe),
+        CheckMode::SAFE
+    ));
+    assert!(matches!(
+        CheckMode::from(CheckModeWrapper::Unchecked),
+        CheckMode::UNSAFE
+    ));
+    for mode in [CheckModeWrapper::Safe, CheckModeWrapper::Unchecked] {
+        assert_eq!(CheckModeWrapper::from(CheckMode::from(mode)), mode);
+    }
+    assert!(matches!(
+        ProofType::from(ProofTypeWrapper::ForAggr),
+        ProofType::ForAggr
+    ));
+}
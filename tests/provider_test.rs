@@ -0,0 +1,144 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{
+    gen_witness_from_provider, prove_from_provider, verify_from_provider, ArtifactProvider,
+    EZKLError,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// An in-memory provider counting the ranges it is asked for.
+#[derive(Default)]
+struct MemoryProvider {
+    artifacts: Mutex<HashMap<String, Vec<u8>>>,
+    range_reads: AtomicUsize,
+}
+
+impl MemoryProvider {
+    fn with_fixture() -> Arc<MemoryProvider> {
+        let fixture = common::fixture();
+        let provider = MemoryProvider::default();
+        for (name, bytes) in [
+            ("circuit", fixture.compiled_circuit.clone()),
+            ("settings", fixture.settings_json.clone().into_bytes()),
+            ("vk", fixture.vk.clone()),
+            ("pk", fixture.pk.clone()),
+            ("srs", fixture.srs.clone()),
+        ] {
+            provider.insert(name, bytes);
+        }
+        Arc::new(provider)
+    }
+
+    fn insert(&self, name: &str, bytes: Vec<u8>) {
+        self.artifacts
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), bytes);
+    }
+
+    fn get(&self, name: &str) -> Result<Vec<u8>, EZKLError> {
+        self.artifacts
+            .lock()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| EZKLError::InvalidInput("no such artifact".to_string()))
+    }
+}
+
+impl ArtifactProvider for MemoryProvider {
+    fn read(&self, name: String) -> Result<Vec<u8>, EZKLError> {
+        self.get(&name)
+    }
+
+    fn read_range(&self, name: String, offset: u64, len: u64) -> Result<Vec<u8>, EZKLError> {
+        self.range_reads.fetch_add(1, Ordering::SeqCst);
+        let bytes = self.get(&name)?;
+        let start = (offset as usize).min(bytes.len());
+        let end = (start + len as usize).min(bytes.len());
+        Ok(bytes[start..end].to_vec())
+    }
+
+    fn size(&self, name: String) -> Result<u64, EZKLError> {
+        Ok(self.get(&name)?.len() as u64)
+    }
+}
+
+#[tokio::test]
+async fn test_prove_and_verify_through_provider() {
+    let fixture = common::fixture();
+    let provider = MemoryProvider::with_fixture();
+
+    let witness = gen_witness_from_provider(
+        fixture.input_json.clone(),
+        provider.clone(),
+        "circuit".to_string(),
+        "vk".to_string(),
+        "srs".to_string(),
+    )
+    .await
+    .expect("Witness generation through the provider failed");
+    assert_eq!(witness, common::witness().await);
+    provider.insert("witness", witness.into_bytes());
+
+    let proof = prove_from_provider(
+        provider.clone(),
+        "witness".to_string(),
+        "circuit".to_string(),
+        "pk".to_string(),
+        "srs".to_string(),
+    )
+    .expect("Proving through the provider failed");
+    assert!(provider.range_reads.load(Ordering::SeqCst) > 0);
+    provider.insert("proof", proof.into_bytes());
+
+    let valid = verify_from_provider(
+        provider.clone(),
+        "proof".to_string(),
+        "settings".to_string(),
+        "vk".to_string(),
+        "srs".to_string(),
+    )
+    .expect("Verification through the provider failed");
+    assert!(valid);
+}
+
+#[tokio::test]
+async fn test_provider_errors_name_the_artifact() {
+    let provider = MemoryProvider::with_fixture();
+    provider.insert("proof", common::proof().await.into_bytes());
+
+    let result = verify_from_provider(
+        provider.clone(),
+        "proof".to_string(),
+        "settings".to_string(),
+        "missing-vk".to_string(),
+        "srs".to_string(),
+    );
+    match result {
+        Err(EZKLError::InvalidInput(message)) => {
+            assert!(message.contains("missing-vk"), "{}", message);
+            assert!(message.contains("no such artifact"), "{}", message);
+        }
+        other => panic!("Expected the provider error, got {:?}", other),
+    }
+
+    // Errors while streaming a JSON artifact name it too
+    let result = prove_from_provider(
+        provider,
+        "missing-witness".to_string(),
+        "circuit".to_string(),
+        "pk".to_string(),
+        "srs".to_string(),
+    );
+    match result {
+        Err(EZKLError::InvalidInput(message)) => {
+            assert!(message.contains("missing-witness"), "{}", message)
+        }
+        other => panic!("Expected the provider error, got {:?}", other),
+    }
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{EZKLError, EzklConfig, ResourceLimits};
+use std::sync::{Arc, Barrier};
+
+#[tokio::test]
+async fn test_concurrent_prove_fails_fast_when_busy() {
+    let fixture = common::fixture();
+    ios_ezkl::initialize(EzklConfig {
+        limits: ResourceLimits {
+            max_concurrent_proofs: 1,
+            queue_when_busy: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .expect("Initialization failed");
+    let witness = common::witness().await;
+
+    // Launch two proofs at once; only one of them may run
+    let barrier = Arc::new(Barrier::new(2));
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let barrier = barrier.clone();
+            let witness = witness.clone();
+            std::thread::spawn(move || {
+                barrier.wait();
+                ios_ezkl::prove(
+                    witness,
+                    fixture.compiled_circuit.clone(),
+                    fixture.pk.clone(),
+                    fixture.srs.clone(),
+                )
+            })
+        })
+        .collect();
+    let results: Vec<_> = handles
+        .into_iter()
+        .map(|handle| handle.join().expect("Proving thread panicked"))
+        .collect();
+
+    assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+    assert!(results
+        .iter()
+        .any(|result| matches!(result, Err(EZKLError::Busy { running_proofs: 1 }))));
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use std::sync::{Arc, Barrier};
+
+// Without `initialize`, the default limits queue concurrent proofs one at a time
+#[tokio::test]
+async fn test_concurrent_prove_is_serialized_by_default() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let barrier = Arc::new(Barrier::new(2));
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let barrier = barrier.clone();
+            let witness = witness.clone();
+            std::thread::spawn(move || {
+                barrier.wait();
+                ios_ezkl::prove(
+                    witness,
+                    fixture.compiled_circuit.clone(),
+                    fixture.pk.clone(),
+                    fixture.srs.clone(),
+                )
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let proof = handle.join().expect("Proving thread panicked");
+        assert!(proof.is_ok(), "Proof generation failed: {:?}", proof);
+    }
+}
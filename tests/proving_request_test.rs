@@ -0,0 +1,95 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use common::Fixture;
+use ios_ezkl::{export_proving_request, gen_witness, prove_request, verify, EZKLError};
+
+async fn request_for(fixture: &Fixture) -> String {
+    let witness = gen_witness(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+    export_proving_request(witness, fixture.compiled_circuit.clone())
+        .expect("Failed to export the proving request")
+}
+
+#[tokio::test]
+async fn test_prove_request_round_trip() {
+    for fixture in [common::fixture(), common::ipa_fixture()] {
+        let request = request_for(fixture).await;
+
+        let proof = prove_request(
+            request,
+            fixture.compiled_circuit.clone(),
+            fixture.pk.clone(),
+            fixture.srs.clone(),
+        )
+        .expect("Proving the request failed");
+
+        assert!(verify(
+            proof,
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+        )
+        .unwrap());
+    }
+}
+
+#[tokio::test]
+async fn test_prove_request_rejects_other_circuit() {
+    let request = request_for(common::fixture()).await;
+    let other = common::ipa_fixture();
+
+    let result = prove_request(
+        request,
+        other.compiled_circuit.clone(),
+        other.pk.clone(),
+        other.srs.clone(),
+    );
+
+    assert!(matches!(
+        result,
+        Err(EZKLError::FingerprintMismatch { ref artifact, .. }) if artifact == "circuit"
+    ));
+}
+
+#[tokio::test]
+async fn test_prove_request_rejects_tampered_settings_hash() {
+    let fixture = common::fixture();
+    let mut request: serde_json::Value = serde_json::from_str(&request_for(fixture).await).unwrap();
+    request["settings_hash"] = serde_json::Value::String("00".repeat(32));
+
+    let result = prove_request(
+        request.to_string(),
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    );
+
+    assert!(matches!(
+        result,
+        Err(EZKLError::FingerprintMismatch { ref artifact, .. }) if artifact == "settings"
+    ));
+}
+
+#[tokio::test]
+async fn test_prove_request_rejects_unknown_version() {
+    let fixture = common::fixture();
+    let mut request: serde_json::Value = serde_json::from_str(&request_for(fixture).await).unwrap();
+    request["version"] = serde_json::Value::from(99);
+
+    let result = prove_request(
+        request.to_string(),
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    );
+
+    assert!(matches!(result, Err(EZKLError::InvalidInput(_))));
+}
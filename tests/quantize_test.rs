@@ -0,0 +1,48 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+#[test]
+fn test_quantize_preview_matches_hand_computed_error() {
+    let fixture = common::fixture();
+    let settings: serde_json::Value = serde_json::from_str(&fixture.settings_json).unwrap();
+    assert_eq!(settings["model_input_scales"][0], 7);
+
+    // At scale 7 values are multiples of 1/128: 0.8731 * 128 = 111.7568 rounds to 112, i.e. 0.875
+    let report = ios_ezkl::quantize_preview(
+        r#"{"input_data": [[0.8731, 0.5, -0.25]]}"#.to_string(),
+        fixture.compiled_circuit.clone(),
+    )
+    .expect("Failed to preview quantization");
+
+    assert_eq!(report.inputs.len(), 1);
+    let input = &report.inputs[0];
+    assert_eq!(input.scale, 7);
+    assert_eq!(input.requantized, vec![0.875, 0.5, -0.25]);
+    assert!((input.max_abs_error - 0.0019).abs() < 1e-12);
+    assert!((input.mean_abs_error - 0.0019 / 3.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_quantize_preview_of_sample_input() {
+    let fixture = common::fixture();
+    let report =
+        ios_ezkl::quantize_preview(fixture.input_json.clone(), fixture.compiled_circuit.clone())
+            .unwrap();
+
+    // The error never exceeds half a quantization step
+    let input = &report.inputs[0];
+    assert_eq!(input.requantized.len(), 784);
+    assert!(input.max_abs_error <= 0.5 / 128.0);
+    assert!(input.mean_abs_error <= input.max_abs_error);
+}
+
+#[test]
+fn test_quantize_preview_rejects_wrong_number_of_inputs() {
+    let fixture = common::fixture();
+    let result = ios_ezkl::quantize_preview(
+        r#"{"input_data": [[0.5], [0.5]]}"#.to_string(),
+        fixture.compiled_circuit.clone(),
+    );
+    assert!(matches!(result, Err(ios_ezkl::EZKLError::InvalidInput(_))));
+}
@@ -0,0 +1,45 @@
+// Holds the proving slot with a test hook: `cargo test --features test-hooks --test queued_time_budget_test`.
+// The proving limits are global, so this is the only test in its binary.
+#![cfg(all(feature = "prover", feature = "test-hooks"))]
+
+mod common;
+
+use ios_ezkl::{EZKLError, EzklConfig, ProveOptions, ResourceLimits};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_queued_call_times_out_while_waiting() {
+    let fixture = common::fixture();
+    ios_ezkl::initialize(EzklConfig {
+        limits: ResourceLimits {
+            max_concurrent_proofs: 1,
+            queue_when_busy: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .expect("Initialization failed");
+    let witness = common::witness().await;
+
+    let slot = ios_ezkl::testing::hold_proving_slot().unwrap();
+    let queued = std::thread::spawn(move || {
+        ios_ezkl::prove_advanced(
+            witness,
+            fixture.compiled_circuit.clone(),
+            fixture.pk.clone(),
+            fixture.srs.clone(),
+            ProveOptions {
+                time_budget_ms: Some(100),
+                ..ProveOptions::default()
+            },
+        )
+    });
+    std::thread::sleep(Duration::from_secs(2));
+    drop(slot);
+
+    // The wait for the slot counts towards the budget, so the call fails as soon as it gets it
+    match queued.join().expect("Proving thread panicked") {
+        Err(EZKLError::TimedOut { elapsed_ms }) => assert!(elapsed_ms >= 2000, "{}", elapsed_ms),
+        other => panic!("Expected TimedOut, got {:?}", other),
+    }
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ezkl::graph::input::GraphData;
+use ios_ezkl::testing::raw_graph_data;
+use ios_ezkl::{gen_witness, gen_witness_from_raw, DType, EZKLError, RawTensor};
+use std::time::Instant;
+
+/// The shape of an RGB image as fed to common vision models.
+const IMAGE_SHAPE: [u64; 4] = [1, 3, 224, 224];
+
+fn sample_values(fixture: &common::Fixture) -> Vec<f64> {
+    let input: serde_json::Value = serde_json::from_str(&fixture.input_json).unwrap();
+    serde_json::from_value(input["input_data"][0].clone()).unwrap()
+}
+
+#[tokio::test]
+async fn test_raw_input_matches_json_input() {
+    let fixture = common::fixture();
+    let values = sample_values(fixture);
+
+    let from_json = gen_witness(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+
+    let raw = |dtype: DType, data: Vec<u8>| RawTensor {
+        shape: vec![1, values.len() as u64],
+        dtype,
+        data,
+    };
+    let f64_data = values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect();
+    let from_raw = gen_witness_from_raw(
+        vec![raw(DType::F64, f64_data)],
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+    assert_eq!(from_raw, from_json);
+
+    // A length that doesn't match the shape is rejected before witness generation
+    let err = gen_witness_from_raw(
+        vec![raw(DType::F32, vec![0; 4 * values.len() - 1])],
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+}
+
+#[test]
+fn test_raw_image_input_matches_json_and_is_faster() {
+    let len = IMAGE_SHAPE.iter().product::<u64>() as usize;
+    let pixels: Vec<u8> = (0..len).map(|i| (i * 31 % 256) as u8).collect();
+    let image = RawTensor {
+        shape: IMAGE_SHAPE.to_vec(),
+        dtype: DType::U8,
+        data: pixels.clone(),
+    };
+
+    let start = Instant::now();
+    let from_raw = raw_graph_data(vec![image]).unwrap();
+    let raw_time = start.elapsed();
+
+    // The JSON path has to build the text of every number and parse it again
+    let start = Instant::now();
+    let input_json = serde_json::json!({ "input_data": [pixels] }).to_string();
+    let from_json: GraphData = serde_json::from_str(&input_json).unwrap();
+    let json_time = start.elapsed();
+
+    assert_eq!(
+        serde_json::to_value(&from_raw).unwrap(),
+        serde_json::to_value(&from_json).unwrap()
+    );
+    println!(
+        "{} pixels: raw input took {:?}, JSON input took {:?}",
+        len, raw_time, json_time
+    );
+    assert!(raw_time < json_time);
+}
+
+#[test]
+fn test_raw_input_validates_lengths() {
+    let tensor = |shape: Vec<u64>, dtype: DType, len: usize| RawTensor {
+        shape,
+        dtype,
+        data: vec![0; len],
+    };
+    for valid in [
+        tensor(vec![2, 3], DType::F32, 24),
+        tensor(vec![2, 3], DType::F64, 48),
+        tensor(vec![2, 3], DType::I64, 48),
+        tensor(vec![2, 3], DType::U8, 6),
+    ] {
+        assert!(raw_graph_data(vec![valid]).is_ok());
+    }
+    for invalid in [
+        tensor(vec![2, 3], DType::F32, 23),
+        tensor(vec![2, 3], DType::U8, 24),
+        tensor(vec![u64::MAX, 2], DType::U8, 0),
+    ] {
+        let err = raw_graph_data(vec![invalid]).unwrap_err();
+        assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+    }
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{self_test, SelfTestStage};
+
+#[tokio::test]
+async fn test_self_test_passes_on_fixtures() {
+    let fixture = common::fixture();
+
+    let report = self_test(
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        fixture.input_json.clone(),
+    )
+    .await
+    .expect("Self test failed to run");
+
+    assert!(report.passed);
+    assert_eq!(report.failed_stage, None);
+    let stages: Vec<SelfTestStage> = report.steps.iter().map(|step| step.stage).collect();
+    assert_eq!(
+        stages,
+        vec![
+            SelfTestStage::LoadCircuit,
+            SelfTestStage::LoadVk,
+            SelfTestStage::LoadPkAndSrs,
+            SelfTestStage::GenWitness,
+            SelfTestStage::Prove,
+            SelfTestStage::Verify,
+        ]
+    );
+    assert!(report.steps.iter().all(|step| step.error.is_none()));
+}
+
+#[tokio::test]
+async fn test_self_test_pinpoints_corrupted_vk() {
+    let fixture = common::fixture();
+    let corrupted_vk = fixture.vk[..fixture.vk.len() / 2].to_vec();
+
+    let report = self_test(
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        corrupted_vk,
+        fixture.srs.clone(),
+        fixture.input_json.clone(),
+    )
+    .await
+    .expect("Self test failed to run");
+
+    assert!(!report.passed);
+    assert_eq!(report.failed_stage, Some(SelfTestStage::LoadVk));
+    // The test stops at the failing stage
+    let last = report.steps.last().expect("No stages were run");
+    assert_eq!(last.stage, SelfTestStage::LoadVk);
+    assert!(last.error.is_some());
+    assert_eq!(report.steps.len(), 2);
+}
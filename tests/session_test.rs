@@ -0,0 +1,263 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{
+    CheckModeWrapper, EZKLError, EzklProver, ProofTypeWrapper, ProveOptions, TranscriptTypeWrapper,
+};
+
+#[tokio::test]
+async fn test_preload_skips_loading_when_proving() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let session = EzklProver::new(
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    );
+    assert!(!session.is_ready());
+
+    session.clone().preload().await.expect("Failed to preload");
+    assert!(session.is_ready());
+    // Preloading again is a no-op
+    session
+        .clone()
+        .preload()
+        .await
+        .expect("Failed to preload again");
+
+    let result = session.prove(witness.clone()).expect("Proving failed");
+    assert_eq!(result.metrics.load_ms, 0);
+
+    let valid = ios_ezkl::verify(
+        result.proof_json,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed");
+    assert!(valid);
+
+    // The artifacts stay loaded for subsequent proofs
+    let result = session.prove(witness).expect("Proving failed");
+    assert_eq!(result.metrics.load_ms, 0);
+}
+
+#[tokio::test]
+async fn test_prove_without_preload_loads_once() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let session = EzklProver::new(
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    );
+    let first = session.prove(witness.clone()).expect("Proving failed");
+    assert!(session.is_ready());
+    assert!(first.metrics.load_ms <= first.metrics.elapsed_ms);
+
+    let second = session.prove(witness).expect("Proving failed");
+    assert_eq!(second.metrics.load_ms, 0);
+}
+
+#[tokio::test]
+async fn test_release_caches_reloads_on_next_proof() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let session = EzklProver::new(
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    );
+    session.clone().preload().await.expect("Failed to preload");
+    let first = session.prove(witness.clone()).expect("Proving failed");
+    assert_eq!(first.metrics.load_ms, 0);
+
+    session.release_caches();
+    assert!(!session.is_ready());
+    // Releasing an unloaded session is a no-op
+    session.release_caches();
+
+    // The artifacts are loaded again, and the time spent loading them is reported
+    let second = session.prove(witness).expect("Proving failed");
+    assert!(session.is_ready());
+    assert!(second.metrics.load_ms > 0);
+    assert!(ios_ezkl::verify(
+        second.proof_json,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed"));
+}
+
+#[tokio::test]
+async fn test_preload_reports_invalid_artifacts() {
+    let fixture = common::fixture();
+    let session = EzklProver::new(
+        fixture.compiled_circuit.clone(),
+        vec![0; 16],
+        fixture.srs.clone(),
+    );
+
+    assert!(session.clone().preload().await.is_err());
+    assert!(!session.is_ready());
+}
+
+#[tokio::test]
+async fn test_interleaved_transcripts_from_one_session() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let session = EzklProver::new(
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    );
+    let evm = ProveOptions::default();
+    let aggr = ProveOptions {
+        proof_type: ProofTypeWrapper::ForAggr,
+        check_mode: CheckModeWrapper::Unchecked,
+        ..ProveOptions::default()
+    };
+
+    // Switching back and forth reuses the loaded artifacts and the compiled protocol
+    for (options, transcript) in [
+        (&evm, TranscriptTypeWrapper::EVM),
+        (&aggr, TranscriptTypeWrapper::Poseidon),
+        (&evm, TranscriptTypeWrapper::EVM),
+        (&aggr, TranscriptTypeWrapper::Poseidon),
+    ] {
+        let result = session
+            .prove_with_options(witness.clone(), options.clone())
+            .expect("Proving failed");
+        assert_eq!(result.transcript, transcript);
+        assert_eq!(
+            ios_ezkl::inspect_proof(result.proof_json.clone())
+                .unwrap()
+                .transcript,
+            transcript
+        );
+
+        let valid = ios_ezkl::verify(
+            result.proof_json,
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+        )
+        .expect("Verification failed");
+        assert!(valid);
+    }
+}
+
+#[tokio::test]
+async fn test_for_aggr_proofs_reuse_the_compiled_protocol() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let session = EzklProver::new(
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    );
+    let aggr = ProveOptions {
+        proof_type: ProofTypeWrapper::ForAggr,
+        ..ProveOptions::default()
+    };
+
+    let mut compile_times = vec![];
+    for _ in 0..3 {
+        let result = session
+            .prove_with_options(witness.clone(), aggr.clone())
+            .expect("Proving failed");
+        assert_eq!(result.transcript, TranscriptTypeWrapper::Poseidon);
+        let valid = ios_ezkl::verify(
+            result.proof_json,
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+        )
+        .expect("Verification failed");
+        assert!(valid);
+        compile_times.push(result.metrics.protocol_compile_ms);
+    }
+
+    // Only the first proof compiles the protocol
+    assert!(compile_times[0].is_some());
+    assert_eq!(compile_times[1..], [None, None]);
+
+    // Single proofs do not need a protocol
+    let single = session.prove(witness).expect("Proving failed");
+    assert_eq!(single.metrics.protocol_compile_ms, None);
+}
+
+#[tokio::test]
+async fn test_session_rejects_srs_extension() {
+    let fixture = common::fixture();
+    let session = EzklProver::new(
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    );
+
+    let err = session
+        .prove_with_options(
+            common::witness().await,
+            ProveOptions {
+                srs_extension: Some(vec![0; 16]),
+                ..ProveOptions::default()
+            },
+        )
+        .expect_err("A session can't extend its SRS");
+    assert!(matches!(err, EZKLError::InvalidInput(_)));
+}
+
+#[tokio::test]
+async fn test_witness_with_truncated_instances_fails_early() {
+    let fixture = common::fixture();
+    let mut witness: serde_json::Value = serde_json::from_str(&common::witness().await).unwrap();
+    // The sample circuit's outputs are its public instances
+    let outputs = witness["outputs"][0].as_array_mut().unwrap();
+    let expected = outputs.len();
+    outputs.pop();
+    let truncated = witness.to_string();
+
+    let session = EzklProver::new(
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    );
+    for proof_type in [ProofTypeWrapper::Single, ProofTypeWrapper::ForAggr] {
+        let options = ProveOptions {
+            proof_type,
+            ..ProveOptions::default()
+        };
+        let err = session
+            .prove_with_options(truncated.clone(), options)
+            .expect_err("A truncated witness can't be proven");
+        let EZKLError::InvalidInput(message) = err else {
+            panic!("unexpected error: {:?}", err);
+        };
+        assert!(
+            message.contains(&format!(
+                "{} public instances, but the circuit settings expect {}",
+                expected - 1,
+                expected
+            )),
+            "{}",
+            message
+        );
+    }
+
+    // The failed proofs leave the session usable for well formed witnesses
+    let aggr = ProveOptions {
+        proof_type: ProofTypeWrapper::ForAggr,
+        ..ProveOptions::default()
+    };
+    assert!(session
+        .prove_with_options(common::witness().await, aggr)
+        .is_ok());
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use ezkl::graph::Visibility;
+use ezkl::RunArgs;
+use ios_ezkl::{CommitmentsWrapper, RunArgsWrapper, VisibilityWrapper};
+
+fn tuned_run_args() -> RunArgsWrapper {
+    RunArgsWrapper {
+        logrows: 15,
+        input_visibility: VisibilityWrapper::Public,
+        param_visibility: VisibilityWrapper::Fixed,
+        output_visibility: VisibilityWrapper::Hashed {
+            hash_is_public: true,
+            outlets: vec![0, 2],
+        },
+        input_scale: 5,
+        param_scale: 6,
+        lookup_range_min: -4096,
+        lookup_range_max: 4096,
+        num_inner_cols: 2,
+        commitment: Some(CommitmentsWrapper::IPA),
+    }
+}
+
+#[test]
+fn test_run_args_round_trip_from_wrapper() {
+    let wrapper = tuned_run_args();
+    let run_args = RunArgs::from(wrapper.clone());
+
+    assert_eq!(run_args.logrows, 15);
+    assert!(matches!(run_args.param_visibility, Visibility::Fixed));
+    assert_eq!(RunArgsWrapper::from(&run_args), wrapper);
+}
+
+#[test]
+fn test_run_args_round_trip_keeps_defaults() {
+    let defaults = RunArgs::default();
+    let converted = RunArgs::from(RunArgsWrapper::from(&defaults));
+
+    assert_eq!(
+        serde_json::to_value(&converted).unwrap(),
+        serde_json::to_value(&defaults).unwrap()
+    );
+
+    // Fields not exposed by the wrapper keep their defaults
+    let tuned = serde_json::to_value(RunArgs::from(tuned_run_args())).unwrap();
+    let defaults = serde_json::to_value(&defaults).unwrap();
+    for field in [
+        "tolerance",
+        "variables",
+        "scale_rebase_multiplier",
+        "check_mode",
+    ] {
+        assert_eq!(tuned[field], defaults[field], "{}", field);
+    }
+}
+
+#[test]
+fn test_visibility_round_trip() {
+    for visibility in [
+        VisibilityWrapper::Private,
+        VisibilityWrapper::Public,
+        VisibilityWrapper::Hashed {
+            hash_is_public: false,
+            outlets: vec![1],
+        },
+        VisibilityWrapper::Polycommit,
+        VisibilityWrapper::Fixed,
+    ] {
+        let converted = Visibility::from(visibility.clone());
+        assert_eq!(VisibilityWrapper::from(&converted), visibility);
+    }
+}
+
+#[test]
+fn test_parse_settings() {
+    let fixture = common::fixture();
+    let settings =
+        ios_ezkl::parse_settings(fixture.settings_json.clone()).expect("Failed to parse settings");
+    let info = ios_ezkl::circuit_info(fixture.compiled_circuit.clone()).unwrap();
+
+    assert_eq!(settings.run_args.logrows, info.logrows);
+    assert_eq!(settings.num_rows, info.num_rows);
+    assert_eq!(settings.total_instances, info.total_instances);
+}
+
+#[test]
+fn test_settings_compatible_with_itself() {
+    let fixture = common::fixture();
+    let report =
+        ios_ezkl::settings_compatible(fixture.settings_json.clone(), fixture.settings_json.clone())
+            .expect("Failed to compare settings");
+
+    assert!(report.compatible);
+    assert!(report.differences.is_empty());
+}
+
+#[test]
+fn test_settings_compatible_across_commitments() {
+    let report = ios_ezkl::settings_compatible(
+        common::fixture().settings_json.clone(),
+        common::ipa_fixture().settings_json.clone(),
+    )
+    .unwrap();
+
+    assert!(!report.compatible);
+    let commitment = report
+        .differences
+        .iter()
+        .find(|d| d.field == "run_args.commitment")
+        .expect("The commitment should differ");
+    assert_eq!(commitment.a, "\"KZG\"");
+    assert_eq!(commitment.b, "\"IPA\"");
+    assert!(commitment.breaks_verification);
+}
+
+#[test]
+fn test_settings_compatible_distinguishes_cosmetic_differences() {
+    let fixture = common::fixture();
+    let mut mutated: serde_json::Value = serde_json::from_str(&fixture.settings_json).unwrap();
+    mutated["version"] = "0.0.0-other".into();
+
+    let report =
+        ios_ezkl::settings_compatible(fixture.settings_json.clone(), mutated.to_string()).unwrap();
+    assert!(report.compatible);
+    assert_eq!(report.differences.len(), 1);
+    assert_eq!(report.differences[0].field, "version");
+    assert_eq!(report.differences[0].b, "\"0.0.0-other\"");
+    assert!(!report.differences[0].breaks_verification);
+
+    // Changing the scales and the row count of the circuit breaks verification
+    mutated["run_args"]["logrows"] = (mutated["run_args"]["logrows"].as_u64().unwrap() + 1).into();
+    mutated["model_output_scales"] = serde_json::json!([0]);
+    let report =
+        ios_ezkl::settings_compatible(fixture.settings_json.clone(), mutated.to_string()).unwrap();
+    assert!(!report.compatible);
+    let breaking: Vec<&str> = report
+        .differences
+        .iter()
+        .filter(|d| d.breaks_verification)
+        .map(|d| d.field.as_str())
+        .collect();
+    assert_eq!(breaking, vec!["model_output_scales", "run_args.logrows"]);
+}
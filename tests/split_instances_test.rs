@@ -0,0 +1,91 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ezkl::graph::GraphWitness;
+use halo2_proofs::halo2curves::bn256::Fr;
+use ios_ezkl::{gen_witness, prove, split_instances, EZKLError};
+
+fn hex(values: &[Fr]) -> Vec<String> {
+    values.iter().map(|value| format!("{:?}", value)).collect()
+}
+
+async fn witness_and_proof(fixture: &common::Fixture) -> (GraphWitness, String) {
+    let witness_json = gen_witness(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+    let proof = prove(
+        witness_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Proof generation failed");
+    (serde_json::from_str(&witness_json).unwrap(), proof)
+}
+
+#[tokio::test]
+async fn test_split_instances_public_outputs() {
+    let fixture = common::fixture();
+    let (witness, proof) = witness_and_proof(fixture).await;
+
+    let grouped = split_instances(proof, fixture.settings_json.clone()).unwrap();
+    // The sample circuit has private inputs and params, and public outputs
+    assert!(grouped.inputs.is_empty());
+    assert!(grouped.params.is_empty());
+    assert_eq!(grouped.outputs, hex(&witness.outputs.concat()));
+}
+
+#[tokio::test]
+async fn test_split_instances_hashed() {
+    let fixture = common::hashed_fixture();
+    let (witness, proof) = witness_and_proof(fixture).await;
+
+    let grouped = split_instances(proof, fixture.settings_json.clone()).unwrap();
+    let hashes = |processed: &Option<ezkl::graph::modules::ModuleForwardResult>| {
+        hex(processed
+            .as_ref()
+            .and_then(|result| result.poseidon_hash.as_ref())
+            .expect("The witness has no Poseidon hashes"))
+    };
+    assert_eq!(grouped.inputs, hashes(&witness.processed_inputs));
+    assert!(grouped.params.is_empty());
+    assert_eq!(grouped.outputs, hashes(&witness.processed_outputs));
+    // One hash per tensor, instead of one instance per value
+    assert_eq!(grouped.inputs.len(), 1);
+    assert_eq!(grouped.outputs.len(), 1);
+}
+
+#[tokio::test]
+async fn test_split_instances_fixed_params() {
+    let fixture = common::fixed_params_fixture();
+    let (witness, proof) = witness_and_proof(fixture).await;
+
+    // Fixed params are baked into the vk, so only the public inputs and outputs are instances
+    let grouped = split_instances(proof, fixture.settings_json.clone()).unwrap();
+    assert_eq!(grouped.inputs, hex(&witness.inputs.concat()));
+    assert!(grouped.params.is_empty());
+    assert_eq!(grouped.outputs, hex(&witness.outputs.concat()));
+}
+
+#[tokio::test]
+async fn test_split_instances_mismatched_settings() {
+    let proof = common::proof().await;
+
+    // The sample proof has ten public outputs, but the hashed settings lay out two hashes
+    let err = split_instances(proof, common::hashed_fixture().settings_json.clone()).unwrap_err();
+    assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+
+    let err =
+        split_instances("{}".to_string(), common::fixture().settings_json.clone()).unwrap_err();
+    assert!(
+        matches!(err, EZKLError::JsonError { ref field, .. } if field == "proof_json"),
+        "{:?}",
+        err
+    );
+}
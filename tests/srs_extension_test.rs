@@ -0,0 +1,77 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{
+    inspect_srs, prove_advanced, split_srs, verify, verify_flexible, EZKLError, ProveOptions,
+};
+
+#[tokio::test]
+async fn test_prove_from_split_srs() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let parts = split_srs(fixture.srs.clone(), 1).expect("Failed to split the SRS");
+    assert_eq!(inspect_srs(parts.verifier_srs.clone()).unwrap().k, 1);
+    assert!(parts.verifier_srs.len() + parts.extension.len() < 2 * fixture.srs.len());
+
+    let proof = prove_advanced(
+        witness,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        parts.verifier_srs.clone(),
+        ProveOptions {
+            srs_extension: Some(parts.extension),
+            ..ProveOptions::default()
+        },
+    )
+    .expect("Proving from the split SRS failed");
+
+    // The verifier SRS alone is enough to verify
+    let valid = verify_flexible(
+        proof.clone(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        Some(parts.verifier_srs),
+    )
+    .expect("Verification with the verifier SRS failed");
+    assert!(valid);
+
+    let valid = verify(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification with the full SRS failed");
+    assert!(valid);
+}
+
+#[tokio::test]
+async fn test_mismatched_extension_is_rejected() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let small = split_srs(fixture.srs.clone(), 1).expect("Failed to split the SRS");
+    let large = split_srs(fixture.srs.clone(), 2).expect("Failed to split the SRS");
+
+    // The extension of a larger verifier SRS lacks the points the smaller one is missing
+    let err = prove_advanced(
+        witness,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        small.verifier_srs,
+        ProveOptions {
+            srs_extension: Some(large.extension),
+            ..ProveOptions::default()
+        },
+    )
+    .expect_err("Proving with a mismatched extension should fail");
+
+    match err {
+        EZKLError::InvalidInput(message) => {
+            assert!(message.contains("SRS extension"), "{}", message)
+        }
+        other => panic!("Unexpected error: {}", other),
+    }
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use ios_ezkl::{CommitmentsWrapper, EZKLError, SRS_HEADER_LEN};
+
+#[test]
+fn test_inspect_bundled_srs() {
+    let srs = std::fs::read(common::SRS_PATH).expect("Failed to read srs file");
+
+    let header = ios_ezkl::inspect_srs(srs.clone()).expect("Failed to inspect SRS");
+    assert_eq!(header.commitment_guess, Some(CommitmentsWrapper::KZG));
+    assert_eq!(header.estimated_full_size, srs.len() as u64);
+    assert_eq!(header.k, u32::from_le_bytes(srs[..4].try_into().unwrap()));
+
+    let file_header =
+        ios_ezkl::inspect_srs_file(common::SRS_PATH.to_string()).expect("Failed to inspect file");
+    assert_eq!(file_header, header);
+}
+
+#[test]
+fn test_inspect_truncated_srs() {
+    let srs = std::fs::read(common::SRS_PATH).expect("Failed to read srs file");
+    let header = ios_ezkl::inspect_srs(srs.clone()).unwrap();
+
+    let truncated = ios_ezkl::inspect_srs(srs[..SRS_HEADER_LEN].to_vec())
+        .expect("Failed to inspect truncated SRS");
+    assert_eq!(truncated, header);
+
+    let path = common::scratch_dir().join("truncated.srs");
+    std::fs::write(&path, &srs[..4096]).unwrap();
+    let truncated_file = ios_ezkl::inspect_srs_file(path.display().to_string())
+        .expect("Failed to inspect truncated file");
+    assert_eq!(truncated_file, header);
+}
+
+#[test]
+fn test_inspect_srs_rejects_garbage() {
+    assert!(matches!(
+        ios_ezkl::inspect_srs(vec![1, 2]),
+        Err(EZKLError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        ios_ezkl::inspect_srs(vec![0xff; SRS_HEADER_LEN]),
+        Err(EZKLError::InvalidInput(_))
+    ));
+}
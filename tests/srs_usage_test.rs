@@ -0,0 +1,131 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ezkl::pfsys::srs::gen_srs;
+use halo2_proofs::halo2curves::bn256::{Bn256, G1Affine};
+use halo2_proofs::poly::commitment::{Params, ParamsProver};
+use halo2_proofs::poly::ipa::commitment::ParamsIPA;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use ios_ezkl::{effective_srs_k, split_srs, EZKLError};
+use std::sync::OnceLock;
+
+/// The logrows of the sample circuit, which its bundled SRS matches.
+const LOGROWS: u32 = 17;
+
+static OVERSIZED_SRS: OnceLock<Vec<u8>> = OnceLock::new();
+
+/// An SRS twice as large as the sample circuit needs.
+fn oversized_srs() -> &'static Vec<u8> {
+    OVERSIZED_SRS.get_or_init(|| {
+        let params = gen_srs::<KZGCommitmentScheme<Bn256>>(LOGROWS + 1);
+        let mut srs = vec![];
+        params.write(&mut srs).unwrap();
+        srs
+    })
+}
+
+#[tokio::test]
+async fn test_prove_and_verify_report_the_srs_usage() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let prove = |srs: &Vec<u8>| {
+        ios_ezkl::prove_detailed(
+            witness.clone(),
+            fixture.compiled_circuit.clone(),
+            fixture.pk.clone(),
+            srs.clone(),
+            ios_ezkl::default_prove_options(),
+        )
+        .expect("Proving failed")
+    };
+
+    let exact = prove(&fixture.srs).metrics;
+    assert_eq!(exact.srs_original_k, LOGROWS);
+    assert_eq!(exact.srs_used_k, LOGROWS);
+    assert!(!exact.downsized);
+
+    let result = prove(oversized_srs());
+    assert_eq!(result.metrics.srs_original_k, LOGROWS + 1);
+    assert_eq!(result.metrics.srs_used_k, LOGROWS);
+    assert!(result.metrics.downsized);
+
+    let verify = |srs: &Vec<u8>| {
+        ios_ezkl::verify_detailed(
+            result.proof_json.clone(),
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            srs.clone(),
+        )
+        .expect("Verification failed to run")
+    };
+
+    // The proof made with the downsized SRS verifies with either SRS
+    let report = verify(oversized_srs());
+    assert_eq!(report.outcome, ios_ezkl::VerifyOutcome::Valid);
+    assert_eq!(report.srs_original_k, Some(LOGROWS + 1));
+    assert_eq!(report.srs_used_k, Some(LOGROWS));
+    assert!(report.downsized);
+
+    let report = verify(&fixture.srs);
+    assert_eq!(report.outcome, ios_ezkl::VerifyOutcome::Valid);
+    assert_eq!(report.srs_original_k, Some(LOGROWS));
+    assert_eq!(report.srs_used_k, Some(LOGROWS));
+    assert!(!report.downsized);
+}
+
+#[test]
+fn test_effective_srs_k() {
+    let fixture = common::fixture();
+
+    let k = |srs: &[u8]| effective_srs_k(srs.to_vec(), fixture.settings_json.clone());
+    assert_eq!(k(&fixture.srs).unwrap(), LOGROWS);
+    assert_eq!(k(oversized_srs()).unwrap(), LOGROWS);
+    // The header is enough
+    assert_eq!(
+        k(&oversized_srs()[..ios_ezkl::SRS_HEADER_LEN]).unwrap(),
+        LOGROWS
+    );
+
+    let verifier_srs = split_srs(fixture.srs.clone(), 1).unwrap().verifier_srs;
+    let err = k(&verifier_srs).unwrap_err();
+    assert!(matches!(err, EZKLError::InvalidInput(_)), "{:?}", err);
+}
+
+/// An IPA SRS of the given size, serialized.
+fn ipa_srs(k: u32) -> Vec<u8> {
+    let mut srs = vec![];
+    ParamsIPA::<G1Affine>::new(k).write(&mut srs).unwrap();
+    srs
+}
+
+#[tokio::test]
+async fn test_ipa_verify_checks_the_srs_size() {
+    let fixture = common::ipa_fixture();
+    let proof = common::proof_for(fixture).await;
+
+    let verify = |srs: Vec<u8>| {
+        ios_ezkl::verify(
+            proof.clone(),
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            srs,
+        )
+    };
+    assert!(verify(fixture.srs.clone()).unwrap());
+    // A larger SRS is downsized to the logrows of the circuit
+    assert!(verify(ipa_srs(LOGROWS + 1)).unwrap());
+
+    // A smaller SRS can't be used at all, instead of verifying against the wrong domain
+    let err = verify(ipa_srs(LOGROWS - 1)).unwrap_err();
+    let EZKLError::InvalidInput(message) = err else {
+        panic!("Unexpected error {:?}", err);
+    };
+    assert!(message.contains(&format!("k = {}", LOGROWS)), "{}", message);
+    assert!(
+        message.contains(&format!("k = {}", LOGROWS - 1)),
+        "{}",
+        message
+    );
+}
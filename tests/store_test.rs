@@ -0,0 +1,144 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{ArtifactKind, ArtifactStore, EZKLError, EzklProver};
+
+fn store_dir(name: &str) -> String {
+    common::scratch_dir().join(name).display().to_string()
+}
+
+#[test]
+fn test_store_load_and_evict() {
+    let store = ArtifactStore::new(store_dir("store-basic"));
+    let fingerprint = "00ff".repeat(16);
+
+    let path = store
+        .path_for(ArtifactKind::ProvingKey, fingerprint.clone())
+        .unwrap();
+    assert!(path.ends_with(&format!("{}/pk.key", fingerprint)));
+    assert!(!store.exists(ArtifactKind::ProvingKey, fingerprint.clone()));
+
+    let metadata = store
+        .store(ArtifactKind::ProvingKey, fingerprint.clone(), vec![1, 2, 3])
+        .unwrap();
+    assert_eq!(metadata.byte_length, 3);
+    assert!(store.exists(ArtifactKind::ProvingKey, fingerprint.clone()));
+    assert_eq!(std::fs::read(&path).unwrap(), vec![1, 2, 3]);
+    assert_eq!(
+        store
+            .load(ArtifactKind::ProvingKey, fingerprint.clone())
+            .unwrap(),
+        vec![1, 2, 3]
+    );
+
+    // Other kinds of the same circuit are separate
+    assert!(!store.exists(ArtifactKind::VerificationKey, fingerprint.clone()));
+
+    assert!(store
+        .evict(ArtifactKind::ProvingKey, fingerprint.clone())
+        .unwrap());
+    assert!(!store.exists(ArtifactKind::ProvingKey, fingerprint.clone()));
+    assert!(!store
+        .evict(ArtifactKind::ProvingKey, fingerprint.clone())
+        .unwrap());
+    assert!(matches!(
+        store.load(ArtifactKind::ProvingKey, fingerprint),
+        Err(EZKLError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn test_modified_artifact_is_detected() {
+    let store = ArtifactStore::new(store_dir("store-corrupt"));
+    let fingerprint = "ab".repeat(32);
+    store
+        .store(ArtifactKind::Srs, fingerprint.clone(), vec![7; 64])
+        .unwrap();
+
+    let path = store
+        .path_for(ArtifactKind::Srs, fingerprint.clone())
+        .unwrap();
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes[10] ^= 1;
+    std::fs::write(&path, bytes).unwrap();
+
+    let err = store
+        .load(ArtifactKind::Srs, fingerprint)
+        .expect_err("The artifact was modified");
+    assert!(matches!(err, EZKLError::FingerprintMismatch { .. }));
+}
+
+#[test]
+fn test_invalid_fingerprints_are_rejected() {
+    let store = ArtifactStore::new(store_dir("store-invalid"));
+    for fingerprint in ["", "../escape", "not-hex"] {
+        assert!(matches!(
+            store.path_for(ArtifactKind::Settings, fingerprint.to_string()),
+            Err(EZKLError::InvalidInput(_))
+        ));
+    }
+}
+
+#[tokio::test]
+async fn test_prove_from_stored_artifacts() {
+    let fixture = common::fixture();
+    let store = ArtifactStore::new(store_dir("store-prove"));
+    let fingerprint = ios_ezkl::circuit_fingerprint(fixture.compiled_circuit.clone()).unwrap();
+    for (kind, bytes) in [
+        (ArtifactKind::CompiledCircuit, &fixture.compiled_circuit),
+        (ArtifactKind::ProvingKey, &fixture.pk),
+        (ArtifactKind::VerificationKey, &fixture.vk),
+        (ArtifactKind::Srs, &fixture.srs),
+    ] {
+        store
+            .store(kind, fingerprint.clone(), bytes.clone())
+            .unwrap();
+    }
+
+    let witness_path = common::scratch_dir().join("store-witness.json");
+    ios_ezkl::gen_witness_to_file_from_store(
+        fixture.input_json.clone(),
+        store.clone(),
+        fingerprint.clone(),
+        witness_path.display().to_string(),
+    )
+    .await
+    .expect("Witness generation failed");
+
+    let proof_path = common::scratch_dir().join("store-proof.json");
+    ios_ezkl::prove_to_file_from_store(
+        witness_path.display().to_string(),
+        store.clone(),
+        fingerprint.clone(),
+        proof_path.display().to_string(),
+    )
+    .expect("Proving failed");
+
+    let session = EzklProver::from_store(store, fingerprint).expect("Failed to open session");
+    let result = session
+        .prove(std::fs::read_to_string(&witness_path).unwrap())
+        .expect("Proving failed");
+
+    // After releasing its caches, the session reads the artifacts from the store again
+    session.release_caches();
+    let reloaded = session
+        .prove(std::fs::read_to_string(&witness_path).unwrap())
+        .expect("Proving failed");
+    assert!(reloaded.metrics.load_ms > 0);
+
+    for proof_json in [
+        std::fs::read_to_string(&proof_path).unwrap(),
+        result.proof_json,
+        reloaded.proof_json,
+    ] {
+        let valid = ios_ezkl::verify(
+            proof_json,
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+        )
+        .expect("Verification failed");
+        assert!(valid);
+    }
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{EZKLError, ProveOptions};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_tiny_budget_times_out_cleanly() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let start = Instant::now();
+    let err = ios_ezkl::prove_detailed(
+        witness.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ProveOptions {
+            time_budget_ms: Some(1),
+            ..ProveOptions::default()
+        },
+    )
+    .expect_err("A 1 ms budget can't fit a proof");
+    match err {
+        EZKLError::TimedOut { elapsed_ms } => assert!(elapsed_ms >= 1),
+        other => panic!("Expected TimedOut, got {:?}", other),
+    }
+    // The budget is checked before proving starts, so the call returns long before a proof would
+    assert!(start.elapsed() < Duration::from_secs(30));
+
+    // The proving slot was released, so a call with a generous budget succeeds
+    let result = ios_ezkl::prove_detailed(
+        witness,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ProveOptions {
+            time_budget_ms: Some(60 * 60 * 1000),
+            ..ProveOptions::default()
+        },
+    )
+    .expect("Proving within the budget failed");
+    assert!(!result.proof_json.is_empty());
+}
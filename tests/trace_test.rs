@@ -0,0 +1,83 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::TraceListener;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Event {
+    Begin(u64, String),
+    End(u64),
+}
+
+#[derive(Default)]
+struct RecordingListener {
+    events: Mutex<Vec<Event>>,
+}
+
+impl TraceListener for RecordingListener {
+    fn begin_interval(&self, name: String) -> u64 {
+        let mut events = self.events.lock().unwrap();
+        let id = events.len() as u64;
+        events.push(Event::Begin(id, name));
+        id
+    }
+
+    fn end_interval(&self, id: u64) {
+        self.events.lock().unwrap().push(Event::End(id));
+    }
+}
+
+#[tokio::test]
+async fn test_trace_listener_receives_balanced_intervals() {
+    let fixture = common::fixture();
+    let witness = common::witness().await;
+
+    let listener = Arc::new(RecordingListener::default());
+    ios_ezkl::set_trace_listener(Some(listener.clone()));
+    let proof = ios_ezkl::prove(
+        witness,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    );
+    ios_ezkl::set_trace_listener(None);
+    proof.expect("Proving failed");
+
+    let events = listener.events.lock().unwrap().clone();
+
+    // Every interval ends, and intervals do not overlap
+    let mut open = None;
+    let mut stages = vec![];
+    for event in &events {
+        match event {
+            Event::Begin(id, name) => {
+                assert_eq!(open, None, "{} began inside another interval", name);
+                open = Some(*id);
+                stages.push(name.as_str());
+            }
+            Event::End(id) => {
+                assert_eq!(open, Some(*id), "unbalanced end of interval {}", id);
+                open = None;
+            }
+        }
+    }
+    assert_eq!(open, None);
+
+    assert_eq!(
+        stages,
+        vec![
+            "deserialize_circuit",
+            "deserialize_pk",
+            "load_srs",
+            "load_witness",
+            "create_proof"
+        ]
+    );
+
+    // Nothing is reported once the listener is removed
+    let count = events.len();
+    common::witness().await;
+    assert_eq!(listener.events.lock().unwrap().len(), count);
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{prove_advanced, EZKLError, ProveOptions, VariableBinding, WitnessOptions};
+
+fn batch_size(value: u64) -> Vec<VariableBinding> {
+    vec![VariableBinding {
+        name: "batch_size".to_string(),
+        value,
+    }]
+}
+
+async fn gen_witness(
+    input_json: String,
+    variables: Vec<VariableBinding>,
+) -> Result<String, EZKLError> {
+    let fixture = common::fixture();
+    ios_ezkl::gen_witness_advanced(
+        input_json,
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+        WitnessOptions {
+            expected_variables: variables,
+            ..WitnessOptions::default()
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_compiled_batch_size_is_accepted() {
+    let fixture = common::fixture();
+    let witness = gen_witness(fixture.input_json.clone(), batch_size(1))
+        .await
+        .expect("Witness generation failed");
+
+    let proof = prove_advanced(
+        witness,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ProveOptions {
+            expected_variables: batch_size(1),
+            ..ProveOptions::default()
+        },
+    )
+    .expect("Proving failed");
+    assert!(ios_ezkl::verify(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone()
+    )
+    .unwrap());
+}
+
+#[tokio::test]
+async fn test_different_batch_size_reports_input_shapes() {
+    // An input for a batch of two
+    let fixture = common::fixture();
+    let mut input: serde_json::Value = serde_json::from_str(&fixture.input_json).unwrap();
+    let mut batch = input["input_data"][0].as_array().unwrap().clone();
+    batch.extend(batch.clone());
+    input["input_data"][0] = batch.into();
+
+    match gen_witness(input.to_string(), batch_size(2)).await {
+        Err(EZKLError::InvalidInput(message)) => {
+            assert!(message.contains("batch_size=1"), "{}", message);
+            assert!(message.contains("batch_size=2"), "{}", message);
+            assert!(message.contains("input shapes"), "{}", message);
+        }
+        other => panic!("Expected an InvalidInput error, got {:?}", other),
+    }
+
+    let err = prove_advanced(
+        common::witness().await,
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+        ProveOptions {
+            expected_variables: batch_size(2),
+            ..ProveOptions::default()
+        },
+    )
+    .expect_err("Proving with a different batch size should fail");
+    assert!(matches!(err, EZKLError::InvalidInput(_)));
+}
+
+#[tokio::test]
+async fn test_unknown_variable_is_rejected() {
+    let fixture = common::fixture();
+    let variables = vec![VariableBinding {
+        name: "sequence_length".to_string(),
+        value: 8,
+    }];
+    match gen_witness(fixture.input_json.clone(), variables).await {
+        Err(EZKLError::InvalidInput(message)) => {
+            assert!(message.contains("sequence_length"), "{}", message);
+            assert!(message.contains("batch_size"), "{}", message);
+        }
+        other => panic!("Expected an InvalidInput error, got {:?}", other),
+    }
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use halo2_proofs::halo2curves::bn256::Bn256;
+use halo2_proofs::poly::commitment::Params;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use ios_ezkl::testing::deserialize_params_prover;
+use ios_ezkl::EZKLError;
+
+fn expect_invalid_input(result: Result<bool, EZKLError>) -> String {
+    match result {
+        Err(EZKLError::InvalidInput(message)) => message,
+        other => panic!("Expected InvalidInput, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_kzg_with_srs() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    let valid = ios_ezkl::verify_flexible(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        Some(fixture.srs.clone()),
+    )
+    .expect("Verification failed");
+    assert!(valid);
+}
+
+#[tokio::test]
+async fn test_kzg_with_reduced_srs() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    let params = deserialize_params_prover::<KZGCommitmentScheme<Bn256>>(Some(&fixture.srs), 1)
+        .expect("Failed to downsize SRS");
+    let mut reduced = vec![];
+    params.write(&mut reduced).unwrap();
+    assert!(reduced.len() < fixture.srs.len());
+
+    let valid = ios_ezkl::verify_flexible(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        Some(reduced),
+    )
+    .expect("Verification with a reduced SRS failed");
+    assert!(valid);
+}
+
+#[tokio::test]
+async fn test_kzg_without_srs() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    let message = expect_invalid_input(ios_ezkl::verify_flexible(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        None,
+    ));
+    assert!(message.contains("KZG"), "{}", message);
+}
+
+#[tokio::test]
+async fn test_ipa_with_srs() {
+    let fixture = common::ipa_fixture();
+    let proof = common::proof_for(fixture).await;
+
+    let valid = ios_ezkl::verify_flexible(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        Some(fixture.srs.clone()),
+    )
+    .expect("Verification failed");
+    assert!(valid);
+}
+
+#[tokio::test]
+async fn test_ipa_without_full_srs() {
+    let fixture = common::ipa_fixture();
+    let proof = common::proof_for(fixture).await;
+    let expected = format!("expected at least {} bytes", fixture.srs.len());
+
+    let message = expect_invalid_input(ios_ezkl::verify_flexible(
+        proof.clone(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        None,
+    ));
+    assert!(message.contains(&expected), "{}", message);
+    assert!(message.contains("got 0 bytes"), "{}", message);
+
+    let truncated = fixture.srs[..fixture.srs.len() / 2].to_vec();
+    let message = expect_invalid_input(ios_ezkl::verify_flexible(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        Some(truncated),
+    ));
+    assert!(message.contains(&expected), "{}", message);
+}
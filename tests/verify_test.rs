@@ -0,0 +1,177 @@
+mod common;
+
+use ios_ezkl::{VerifyFailureCategory, VerifyOutcome};
+
+#[tokio::test]
+async fn test_verify_detailed_valid_proof() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    let report = ios_ezkl::verify_detailed(
+        proof,
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed to run");
+
+    assert_eq!(report.outcome, VerifyOutcome::Valid);
+    assert_eq!(report.failure, None);
+    assert_eq!(report.expected_instances, report.provided_instances);
+}
+
+#[tokio::test]
+async fn test_verify_detailed_failure_categories() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    // 1. Truncating the proof bytes makes the transcript unreadable
+    let mut truncated: serde_json::Value = serde_json::from_str(&proof).unwrap();
+    let bytes = truncated["proof"].as_array().unwrap().clone();
+    truncated["proof"] = serde_json::Value::Array(bytes[..bytes.len() / 2].to_vec());
+    truncated["hex_proof"] = serde_json::Value::Null;
+
+    let report = ios_ezkl::verify_detailed(
+        truncated.to_string(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed to run");
+    assert_eq!(report.outcome, VerifyOutcome::InvalidProof);
+    assert_eq!(
+        report.failure,
+        Some(VerifyFailureCategory::MalformedTranscript)
+    );
+
+    // 2. Altering a public instance makes the final check fail
+    let report = ios_ezkl::verify_detailed(
+        common::tamper_instance(&proof),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed to run");
+    assert_eq!(report.outcome, VerifyOutcome::InvalidProof);
+    assert!(report.failure.is_some());
+    assert_ne!(
+        report.failure,
+        Some(VerifyFailureCategory::MalformedTranscript)
+    );
+    assert!(report.failure_message.is_some());
+}
+
+#[tokio::test]
+async fn test_verify_rejects_truncated_instances() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    // Drop the last public instance of the proof
+    let mut truncated: serde_json::Value = serde_json::from_str(&proof).unwrap();
+    let instances = truncated["instances"][0].as_array().unwrap().clone();
+    let expected = instances.len();
+    truncated["instances"][0] = serde_json::Value::Array(instances[..expected - 1].to_vec());
+
+    let result = ios_ezkl::verify(
+        truncated.to_string(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    );
+    match result {
+        Err(ios_ezkl::EZKLError::InvalidInput(message)) => {
+            assert!(message.contains(&expected.to_string()), "{}", message);
+            assert!(message.contains(&(expected - 1).to_string()), "{}", message);
+        }
+        other => panic!("Expected an InvalidInput error, got {:?}", other),
+    }
+
+    // The detailed report flags the mismatch instead of failing
+    let report = ios_ezkl::verify_detailed(
+        truncated.to_string(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed to run");
+    assert_eq!(report.outcome, VerifyOutcome::Mismatch);
+    assert_eq!(report.provided_instances, vec![expected as u64 - 1]);
+}
+
+#[tokio::test]
+async fn test_verify_ignores_unknown_proof_fields() {
+    let fixture = common::fixture();
+    let mut proof: serde_json::Value = serde_json::from_str(&common::proof().await).unwrap();
+    proof["added_by_a_newer_ezkl"] = serde_json::json!({ "nested": [1, 2, 3] });
+
+    let valid = ios_ezkl::verify(
+        proof.to_string(),
+        fixture.settings_json.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Verification failed");
+    assert!(valid);
+}
+
+#[tokio::test]
+async fn test_verify_rejects_out_of_range_logrows() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    for logrows in [0, 31, 32, 255] {
+        let mut settings: serde_json::Value = serde_json::from_str(&fixture.settings_json).unwrap();
+        settings["run_args"]["logrows"] = logrows.into();
+
+        let results = [
+            ios_ezkl::verify(
+                proof.clone(),
+                settings.to_string(),
+                fixture.vk.clone(),
+                fixture.srs.clone(),
+            ),
+            ios_ezkl::verify_flexible(
+                proof.clone(),
+                settings.to_string(),
+                fixture.vk.clone(),
+                Some(fixture.srs.clone()),
+            ),
+        ];
+        for result in results {
+            match result {
+                Err(ios_ezkl::EZKLError::InvalidInput(message)) => {
+                    assert!(message.contains("logrows"), "{}", message)
+                }
+                other => panic!(
+                    "Expected an InvalidInput error for logrows {}, got {:?}",
+                    logrows, other
+                ),
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_verify_and_verify_detailed_agree() {
+    let fixture = common::fixture();
+    let proof = common::proof().await;
+
+    for proof in [proof.clone(), common::tamper_instance(&proof)] {
+        let plain = ios_ezkl::verify(
+            proof.clone(),
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+        )
+        .is_ok_and(|valid| valid);
+        let report = ios_ezkl::verify_detailed(
+            proof,
+            fixture.settings_json.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+        )
+        .expect("Verification failed to run");
+
+        assert_eq!(plain, report.outcome == VerifyOutcome::Valid);
+    }
+}
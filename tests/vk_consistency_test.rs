@@ -0,0 +1,47 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ezkl::graph::GraphCircuit;
+use ezkl::pfsys::create_keys;
+use halo2_proofs::halo2curves::bn256::Bn256;
+use halo2_proofs::poly::commitment::ParamsProver;
+use halo2_proofs::poly::kzg::commitment::{KZGCommitmentScheme, ParamsKZG};
+use halo2_proofs::SerdeFormat::RawBytes;
+use ios_ezkl::EZKLError;
+
+#[tokio::test]
+async fn test_vk_of_differently_configured_build_is_rejected() {
+    let fixture = common::fixture();
+
+    // Only one circuit is bundled, so generate a vk for a build with different settings
+    let mut circuit: GraphCircuit = bincode::deserialize(&fixture.compiled_circuit).unwrap();
+    circuit.core.settings.run_args.logrows += 1;
+    let params = ParamsKZG::<Bn256>::new(circuit.settings().run_args.logrows);
+    let pk = create_keys::<KZGCommitmentScheme<Bn256>, GraphCircuit>(&circuit, &params, false)
+        .expect("Failed to create keys");
+    let mut other_vk = Vec::new();
+    pk.get_vk().write(&mut other_vk, RawBytes).unwrap();
+
+    let result = ios_ezkl::gen_witness(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        other_vk,
+        fixture.srs.clone(),
+    )
+    .await;
+    match result {
+        Err(EZKLError::InvalidInput(message)) => assert!(message.contains("logrows"), "{message}"),
+        other => panic!("Expected InvalidInput, got {:?}", other.map(|_| ())),
+    }
+
+    // The vk of the fixture build itself is accepted
+    ios_ezkl::gen_witness(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+}
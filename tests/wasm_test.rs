@@ -0,0 +1,72 @@
+#![cfg(all(feature = "prover", feature = "wasm", target_arch = "wasm32"))]
+
+use ezkl::commands::DEFAULT_DISABLE_SELECTOR_COMPRESSION;
+use ezkl::graph::GraphCircuit;
+use ezkl::pfsys::create_keys;
+use halo2_proofs::halo2curves::bn256::Bn256;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use halo2_proofs::SerdeFormat;
+use ios_ezkl::testing::deserialize_params_prover;
+use ios_ezkl::wasm;
+use wasm_bindgen::{JsError, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+// The fixtures are embedded, as the tests run in Node without access to the repository
+const INPUT_JSON: &str = include_str!("ezkl-sample/input.json");
+const COMPILED_CIRCUIT: &[u8] = include_bytes!("ezkl-sample/network.ezkl");
+const SRS: &[u8] = include_bytes!("ezkl-sample/kzg.srs");
+
+/// The settings, vk and pk of the sample circuit, with the keys generated in memory.
+fn keys() -> (String, Vec<u8>, Vec<u8>) {
+    let circuit: GraphCircuit = bincode::deserialize(COMPILED_CIRCUIT).unwrap();
+    let params = deserialize_params_prover::<KZGCommitmentScheme<Bn256>>(
+        Some(SRS),
+        circuit.settings().run_args.logrows,
+    )
+    .unwrap();
+    let pk = create_keys::<KZGCommitmentScheme<Bn256>, GraphCircuit>(
+        &circuit,
+        &params,
+        DEFAULT_DISABLE_SELECTOR_COMPRESSION.parse().unwrap(),
+    )
+    .unwrap();
+
+    let mut vk = Vec::new();
+    pk.get_vk().write(&mut vk, SerdeFormat::RawBytes).unwrap();
+    let mut pk_bytes = Vec::new();
+    pk.write(&mut pk_bytes, SerdeFormat::RawBytes).unwrap();
+    (circuit.settings().as_json().unwrap(), vk, pk_bytes)
+}
+
+fn ok<T>(result: Result<T, JsError>) -> T {
+    result.map_err(JsValue::from).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn test_verify_fixture_proof() {
+    let (settings_json, vk, pk) = keys();
+
+    let witness_json = ok(wasm::gen_witness(
+        INPUT_JSON.to_string(),
+        COMPILED_CIRCUIT.to_vec(),
+        vk.clone(),
+        SRS.to_vec(),
+    )
+    .await);
+    let proof_json = ok(wasm::prove(
+        witness_json,
+        COMPILED_CIRCUIT.to_vec(),
+        pk,
+        SRS.to_vec(),
+    ));
+
+    assert!(ok(wasm::verify(
+        proof_json,
+        settings_json.clone(),
+        vk.clone(),
+        SRS.to_vec()
+    )));
+
+    // Errors surface as JavaScript errors
+    assert!(wasm::verify("not a proof".to_string(), settings_json, vk, SRS.to_vec()).is_err());
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use halo2_proofs::halo2curves::bn256::Bn256;
+use halo2_proofs::poly::commitment::{Params, ParamsProver};
+use halo2_proofs::poly::kzg::commitment::ParamsKZG;
+use ios_ezkl::EzklConfig;
+use std::path::Path;
+
+fn cache_entries(dir: &Path) -> Vec<std::path::PathBuf> {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect()
+}
+
+// The configuration is process-wide, so all scenarios run sequentially in a single test
+#[tokio::test]
+async fn test_witness_cache_hit_miss_and_corruption() {
+    let fixture = common::fixture();
+    let cache_dir = common::scratch_dir().join("witness-cache");
+    ios_ezkl::initialize(EzklConfig {
+        witness_cache_dir: Some(cache_dir.display().to_string()),
+        ..EzklConfig::default()
+    })
+    .unwrap();
+
+    let gen_witness = |input_json: String| {
+        ios_ezkl::gen_witness_detailed(
+            input_json,
+            fixture.compiled_circuit.clone(),
+            fixture.vk.clone(),
+            fixture.srs.clone(),
+        )
+    };
+
+    // 1. The first generation is a miss and populates the cache
+    let miss = gen_witness(fixture.input_json.clone()).await.unwrap();
+    assert!(!miss.from_cache);
+    assert_eq!(cache_entries(&cache_dir).len(), 1);
+
+    // 2. The same input is read from the cache, also when formatted differently
+    let hit = gen_witness(fixture.input_json.clone()).await.unwrap();
+    assert!(hit.from_cache);
+    assert_eq!(hit.witness_json, miss.witness_json);
+    let reformatted: serde_json::Value = serde_json::from_str(&fixture.input_json).unwrap();
+    let hit = gen_witness(serde_json::to_string_pretty(&reformatted).unwrap())
+        .await
+        .unwrap();
+    assert!(hit.from_cache);
+
+    // 3. A corrupt entry is ignored and regenerated
+    let entry = cache_entries(&cache_dir).remove(0);
+    std::fs::write(&entry, "{\"inputs\": [[").unwrap();
+    let regenerated = gen_witness(fixture.input_json.clone()).await.unwrap();
+    assert!(!regenerated.from_cache);
+    assert_eq!(regenerated.witness_json, miss.witness_json);
+    assert!(
+        gen_witness(fixture.input_json.clone())
+            .await
+            .unwrap()
+            .from_cache
+    );
+
+    // 4. A different input is a miss
+    let mut other = reformatted;
+    other["input_data"][0][0] = 0.5.into();
+    assert!(!gen_witness(other.to_string()).await.unwrap().from_cache);
+    assert_eq!(cache_entries(&cache_dir).len(), 2);
+
+    // The simple API uses the same cache
+    let witness = ios_ezkl::gen_witness(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(witness, miss.witness_json);
+
+    // 5. With polycommit visibility, the witness commits to its inputs and outputs with the SRS,
+    //    so a different SRS is a miss
+    let polycommit = common::polycommit_fixture();
+    let gen_polycommit_witness = |srs: Vec<u8>| {
+        ios_ezkl::gen_witness_detailed(
+            polycommit.input_json.clone(),
+            polycommit.compiled_circuit.clone(),
+            polycommit.vk.clone(),
+            srs,
+        )
+    };
+    let committed = gen_polycommit_witness(polycommit.srs.clone())
+        .await
+        .unwrap();
+    assert!(!committed.from_cache);
+    assert!(
+        gen_polycommit_witness(polycommit.srs.clone())
+            .await
+            .unwrap()
+            .from_cache
+    );
+    let settings: serde_json::Value = serde_json::from_str(&polycommit.settings_json).unwrap();
+    let logrows = settings["run_args"]["logrows"].as_u64().unwrap() as u32;
+    let mut other_srs = Vec::new();
+    ParamsKZG::<Bn256>::new(logrows)
+        .write(&mut other_srs)
+        .unwrap();
+    let recommitted = gen_polycommit_witness(other_srs).await.unwrap();
+    assert!(!recommitted.from_cache);
+    assert_ne!(recommitted.witness_json, committed.witness_json);
+}
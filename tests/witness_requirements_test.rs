@@ -0,0 +1,64 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::{circuit_info, gen_witness_simple, EZKLError};
+
+#[tokio::test]
+async fn test_plain_circuit_needs_no_srs_or_vk_for_witness() {
+    let fixture = common::fixture();
+
+    let info = circuit_info(fixture.compiled_circuit.clone()).unwrap();
+    assert!(!info.requires_srs_for_witness);
+    assert!(!info.requires_vk_for_witness);
+
+    let witness = gen_witness_simple(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        None,
+        None,
+    )
+    .await
+    .expect("Witness generation without the vk and SRS failed");
+    assert_eq!(witness, common::witness().await);
+}
+
+#[tokio::test]
+async fn test_polycommit_circuit_needs_srs_and_vk_for_witness() {
+    let fixture = common::polycommit_fixture();
+
+    let info = circuit_info(fixture.compiled_circuit.clone()).unwrap();
+    assert!(info.requires_srs_for_witness);
+    assert!(info.requires_vk_for_witness);
+
+    for (vk, srs, missing) in [
+        (Some(fixture.vk.clone()), None, "SRS"),
+        (None, Some(fixture.srs.clone()), "vk"),
+    ] {
+        let result = gen_witness_simple(
+            fixture.input_json.clone(),
+            fixture.compiled_circuit.clone(),
+            vk,
+            srs,
+        )
+        .await;
+        match result {
+            Err(EZKLError::InvalidInput(message)) => {
+                assert!(message.contains(missing), "{}", message)
+            }
+            other => panic!(
+                "Expected the missing {} to be rejected, got {:?}",
+                missing, other
+            ),
+        }
+    }
+
+    gen_witness_simple(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        Some(fixture.vk.clone()),
+        Some(fixture.srs.clone()),
+    )
+    .await
+    .expect("Witness generation with the vk and SRS failed");
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "prover")]
+#![cfg(feature = "diagnostics")]
+
+mod common;
+
+use ios_ezkl::testing::{allocated_bytes, measure_peak_bytes};
+use ios_ezkl::BatchItemResult;
+
+const SOAK_WITNESSES: usize = 50;
+
+#[test]
+fn test_witness_memory_is_stable_over_many_witnesses() {
+    let fixture = common::fixture();
+    ios_ezkl::set_diagnostics(true);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let batch = |inputs: Vec<String>| {
+        runtime
+            .block_on(ios_ezkl::gen_witness_batch(
+                inputs,
+                fixture.compiled_circuit.clone(),
+                fixture.vk.clone(),
+                fixture.srs.clone(),
+            ))
+            .expect("Failed to load the artifacts")
+    };
+    let output_bytes = |results: &[BatchItemResult]| -> u64 {
+        results
+            .iter()
+            .map(|result| match result {
+                BatchItemResult::Success { json } => json.len() as u64,
+                BatchItemResult::Failure { error } => {
+                    panic!("Witness generation failed: {}", error)
+                }
+            })
+            .sum()
+    };
+
+    // Warm up the lazily initialized state of the process
+    batch(vec![fixture.input_json.clone()]);
+
+    // 1. One session generating many witnesses peaks no higher than one generating a few, apart
+    //    from the witnesses it returns
+    let few = vec![fixture.input_json.clone(); 5];
+    let (results, few_peak) = measure_peak_bytes(|| batch(few));
+    output_bytes(&results);
+    let many = vec![fixture.input_json.clone(); SOAK_WITNESSES];
+    let (results, many_peak) = measure_peak_bytes(|| batch(many));
+    let many_output = output_bytes(&results);
+    drop(results);
+
+    let few_peak = few_peak.expect("Peak not tracked");
+    let many_peak = many_peak.expect("Peak not tracked");
+    println!(
+        "peak of 5 witnesses: {} bytes, of {} witnesses: {} bytes",
+        few_peak, SOAK_WITNESSES, many_peak
+    );
+    assert!(many_peak <= few_peak + 2 * many_output);
+
+    // 2. Generating witnesses one after another retains nothing between them
+    let before = allocated_bytes().expect("Allocations not tracked");
+    for _ in 0..SOAK_WITNESSES {
+        batch(vec![fixture.input_json.clone()]);
+    }
+    let after = allocated_bytes().expect("Allocations not tracked");
+    println!(
+        "retained after {} witnesses: {} bytes",
+        SOAK_WITNESSES,
+        after.saturating_sub(before)
+    );
+    assert!(after.saturating_sub(before) < few_peak);
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "prover")]
+
+mod common;
+
+use ios_ezkl::testing::wiped_buffers;
+
+// The counter is process-wide, so all scenarios run sequentially in a single test
+#[tokio::test]
+async fn test_private_buffers_are_wiped_on_success_and_error() {
+    let fixture = common::fixture();
+
+    // 1. Successful witness generation wipes the input JSON, the parsed data and the tensors
+    let before = wiped_buffers();
+    let witness = ios_ezkl::gen_witness(
+        fixture.input_json.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect("Witness generation failed");
+    assert!(wiped_buffers() >= before + 3);
+
+    // 2. A failed witness generation still wipes the input JSON
+    let before = wiped_buffers();
+    ios_ezkl::gen_witness(
+        "{\"input_data\": [[0.1, ".to_string(),
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .await
+    .expect_err("Witness generation should fail on truncated input");
+    assert!(wiped_buffers() > before);
+
+    // 3. Successful proving wipes the witness JSON and the parsed witness
+    let before = wiped_buffers();
+    ios_ezkl::prove(
+        witness.clone(),
+        fixture.compiled_circuit.clone(),
+        fixture.pk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect("Proving failed");
+    assert!(wiped_buffers() >= before + 2);
+
+    // 4. A failed proof still wipes the witness JSON and the parsed witness
+    let before = wiped_buffers();
+    ios_ezkl::prove(
+        witness,
+        fixture.compiled_circuit.clone(),
+        fixture.vk.clone(),
+        fixture.srs.clone(),
+    )
+    .expect_err("Proving with the vk in place of the pk should fail");
+    assert!(wiped_buffers() > before);
+}